@@ -6,23 +6,115 @@
 // Mirror the enums defined in the Rust `protocol.rs` file.
 //=========================================================================================
 
+// The encoding of the reader's voice in binary frames. `sample_rate` is only set
+// for "wav" and "pcm16"; compressed codecs carry their own.
+export type AudioFormat = {
+  codec: "wav" | "pcm16" | "mp3" | "opus";
+  sample_rate?: number;
+};
+
 // Messages sent FROM the Client (browser) TO the Server
 type ClientToServerMessage =
-  | { type: "init"; session_id: string }
+  | {
+      type: "init";
+      session_id: string;
+      capabilities?: { audio_free?: boolean };
+      audio_format?: AudioFormat;
+      handoff_token?: string;
+      resume?: boolean;
+    }
   | { type: "interrupt_started" }
+  | { type: "start_handoff"; sentence_index?: number }
   | { type: "interrupt_ended" }
+  | { type: "text_question"; text: string }
+  | { type: "cancel_question" }
+  | { type: "cancel_answer" }
+  | { type: "seek_to"; sentence_index: number }
+  | { type: "skip_back"; sentences: number }
+  | { type: "reread_slower"; sentences: number }
+  | { type: "go_to_chapter"; index: number }
   | { type: "pause_reading" }
-  | { type: "resume_reading" };
+  | { type: "resume_reading" }
+  | { type: "set_speed"; rate: number }
+  | { type: "set_voice"; voice: string }
+  | { type: "rate_session"; rating: number; feedback?: string }
+  | { type: "switch_session"; session_id: string };
+
+export type ProcessingStage =
+  | "transcribing"
+  | "searching_document"
+  | "thinking"
+  | "synthesizing";
+
+// A chapter of the document, found from its headings.
+export type Chapter = {
+  title: string;
+  sentence_index: number;
+};
+
+// What a resumed session is doing: "listening" waits for the next question,
+// "clarifying" for the reply to the clarifying question just asked.
+export type ResumedMode = "reading" | "paused" | "listening" | "clarifying";
+
+// What an `audio` chunk contains. `index` is the sentence index for "reading" audio
+// and the sentence's position for "answer" audio; "resume" chunks are empty and only
+// restart playback of audio already received.
+export type AudioKind = "welcome" | "reading" | "answer" | "announcement" | "resume";
+
+export type AudioHeader = {
+  kind: AudioKind;
+  index?: number;
+  byte_length: number;
+};
 
 // Messages sent FROM the Server TO the Client (browser)
 type ServerToClientMessage =
-  | { type: "session_initialized"; session_id: string }
-  | { type: "error"; message: string }
+  | { type: "session_initialized"; session_id: string; audio_format?: AudioFormat }
+  | { type: "table_of_contents"; chapters: Chapter[] }
+  | {
+      type: "session_resumed";
+      mode: ResumedMode;
+      last_question: string | null;
+      last_answer: string | null;
+    }
+  | { type: "error"; code: string; message: string; fatal: boolean }
   | { type: "reading_started" }
+  | { type: "sentence_skipped"; index: number }
+  | { type: "caption"; text: string }
+  | ({ type: "audio_header" } & AudioHeader)
+  | { type: "sentence_started"; index: number; text: string }
+  | { type: "sentence_text"; index: number; text: string }
+  | { type: "assistant_text"; text: string }
+  | { type: "answer_sentence"; text: string }
+  | { type: "answer_sources"; sentence_indices: number[] }
+  | { type: "answer_grounding"; supported: boolean; reason: string | null }
+  | { type: "explanation"; text: string; sentence_indices: number[] }
+  | {
+      type: "word_defined";
+      word: string;
+      phonetic: string | null;
+      part_of_speech: string | null;
+      meaning: string;
+    }
   | { type: "reading_paused" }
   | { type: "reading_ended" }
+  | { type: "checkpoint_reached" }
   | { type: "answering_started" }
-  | { type: "answering_ended" };
+  | { type: "processing_status"; stage: ProcessingStage }
+  | { type: "question_transcribed"; text: string }
+  | { type: "transcript_partial"; text: string }
+  | {
+      type: "related_document";
+      document_id: string;
+      session_id: string | null;
+      sentence_index: number;
+      excerpt: string;
+    }
+  | { type: "clarification_requested"; question: string }
+  | { type: "answering_ended" }
+  | { type: "session_rated" }
+  | { type: "handoff_ready"; token: string; expires_in_secs: number }
+  | { type: "session_handed_off" };
 
 //=========================================================================================
 // Client-Side Event Definitions
@@ -33,14 +125,70 @@ interface WsClientEvents {
   open: () => void;
   close: () => void;
   error: (error: Event) => void;
-  initialized: () => void;
+  // `audioFormat` is what the server settled on; absent in audio-free sessions.
+  initialized: (audioFormat?: AudioFormat) => void;
+  // Sent after `initialized`; empty if the document has no headings.
+  tableOfContents: (chapters: Chapter[]) => void;
+  // Sent after `tableOfContents` when `sendInit` resumed the last connection.
+  sessionResumed: (mode: ResumedMode, lastQuestion: string | null, lastAnswer: string | null) => void;
   readingStarted: () => void;
   readingPaused: () => void;
   readingEnded: () => void;
+  // Reading stopped to ask a question about the last few paragraphs; answer it like a
+  // question, or say "skip". Reading carries on by itself afterwards.
+  checkpointReached: () => void;
+  // The rating sent with `sendRateSession` was saved.
+  sessionRated: () => void;
+  // Pass `token` to `sendInit` on the other device within `expiresInSecs`.
+  handoffReady: (token: string, expiresInSecs: number) => void;
+  // Another device took over the session; the server closes this connection next.
+  sessionHandedOff: () => void;
   answeringStarted: () => void;
   answeringEnded: () => void;
-  audio: (data: ArrayBuffer) => void;
-  serverError: (message: string) => void;
+  // Progress between `answeringStarted` and the answer's audio.
+  processingStatus: (stage: ProcessingStage) => void;
+  // What the server heard; call `sendCancelQuestion` if it's wrong.
+  questionTranscribed: (text: string) => void;
+  // What has been heard of the question so far; each replaces the last.
+  transcriptPartial: (text: string) => void;
+  // Another of the user's documents covers the question's topic ("you also have a
+  // document on this"); `sessionId` opens it, `sentenceIndex` is the matching passage.
+  relatedDocument: (
+    documentId: string,
+    sessionId: string | null,
+    sentenceIndex: number,
+    excerpt: string,
+  ) => void;
+  // The server asked what the user meant; their next question is the reply.
+  clarificationRequested: (question: string) => void;
+  // `header` describes the chunk; every chunk from the server has one.
+  audio: (data: ArrayBuffer, header?: AudioHeader) => void;
+  // The document sentence whose audio comes next, for highlighting.
+  sentenceStarted: (index: number, text: string) => void;
+  // The text of the next `audio` chunk.
+  caption: (text: string) => void;
+  // Audio-free sessions receive text in place of `audio`.
+  sentenceText: (index: number, text: string) => void;
+  assistantText: (text: string) => void;
+  // One sentence of an answer still being written; its audio (if any) comes next.
+  answerSentence: (text: string) => void;
+  // The document sentences the answer that follows is based on, for highlighting.
+  answerSources: (sentenceIndices: number[]) => void;
+  // Whether the answer just given is backed up by the document; only sent when the
+  // server checks. `reason` says what isn't.
+  answerGrounding: (supported: boolean, reason: string | null) => void;
+  // The sentences just read, re-explained more simply because the user asked; not an
+  // answer. Its audio (if any) comes next.
+  explanation: (text: string, sentenceIndices: number[]) => void;
+  // A word the user had defined; it's also in GET /sessions/{sessionId}/glossary.
+  wordDefined: (definition: {
+    word: string;
+    phonetic: string | null;
+    partOfSpeech: string | null;
+    meaning: string;
+  }) => void;
+  // `fatal` errors are followed by the server closing the connection.
+  serverError: (message: string, code: string, fatal: boolean) => void;
 }
 
 //=========================================================================================
@@ -49,6 +197,8 @@ interface WsClientEvents {
 
 export class WsClient {
   private ws: WebSocket | null = null;
+  // The `audio_header` received for the Binary frame still to come.
+  private pendingAudioHeader: AudioHeader | null = null;
   private listeners: {
     [K in keyof WsClientEvents]?: Array<WsClientEvents[K]>;
   } = {};
@@ -102,7 +252,8 @@ export class WsClient {
           console.error("WsClient: Failed to parse server message.", error);
         }
       } else if (event.data instanceof ArrayBuffer) {
-        this.emit("audio", event.data);
+        this.emit("audio", event.data, this.pendingAudioHeader ?? undefined);
+        this.pendingAudioHeader = null;
       }
     };
 
@@ -130,7 +281,13 @@ export class WsClient {
     console.log("WsClient: Received message from server:", message.type);
     switch (message.type) {
       case "session_initialized":
-        this.emit("initialized");
+        this.emit("initialized", message.audio_format);
+        break;
+      case "table_of_contents":
+        this.emit("tableOfContents", message.chapters);
+        break;
+      case "session_resumed":
+        this.emit("sessionResumed", message.mode, message.last_question, message.last_answer);
         break;
       case "reading_started":
         this.emit("readingStarted");
@@ -138,17 +295,93 @@ export class WsClient {
       case "reading_paused":
         this.emit("readingPaused");
         break;
+      case "checkpoint_reached":
+        this.emit("checkpointReached");
+        break;
+      case "sentence_skipped":
+        console.warn(`WsClient: Sentence ${message.index} could not be read and was skipped.`);
+        break;
+      case "sentence_started":
+        this.emit("sentenceStarted", message.index, message.text);
+        break;
+      case "audio_header":
+        // The Binary frame it describes comes next.
+        this.pendingAudioHeader = {
+          kind: message.kind,
+          index: message.index,
+          byte_length: message.byte_length,
+        };
+        break;
+      case "caption":
+        this.emit("caption", message.text);
+        break;
+      case "sentence_text":
+        this.emit("sentenceText", message.index, message.text);
+        break;
+      case "assistant_text":
+        this.emit("assistantText", message.text);
+        break;
+      case "answer_sentence":
+        this.emit("answerSentence", message.text);
+        break;
+      case "answer_sources":
+        this.emit("answerSources", message.sentence_indices);
+        break;
+      case "answer_grounding":
+        this.emit("answerGrounding", message.supported, message.reason);
+        break;
+      case "explanation":
+        this.emit("explanation", message.text, message.sentence_indices);
+        break;
+      case "word_defined":
+        this.emit("wordDefined", {
+          word: message.word,
+          phonetic: message.phonetic,
+          partOfSpeech: message.part_of_speech,
+          meaning: message.meaning,
+        });
+        break;
       case "reading_ended":
         this.emit("readingEnded");
         break;
       case "answering_started":
         this.emit("answeringStarted");
         break;
+      case "processing_status":
+        this.emit("processingStatus", message.stage);
+        break;
+      case "question_transcribed":
+        this.emit("questionTranscribed", message.text);
+        break;
+      case "transcript_partial":
+        this.emit("transcriptPartial", message.text);
+        break;
+      case "related_document":
+        this.emit(
+          "relatedDocument",
+          message.document_id,
+          message.session_id,
+          message.sentence_index,
+          message.excerpt,
+        );
+        break;
+      case "clarification_requested":
+        this.emit("clarificationRequested", message.question);
+        break;
       case "answering_ended":
         this.emit("answeringEnded");
         break;
+      case "session_rated":
+        this.emit("sessionRated");
+        break;
+      case "handoff_ready":
+        this.emit("handoffReady", message.token, message.expires_in_secs);
+        break;
+      case "session_handed_off":
+        this.emit("sessionHandedOff");
+        break;
       case "error":
-        this.emit("serverError", message.message);
+        this.emit("serverError", message.message, message.code, message.fatal);
         break;
     }
   }
@@ -164,8 +397,30 @@ export class WsClient {
 
   // --- Public Methods for Sending Data ---
 
-  public sendInit(sessionId: string): void {
-    this.sendMessageToServer({ type: "init", session_id: sessionId });
+  // `audioFree` asks for text instead of speech, for silent reading. `audioFormat`
+  // asks for a codec other than WAV, e.g. opus on slow connections.
+  // `handoffToken` takes the session over from another device (see `sendStartHandoff`).
+  // `resume` picks up where this session's last connection dropped, e.g. after a refresh.
+  public sendInit(
+    sessionId: string,
+    audioFree = false,
+    audioFormat?: AudioFormat,
+    handoffToken?: string,
+    resume = false,
+  ): void {
+    this.sendMessageToServer({
+      type: "init",
+      session_id: sessionId,
+      capabilities: { audio_free: audioFree },
+      audio_format: audioFormat,
+      handoff_token: handoffToken,
+      resume,
+    });
+  }
+
+  // `sentenceIndex` is the sentence playing now, where the other device picks up.
+  public sendStartHandoff(sentenceIndex?: number): void {
+    this.sendMessageToServer({ type: "start_handoff", sentence_index: sentenceIndex });
   }
 
   public sendInterruptStarted(): void {
@@ -176,6 +431,39 @@ export class WsClient {
     this.sendMessageToServer({ type: "interrupt_ended" });
   }
 
+  // For users who can't speak: the question is answered aloud like a spoken one.
+  public sendTextQuestion(text: string): void {
+    this.sendMessageToServer({ type: "text_question", text });
+  }
+
+  public sendCancelQuestion(): void {
+    this.sendMessageToServer({ type: "cancel_question" });
+  }
+
+  // Stops the answer; drop any answer audio already queued for playback.
+  public sendCancelAnswer(): void {
+    this.sendMessageToServer({ type: "cancel_answer" });
+  }
+
+  // Jumps reading to a sentence, e.g. a note's `source_start_index`.
+  public sendSeekTo(sentenceIndex: number): void {
+    this.sendMessageToServer({ type: "seek_to", sentence_index: sentenceIndex });
+  }
+
+  public sendSkipBack(sentences: number): void {
+    this.sendMessageToServer({ type: "skip_back", sentences });
+  }
+
+  // Reads the last `sentences` again slower, without changing the session's speed.
+  public sendRereadSlower(sentences: number): void {
+    this.sendMessageToServer({ type: "reread_slower", sentences });
+  }
+
+  // `index` is the chapter's position in `tableOfContents`.
+  public sendGoToChapter(index: number): void {
+    this.sendMessageToServer({ type: "go_to_chapter", index });
+  }
+
   public sendPauseReading(): void {
     this.sendMessageToServer({ type: "pause_reading" });
   }
@@ -184,6 +472,30 @@ export class WsClient {
     this.sendMessageToServer({ type: "resume_reading" });
   }
 
+  // Changes the reading speed from the next sentence on (1.0 is normal, 0.25-4.0),
+  // and saves it as the user's preference.
+  public sendSetSpeed(rate: number): void {
+    this.sendMessageToServer({ type: "set_speed", rate });
+  }
+
+  // Switches the narrator voice (one of GET /voices) from the next sentence on, for
+  // this session only. An empty string goes back to the user's saved voice.
+  public sendSetVoice(voice: string): void {
+    this.sendMessageToServer({ type: "set_voice", voice });
+  }
+
+  // Rates the session 1-5, e.g. once `readingEnded` fires. After the session has
+  // ended, POST the rating to /sessions/{sessionId}/rating instead.
+  public sendRateSession(rating: number, feedback?: string): void {
+    this.sendMessageToServer({ type: "rate_session", rating, feedback });
+  }
+
+  // Switches to another open session without reconnecting. Audio queued from the old
+  // session should be dropped on the next `initialized`.
+  public sendSwitchSession(sessionId: string): void {
+    this.sendMessageToServer({ type: "switch_session", session_id: sessionId });
+  }
+
   public sendAudio(chunk: ArrayBuffer): void {
     if (this.ws?.readyState === WebSocket.OPEN) {
       this.ws.send(chunk);
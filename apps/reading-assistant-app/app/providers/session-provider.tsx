@@ -178,8 +178,8 @@ export function SessionProvider({ children }: SessionProviderProps) {
         }, 'reading');
       });
 
-      wsClientRef.current.on("serverError", (message) => {
-        console.error("Server Error:", message);
+      wsClientRef.current.on("serverError", (message, code, fatal) => {
+        console.error(`Server Error (${code}${fatal ? ", fatal" : ""}):`, message);
       });
 
       wsClientRef.current.on("answeringStarted", () => {
@@ -0,0 +1,76 @@
+//! crates/reading_assistant_core/src/audio_format.rs
+//!
+//! Settles which audio format a session's speech is sent in, from what the client
+//! asked for and what the TTS provider can produce.
+
+use crate::domain::{AudioEncoding, AudioFormat};
+
+/// The lowest sample rate a client can ask for; below it speech is hard to follow.
+pub const MIN_SAMPLE_RATE: u32 = 8_000;
+/// The highest sample rate a client can ask for; TTS voices carry nothing above it.
+pub const MAX_SAMPLE_RATE: u32 = 48_000;
+
+/// The format to send speech in. An encoding the provider can't produce falls back to
+/// WAV, which every provider supports, and a sample rate is clamped to the supported
+/// range and dropped for compressed encodings, whose rate the provider picks.
+pub fn negotiate(requested: AudioFormat, supported: &[AudioEncoding]) -> AudioFormat {
+    let encoding = if supported.contains(&requested.encoding) {
+        requested.encoding
+    } else {
+        AudioEncoding::Wav
+    };
+    let sample_rate = if encoding.is_compressed() {
+        None
+    } else {
+        requested
+            .sample_rate
+            .map(|rate| rate.clamp(MIN_SAMPLE_RATE, MAX_SAMPLE_RATE))
+    };
+    AudioFormat {
+        encoding,
+        sample_rate,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unsupported_encodings_fall_back_to_wav() {
+        let requested = AudioFormat {
+            encoding: AudioEncoding::Opus,
+            sample_rate: Some(16_000),
+        };
+        let negotiated = negotiate(requested, &[AudioEncoding::Wav, AudioEncoding::Pcm16]);
+        assert_eq!(
+            negotiated,
+            AudioFormat {
+                encoding: AudioEncoding::Wav,
+                sample_rate: Some(16_000),
+            }
+        );
+    }
+
+    #[test]
+    fn sample_rates_are_clamped_and_dropped_for_compressed_audio() {
+        let supported = [AudioEncoding::Wav, AudioEncoding::Pcm16, AudioEncoding::Opus];
+        let pcm = negotiate(
+            AudioFormat {
+                encoding: AudioEncoding::Pcm16,
+                sample_rate: Some(4_000),
+            },
+            &supported,
+        );
+        assert_eq!(pcm.sample_rate, Some(MIN_SAMPLE_RATE));
+
+        let opus = negotiate(
+            AudioFormat {
+                encoding: AudioEncoding::Opus,
+                sample_rate: Some(16_000),
+            },
+            &supported,
+        );
+        assert_eq!(opus, AudioFormat { encoding: AudioEncoding::Opus, sample_rate: None });
+    }
+}
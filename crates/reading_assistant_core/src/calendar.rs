@@ -0,0 +1,125 @@
+//! crates/reading_assistant_core/src/calendar.rs
+//!
+//! Renders planned sessions as an iCalendar (RFC 5545) feed that calendar apps can
+//! subscribe to.
+
+use chrono::{DateTime, Duration, Utc};
+
+use crate::domain::{PlanKind, PlannedSession};
+
+/// Calendar lines longer than this many bytes are folded onto continuation lines.
+const MAX_LINE_BYTES: usize = 75;
+
+/// Renders `plans` as a calendar named `name`. `generated_at` stamps every event.
+pub fn render_ics(name: &str, plans: &[PlannedSession], generated_at: DateTime<Utc>) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Reading Assistant//Planned Sessions//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+        "METHOD:PUBLISH".to_string(),
+        format!("X-WR-CALNAME:{}", escape_text(name)),
+    ];
+    for plan in plans {
+        let ends_at = plan.starts_at + Duration::minutes(i64::from(plan.duration_minutes));
+        let default_title = match plan.kind {
+            PlanKind::Reading => "Reading session",
+            PlanKind::Review => "Review session",
+        };
+        lines.extend([
+            "BEGIN:VEVENT".to_string(),
+            format!("UID:{}@reading-assistant", plan.id),
+            format!("DTSTAMP:{}", format_time(generated_at)),
+            format!("DTSTART:{}", format_time(plan.starts_at)),
+            format!("DTEND:{}", format_time(ends_at)),
+            format!("SUMMARY:{}", escape_text(plan.title.as_deref().unwrap_or(default_title))),
+            "END:VEVENT".to_string(),
+        ]);
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold_line(line)).collect()
+}
+
+fn format_time(time: DateTime<Utc>) -> String {
+    time.format("%Y%m%dT%H%M%SZ").to_string()
+}
+
+/// Escapes the characters that are special in calendar text values.
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Ends a line with CRLF, folding it so no line is longer than `MAX_LINE_BYTES`.
+/// Continuation lines start with a space, and folds never split a character.
+fn fold_line(line: &str) -> String {
+    let mut folded = String::with_capacity(line.len() + 8);
+    let mut line_bytes = 0;
+    for c in line.chars() {
+        if line_bytes + c.len_utf8() > MAX_LINE_BYTES {
+            folded.push_str("\r\n ");
+            line_bytes = 1;
+        }
+        folded.push(c);
+        line_bytes += c.len_utf8();
+    }
+    folded.push_str("\r\n");
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use uuid::Uuid;
+
+    #[test]
+    fn renders_one_event_per_plan() {
+        let starts_at = Utc.with_ymd_and_hms(2025, 12, 3, 18, 30, 0).unwrap();
+        let plans = [
+            PlannedSession {
+                id: Uuid::nil(),
+                session_id: Uuid::nil(),
+                kind: PlanKind::Reading,
+                title: None,
+                starts_at,
+                duration_minutes: 45,
+            },
+            PlannedSession {
+                id: Uuid::nil(),
+                session_id: Uuid::nil(),
+                kind: PlanKind::Review,
+                title: Some("Chapter 2; notes, again".to_string()),
+                starts_at,
+                duration_minutes: 15,
+            },
+        ];
+        let ics = render_ics("My reading", &plans, starts_at);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 2);
+        assert!(ics.contains("DTSTART:20251203T183000Z\r\nDTEND:20251203T191500Z\r\n"));
+        assert!(ics.contains("SUMMARY:Reading session\r\n"));
+        assert!(ics.contains("SUMMARY:Chapter 2\\; notes\\, again\r\n"));
+    }
+
+    #[test]
+    fn long_lines_are_folded_between_characters() {
+        let line = format!("SUMMARY:{}", "é".repeat(60));
+        let folded = fold_line(&line);
+        assert!(folded.split("\r\n").all(|part| part.len() <= MAX_LINE_BYTES));
+        assert_eq!(folded.replace("\r\n ", "").trim_end(), line);
+    }
+}
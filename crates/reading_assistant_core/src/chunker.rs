@@ -0,0 +1,129 @@
+//! crates/reading_assistant_core/src/chunker.rs
+//!
+//! Splits document text into the sentence chunks that are read aloud.
+//! Chunks are persisted at upload time, so any change to the splitting rules
+//! must bump `CHUNKER_VERSION` to keep existing sessions' indices stable.
+
+/// The version of the chunking rules implemented by `chunk_into_sentences`.
+pub const CHUNKER_VERSION: i32 = 1;
+
+/// Lines longer than this many words are taken for text rather than a heading.
+const MAX_HEADING_WORDS: usize = 12;
+
+/// A heading in a document and the chunk it opens.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chapter {
+    pub title: String,
+    pub sentence_index: usize,
+}
+
+/// Where a document's paragraphs and chapters start, by chunk index. It is recorded
+/// next to the chunks at upload time, since both only survive in the original text.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DocumentStructure {
+    pub paragraph_starts: Vec<usize>,
+    pub chapters: Vec<Chapter>,
+}
+
+/// Splits a block of text into sentences.
+pub fn chunk_into_sentences(text: &str) -> Vec<String> {
+    split_sentences(text)
+        .filter(|s| !s.trim().is_empty())
+        .map(|s| format!("{}.", s.trim()))
+        .collect()
+}
+
+/// Returns the indices of the chunks from `chunk_into_sentences` that open a new
+/// paragraph, i.e. that follow a blank line. The first chunk is not included.
+pub fn paragraph_starts(text: &str) -> Vec<usize> {
+    let mut starts = Vec::new();
+    let mut index = 0;
+    let mut after_break = false;
+    for piece in split_sentences(text) {
+        // Pieces that aren't chunks (e.g. between "?!") can still hold the break.
+        if piece.trim().is_empty() {
+            after_break |= has_blank_line(piece);
+            continue;
+        }
+        if index > 0 && (after_break || has_blank_line(piece.trim_end())) {
+            starts.push(index);
+        }
+        after_break = false;
+        index += 1;
+    }
+    starts
+}
+
+/// Returns the chapters of `text`: the chunks from `chunk_into_sentences` that open
+/// with a heading, a short line of its own followed by a blank line and then text.
+/// Headings have no closing punctuation, so they always land at the start of a chunk.
+pub fn chapters(text: &str) -> Vec<Chapter> {
+    split_sentences(text)
+        .filter(|s| !s.trim().is_empty())
+        .enumerate()
+        .filter_map(|(sentence_index, piece)| {
+            heading(piece.trim()).map(|title| Chapter { title, sentence_index })
+        })
+        .collect()
+}
+
+/// Returns the paragraph and chapter starts of the chunks from `chunk_into_sentences`.
+pub fn structure(text: &str) -> DocumentStructure {
+    DocumentStructure {
+        paragraph_starts: paragraph_starts(text),
+        chapters: chapters(text),
+    }
+}
+
+/// The heading `chunk` opens with, without any Markdown `#` marks or trailing colon.
+fn heading(chunk: &str) -> Option<String> {
+    let mut lines = chunk.lines();
+    let first = lines.next()?;
+    if !lines.next()?.trim().is_empty() || !lines.any(|line| !line.trim().is_empty()) {
+        return None;
+    }
+    let title = first
+        .trim()
+        .trim_start_matches('#')
+        .trim_end_matches(':')
+        .trim();
+    let words = title.split_whitespace().count();
+    (1..=MAX_HEADING_WORDS).contains(&words).then(|| title.to_string())
+}
+
+fn split_sentences(text: &str) -> impl Iterator<Item = &str> {
+    text.split(['.', '?', '!'])
+}
+
+fn has_blank_line(text: &str) -> bool {
+    text.lines().skip(1).any(|line| line.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chapters_point_at_the_chunk_their_heading_opens() {
+        let text = "# Chapter 1: Beginnings\n\nIt was early. Nobody was up.\n\n\
+                    Chapter 2\n\nThe sun rose. Birds sang!";
+        let chunks = chunk_into_sentences(text);
+        let found = chapters(text);
+
+        assert_eq!(
+            found,
+            vec![
+                Chapter { title: "Chapter 1: Beginnings".to_string(), sentence_index: 0 },
+                Chapter { title: "Chapter 2".to_string(), sentence_index: 2 },
+            ]
+        );
+        assert!(chunks[2].starts_with("Chapter 2"));
+    }
+
+    #[test]
+    fn ordinary_paragraphs_are_not_chapters() {
+        let long_line = "This line runs on for far too many words to be taken for any kind of heading";
+        let text = format!("One sentence.\n\nAnother one.\n\n{}\n\nText.", long_line);
+        assert!(chapters(&text).is_empty());
+    }
+}
@@ -4,7 +4,10 @@
 //! These structs are independent of any database or serialization format.
 
 use uuid::Uuid;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, NaiveDate, Utc};
+
+use crate::chunker::DocumentStructure;
+use crate::session_machine::SessionMode;
 
 
 #[derive(Debug, Clone)]
@@ -15,6 +18,10 @@ pub struct Session {
     pub reading_progress_index: usize,
     pub created_at: DateTime<Utc>,  // ✅ Add this
     pub last_accessed_at: DateTime<Utc>,  // ✅ Add this
+    /// Child/education mode: age-appropriate prompts and stricter moderation.
+    pub education_mode: bool,
+    /// The user's title for this session, shown instead of the document's.
+    pub title: Option<String>,
 }
 
 /// Represents a text document uploaded by a user.
@@ -22,7 +29,44 @@ pub struct Session {
 pub struct Document {
     pub id: Uuid,
     pub user_id: Uuid,
+    /// The file name or article title it was created with, unless the user changed it.
+    pub title: Option<String>,
     pub original_text: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A document as listed in the user's library, without its text.
+#[derive(Debug, Clone)]
+pub struct DocumentSummary {
+    pub id: Uuid,
+    pub title: Option<String>,
+    /// The start of the document's text.
+    pub preview: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Who else depends on a document, which its owner deleting it would affect.
+#[derive(Debug, Clone, Default)]
+pub struct DocumentUsage {
+    /// Users it is shared with.
+    pub shares: usize,
+    /// Sessions on it that belong to other users, e.g. opened from a workspace or the gallery.
+    pub other_users_sessions: usize,
+    /// Workspaces it was added to.
+    pub workspaces: usize,
+    pub in_gallery: bool,
+}
+
+/// A document's persisted chunks as a session opens them, without any text.
+#[derive(Debug, Clone)]
+pub struct DocumentLayout {
+    pub sentence_count: usize,
+    /// The chunker version the chunks were split with; 0 for chunks stored before
+    /// versions were recorded.
+    pub chunker_version: i32,
+    /// Where the chunks' paragraphs and chapters start, unless it was never recorded
+    /// for this chunker version.
+    pub structure: Option<DocumentStructure>,
 }
 
 // Represents a user - used throughout app
@@ -30,6 +74,9 @@ pub struct Document {
 pub struct User {
     pub user_id: Uuid,
     pub email: Option<String>,  // Optional because old users won't have it
+    /// An anonymous trial user, deleted after `guest_expires_at` unless they sign up.
+    pub is_guest: bool,
+    pub guest_expires_at: Option<DateTime<Utc>>,
 }
 
 // Only used internally for login/signup - contains sensitive data
@@ -48,6 +95,71 @@ pub struct AuthSession {
     pub expires_at: DateTime<Utc>,
 }
 
+/// The moderation verdict for a piece of text.
+#[derive(Debug, Clone)]
+pub struct ModerationResult {
+    /// Whether the provider flagged the text under its default policy.
+    pub flagged: bool,
+    /// The highest score across all categories, from 0.0 to 1.0.
+    pub max_category_score: f32,
+}
+
+/// How long spoken answers to questions should be.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnswerVerbosity {
+    Brief,
+    #[default]
+    Normal,
+    Detailed,
+}
+
+/// The reading level a passage is re-explained at when the user asks for it "more simply".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReadingLevel {
+    /// For young readers, around 8 years old.
+    Elementary,
+    /// For readers around 12 years old.
+    MiddleSchool,
+    /// Plain everyday language for adults.
+    #[default]
+    General,
+}
+
+/// Per-user settings. Users without a stored row get the defaults.
+#[derive(Debug, Clone, Default)]
+pub struct UserPreferences {
+    pub answer_verbosity: AnswerVerbosity,
+    /// Speak a one-sentence recap when a session is reopened after a long break.
+    pub resume_recap: bool,
+    /// Silence added after each narrated sentence, in milliseconds.
+    pub sentence_gap_ms: u32,
+    /// Fade applied to both ends of each narrated sentence, in milliseconds.
+    pub crossfade_ms: u32,
+    /// Take key-point notes on each paragraph as it is read, without being asked.
+    pub auto_notes: bool,
+    /// Active reading: stop every this many paragraphs for one quick question about
+    /// them. `None` reads straight through.
+    pub checkpoint_paragraphs: Option<u32>,
+    /// Write a recap of each week's notes, spoken at the start of the next session.
+    pub weekly_recap: bool,
+    /// Leave this user's sessions out of usage analytics.
+    pub analytics_opt_out: bool,
+    /// The voice to read in; `None` uses the server's default voice.
+    pub voice: Option<String>,
+    /// The speaking speed, where 1.0 is normal; `None` reads at normal speed.
+    pub speaking_speed: Option<f32>,
+    /// The level "explain this more simply" re-explains passages at.
+    pub reading_level: ReadingLevel,
+}
+
+/// A tag attached to a document. Suggested tags come from the LLM and
+/// become regular tags once the user confirms them.
+#[derive(Debug, Clone)]
+pub struct DocumentTag {
+    pub tag: String,
+    pub suggested: bool,
+}
+
 /// Represents a single question-and-answer exchange within a session.
 #[derive(Debug, Clone)]
 pub struct QAPair {
@@ -55,13 +167,540 @@ pub struct QAPair {
     pub session_id: Uuid,
     pub question_text: String,
     pub answer_text: String,
+    /// The user stopped the answer partway; `answer_text` is only what was sent before.
+    pub answer_interrupted: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+/// An answer the user stopped before the end, kept so they can ask to hear the rest.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InterruptedAnswer {
+    /// The saved question and answer, which holds the part that was heard.
+    pub qa_pair_id: Uuid,
+    /// The sentences sent before the user stopped it.
+    pub heard: Vec<String>,
+    /// The sentences still to be sent. For a streamed answer, only those written
+    /// before it was stopped.
+    pub remaining: Vec<String>,
+}
+
+/// A saved question whose note hasn't been written yet. The answer is saved before it is
+/// spoken, so a note cut short by a crash or restart can be written afterwards.
+#[derive(Debug, Clone)]
+pub struct PendingNote {
+    pub qa_pair: QAPair,
+    /// The first sentence of the passage the note links back to.
+    pub source_start: usize,
+    /// One past the last sentence of that passage.
+    pub source_end: usize,
+    pub education_mode: bool,
+}
+
+/// The parts of a live session that are otherwise only kept in memory, saved every few
+/// seconds and when its connection closes so a client that reconnects, even after a
+/// crash, can pick up where it was.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionSnapshot {
+    pub session_id: Uuid,
+    pub mode: SessionMode,
+    pub reading_progress_index: usize,
+    pub last_question: Option<String>,
+    pub last_answer: Option<String>,
+    /// The question a clarifying question was asked about, while `Clarifying`.
+    pub pending_clarification: Option<String>,
+    pub interrupted_answer: Option<InterruptedAnswer>,
+    pub saved_at: DateTime<Utc>,
+}
+
+/// A comprehension question generated from a passage, with the answer it expects.
+#[derive(Debug, Clone)]
+pub struct QuizQuestion {
+    pub question: String,
+    pub expected_answer: String,
+}
+
+/// The QA model's reply to a question.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum QaReply {
+    /// `sources` are the indices of the document sentences the answer is based on,
+    /// as numbered in the context.
+    Answer { text: String, sources: Vec<usize> },
+    /// The question was ambiguous; this asks the user what they meant.
+    Clarification(String),
+}
+
+/// Whether an answer is backed up by the context it was written from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroundingVerdict {
+    pub supported: bool,
+    /// What the context doesn't back up, when it isn't supported.
+    pub reason: String,
+}
+
+/// A dictionary's entry for a word: its first sense only.
+#[derive(Debug, Clone)]
+pub struct Definition {
+    pub word: String,
+    /// The pronunciation in IPA, e.g. "/ˈwɜːd/", when the dictionary gives one.
+    pub phonetic: Option<String>,
+    pub part_of_speech: Option<String>,
+    pub meaning: String,
+}
+
+/// A word the user had defined during a session.
+#[derive(Debug, Clone)]
+pub struct GlossaryEntry {
+    pub id: Uuid,
+    pub session_id: Uuid,
+    pub word: String,
+    pub part_of_speech: Option<String>,
+    pub meaning: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// The LLM's verdict on a user's spoken answer to a quiz question.
+#[derive(Debug, Clone)]
+pub struct QuizGrade {
+    pub correct: bool,
+    pub feedback: String,
 }
 
-/// Represents a single, summarized note generated from a QAPair.
+/// A graded answer to a quiz question, stored for later review.
+#[derive(Debug, Clone)]
+pub struct QuizAttempt {
+    pub id: Uuid,
+    pub session_id: Uuid,
+    pub question_text: String,
+    pub expected_answer: String,
+    pub user_answer: String,
+    pub correct: bool,
+    pub feedback: String,
+}
+
+/// What a note was generated from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NoteSource {
+    /// A question the user asked and its answer.
+    #[default]
+    Question,
+    /// The key points of a paragraph, extracted as it was read.
+    Auto,
+    /// The session's other notes, condensed.
+    Summary,
+    /// A recap of everything the user noted in one week, across sessions.
+    WeeklyRecap,
+    /// A highlight or note imported from another reading app.
+    Imported,
+}
+
+/// Represents a single, summarized note generated from a QAPair or a passage.
 #[derive(Debug, Clone)]
 pub struct Note {
     pub id: Uuid,
     pub session_id: Uuid,
     pub generated_note_text: String,
     pub created_at: DateTime<Utc>,
-}
\ No newline at end of file
+    /// The sentences (end exclusive) the note was taken on: the passage being read when
+    /// its question was asked, or the paragraph an auto note covers. `None` for summaries.
+    pub source_start_index: Option<usize>,
+    pub source_end_index: Option<usize>,
+    pub source: NoteSource,
+}
+
+/// A recap of one week's notes, stored as a `NoteSource::WeeklyRecap` note.
+#[derive(Debug, Clone)]
+pub struct WeeklyRecap {
+    pub user_id: Uuid,
+    /// The Monday the recapped week started on.
+    pub week_start: NaiveDate,
+    pub text: String,
+    /// When the recap was spoken at the start of a session, if it has been.
+    pub spoken_at: Option<DateTime<Utc>>,
+}
+
+/// The passage of another of the user's documents that best matches a question.
+#[derive(Debug, Clone)]
+pub struct RelatedPassage {
+    pub document_id: Uuid,
+    /// The user's most recent session on the document, if they have one.
+    pub session_id: Option<Uuid>,
+    pub chunk_index: usize,
+    pub text: String,
+    /// Cosine similarity to the question, from -1.0 to 1.0.
+    pub similarity: f32,
+}
+
+/// A cheap fingerprint of a list, for telling whether it changed without loading it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListVersion {
+    pub count: usize,
+    /// When the most recently changed item last changed; `None` for an empty list.
+    pub last_changed_at: Option<DateTime<Utc>>,
+}
+
+/// A document available for import from an external source (e.g. Google Drive).
+#[derive(Debug, Clone)]
+pub struct ExternalDocument {
+    pub id: String,
+    pub title: String,
+}
+
+/// An RSS/Atom feed a user has subscribed to for their reading queue.
+#[derive(Debug, Clone)]
+pub struct Feed {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub url: String,
+    pub last_fetched_at: Option<DateTime<Utc>>,
+}
+
+/// The ingestion status of a reading-queue item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueueItemStatus {
+    Pending,
+    Ready,
+    Failed,
+}
+
+/// A link waiting to be (or already) ingested into the user's reading queue.
+#[derive(Debug, Clone)]
+pub struct QueueItem {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub feed_id: Option<Uuid>,
+    pub url: String,
+    pub title: Option<String>,
+    pub status: QueueItemStatus,
+    pub session_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A single entry discovered in a feed.
+#[derive(Debug, Clone)]
+pub struct FeedEntry {
+    pub url: String,
+    pub title: Option<String>,
+}
+
+/// The role a user plays in a class workspace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WorkspaceRole {
+    Teacher,
+    Student,
+}
+
+/// A class workspace where a teacher shares documents with students.
+#[derive(Debug, Clone)]
+pub struct Workspace {
+    pub id: Uuid,
+    pub name: String,
+    pub owner_id: Uuid,
+    /// The code students enter to join the workspace.
+    pub join_code: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A document shared into a workspace.
+#[derive(Debug, Clone)]
+pub struct WorkspaceDocument {
+    pub workspace_id: Uuid,
+    pub document_id: Uuid,
+    pub title: String,
+    pub added_at: DateTime<Utc>,
+}
+
+/// A document its owner shared with another user, who can start sessions on it and
+/// read the owner's notes on it.
+#[derive(Debug, Clone)]
+pub struct DocumentShare {
+    pub document_id: Uuid,
+    pub owner_id: Uuid,
+    /// `None` for owners without an email, such as guests.
+    pub owner_email: Option<String>,
+    pub user_id: Uuid,
+    pub user_email: String,
+    pub shared_at: DateTime<Utc>,
+}
+
+/// A public-domain document in the gallery, which any user can start a session on.
+#[derive(Debug, Clone)]
+pub struct GalleryDocument {
+    pub document_id: Uuid,
+    pub title: String,
+    pub description: String,
+    pub added_at: DateTime<Utc>,
+}
+
+/// Characters synthesized by one TTS provider/model on one UTC day.
+#[derive(Debug, Clone)]
+pub struct TtsUsage {
+    pub usage_date: NaiveDate,
+    pub provider: String,
+    pub model: String,
+    pub characters: u64,
+    pub requests: u64,
+}
+
+/// Tokens reported by one QA provider/model on one UTC day.
+#[derive(Debug, Clone)]
+pub struct LlmUsage {
+    pub usage_date: NaiveDate,
+    pub provider: String,
+    pub model: String,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub requests: u64,
+}
+
+/// The tokens one model request used, as reported by the provider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TokenUsage {
+    pub prompt_tokens: u32,
+    pub completion_tokens: u32,
+}
+
+/// A model's result along with the tokens it used, when the provider reports them.
+#[derive(Debug, Clone)]
+pub struct Metered<T> {
+    pub value: T,
+    pub usage: Option<TokenUsage>,
+}
+
+/// A stage of answering a question, timed for latency reports.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LatencyStage {
+    /// Transcribing the spoken question. Typed questions skip it.
+    Stt,
+    /// From asking the model to the start of its reply (all of it, unless streamed).
+    Llm,
+    /// From the model's reply to the answer's last audio being sent. For a streamed
+    /// reply this includes the rest of the writing.
+    Tts,
+    /// The whole question, from `AnsweringStarted` to the end of the answer.
+    Total,
+}
+
+/// How long one stage of one question took.
+#[derive(Debug, Clone, Copy)]
+pub struct StageLatency {
+    pub stage: LatencyStage,
+    pub duration_ms: u32,
+}
+
+/// The spread of one stage's latency over the questions of one UTC day.
+#[derive(Debug, Clone)]
+pub struct LatencyPercentiles {
+    pub day: NaiveDate,
+    pub stage: LatencyStage,
+    pub samples: u64,
+    pub p50_ms: u32,
+    pub p90_ms: u32,
+    pub p99_ms: u32,
+}
+
+/// A user's own API key for an AI provider. The key itself is only ever held
+/// encrypted; `key_hint` shows its last few characters.
+#[derive(Debug, Clone)]
+pub struct UserApiKey {
+    pub provider: String,
+    pub encrypted_key: String,
+    pub key_hint: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// The tenant that owns all data created before multi-tenancy, and every request
+/// when multi-tenancy is disabled.
+pub const DEFAULT_TENANT_ID: Uuid = Uuid::nil();
+
+/// An isolated organization served by this deployment.
+#[derive(Debug, Clone)]
+pub struct Tenant {
+    pub id: Uuid,
+    pub slug: String,
+    pub name: String,
+    pub hostname: Option<String>,
+}
+
+/// Every user's documents, sessions and notes, read at one point in time for a backup.
+#[derive(Debug, Clone, Default)]
+pub struct BackupSnapshot {
+    pub documents: Vec<Document>,
+    pub sessions: Vec<Session>,
+    pub notes: Vec<Note>,
+}
+
+/// A stored backup file.
+#[derive(Debug, Clone)]
+pub struct BackupInfo {
+    pub name: String,
+    pub size_bytes: u64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Data left inconsistent, e.g. by a failure between two writes, or by a restore or
+/// migration that bypassed the schema's foreign keys.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntegrityReport {
+    /// Sessions whose document no longer exists. Repaired by deleting them, with
+    /// their questions and notes.
+    pub sessions_without_document: Vec<Uuid>,
+    /// Notes whose session no longer exists. Repaired by deleting them.
+    pub notes_without_session: Vec<Uuid>,
+    /// Questions with recorded latencies that no longer exist. Repaired by deleting
+    /// their latencies.
+    pub latencies_without_question: Vec<Uuid>,
+    /// Documents without sentence chunks, which can't be read. Repaired by chunking
+    /// their text again.
+    pub documents_without_chunks: Vec<Uuid>,
+    /// Sessions positioned past the end of their document. Repaired by moving them
+    /// to the end.
+    pub sessions_past_end: Vec<Uuid>,
+    /// Read-later links marked ready whose session was deleted. Repaired by removing
+    /// them, so the link can be saved again.
+    pub queue_items_without_session: Vec<Uuid>,
+    /// Whether what was found has been repaired.
+    pub repaired: bool,
+}
+
+impl IntegrityReport {
+    /// Whether nothing inconsistent was found.
+    pub fn is_clean(&self) -> bool {
+        self.sessions_without_document.is_empty()
+            && self.notes_without_session.is_empty()
+            && self.latencies_without_question.is_empty()
+            && self.documents_without_chunks.is_empty()
+            && self.sessions_past_end.is_empty()
+            && self.queue_items_without_session.is_empty()
+    }
+}
+
+/// A feature a listener used, recorded for usage analytics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum UsageEventKind {
+    SessionOpened,
+    QuestionAsked,
+    Paused,
+    Resumed,
+    Seeked,
+    QuizStarted,
+    /// Spoken commands, told apart from questions by the intent classifier.
+    ResumeCommand,
+    SeekCommand,
+    SummaryCommand,
+    SpeedCommand,
+}
+
+/// One use of a feature. Events never carry content (questions, documents, notes) and
+/// are tied to a random ID per connection, never to a user or session.
+#[derive(Debug, Clone)]
+pub struct UsageEvent {
+    pub visit_id: Uuid,
+    pub kind: UsageEventKind,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// How often one feature was used on one day (UTC), and on how many visits.
+#[derive(Debug, Clone)]
+pub struct UsageEventCount {
+    pub day: NaiveDate,
+    pub kind: UsageEventKind,
+    pub events: u64,
+    pub visits: u64,
+}
+
+/// A listener's rating of a finished session, kept for quality tracking. A session has
+/// at most one; rating it again replaces the previous one.
+#[derive(Debug, Clone)]
+pub struct SessionRating {
+    pub session_id: Uuid,
+    /// 1 (poor) to 5 (excellent).
+    pub rating: u8,
+    pub feedback: Option<String>,
+    pub rated_at: DateTime<Utc>,
+}
+
+/// A listening goal: at least `minutes_per_day` minutes on `days_per_week` days of
+/// each week (Monday to Sunday, UTC).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadingGoal {
+    pub minutes_per_day: u32,
+    pub days_per_week: u8,
+    /// Send a reminder when the week's goal can only just still be met.
+    pub reminders: bool,
+}
+
+/// How long a user listened on one day (UTC).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ListeningDay {
+    pub day: NaiveDate,
+    pub seconds: u64,
+}
+
+/// A message for a user outside of a session, e.g. a goal reminder.
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub title: String,
+    pub body: String,
+}
+
+/// What a planned session is for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanKind {
+    /// Reading on from where the session left off.
+    Reading,
+    /// Going back over the session's notes and questions.
+    Review,
+}
+
+/// A time a user set aside to read or review one of their sessions.
+#[derive(Debug, Clone)]
+pub struct PlannedSession {
+    pub id: Uuid,
+    pub session_id: Uuid,
+    pub kind: PlanKind,
+    /// Shown in calendars; a default is used when absent.
+    pub title: Option<String>,
+    pub starts_at: DateTime<Utc>,
+    pub duration_minutes: u32,
+}
+
+/// How synthesized speech is encoded for a client.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AudioEncoding {
+    /// 16-bit mono WAV, one file per clip.
+    #[default]
+    Wav,
+    /// Raw 16-bit little-endian mono PCM, without a header.
+    Pcm16,
+    Mp3,
+    /// Opus in an Ogg container, for clients on low bandwidth.
+    Opus,
+}
+
+impl AudioEncoding {
+    /// Compressed clips are encoded by the TTS provider, so they can't be trimmed,
+    /// normalized, paced or resampled afterwards.
+    pub fn is_compressed(self) -> bool {
+        matches!(self, AudioEncoding::Mp3 | AudioEncoding::Opus)
+    }
+}
+
+/// The encoding and sample rate of the speech sent to one session.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct AudioFormat {
+    pub encoding: AudioEncoding,
+    /// Samples per second for `Wav` and `Pcm16`; `None` keeps the provider's own rate.
+    /// Compressed encodings always use the provider's rate.
+    pub sample_rate: Option<u32>,
+}
+
+/// How one session's speech is synthesized.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SpeechSettings {
+    /// The listener's voice; `None`, or a voice the provider doesn't offer, uses the
+    /// provider's default voice.
+    pub voice: Option<String>,
+    /// The speaking speed, where 1.0 is normal; `None` reads at normal speed.
+    pub speed: Option<f32>,
+    pub format: AudioFormat,
+}
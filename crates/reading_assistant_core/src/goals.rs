@@ -0,0 +1,138 @@
+//! crates/reading_assistant_core/src/goals.rs
+//!
+//! Progress towards a user's listening goal, worked out from how long they listened
+//! each day. Weeks run Monday to Sunday (UTC), like weekly recaps.
+
+use chrono::{Datelike, Duration, NaiveDate};
+
+use crate::domain::{ListeningDay, ReadingGoal};
+
+/// How many weeks back a streak is counted. Longer streaks are reported as this long.
+pub const STREAK_LOOKBACK_WEEKS: i64 = 52;
+
+/// Where a user stands against their goal on a given day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GoalProgress {
+    /// The Monday this week started on.
+    pub week_start: NaiveDate,
+    /// Days this week on which the daily minutes were reached.
+    pub days_met: u8,
+    /// Minutes listened today.
+    pub minutes_today: u32,
+    /// Weeks in a row the goal was met, counting this week once it is met.
+    pub streak_weeks: u32,
+    /// This week's goal can only still be met by listening every remaining day,
+    /// starting today, and a streak would end if it isn't.
+    pub at_risk: bool,
+}
+
+/// The Monday of the week `day` falls in.
+pub fn week_start(day: NaiveDate) -> NaiveDate {
+    day - Duration::days(day.weekday().num_days_from_monday() as i64)
+}
+
+/// The first day listening history is needed from to work out progress on `today`.
+pub fn history_start(today: NaiveDate) -> NaiveDate {
+    week_start(today) - Duration::weeks(STREAK_LOOKBACK_WEEKS)
+}
+
+/// Progress towards `goal` on `today`, from the listening history since
+/// `history_start(today)`. Days missing from `days` count as no listening.
+pub fn progress(goal: &ReadingGoal, days: &[ListeningDay], today: NaiveDate) -> GoalProgress {
+    let target_seconds = u64::from(goal.minutes_per_day) * 60;
+    let seconds_on = |day: NaiveDate| -> u64 {
+        days.iter()
+            .filter(|listened| listened.day == day)
+            .map(|listened| listened.seconds)
+            .sum()
+    };
+    let days_met_in = |start: NaiveDate, end: NaiveDate| -> u8 {
+        start
+            .iter_days()
+            .take_while(|day| *day <= end)
+            .filter(|day| seconds_on(*day) >= target_seconds)
+            .count() as u8
+    };
+
+    let this_week = week_start(today);
+    let days_met = days_met_in(this_week, today);
+    let week_met = days_met >= goal.days_per_week;
+
+    let mut streak_weeks = 0;
+    let mut start = this_week;
+    for _ in 0..STREAK_LOOKBACK_WEEKS {
+        start -= Duration::weeks(1);
+        if days_met_in(start, start + Duration::days(6)) < goal.days_per_week {
+            break;
+        }
+        streak_weeks += 1;
+    }
+
+    let today_met = seconds_on(today) >= target_seconds;
+    let days_left = 7 - today.weekday().num_days_from_monday() as u8;
+    let days_needed = goal.days_per_week.saturating_sub(days_met);
+    let at_risk = !week_met && !today_met && streak_weeks > 0 && days_left == days_needed;
+
+    GoalProgress {
+        week_start: this_week,
+        days_met,
+        minutes_today: (seconds_on(today) / 60) as u32,
+        streak_weeks: streak_weeks + u32::from(week_met),
+        at_risk,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(day: u32) -> NaiveDate {
+        // December 2025: the 1st is a Monday.
+        NaiveDate::from_ymd_opt(2025, 12, day).unwrap()
+    }
+
+    fn listened(day: u32, minutes: u64) -> ListeningDay {
+        ListeningDay { day: date(day), seconds: minutes * 60 }
+    }
+
+    const GOAL: ReadingGoal = ReadingGoal { minutes_per_day: 30, days_per_week: 4, reminders: true };
+
+    #[test]
+    fn counts_this_week_and_the_streak_before_it() {
+        // Last week: 4 days of 30+ minutes. This week: 2 so far, one day too short.
+        let days = [
+            listened(1, 45),
+            listened(2, 30),
+            listened(3, 60),
+            listened(5, 31),
+            listened(8, 30),
+            listened(9, 29),
+            listened(10, 40),
+        ];
+        let progress = progress(&GOAL, &days, date(10));
+        assert_eq!(progress.week_start, date(8));
+        assert_eq!(progress.days_met, 2);
+        assert_eq!(progress.minutes_today, 40);
+        assert_eq!(progress.streak_weeks, 1);
+        assert!(!progress.at_risk);
+    }
+
+    #[test]
+    fn at_risk_only_when_every_remaining_day_is_needed() {
+        let last_week = [listened(1, 30), listened(2, 30), listened(3, 30), listened(4, 30)];
+        // Thursday with nothing this week: four days left, four needed.
+        assert!(progress(&GOAL, &last_week, date(11)).at_risk);
+        // Wednesday: one day to spare.
+        assert!(!progress(&GOAL, &last_week, date(10)).at_risk);
+        // Friday: the week is already lost.
+        assert!(!progress(&GOAL, &last_week, date(12)).at_risk);
+
+        // Once today's minutes are in, there's nothing to remind about.
+        let mut listened_today = last_week.to_vec();
+        listened_today.push(listened(11, 30));
+        assert!(!progress(&GOAL, &listened_today, date(11)).at_risk);
+
+        // Without a streak there's nothing to break.
+        assert!(!progress(&GOAL, &[], date(11)).at_risk);
+    }
+}
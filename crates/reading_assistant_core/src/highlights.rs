@@ -0,0 +1,259 @@
+//! crates/reading_assistant_core/src/highlights.rs
+//!
+//! Parses highlights exported from other reading apps: Kindle's `My Clippings.txt`
+//! and Readwise's CSV export. Both are grouped by book so each book can become a
+//! document, or be attached to one the user already has.
+
+use thiserror::Error;
+
+/// One passage the user highlighted, with the note they wrote on it, if any.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Highlight {
+    /// Empty for a note that wasn't attached to any highlight.
+    pub text: String,
+    pub note: Option<String>,
+}
+
+/// All highlights from one book, in the order they appear in the export.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BookHighlights {
+    pub title: String,
+    pub author: Option<String>,
+    pub highlights: Vec<Highlight>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum HighlightParseError {
+    #[error("the CSV has no '{0}' column")]
+    MissingColumn(&'static str),
+    #[error("the CSV has a quoted field that is never closed")]
+    UnterminatedQuote,
+}
+
+/// Kindle separates clippings with a line of ten equals signs.
+const KINDLE_SEPARATOR: &str = "==========";
+
+/// Parses either format, telling them apart by the file name and the CSV header.
+pub fn parse_highlights(
+    file_name: &str,
+    text: &str,
+) -> Result<Vec<BookHighlights>, HighlightParseError> {
+    let text = text.trim_start_matches('\u{feff}');
+    let first_line = text.lines().next().unwrap_or_default();
+    if file_name.to_lowercase().ends_with(".csv")
+        || (first_line.contains("Highlight") && first_line.contains("Book Title"))
+    {
+        parse_readwise_csv(text)
+    } else {
+        Ok(parse_kindle_clippings(text))
+    }
+}
+
+/// Parses Kindle's `My Clippings.txt`. Each clipping is a title line ("Title (Author)"),
+/// a metadata line ("- Your Highlight on page 3 | Location 40-42 | Added on ..."), a
+/// blank line and the text. Notes are attached to the highlight before them, bookmarks
+/// are skipped, and repeated highlights (Kindle appends one per edit) are dropped.
+pub fn parse_kindle_clippings(text: &str) -> Vec<BookHighlights> {
+    let mut books: Vec<BookHighlights> = Vec::new();
+
+    for clipping in text.split(KINDLE_SEPARATOR) {
+        let mut lines = clipping.lines().map(|line| line.trim_start_matches('\u{feff}').trim());
+        let Some(title_line) = lines.by_ref().find(|line| !line.is_empty()) else {
+            continue;
+        };
+        let metadata = lines.next().unwrap_or_default();
+        let body = lines.collect::<Vec<_>>().join("\n").trim().to_string();
+        if body.is_empty() || metadata.contains("Bookmark") {
+            continue;
+        }
+
+        let (title, author) = split_title_and_author(title_line);
+        let book = book_entry(&mut books, title, author);
+        if metadata.contains("Note") {
+            match book.highlights.last_mut() {
+                Some(highlight) if highlight.note.is_none() && !highlight.text.is_empty() => {
+                    highlight.note = Some(body);
+                }
+                _ => book.highlights.push(Highlight { text: String::new(), note: Some(body) }),
+            }
+        } else if !book.highlights.iter().any(|h| h.text == body) {
+            book.highlights.push(Highlight { text: body, note: None });
+        }
+    }
+
+    books
+}
+
+/// Parses Readwise's CSV export, which has a header row naming its columns.
+pub fn parse_readwise_csv(text: &str) -> Result<Vec<BookHighlights>, HighlightParseError> {
+    let mut rows = parse_csv(text)?.into_iter();
+    let header = rows.next().unwrap_or_default();
+    let column = |name: &'static str| {
+        header
+            .iter()
+            .position(|h| h.trim() == name)
+            .ok_or(HighlightParseError::MissingColumn(name))
+    };
+    let highlight_col = column("Highlight")?;
+    let title_col = column("Book Title")?;
+    let author_col = column("Book Author").ok();
+    let note_col = column("Note").ok();
+
+    let field = |row: &[String], col: Option<usize>| {
+        col.and_then(|c| row.get(c))
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+
+    let mut books: Vec<BookHighlights> = Vec::new();
+    for row in rows {
+        let text = field(&row, Some(highlight_col)).unwrap_or_default();
+        let note = field(&row, note_col);
+        if text.is_empty() && note.is_none() {
+            continue;
+        }
+        let title = field(&row, Some(title_col)).unwrap_or_else(|| "Untitled".to_string());
+        let book = book_entry(&mut books, title, field(&row, author_col));
+        book.highlights.push(Highlight { text, note });
+    }
+
+    Ok(books)
+}
+
+/// Returns the book with this title and author, adding it if it's new.
+fn book_entry(
+    books: &mut Vec<BookHighlights>,
+    title: String,
+    author: Option<String>,
+) -> &mut BookHighlights {
+    let index = match books.iter().position(|b| b.title == title && b.author == author) {
+        Some(index) => index,
+        None => {
+            books.push(BookHighlights { title, author, highlights: Vec::new() });
+            books.len() - 1
+        }
+    };
+    &mut books[index]
+}
+
+/// Splits "Title (Author)" into its parts. Titles without a trailing parenthesis
+/// have no author.
+fn split_title_and_author(line: &str) -> (String, Option<String>) {
+    if let Some(without_close) = line.strip_suffix(')') {
+        if let Some(open) = without_close.rfind('(') {
+            let title = without_close[..open].trim();
+            let author = without_close[open + 1..].trim();
+            if !title.is_empty() && !author.is_empty() {
+                return (title.to_string(), Some(author.to_string()));
+            }
+        }
+    }
+    (line.to_string(), None)
+}
+
+/// A minimal RFC 4180 reader: comma-separated fields, optionally quoted, where quoted
+/// fields may contain commas, newlines and doubled quotes.
+fn parse_csv(text: &str) -> Result<Vec<Vec<String>>, HighlightParseError> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', true) => in_quotes = false,
+            ('"', false) if field.is_empty() => in_quotes = true,
+            (',', false) => row.push(std::mem::take(&mut field)),
+            ('\r', false) => {}
+            ('\n', false) => {
+                row.push(std::mem::take(&mut field));
+                if row.iter().any(|f| !f.is_empty()) {
+                    rows.push(std::mem::take(&mut row));
+                }
+                row.clear();
+            }
+            (c, _) => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(HighlightParseError::UnterminatedQuote);
+    }
+    row.push(field);
+    if row.iter().any(|f| !f.is_empty()) {
+        rows.push(row);
+    }
+
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn kindle_clippings_are_grouped_by_book_with_notes_attached() {
+        let clippings = "\u{feff}Dune (Frank Herbert)\n\
+            - Your Highlight on page 4 | Location 60-61 | Added on Monday, 1 May 2023\n\n\
+            Fear is the mind-killer.\n\
+            ==========\n\
+            Dune (Frank Herbert)\n\
+            - Your Note on page 4 | Location 61 | Added on Monday, 1 May 2023\n\n\
+            The litany.\n\
+            ==========\n\
+            Dune (Frank Herbert)\n\
+            - Your Bookmark on page 9 | Location 120 | Added on Monday, 1 May 2023\n\n\n\
+            ==========\n\
+            Dune (Frank Herbert)\n\
+            - Your Highlight on page 4 | Location 60-61 | Added on Monday, 1 May 2023\n\n\
+            Fear is the mind-killer.\n\
+            ==========\n";
+
+        let books = parse_highlights("My Clippings.txt", clippings).unwrap();
+        assert_eq!(
+            books,
+            vec![BookHighlights {
+                title: "Dune".to_string(),
+                author: Some("Frank Herbert".to_string()),
+                highlights: vec![Highlight {
+                    text: "Fear is the mind-killer.".to_string(),
+                    note: Some("The litany.".to_string()),
+                }],
+            }]
+        );
+    }
+
+    #[test]
+    fn readwise_csv_handles_quoted_fields() {
+        let csv = "Highlight,Book Title,Book Author,Amazon Book ID,Note\r\n\
+            \"One, two\nthree \"\"four\"\"\",Numbers,Ann,,\r\n\
+            Plain,Numbers,Ann,,Mine\r\n";
+
+        let books = parse_highlights("readwise.csv", csv).unwrap();
+        assert_eq!(books.len(), 1);
+        assert_eq!(books[0].author.as_deref(), Some("Ann"));
+        assert_eq!(
+            books[0].highlights,
+            vec![
+                Highlight { text: "One, two\nthree \"four\"".to_string(), note: None },
+                Highlight { text: "Plain".to_string(), note: Some("Mine".to_string()) },
+            ]
+        );
+    }
+
+    #[test]
+    fn readwise_csv_requires_its_columns() {
+        assert_eq!(
+            parse_readwise_csv("Title,Text\nA,B\n"),
+            Err(HighlightParseError::MissingColumn("Highlight"))
+        );
+        assert_eq!(
+            parse_readwise_csv("Highlight,Book Title\n\"open,A\n"),
+            Err(HighlightParseError::UnterminatedQuote)
+        );
+    }
+}
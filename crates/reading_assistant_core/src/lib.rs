@@ -1,7 +1,15 @@
+pub mod audio_format;
+pub mod calendar;
+pub mod chunker;
 pub mod domain;
+pub mod goals;
+pub mod highlights;
 pub mod ports;
+pub mod sentence_stream;
+pub mod sentence_window;
+pub mod session_machine;
 
-pub use domain::{Document, Note, QAPair, Session,User, UserCredentials, AuthSession};
-pub use ports::{ DatabaseService, NoteGenerationService, PortError, PortResult, QuestionAnsweringService,
-    SpeechToTextService, TextToSpeechService};
+pub use domain::{AnswerVerbosity, AudioEncoding, AudioFormat, BackupInfo, BackupSnapshot, Document, DocumentTag, ExternalDocument, Feed, FeedEntry, ListVersion, ListeningDay, ModerationResult, Note, NoteSource, Notification, PlanKind, PlannedSession, QAPair, QaReply, QueueItem, QueueItemStatus, QuizAttempt, QuizGrade, QuizQuestion, ReadingGoal, RelatedPassage, Session, SessionRating, SpeechSettings, User, Tenant, TtsUsage, UsageEvent, UsageEventCount, UsageEventKind, UserApiKey, UserCredentials, UserPreferences, AuthSession, WeeklyRecap, Workspace, WorkspaceDocument, WorkspaceRole, DEFAULT_TENANT_ID};
+pub use ports::{ AnalyticsService, AudioCacheService, BackupStorageService, ContentFetchService, DatabaseService, DocumentImportService, EmbeddingService, ModerationService, NoteExportService, NoteGenerationService, NotificationService, PortError, PortResult, QaReplyStream, QuestionAnsweringService,
+    SpeechToTextService, TextCleanupService, TextToSpeechService};
 
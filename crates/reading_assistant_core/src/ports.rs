@@ -7,9 +7,14 @@
 use async_trait::async_trait;
 use uuid::Uuid;
 use futures::Stream;
+use std::ops::Range;
 use std::pin::Pin;
-use chrono::{DateTime, Utc};
-use crate::domain::{Document, Note, QAPair, Session, User, UserCredentials};
+use chrono::{DateTime, NaiveDate, Utc};
+use crate::chunker::DocumentStructure;
+use crate::domain::{
+    AnswerVerbosity, AudioEncoding, BackupInfo, BackupSnapshot, Definition, Document, DocumentLayout, DocumentShare, DocumentSummary, DocumentUsage, DocumentTag, ExternalDocument, Feed, FeedEntry, GalleryDocument, GlossaryEntry, GroundingVerdict, IntegrityReport, LatencyPercentiles, ListVersion, LlmUsage, Metered, ListeningDay, ModerationResult, Note, Notification, PendingNote, PlanKind, PlannedSession, QAPair, QaReply, QueueItem, QuizAttempt, QuizGrade, QuizQuestion, ReadingGoal, ReadingLevel, RelatedPassage, Session, SessionRating, SessionSnapshot, SpeechSettings, StageLatency, Tenant, User,
+    TokenUsage, TtsUsage, UsageEvent, UsageEventCount, UserApiKey, UserCredentials, UserPreferences, WeeklyRecap, Workspace, WorkspaceDocument, WorkspaceRole,
+};
 
 //=========================================================================================
 // Generic Port Error and Result Types
@@ -30,6 +35,26 @@ pub enum PortError {
 /// A convenience type alias for `Result<T, PortError>`.
 pub type PortResult<T> = Result<T, PortError>;
 
+/// An answer as the model writes it, a few characters at a time.
+pub type AnswerStream = Pin<Box<dyn Stream<Item = PortResult<String>> + Send>>;
+
+/// The tokens a streamed answer used, sent once the stream has ended. Providers that
+/// don't report usage drop the sender, so the receiver resolves to `Canceled`.
+pub type UsageReceiver = futures::channel::oneshot::Receiver<TokenUsage>;
+
+/// The QA model's reply to a question, with the answer streamed.
+pub enum QaReplyStream {
+    /// `sources` are the indices of the document sentences the answer is based on,
+    /// as numbered in the context.
+    Answer {
+        stream: AnswerStream,
+        sources: Vec<usize>,
+        usage: UsageReceiver,
+    },
+    /// The question was ambiguous; this asks the user what they meant.
+    Clarification(String),
+}
+
 //=========================================================================================
 // Service Ports (Traits)
 //=========================================================================================
@@ -39,14 +64,21 @@ pub trait DatabaseService: Send + Sync {
     // --- User Management ---
     async fn get_or_create_user(&self, user_id: Uuid) -> PortResult<User>;
     
+    // --- Tenants ---
+    async fn get_tenant_by_slug(&self, slug: &str) -> PortResult<Tenant>;
+
+    async fn get_tenant_by_hostname(&self, hostname: &str) -> PortResult<Tenant>;
+
     // --- Auth Methods ---
     async fn create_user_with_email(
         &self,
+        tenant_id: Uuid,
         email: &str,
         hashed_password: &str,
     ) -> PortResult<User>;
     
-    async fn get_user_by_email(&self, email: &str) -> PortResult<UserCredentials>;
+    /// Looks up a user by email within one tenant; emails are unique per tenant.
+    async fn get_user_by_email(&self, tenant_id: Uuid, email: &str) -> PortResult<UserCredentials>;
     
     async fn create_auth_session(
         &self,
@@ -55,13 +87,77 @@ pub trait DatabaseService: Send + Sync {
         expires_at: DateTime<Utc>,
     ) -> PortResult<()>;
     
-    async fn validate_auth_session(&self, session_id: &str) -> PortResult<Uuid>;
+    /// Returns the session's user, or `Unauthorized` if it is expired or the user
+    /// belongs to another tenant.
+    async fn validate_auth_session(&self, session_id: &str, tenant_id: Uuid) -> PortResult<Uuid>;
     
     async fn delete_auth_session(&self, session_id: &str) -> PortResult<()>;
 
+    // --- Guest Users ---
+    async fn get_user(&self, user_id: Uuid) -> PortResult<User>;
+
+    async fn create_guest_user(
+        &self,
+        tenant_id: Uuid,
+        expires_at: DateTime<Utc>,
+        client_ip: &str,
+    ) -> PortResult<User>;
+
+    /// Counts guests created from `client_ip` since `since`, for rate limiting.
+    async fn count_guests_created_since(&self, client_ip: &str, since: DateTime<Utc>) -> PortResult<usize>;
+
+    /// Turns a guest into a regular account, keeping all of their documents and sessions.
+    /// Returns `NotFound` if the user is not a guest.
+    async fn convert_guest_user(
+        &self,
+        user_id: Uuid,
+        email: &str,
+        hashed_password: &str,
+    ) -> PortResult<User>;
+
+    /// Deletes expired guests and everything they own. Returns how many were deleted.
+    async fn delete_expired_guests(&self) -> PortResult<u64>;
+
+    // --- User Preferences ---
+    /// Returns the user's preferences, or the defaults if they never saved any.
+    async fn get_user_preferences(&self, user_id: Uuid) -> PortResult<UserPreferences>;
+
+    async fn save_user_preferences(
+        &self,
+        user_id: Uuid,
+        preferences: &UserPreferences,
+    ) -> PortResult<()>;
+
     // --- Document Management ---
     async fn get_document_by_id(&self, document_id: Uuid) -> PortResult<Document>;
+
+    /// Lists a user's documents, newest first, skipping the first `offset` and returning
+    /// at most `limit` (all of them with `None`). Previews are cut to `preview_chars`
+    /// characters, so no document's text is loaded in full.
+    async fn get_document_summaries_by_user(
+        &self,
+        user_id: Uuid,
+        offset: usize,
+        limit: Option<usize>,
+        preview_chars: usize,
+    ) -> PortResult<Vec<DocumentSummary>>;
+
+    /// Deletes a document with its chunks and every session on it, including their
+    /// questions and notes.
+    async fn delete_document(&self, document_id: Uuid) -> PortResult<()>;
+
+    /// Sets a document's title, or clears it with `None`.
+    async fn set_document_title(&self, document_id: Uuid, title: Option<&str>) -> PortResult<()>;
+
+    /// Reads every document (decrypted), session and note in one consistent snapshot.
+    async fn get_backup_snapshot(&self) -> PortResult<BackupSnapshot>;
+
+    /// Looks for inconsistent data across all tenants, and repairs it if `repair` is set.
+    /// Documents without chunks are repaired first, so the sessions on them are checked
+    /// against their new chunks.
+    async fn audit_integrity(&self, repair: bool) -> PortResult<IntegrityReport>;
     
+    /// Stores a document together with its sentence chunks.
     async fn create_document(
         &self,
         user_id: Uuid,
@@ -69,27 +165,449 @@ pub trait DatabaseService: Send + Sync {
         original_text: &str,
     ) -> PortResult<Document>;
 
+    /// Returns the persisted sentence chunks of a document, in reading order.
+    /// Documents created before chunks were persisted return an empty list.
+    async fn get_document_chunks(&self, document_id: Uuid) -> PortResult<Vec<String>>;
+
+    /// Returns how many sentence chunks a document has been persisted with.
+    async fn count_document_chunks(&self, document_id: Uuid) -> PortResult<usize>;
+
+    /// Returns the persisted chunks of a document with indices in `range`, in reading
+    /// order. Indices past the last chunk are ignored.
+    async fn get_document_chunk_range(
+        &self,
+        document_id: Uuid,
+        range: Range<usize>,
+    ) -> PortResult<Vec<String>>;
+
+    /// Returns the persisted chunks of a document at `indices`, as (index, text) in
+    /// reading order. Indices past the last chunk are ignored.
+    async fn get_document_chunks_at(
+        &self,
+        document_id: Uuid,
+        indices: &[usize],
+    ) -> PortResult<Vec<(usize, String)>>;
+
+    /// Returns how many chunks a document has been persisted with and, if recorded,
+    /// where their paragraphs and chapters start. `NotFound` if it doesn't exist.
+    async fn get_document_layout(&self, document_id: Uuid) -> PortResult<DocumentLayout>;
+
+    /// Replaces a document's chunks, and the structure recorded for them.
+    async fn save_document_chunks(
+        &self,
+        document_id: Uuid,
+        chunker_version: i32,
+        chunks: &[String],
+        structure: &DocumentStructure,
+    ) -> PortResult<()>;
+
+    /// Records the structure of a document's persisted chunks, for documents whose
+    /// chunks were stored before it was recorded alongside them.
+    async fn save_document_structure(
+        &self,
+        document_id: Uuid,
+        structure: &DocumentStructure,
+    ) -> PortResult<()>;
+
+    /// Returns the chunks of a document that have no embedding yet, as (index, text).
+    async fn get_unembedded_chunks(&self, document_id: Uuid) -> PortResult<Vec<(usize, String)>>;
+
+    /// Stores embeddings for chunks of a document, as (chunk index, embedding).
+    async fn save_chunk_embeddings(
+        &self,
+        document_id: Uuid,
+        embeddings: &[(usize, Vec<f32>)],
+    ) -> PortResult<()>;
+
+    /// Returns the indices of the `limit` chunks of a document most similar to
+    /// `embedding`, best first, with their cosine similarity to it. Chunks without an
+    /// embedding are never returned.
+    async fn search_document_chunks(
+        &self,
+        document_id: Uuid,
+        embedding: &[f32],
+        limit: usize,
+    ) -> PortResult<Vec<(usize, f32)>>;
+
+    /// Returns the chunk most similar to `embedding` among the user's other documents
+    /// (all but `exclude_document_id`), if any of them has been embedded.
+    async fn find_related_passage(
+        &self,
+        user_id: Uuid,
+        exclude_document_id: Uuid,
+        embedding: &[f32],
+    ) -> PortResult<Option<RelatedPassage>>;
+
     // --- Session Management (Reading Sessions) ---
     async fn get_session_by_id(&self, session_id: Uuid) -> PortResult<Session>;
     
-    async fn create_session(&self, user_id: Uuid, document_id: Uuid) -> PortResult<Session>;
+    async fn create_session(
+        &self,
+        user_id: Uuid,
+        document_id: Uuid,
+        education_mode: bool,
+    ) -> PortResult<Session>;
+
+    /// Deletes a session with its questions, notes and everything else kept about it.
+    async fn delete_session(&self, session_id: Uuid) -> PortResult<()>;
+
+    /// Sets the user's title for a session, or clears it with `None` so the document's
+    /// is shown again.
+    async fn set_session_title(&self, session_id: Uuid, title: Option<&str>) -> PortResult<()>;
     
+    /// Records reading progress. The session keeps the furthest index reached on any
+    /// device, while `device_id` (when given) tracks where that device last listened.
     async fn update_session_progress(
         &self,
         session_id: Uuid,
+        device_id: Option<&str>,
         new_progress_index: usize,
     ) -> PortResult<()>;
 
+    /// Returns the last position listened to on a device, if it has one.
+    async fn get_device_position(
+        &self,
+        session_id: Uuid,
+        device_id: &str,
+    ) -> PortResult<Option<usize>>;
+
+    /// Saves what an open or closing connection keeps in memory, replacing any earlier snapshot.
+    async fn save_session_snapshot(&self, snapshot: &SessionSnapshot) -> PortResult<()>;
+
+    /// Removes and returns the session's snapshot, if it has one, so it is restored once.
+    async fn take_session_snapshot(&self, session_id: Uuid) -> PortResult<Option<SessionSnapshot>>;
+
     // --- Q&A and Note Management ---
-    async fn save_qa_pair(&self, qa_pair: QAPair) -> PortResult<()>;
+    /// Saves a question and answer whose note is still to be written. Its note is only
+    /// picked up once `mark_answer_delivered` is called.
+    async fn save_pending_qa_pair(&self, pending: &PendingNote) -> PortResult<()>;
+
+    /// Marks a saved question's answer as delivered, so its note is written even if the
+    /// server stops first.
+    async fn mark_answer_delivered(&self, qa_pair_id: Uuid) -> PortResult<()>;
+
+    /// Marks a question's note as written, or as not needed.
+    async fn mark_note_done(&self, qa_pair_id: Uuid) -> PortResult<()>;
+
+    /// Marks a question's note as failed, so it isn't retried.
+    async fn mark_note_failed(&self, qa_pair_id: Uuid) -> PortResult<()>;
+
+    /// Claims up to `limit` questions saved before `before` whose note is still pending,
+    /// oldest first. Claimed notes aren't returned again, to this or any other server.
+    async fn claim_pending_notes(&self, before: DateTime<Utc>, limit: i64) -> PortResult<Vec<PendingNote>>;
+
+    /// Removes a saved question, e.g. one stopped before any of its answer was heard.
+    async fn delete_qa_pair(&self, qa_pair_id: Uuid) -> PortResult<()>;
+
+    /// Replaces a saved answer, e.g. once the rest of an interrupted answer is heard.
+    async fn update_qa_pair_answer(
+        &self,
+        qa_pair_id: Uuid,
+        answer_text: &str,
+        answer_interrupted: bool,
+    ) -> PortResult<()>;
     
     async fn get_qa_pairs_for_session(&self, session_id: Uuid) -> PortResult<Vec<QAPair>>;
     
     async fn save_note(&self, note: Note) -> PortResult<()>;
+
+    /// Adds a defined word to the session's glossary.
+    async fn save_glossary_entry(&self, session_id: Uuid, definition: &Definition) -> PortResult<GlossaryEntry>;
+
+    /// The session's glossary, oldest first.
+    async fn get_glossary_for_session(&self, session_id: Uuid) -> PortResult<Vec<GlossaryEntry>>;
+
+    /// Stores a graded quiz answer.
+    async fn save_quiz_attempt(&self, attempt: QuizAttempt) -> PortResult<()>;
     
     async fn get_notes_for_session(&self, session_id: Uuid) -> PortResult<Vec<Note>>;
 
-    async fn get_sessions_by_user(&self, user_id: Uuid) -> PortResult<Vec<Session>>;
+    /// Lists a user's sessions, optionally only those whose document carries `tag`.
+    async fn get_sessions_by_user(
+        &self,
+        user_id: Uuid,
+        tag: Option<&str>,
+    ) -> PortResult<Vec<Session>>;
+
+    /// The version of the list `get_sessions_by_user` returns. Sessions change when
+    /// they are read (`last_accessed_at`).
+    async fn get_sessions_version(&self, user_id: Uuid, tag: Option<&str>) -> PortResult<ListVersion>;
+
+    /// The version of the list `get_notes_for_session` returns. Notes never change
+    /// once written.
+    async fn get_notes_version(&self, session_id: Uuid) -> PortResult<ListVersion>;
+
+    // --- Document Tags ---
+    /// Adds tags to a document. Adding a tag that was only suggested confirms it.
+    async fn add_document_tags(
+        &self,
+        document_id: Uuid,
+        user_id: Uuid,
+        tags: &[String],
+        suggested: bool,
+    ) -> PortResult<()>;
+
+    async fn remove_document_tag(&self, document_id: Uuid, tag: &str) -> PortResult<()>;
+
+    async fn get_document_tags(&self, document_id: Uuid) -> PortResult<Vec<DocumentTag>>;
+
+    /// Returns the user's distinct confirmed tags, optionally filtered by prefix (for autocomplete).
+    async fn get_tags_by_user(&self, user_id: Uuid, prefix: Option<&str>) -> PortResult<Vec<String>>;
+
+    // --- Third-Party Integrations ---
+    async fn save_integration_token(
+        &self,
+        user_id: Uuid,
+        provider: &str,
+        access_token: &str,
+    ) -> PortResult<()>;
+
+    async fn get_integration_token(&self, user_id: Uuid, provider: &str) -> PortResult<String>;
+
+    // --- User API Keys ---
+    /// Stores (or replaces) a user's own, already encrypted, provider API key.
+    async fn save_user_api_key(
+        &self,
+        user_id: Uuid,
+        provider: &str,
+        encrypted_key: &str,
+        key_hint: &str,
+    ) -> PortResult<UserApiKey>;
+
+    async fn get_user_api_key(&self, user_id: Uuid, provider: &str) -> PortResult<Option<UserApiKey>>;
+
+    async fn delete_user_api_key(&self, user_id: Uuid, provider: &str) -> PortResult<()>;
+
+    // --- Reading Queue ---
+    async fn create_feed(&self, user_id: Uuid, url: &str) -> PortResult<Feed>;
+
+    async fn get_feeds_by_user(&self, user_id: Uuid) -> PortResult<Vec<Feed>>;
+
+    /// Returns feeds that have not been fetched since `fetched_before`.
+    async fn get_feeds_due(&self, fetched_before: DateTime<Utc>) -> PortResult<Vec<Feed>>;
+
+    async fn mark_feed_fetched(&self, feed_id: Uuid) -> PortResult<()>;
+
+    /// Adds a link to the user's queue. Links already in the queue are ignored.
+    async fn enqueue_link(
+        &self,
+        user_id: Uuid,
+        feed_id: Option<Uuid>,
+        url: &str,
+        title: Option<&str>,
+    ) -> PortResult<()>;
+
+    async fn get_pending_queue_items(&self, limit: i64) -> PortResult<Vec<QueueItem>>;
+
+    async fn mark_queue_item_ready(
+        &self,
+        item_id: Uuid,
+        title: &str,
+        session_id: Uuid,
+    ) -> PortResult<()>;
+
+    async fn mark_queue_item_failed(&self, item_id: Uuid) -> PortResult<()>;
+
+    async fn get_queue_for_user(&self, user_id: Uuid) -> PortResult<Vec<QueueItem>>;
+
+    // --- Class Workspaces ---
+    /// Creates a workspace and makes `owner_id` its teacher.
+    async fn create_workspace(
+        &self,
+        owner_id: Uuid,
+        name: &str,
+        join_code: &str,
+    ) -> PortResult<Workspace>;
+
+    /// Finds a workspace by join code among the workspaces of one tenant.
+    async fn get_workspace_by_join_code(&self, tenant_id: Uuid, join_code: &str) -> PortResult<Workspace>;
+
+    /// Lists the workspaces a user belongs to, with their role in each.
+    async fn get_workspaces_for_user(
+        &self,
+        user_id: Uuid,
+    ) -> PortResult<Vec<(Workspace, WorkspaceRole)>>;
+
+    /// Returns the user's role in a workspace, or `None` if they are not a member.
+    async fn get_workspace_role(
+        &self,
+        workspace_id: Uuid,
+        user_id: Uuid,
+    ) -> PortResult<Option<WorkspaceRole>>;
+
+    /// Adds a member. Joining again is a no-op and never changes an existing role.
+    async fn add_workspace_member(
+        &self,
+        workspace_id: Uuid,
+        user_id: Uuid,
+        role: WorkspaceRole,
+    ) -> PortResult<()>;
+
+    async fn add_workspace_document(
+        &self,
+        workspace_id: Uuid,
+        document_id: Uuid,
+        title: &str,
+    ) -> PortResult<WorkspaceDocument>;
+
+    async fn get_workspace_documents(&self, workspace_id: Uuid) -> PortResult<Vec<WorkspaceDocument>>;
+
+    async fn get_workspace_document(
+        &self,
+        workspace_id: Uuid,
+        document_id: Uuid,
+    ) -> PortResult<WorkspaceDocument>;
+
+    /// Adds a document to the public gallery, or updates its title and description.
+    async fn add_gallery_document(
+        &self,
+        document_id: Uuid,
+        title: &str,
+        description: &str,
+    ) -> PortResult<GalleryDocument>;
+
+    /// The gallery, newest first.
+    async fn get_gallery_documents(&self) -> PortResult<Vec<GalleryDocument>>;
+
+    /// Returns `PortError::NotFound` when the document is not in the gallery.
+    async fn get_gallery_document(&self, document_id: Uuid) -> PortResult<GalleryDocument>;
+
+    /// Every question asked about a document, across all sessions, oldest first.
+    async fn get_qa_pairs_for_document(&self, document_id: Uuid) -> PortResult<Vec<QAPair>>;
+
+    /// Every note generated for a document, across all sessions, oldest first.
+    async fn get_notes_for_document(&self, document_id: Uuid) -> PortResult<Vec<Note>>;
+
+    /// Notes from the document's sessions that belong to one of `user_ids`, oldest first.
+    async fn get_notes_for_document_by_users(
+        &self,
+        document_id: Uuid,
+        user_ids: &[Uuid],
+    ) -> PortResult<Vec<Note>>;
+
+    // --- Document Sharing ---
+    /// Grants `user_id` access to the document. Sharing it again changes nothing.
+    async fn share_document(&self, document_id: Uuid, user_id: Uuid) -> PortResult<DocumentShare>;
+
+    /// Returns `PortError::NotFound` when the document isn't shared with the user.
+    async fn unshare_document(&self, document_id: Uuid, user_id: Uuid) -> PortResult<()>;
+
+    /// Who the document is shared with, oldest grant first.
+    async fn get_document_shares(&self, document_id: Uuid) -> PortResult<Vec<DocumentShare>>;
+
+    /// Counts what, besides its owner's own sessions, depends on a document.
+    async fn get_document_usage(&self, document_id: Uuid) -> PortResult<DocumentUsage>;
+
+    /// The documents other users shared with `user_id`, newest grant first.
+    async fn get_documents_shared_with(&self, user_id: Uuid) -> PortResult<Vec<DocumentShare>>;
+
+    // --- Usage Tracking ---
+    /// Adds one synthesis request of `characters` characters to today's (UTC) total.
+    async fn record_tts_usage(&self, provider: &str, model: &str, characters: usize) -> PortResult<()>;
+
+    /// Daily TTS usage between `from` and `to` (inclusive), oldest first.
+    async fn get_tts_usage(&self, from: NaiveDate, to: NaiveDate) -> PortResult<Vec<TtsUsage>>;
+
+    /// Adds one model request's tokens to today's (UTC) total.
+    async fn record_llm_usage(&self, provider: &str, model: &str, usage: TokenUsage) -> PortResult<()>;
+
+    /// Daily QA model token usage between `from` and `to` (inclusive), oldest first.
+    async fn get_llm_usage(&self, from: NaiveDate, to: NaiveDate) -> PortResult<Vec<LlmUsage>>;
+
+    /// Stores one usage analytics event.
+    async fn save_usage_event(&self, event: &UsageEvent) -> PortResult<()>;
+
+    /// Daily usage analytics between `from` and `to` (inclusive), oldest first.
+    async fn get_usage_event_counts(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> PortResult<Vec<UsageEventCount>>;
+
+    /// Stores how long each stage of one answered question took.
+    async fn save_question_latencies(
+        &self,
+        question_id: Uuid,
+        latencies: &[StageLatency],
+    ) -> PortResult<()>;
+
+    /// Daily latency percentiles per stage between `from` and `to` (inclusive), oldest first.
+    async fn get_latency_percentiles(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> PortResult<Vec<LatencyPercentiles>>;
+
+    // --- Session Ratings ---
+    /// Stores the rating for `rating.session_id`, replacing any earlier one.
+    async fn save_session_rating(&self, rating: &SessionRating) -> PortResult<()>;
+
+    // --- Reading Goals ---
+    async fn get_reading_goal(&self, user_id: Uuid) -> PortResult<Option<ReadingGoal>>;
+
+    /// Sets the user's goal, replacing any earlier one.
+    async fn save_reading_goal(&self, user_id: Uuid, goal: &ReadingGoal) -> PortResult<()>;
+
+    async fn delete_reading_goal(&self, user_id: Uuid) -> PortResult<()>;
+
+    /// Adds `seconds` of listening to the user's total for `day`.
+    async fn add_listening_time(&self, user_id: Uuid, day: NaiveDate, seconds: u64) -> PortResult<()>;
+
+    /// The days between `from` and `to` (inclusive) the user listened on, oldest first.
+    async fn get_listening_days(
+        &self,
+        user_id: Uuid,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> PortResult<Vec<ListeningDay>>;
+
+    /// Users with reminders on who haven't been reminded on `day`, with their goals.
+    async fn get_users_due_goal_reminder(&self, day: NaiveDate) -> PortResult<Vec<(Uuid, ReadingGoal)>>;
+
+    /// Notes that the user was reminded on `day`.
+    async fn mark_goal_reminded(&self, user_id: Uuid, day: NaiveDate) -> PortResult<()>;
+
+    // --- Planned Sessions ---
+    /// Plans time to read or review `session_id` at `starts_at`.
+    async fn create_planned_session(
+        &self,
+        session_id: Uuid,
+        kind: PlanKind,
+        title: Option<&str>,
+        starts_at: DateTime<Utc>,
+        duration_minutes: u32,
+    ) -> PortResult<PlannedSession>;
+
+    /// The user's planned sessions ending after `after`, soonest first.
+    async fn get_planned_sessions(&self, user_id: Uuid, after: DateTime<Utc>) -> PortResult<Vec<PlannedSession>>;
+
+    /// Deletes one of the user's planned sessions. `NotFound` if they have no such plan.
+    async fn delete_planned_session(&self, user_id: Uuid, plan_id: Uuid) -> PortResult<()>;
+
+    // --- Weekly Recaps ---
+    /// Users who want weekly recaps, took notes in the week starting `week_start`,
+    /// and have no recap for it yet.
+    async fn get_users_due_weekly_recap(&self, week_start: NaiveDate) -> PortResult<Vec<Uuid>>;
+
+    /// A user's notes created between `from` (inclusive) and `to` (exclusive), across
+    /// all sessions, oldest first. Weekly recaps themselves are left out.
+    async fn get_user_notes_between(
+        &self,
+        user_id: Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> PortResult<Vec<Note>>;
+
+    /// Stores a weekly recap together with the note that shows it. Does nothing if
+    /// the user already has a recap for that week.
+    async fn save_weekly_recap(&self, recap: &WeeklyRecap, note: Note) -> PortResult<()>;
+
+    /// The user's most recent weekly recap that hasn't been spoken yet, if any.
+    async fn get_unspoken_weekly_recap(&self, user_id: Uuid) -> PortResult<Option<WeeklyRecap>>;
+
+    /// Records that a weekly recap was spoken, so it isn't repeated. Older unspoken
+    /// recaps are marked too, since they are stale by now.
+    async fn mark_weekly_recap_spoken(&self, user_id: Uuid, week_start: NaiveDate) -> PortResult<()>;
 }
 
 #[async_trait]
@@ -100,23 +618,222 @@ pub trait SpeechToTextService: Send + Sync {
 
 #[async_trait]
 pub trait TextToSpeechService: Send + Sync {
-    /// Generates audio data from a string of text.
-    async fn generate_audio(&self, text: &str) -> PortResult<Vec<u8>>;
+    /// Generates audio data from a string of text, in the listener's voice and speed and
+    /// in `speech.format`. Callers pick a format from `supported_encodings` (see
+    /// `audio_format::negotiate`).
+    async fn generate_audio(&self, text: &str, speech: &SpeechSettings) -> PortResult<Vec<u8>>;
+
+    /// Generates audio with a specific voice and speed instead of the configured ones.
+    /// Returns `PortError::NotFound` for a voice this provider does not offer.
+    async fn generate_audio_with_voice(
+        &self,
+        text: &str,
+        voice: &str,
+        speed: f32,
+    ) -> PortResult<Vec<u8>>;
+
+    /// The provider's name, e.g. "openai".
+    fn provider(&self) -> &str;
+
+    /// The voice identifiers this provider offers.
+    fn available_voices(&self) -> Vec<String>;
+
+    /// The voice used by `generate_audio`.
+    fn default_voice(&self) -> String;
+
+    /// The synthesis model, as named on the provider's invoices (e.g. "tts-1-hd").
+    fn model(&self) -> String;
+
+    /// The encodings `generate_audio` can produce. Always includes `AudioEncoding::Wav`.
+    fn supported_encodings(&self) -> Vec<AudioEncoding>;
+
+    /// The sample rate of uncompressed clips when the format doesn't ask for one.
+    fn native_sample_rate(&self) -> u32;
 }
 
 #[async_trait]
 pub trait QuestionAnsweringService: Send + Sync {
-    /// Answers a question based on a provided context.
-    async fn answer_question(&self, question: &str, context: &str) -> PortResult<String>;
+    /// Answers a question based on a provided context, at the requested length.
+    /// `education_mode` asks for age-appropriate language. With `allow_clarification`,
+    /// an ambiguous question gets a clarifying question back instead of a guess.
+    async fn answer_question(
+        &self,
+        question: &str,
+        context: &str,
+        verbosity: AnswerVerbosity,
+        education_mode: bool,
+        allow_clarification: bool,
+    ) -> PortResult<Metered<QaReply>>;
+    /// Like `answer_question`, but the answer is returned as soon as the model starts
+    /// writing it. A clarifying question is only returned once it is complete.
     async fn answer_question_streaming(
         &self,
         question: &str,
         context: &str,
-    ) -> PortResult<Pin<Box<dyn Stream<Item = Result<String, PortError>> + Send>>>;
+        verbosity: AnswerVerbosity,
+        education_mode: bool,
+        allow_clarification: bool,
+    ) -> PortResult<QaReplyStream>;
+    /// Checks, as a second pass, whether everything `answer` claims is supported by
+    /// the `context` it was written from.
+    async fn verify_grounding(&self, answer: &str, context: &str) -> PortResult<GroundingVerdict>;
+    /// Summarizes a passage in a few plain spoken-style sentences.
+    async fn summarize_passage(&self, text: &str) -> PortResult<String>;
+    /// Summarizes a passage in a single sentence, for a "last time we covered" recap.
+    async fn summarize_passage_in_one_sentence(&self, text: &str) -> PortResult<String>;
+    /// Re-explains a passage in simpler words, at the given reading level.
+    async fn simplify_passage(&self, text: &str, level: ReadingLevel) -> PortResult<String>;
+    /// Writes up to `count` short comprehension questions about a passage.
+    async fn generate_quiz_questions(
+        &self,
+        context: &str,
+        count: usize,
+    ) -> PortResult<Vec<QuizQuestion>>;
+    /// Grades a user's answer to a quiz question against the expected answer.
+    async fn grade_quiz_answer(
+        &self,
+        question: &QuizQuestion,
+        user_answer: &str,
+    ) -> PortResult<QuizGrade>;
+
+    /// The provider's name, e.g. "openai", for usage reports.
+    fn provider(&self) -> &str;
+
+    /// The model answering questions, as named on the provider's invoices.
+    fn model(&self) -> String;
 }
 
 #[async_trait]
 pub trait NoteGenerationService: Send + Sync {
-    /// Generates a concise note from a QAPair.
-    async fn generate_note_from_qapair(&self, qapair: &QAPair) -> PortResult<String>;
+    /// Generates a concise note from a QAPair. `education_mode` asks for age-appropriate language.
+    async fn generate_note_from_qapair(
+        &self,
+        qapair: &QAPair,
+        education_mode: bool,
+    ) -> PortResult<String>;
+
+    /// Extracts the key points of a passage that was just read, one short note each.
+    /// Returns no points if the passage has nothing worth noting (e.g. a heading).
+    async fn extract_key_points(
+        &self,
+        passage: &str,
+        education_mode: bool,
+    ) -> PortResult<Vec<String>>;
+
+    /// Turns a week's notes, from any number of sessions, into a short spoken-style
+    /// recap ("This week you read about ...").
+    async fn generate_weekly_recap(&self, notes: &[Note]) -> PortResult<String>;
+
+    /// Condenses a session's notes into a short summary paragraph.
+    async fn summarize_notes(&self, notes: &[Note]) -> PortResult<String>;
+
+    /// Suggests a few short topic tags for a document, preferring the user's existing tags.
+    async fn suggest_tags(&self, text: &str, existing_tags: &[String]) -> PortResult<Vec<String>>;
+}
+
+#[async_trait]
+pub trait ModerationService: Send + Sync {
+    /// Scores text against the provider's content policy.
+    async fn moderate(&self, text: &str) -> PortResult<ModerationResult>;
+}
+
+#[async_trait]
+pub trait DictionaryService: Send + Sync {
+    /// Looks up a word's first sense. Returns `PortError::NotFound` for words the
+    /// dictionary doesn't have.
+    async fn define(&self, word: &str) -> PortResult<Definition>;
+}
+
+#[async_trait]
+pub trait EmbeddingService: Send + Sync {
+    /// Embeds each text into a vector. The output has one vector per input, in order.
+    async fn embed(&self, texts: &[String]) -> PortResult<Vec<Vec<f32>>>;
+}
+
+#[async_trait]
+pub trait TextCleanupService: Send + Sync {
+    /// Repairs extraction artifacts (broken line wraps, hyphenation, OCR noise)
+    /// without changing the wording of the text.
+    async fn clean_text(&self, text: &str) -> PortResult<String>;
+}
+
+#[async_trait]
+pub trait NoteExportService: Send + Sync {
+    /// Returns the URL the user should visit to grant the application access.
+    fn authorize_url(&self, state: &str) -> String;
+
+    /// Exchanges an OAuth authorization code for an access token.
+    async fn exchange_code(&self, code: &str) -> PortResult<String>;
+
+    /// Pushes a session's summary and notes to the external service.
+    /// Returns a link to the created page.
+    async fn export_notes(
+        &self,
+        access_token: &str,
+        parent_id: &str,
+        title: &str,
+        summary: &str,
+        notes: &[Note],
+    ) -> PortResult<String>;
+}
+
+#[async_trait]
+pub trait DocumentImportService: Send + Sync {
+    /// Returns the URL the user should visit to grant the application access.
+    fn authorize_url(&self, state: &str) -> String;
+
+    /// Exchanges an OAuth authorization code for an access token.
+    async fn exchange_code(&self, code: &str) -> PortResult<String>;
+
+    /// Lists the documents the user can import.
+    async fn list_documents(&self, access_token: &str) -> PortResult<Vec<ExternalDocument>>;
+
+    /// Fetches a single document, returning its title and plain-text contents.
+    async fn fetch_document(
+        &self,
+        access_token: &str,
+        document_id: &str,
+    ) -> PortResult<(ExternalDocument, String)>;
+}
+
+#[async_trait]
+pub trait ContentFetchService: Send + Sync {
+    /// Fetches an RSS/Atom feed and returns the entries it currently lists.
+    async fn fetch_feed(&self, url: &str) -> PortResult<Vec<FeedEntry>>;
+
+    /// Fetches a web page and extracts its title and readable text.
+    async fn fetch_article(&self, url: &str) -> PortResult<(String, String)>;
+}
+
+#[async_trait]
+pub trait AudioCacheService: Send + Sync {
+    /// Returns the audio stored under `key`, or `None` if there is none.
+    async fn get(&self, key: &str) -> PortResult<Option<Vec<u8>>>;
+
+    /// Stores audio under `key`, replacing any previous entry.
+    async fn put(&self, key: &str, audio: &[u8]) -> PortResult<()>;
+}
+
+#[async_trait]
+pub trait AnalyticsService: Send + Sync {
+    /// Records that a feature was used.
+    async fn record(&self, event: UsageEvent) -> PortResult<()>;
+}
+
+#[async_trait]
+pub trait BackupStorageService: Send + Sync {
+    /// Stores a backup under `name`.
+    async fn put(&self, name: &str, data: &[u8]) -> PortResult<()>;
+
+    /// Lists the stored backups, newest first.
+    async fn list(&self) -> PortResult<Vec<BackupInfo>>;
+
+    /// Returns the backup stored under `name`, or `None` if there is none.
+    async fn get(&self, name: &str) -> PortResult<Option<Vec<u8>>>;
+}
+
+#[async_trait]
+pub trait NotificationService: Send + Sync {
+    /// Sends `notification` to the user.
+    async fn notify(&self, user_id: Uuid, notification: &Notification) -> PortResult<()>;
 }
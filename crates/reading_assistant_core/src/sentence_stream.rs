@@ -0,0 +1,84 @@
+//! crates/reading_assistant_core/src/sentence_stream.rs
+//!
+//! Finds sentence boundaries in text that arrives a few characters at a time, such as
+//! a streamed LLM answer, so each sentence can be spoken as soon as it is complete.
+
+/// Buffers streamed text and hands out each sentence once it is complete.
+#[derive(Debug, Default)]
+pub struct SentenceBuffer {
+    pending: String,
+}
+
+impl SentenceBuffer {
+    /// Adds the next piece of text and returns the sentences it completed, in order.
+    /// A sentence is complete once its closing '.', '?' or '!' (and any closing quote
+    /// or bracket) is followed by whitespace, so "3.5" or "e.g.," don't end one.
+    pub fn push(&mut self, text: &str) -> Vec<String> {
+        self.pending.push_str(text);
+
+        let mut sentences = Vec::new();
+        while let Some(end) = sentence_end(&self.pending) {
+            let sentence = self.pending[..end].trim().to_string();
+            self.pending.drain(..end);
+            if !sentence.is_empty() {
+                sentences.push(sentence);
+            }
+        }
+        sentences
+    }
+
+    /// Returns whatever is left once the text has ended, as a last sentence.
+    pub fn finish(self) -> Option<String> {
+        let rest = self.pending.trim();
+        (!rest.is_empty()).then(|| rest.to_string())
+    }
+}
+
+/// The byte offset just past the first complete sentence in `text`, if there is one.
+fn sentence_end(text: &str) -> Option<usize> {
+    let mut chars = text.char_indices().peekable();
+    while let Some((_, c)) = chars.next() {
+        if !matches!(c, '.' | '?' | '!') {
+            continue;
+        }
+        while let Some(&(_, next)) = chars.peek() {
+            if matches!(next, '.' | '?' | '!' | '"' | '\'' | '”' | '’' | ')') {
+                chars.next();
+            } else {
+                break;
+            }
+        }
+        if let Some(&(index, next)) = chars.peek() {
+            if next.is_whitespace() {
+                return Some(index);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sentences_are_released_once_complete() {
+        let mut buffer = SentenceBuffer::default();
+        assert!(buffer.push("The mitochon").is_empty());
+        assert!(buffer.push("dria makes energy.").is_empty());
+        assert_eq!(
+            buffer.push(" Really? \"Yes!\" It"),
+            vec!["The mitochondria makes energy.", "Really?", "\"Yes!\""]
+        );
+        assert_eq!(buffer.push(" does"), Vec::<String>::new());
+        assert_eq!(buffer.finish().as_deref(), Some("It does"));
+    }
+
+    #[test]
+    fn punctuation_inside_a_sentence_does_not_end_it() {
+        let mut buffer = SentenceBuffer::default();
+        assert!(buffer.push("It costs 3.5 dollars, e.g.,").is_empty());
+        assert_eq!(buffer.push(" roughly. "), vec!["It costs 3.5 dollars, e.g., roughly."]);
+        assert_eq!(buffer.finish(), None);
+    }
+}
@@ -0,0 +1,141 @@
+//! crates/reading_assistant_core/src/sentence_window.rs
+//!
+//! The sentence chunks of a document a session holds in memory. Documents up to the
+//! window's capacity are held whole; longer ones hold a window of consecutive chunks
+//! around the reading position, reloaded from the database as reading moves on.
+//! Windows start at multiples of half the capacity, so sessions reading near each
+//! other in the same document load the same windows and can share them.
+
+use std::{ops::Range, sync::Arc};
+
+/// Windows are loaded with at least this fraction of their capacity before the range
+/// asked for, so the sentences just read stay available (recaps, "explain that more
+/// simply").
+const WINDOW_BEHIND_FRACTION: usize = 4;
+
+/// A window of consecutive sentences of a document, addressed by document-wide index.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SentenceWindow {
+    /// The number of sentences in the whole document.
+    len: usize,
+    /// The index of the first loaded sentence.
+    start: usize,
+    sentences: Arc<[String]>,
+}
+
+impl SentenceWindow {
+    /// A window holding the whole document.
+    pub fn whole(sentences: Vec<String>) -> Self {
+        Self { len: sentences.len(), start: 0, sentences: sentences.into() }
+    }
+
+    /// A window over a document of `len` sentences with none of them loaded yet.
+    pub fn unloaded(len: usize) -> Self {
+        Self { len, start: 0, sentences: Arc::from([]) }
+    }
+
+    /// The number of sentences in the whole document, loaded or not.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// The indices of the loaded sentences.
+    pub fn loaded(&self) -> Range<usize> {
+        self.start..self.start + self.sentences.len()
+    }
+
+    /// The sentence at `index`, if it is loaded.
+    pub fn get(&self, index: usize) -> Option<&str> {
+        let offset = index.checked_sub(self.start)?;
+        self.sentences.get(offset).map(String::as_str)
+    }
+
+    /// The loaded sentences of `range`, joined with spaces.
+    pub fn passage(&self, range: Range<usize>) -> String {
+        range
+            .filter_map(|index| self.get(index))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// The sentences to load, `capacity` of them unless `range` is longer, so that all
+    /// of `range` is loaded, or `None` if it already is. Documents no longer than
+    /// `capacity` are loaded whole.
+    pub fn range_to_load(&self, range: Range<usize>, capacity: usize) -> Option<Range<usize>> {
+        let range = range.start.min(self.len)..range.end.min(self.len);
+        let loaded = self.loaded();
+        if range.is_empty() || (loaded.start <= range.start && range.end <= loaded.end) {
+            return None;
+        }
+        if self.len <= capacity {
+            return Some(0..self.len);
+        }
+        let step = (capacity / 2).max(1);
+        let start = range.start.saturating_sub(capacity / WINDOW_BEHIND_FRACTION) / step * step;
+        let end = range.end.max(start + capacity).min(self.len);
+        Some(start..end)
+    }
+
+    /// Replaces the loaded sentences with `sentences`, which start at index `start`.
+    pub fn replace(&mut self, start: usize, sentences: Arc<[String]>) {
+        self.start = start;
+        self.sentences = sentences;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sentences(range: Range<usize>) -> Vec<String> {
+        range.map(|index| format!("Sentence {}.", index)).collect()
+    }
+
+    #[test]
+    fn short_documents_are_loaded_whole() {
+        let window = SentenceWindow::unloaded(50);
+        assert_eq!(window.range_to_load(40..41, 100), Some(0..50));
+
+        let window = SentenceWindow::whole(sentences(0..50));
+        assert_eq!(window.range_to_load(0..50, 100), None);
+        assert_eq!(window.get(49), Some("Sentence 49."));
+    }
+
+    #[test]
+    fn long_documents_load_a_window_reaching_back_from_the_range() {
+        let mut window = SentenceWindow::unloaded(10_000);
+        let load = window.range_to_load(5_010..5_011, 100).unwrap();
+        assert_eq!(load, 4_950..5_050);
+
+        window.replace(load.start, sentences(load.clone()).into());
+        assert_eq!(window.range_to_load(4_980..5_040, 100), None);
+        assert_eq!(window.range_to_load(5_045..5_055, 100), Some(5_000..5_100));
+        assert_eq!(window.get(4_949), None);
+        assert_eq!(window.get(4_950), Some("Sentence 4950."));
+    }
+
+    #[test]
+    fn sessions_reading_nearby_load_the_same_window() {
+        let window = SentenceWindow::unloaded(10_000);
+        assert_eq!(window.range_to_load(5_080..5_081, 100), Some(5_050..5_150));
+        assert_eq!(window.range_to_load(5_099..5_100, 100), Some(5_050..5_150));
+    }
+
+    #[test]
+    fn ranges_past_the_end_are_clamped() {
+        let window = SentenceWindow::unloaded(1_000);
+        assert_eq!(window.range_to_load(990..1_010, 100), Some(950..1_000));
+        assert_eq!(window.range_to_load(1_000..1_010, 100), None);
+    }
+
+    #[test]
+    fn passages_skip_sentences_that_are_not_loaded() {
+        let mut window = SentenceWindow::unloaded(1_000);
+        window.replace(10, sentences(10..13).into());
+        assert_eq!(window.passage(8..12), "Sentence 10. Sentence 11.");
+    }
+}
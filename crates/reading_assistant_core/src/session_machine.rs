@@ -0,0 +1,276 @@
+//! crates/reading_assistant_core/src/session_machine.rs
+//!
+//! The state machine of a live reading session. Every mode change goes through
+//! `SessionMode::transition`, so impossible sequences (answering a question that was
+//! never asked, resuming a session that isn't paused, ...) are rejected in one place
+//! instead of being guarded ad hoc by each caller.
+
+use thiserror::Error;
+
+/// What a live session is doing right now.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionMode {
+    /// The document is being read aloud.
+    Reading,
+    /// Reading stopped and the user's spoken question (or quiz answer) is being recorded.
+    InterruptedListening,
+    /// A question, quiz answer or quiz request is being handled.
+    ProcessingQuestion,
+    /// The assistant asked what an ambiguous question meant and is waiting for the
+    /// user's spoken reply, which is combined with the original question.
+    Clarifying,
+    /// Active reading stopped after a few paragraphs for a quick comprehension question,
+    /// and is waiting for the user's spoken answer before it reads on.
+    Checkpoint,
+    /// Reading stopped until the user resumes.
+    Paused,
+    /// The session is over; nothing leaves this mode.
+    Ended,
+}
+
+/// Something that happened to a session, requesting a change of mode.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionEvent {
+    /// The user started speaking, by hand or via the hot-word.
+    Interrupt,
+    /// The user finished speaking their question or quiz answer.
+    SubmitQuestion,
+    /// The user asked to be quizzed.
+    StartQuiz,
+    /// A question, answer or quiz request has been handled.
+    FinishProcessing,
+    /// The question was ambiguous and a clarifying question was asked back.
+    AskClarification,
+    /// Reading reached a comprehension checkpoint and asked its question.
+    ReachCheckpoint,
+    /// A reading task starts: when the session opens, or after an answer.
+    StartReading,
+    /// The user paused reading.
+    Pause,
+    /// The user resumed a paused session.
+    Resume,
+    /// The user jumped to another passage, e.g. by clicking a note.
+    Seek,
+    /// The reading task gave up, e.g. because speech could not be generated.
+    ReadingFailed,
+    /// The session is closing.
+    End,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+#[error("{event:?} is not allowed while {from:?}")]
+pub struct InvalidTransition {
+    pub from: SessionMode,
+    pub event: SessionEvent,
+}
+
+impl SessionMode {
+    /// Returns the mode `event` leads to from this one, or an error if `event`
+    /// can't happen in this mode.
+    pub fn transition(self, event: SessionEvent) -> Result<SessionMode, InvalidTransition> {
+        use SessionEvent as E;
+        use SessionMode as M;
+
+        let next = match (self, event) {
+            (M::Ended, _) => None,
+            (_, E::End) => Some(M::Ended),
+            (M::Reading | M::Paused | M::InterruptedListening, E::Interrupt) => {
+                Some(M::InterruptedListening)
+            }
+            // The user starts speaking their reply to a clarifying or checkpoint question.
+            (M::Clarifying, E::Interrupt) => Some(M::Clarifying),
+            (M::Checkpoint, E::Interrupt) => Some(M::Checkpoint),
+            (M::InterruptedListening | M::Clarifying | M::Checkpoint, E::SubmitQuestion) => {
+                Some(M::ProcessingQuestion)
+            }
+            (M::Reading | M::Paused | M::InterruptedListening | M::Clarifying, E::StartQuiz) => {
+                Some(M::ProcessingQuestion)
+            }
+            (M::ProcessingQuestion, E::FinishProcessing) => Some(M::InterruptedListening),
+            (M::ProcessingQuestion, E::AskClarification) => Some(M::Clarifying),
+            (M::Reading | M::ProcessingQuestion, E::StartReading) => Some(M::Reading),
+            (M::Reading, E::ReachCheckpoint) => Some(M::Checkpoint),
+            (M::Reading | M::Paused | M::InterruptedListening | M::Clarifying | M::Checkpoint, E::Pause) => {
+                Some(M::Paused)
+            }
+            // Resuming at a checkpoint skips its question.
+            (M::Paused | M::Checkpoint, E::Resume) => Some(M::Reading),
+            (M::Reading | M::Paused | M::InterruptedListening | M::Checkpoint, E::Seek) => Some(M::Reading),
+            (M::Reading, E::ReadingFailed) => Some(M::Paused),
+            _ => None,
+        };
+        next.ok_or(InvalidTransition { from: self, event })
+    }
+
+    /// Whether binary frames are the user's question audio.
+    pub fn accepts_question_audio(self) -> bool {
+        matches!(
+            self,
+            SessionMode::InterruptedListening | SessionMode::Clarifying | SessionMode::Checkpoint
+        )
+    }
+
+    /// Whether binary frames are hot-word sidechannel clips.
+    pub fn accepts_hotword_clips(self) -> bool {
+        self == SessionMode::Reading
+    }
+
+    /// The mode to pick a session back up in after its connection dropped in this one.
+    /// Whatever was in flight is lost with the connection, so a question being handled
+    /// waits for the user to ask again, and a session that was at a checkpoint or
+    /// ending just reads on.
+    pub fn resumed(self) -> SessionMode {
+        match self {
+            SessionMode::ProcessingQuestion => SessionMode::InterruptedListening,
+            SessionMode::Checkpoint | SessionMode::Ended => SessionMode::Reading,
+            mode => mode,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SessionEvent as E, SessionMode as M, *};
+
+    const ALL_MODES: [M; 7] = [
+        M::Reading,
+        M::InterruptedListening,
+        M::ProcessingQuestion,
+        M::Clarifying,
+        M::Checkpoint,
+        M::Paused,
+        M::Ended,
+    ];
+
+    #[test]
+    fn question_round_trip_resumes_reading() {
+        let mode = M::Reading
+            .transition(E::Interrupt)
+            .and_then(|m| m.transition(E::SubmitQuestion))
+            .and_then(|m| m.transition(E::StartReading));
+        assert_eq!(mode, Ok(M::Reading));
+    }
+
+    #[test]
+    fn answered_question_waits_for_the_next_one() {
+        let mode = M::InterruptedListening
+            .transition(E::SubmitQuestion)
+            .and_then(|m| m.transition(E::FinishProcessing))
+            .and_then(|m| m.transition(E::Interrupt));
+        assert_eq!(mode, Ok(M::InterruptedListening));
+    }
+
+    #[test]
+    fn clarifying_question_waits_for_the_reply() {
+        let mode = M::InterruptedListening
+            .transition(E::SubmitQuestion)
+            .and_then(|m| m.transition(E::AskClarification))
+            .and_then(|m| m.transition(E::Interrupt));
+        assert_eq!(mode, Ok(M::Clarifying));
+        assert_eq!(M::Clarifying.transition(E::SubmitQuestion), Ok(M::ProcessingQuestion));
+    }
+
+    #[test]
+    fn only_a_question_being_processed_can_ask_for_clarification() {
+        for mode in ALL_MODES {
+            assert_eq!(
+                mode.transition(E::AskClarification).is_ok(),
+                mode == M::ProcessingQuestion
+            );
+        }
+    }
+
+    #[test]
+    fn question_cannot_be_submitted_without_interrupting() {
+        for mode in [M::Reading, M::Paused, M::ProcessingQuestion] {
+            assert_eq!(
+                mode.transition(E::SubmitQuestion),
+                Err(InvalidTransition { from: mode, event: E::SubmitQuestion })
+            );
+        }
+    }
+
+    #[test]
+    fn only_a_paused_session_resumes() {
+        assert_eq!(M::Paused.transition(E::Resume), Ok(M::Reading));
+        for mode in [M::Reading, M::InterruptedListening, M::ProcessingQuestion] {
+            assert!(mode.transition(E::Resume).is_err());
+        }
+    }
+
+    #[test]
+    fn jumping_to_a_passage_starts_reading_there() {
+        for mode in [M::Reading, M::Paused, M::InterruptedListening] {
+            assert_eq!(mode.transition(E::Seek), Ok(M::Reading));
+        }
+        for mode in [M::ProcessingQuestion, M::Clarifying, M::Ended] {
+            assert!(mode.transition(E::Seek).is_err());
+        }
+    }
+
+    #[test]
+    fn checkpoint_waits_for_an_answer_then_reads_on() {
+        let mode = M::Reading
+            .transition(E::ReachCheckpoint)
+            .and_then(|m| m.transition(E::Interrupt))
+            .and_then(|m| m.transition(E::SubmitQuestion))
+            .and_then(|m| m.transition(E::StartReading));
+        assert_eq!(mode, Ok(M::Reading));
+        assert_eq!(M::Checkpoint.transition(E::Resume), Ok(M::Reading));
+        for mode in [M::Paused, M::InterruptedListening, M::Clarifying, M::ProcessingQuestion] {
+            assert!(mode.transition(E::ReachCheckpoint).is_err());
+        }
+    }
+
+    #[test]
+    fn nothing_interrupts_processing() {
+        for event in [E::Interrupt, E::StartQuiz, E::Pause, E::Resume, E::Seek] {
+            assert!(M::ProcessingQuestion.transition(event).is_err());
+        }
+    }
+
+    #[test]
+    fn reading_failure_is_ignored_once_reading_stopped() {
+        assert_eq!(M::Reading.transition(E::ReadingFailed), Ok(M::Paused));
+        for mode in [M::InterruptedListening, M::ProcessingQuestion, M::Paused] {
+            assert!(mode.transition(E::ReadingFailed).is_err());
+        }
+    }
+
+    #[test]
+    fn every_live_mode_can_end_and_ended_is_terminal() {
+        for mode in ALL_MODES {
+            let ended = mode.transition(E::End);
+            if mode == M::Ended {
+                assert!(ended.is_err());
+            } else {
+                assert_eq!(ended, Ok(M::Ended));
+            }
+        }
+        for event in [E::Interrupt, E::StartReading, E::Resume, E::Pause] {
+            assert!(M::Ended.transition(event).is_err());
+        }
+    }
+
+    #[test]
+    fn binary_frames_are_routed_by_mode() {
+        for mode in ALL_MODES {
+            assert_eq!(
+                mode.accepts_question_audio(),
+                matches!(mode, M::InterruptedListening | M::Clarifying | M::Checkpoint)
+            );
+            assert_eq!(mode.accepts_hotword_clips(), mode == M::Reading);
+        }
+    }
+
+    #[test]
+    fn resumed_sessions_never_wait_on_lost_work() {
+        for mode in ALL_MODES {
+            let resumed = mode.resumed();
+            assert_ne!(resumed, M::ProcessingQuestion);
+            assert_ne!(resumed, M::Ended);
+        }
+        assert_eq!(M::Paused.resumed(), M::Paused);
+        assert_eq!(M::Clarifying.resumed(), M::Clarifying);
+    }
+}
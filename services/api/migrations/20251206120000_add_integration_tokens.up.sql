@@ -0,0 +1,10 @@
+-- services/api/migrations/20251206120000_add_integration_tokens.up.sql
+
+-- Stores per-user OAuth access tokens for third-party integrations (Notion, ...).
+CREATE TABLE integration_tokens (
+    user_id UUID NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    provider TEXT NOT NULL,
+    access_token TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, provider)
+);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS queue_items;
+DROP TABLE IF EXISTS feeds;
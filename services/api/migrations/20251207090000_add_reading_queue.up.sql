@@ -0,0 +1,26 @@
+-- services/api/migrations/20251207090000_add_reading_queue.up.sql
+
+-- Feeds a user has subscribed to; a background fetcher polls these for new items.
+CREATE TABLE feeds (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    url TEXT NOT NULL,
+    last_fetched_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, url)
+);
+
+-- Links waiting to be ingested as documents, either saved directly or discovered in a feed.
+CREATE TABLE queue_items (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    user_id UUID NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    feed_id UUID REFERENCES feeds(id) ON DELETE SET NULL,
+    url TEXT NOT NULL,
+    title TEXT,
+    status TEXT NOT NULL DEFAULT 'pending',
+    session_id UUID REFERENCES sessions(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (user_id, url)
+);
+
+CREATE INDEX idx_queue_items_status ON queue_items(status);
@@ -0,0 +1,12 @@
+-- services/api/migrations/20251208100000_add_document_chunks.up.sql
+
+-- Sentence chunks are stored at upload time so reading indices stay stable
+-- even if the chunking rules change later.
+ALTER TABLE documents ADD COLUMN chunker_version INTEGER;
+
+CREATE TABLE document_chunks (
+    document_id UUID NOT NULL REFERENCES documents(id) ON DELETE CASCADE,
+    chunk_index INTEGER NOT NULL,
+    text TEXT NOT NULL,
+    PRIMARY KEY (document_id, chunk_index)
+);
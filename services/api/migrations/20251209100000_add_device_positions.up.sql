@@ -0,0 +1,11 @@
+-- services/api/migrations/20251209100000_add_device_positions.up.sql
+
+-- The last position listened to on each device. `sessions.reading_progress_index`
+-- keeps the furthest position reached on any device.
+CREATE TABLE device_positions (
+    session_id UUID NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+    device_id TEXT NOT NULL,
+    reading_progress_index INTEGER NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (session_id, device_id)
+);
@@ -0,0 +1,14 @@
+-- services/api/migrations/20251210100000_add_document_tags.up.sql
+
+-- User-defined tags on documents. `suggested` marks tags proposed by the LLM at
+-- upload time that the user has not confirmed yet.
+CREATE TABLE document_tags (
+    document_id UUID NOT NULL REFERENCES documents(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    tag TEXT NOT NULL,
+    suggested BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (document_id, tag)
+);
+
+CREATE INDEX idx_document_tags_user_tag ON document_tags(user_id, tag);
@@ -0,0 +1,15 @@
+-- services/api/migrations/20251211100000_add_quiz_attempts.up.sql
+
+-- Graded answers from the spoken quiz mode.
+CREATE TABLE quiz_attempts (
+    id UUID PRIMARY KEY,
+    session_id UUID NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+    question_text TEXT NOT NULL,
+    expected_answer TEXT NOT NULL,
+    user_answer TEXT NOT NULL,
+    correct BOOLEAN NOT NULL,
+    feedback TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_quiz_attempts_session_id ON quiz_attempts(session_id);
@@ -0,0 +1,9 @@
+-- services/api/migrations/20251212100000_add_user_preferences.up.sql
+
+-- Per-user settings. A missing row means every setting is at its default.
+CREATE TABLE user_preferences (
+    user_id UUID PRIMARY KEY REFERENCES users(user_id) ON DELETE CASCADE,
+    answer_verbosity TEXT NOT NULL DEFAULT 'normal'
+        CHECK (answer_verbosity IN ('brief', 'normal', 'detailed')),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,4 @@
+-- services/api/migrations/20251213100000_add_education_mode.up.sql
+
+-- Child/education mode: age-appropriate prompts and stricter moderation for the session.
+ALTER TABLE sessions ADD COLUMN education_mode BOOLEAN NOT NULL DEFAULT FALSE;
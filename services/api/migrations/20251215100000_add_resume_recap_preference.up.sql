@@ -0,0 +1,4 @@
+-- services/api/migrations/20251215100000_add_resume_recap_preference.up.sql
+
+-- Whether to speak a one-sentence "last time we covered" recap when a session is reopened after a break.
+ALTER TABLE user_preferences ADD COLUMN resume_recap BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,2 @@
+ALTER TABLE user_preferences DROP COLUMN IF EXISTS crossfade_ms;
+ALTER TABLE user_preferences DROP COLUMN IF EXISTS sentence_gap_ms;
@@ -0,0 +1,8 @@
+-- services/api/migrations/20251216100000_add_narration_pacing.up.sql
+
+-- Per-user narration pacing, applied to synthesized sentences before they are sent.
+ALTER TABLE user_preferences
+    ADD COLUMN sentence_gap_ms INTEGER NOT NULL DEFAULT 0
+        CHECK (sentence_gap_ms BETWEEN 0 AND 2000),
+    ADD COLUMN crossfade_ms INTEGER NOT NULL DEFAULT 0
+        CHECK (crossfade_ms BETWEEN 0 AND 50);
@@ -0,0 +1,11 @@
+-- services/api/migrations/20251217100000_add_tts_usage.up.sql
+
+-- Characters sent to each TTS provider/model per UTC day, for reconciling provider invoices.
+CREATE TABLE tts_usage (
+    usage_date DATE NOT NULL,
+    provider TEXT NOT NULL,
+    model TEXT NOT NULL,
+    characters BIGINT NOT NULL DEFAULT 0,
+    requests BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (usage_date, provider, model)
+);
@@ -0,0 +1,13 @@
+-- services/api/migrations/20251218100000_add_user_api_keys.up.sql
+
+-- Users' own provider API keys ("bring your own key"), encrypted by the application.
+CREATE TABLE user_api_keys (
+    user_id UUID NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    provider TEXT NOT NULL,
+    -- base64(nonce || AES-256-GCM ciphertext); never stored in plaintext.
+    encrypted_key TEXT NOT NULL,
+    -- The last few characters of the key, so users can tell which key is stored.
+    key_hint TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, provider)
+);
@@ -0,0 +1,2 @@
+ALTER TABLE document_chunks DROP COLUMN IF EXISTS encrypted;
+ALTER TABLE documents DROP COLUMN IF EXISTS encrypted;
@@ -0,0 +1,6 @@
+-- services/api/migrations/20251219100000_add_document_encryption.up.sql
+
+-- Marks document text sealed by the application (base64 of nonce || AES-256-GCM ciphertext).
+-- Existing rows stay plaintext; each row is decrypted according to its own flag.
+ALTER TABLE documents ADD COLUMN encrypted BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE document_chunks ADD COLUMN encrypted BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,8 @@
+DROP INDEX IF EXISTS idx_sessions_tenant_id;
+DROP INDEX IF EXISTS idx_documents_tenant_id;
+ALTER TABLE sessions DROP COLUMN IF EXISTS tenant_id;
+ALTER TABLE documents DROP COLUMN IF EXISTS tenant_id;
+ALTER TABLE users DROP CONSTRAINT IF EXISTS users_tenant_email_key;
+ALTER TABLE users DROP COLUMN IF EXISTS tenant_id;
+ALTER TABLE users ADD CONSTRAINT users_email_key UNIQUE (email);
+DROP TABLE IF EXISTS tenants;
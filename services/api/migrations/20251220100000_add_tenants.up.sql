@@ -0,0 +1,37 @@
+-- services/api/migrations/20251220100000_add_tenants.up.sql
+
+-- Isolated organizations served by one deployment. Everything that existed before
+-- multi-tenancy belongs to the default tenant (the nil UUID).
+CREATE TABLE tenants (
+    id UUID PRIMARY KEY,
+    slug TEXT NOT NULL UNIQUE,
+    name TEXT NOT NULL,
+    -- The hostname requests for this tenant arrive on, e.g. "acme.example.com".
+    hostname TEXT UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO tenants (id, slug, name)
+VALUES ('00000000-0000-0000-0000-000000000000', 'default', 'Default');
+
+ALTER TABLE users
+    ADD COLUMN tenant_id UUID NOT NULL DEFAULT '00000000-0000-0000-0000-000000000000'
+        REFERENCES tenants(id);
+
+-- Emails are unique per tenant rather than globally.
+ALTER TABLE users DROP CONSTRAINT users_email_key;
+ALTER TABLE users ADD CONSTRAINT users_tenant_email_key UNIQUE (tenant_id, email);
+
+-- Documents and sessions always take their owner's tenant.
+ALTER TABLE documents
+    ADD COLUMN tenant_id UUID NOT NULL DEFAULT '00000000-0000-0000-0000-000000000000'
+        REFERENCES tenants(id);
+ALTER TABLE documents ALTER COLUMN tenant_id DROP DEFAULT;
+
+ALTER TABLE sessions
+    ADD COLUMN tenant_id UUID NOT NULL DEFAULT '00000000-0000-0000-0000-000000000000'
+        REFERENCES tenants(id);
+ALTER TABLE sessions ALTER COLUMN tenant_id DROP DEFAULT;
+
+CREATE INDEX idx_documents_tenant_id ON documents(tenant_id);
+CREATE INDEX idx_sessions_tenant_id ON sessions(tenant_id);
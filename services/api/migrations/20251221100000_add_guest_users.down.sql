@@ -0,0 +1,4 @@
+DROP INDEX IF EXISTS idx_users_guest_ip;
+ALTER TABLE users DROP COLUMN IF EXISTS guest_ip;
+ALTER TABLE users DROP COLUMN IF EXISTS guest_expires_at;
+ALTER TABLE users DROP COLUMN IF EXISTS is_guest;
@@ -0,0 +1,9 @@
+-- services/api/migrations/20251221100000_add_guest_users.up.sql
+
+-- Anonymous trial ("guest") users. A guest has no email or password, expires at
+-- guest_expires_at unless they sign up, and guest_ip is kept for rate limiting.
+ALTER TABLE users ADD COLUMN is_guest BOOLEAN NOT NULL DEFAULT FALSE;
+ALTER TABLE users ADD COLUMN guest_expires_at TIMESTAMPTZ;
+ALTER TABLE users ADD COLUMN guest_ip TEXT;
+
+CREATE INDEX idx_users_guest_ip ON users(guest_ip, created_at) WHERE is_guest;
@@ -0,0 +1,2 @@
+ALTER TABLE notes DROP COLUMN IF EXISTS source_end_index;
+ALTER TABLE notes DROP COLUMN IF EXISTS source_start_index;
@@ -0,0 +1,7 @@
+-- services/api/migrations/20251222100000_add_note_anchors.up.sql
+
+-- The sentence range (end exclusive) that was being read when a note's question
+-- was asked, so clicking the note can jump back to that passage. NULL for notes
+-- not tied to a question, such as session summaries.
+ALTER TABLE notes ADD COLUMN source_start_index INTEGER;
+ALTER TABLE notes ADD COLUMN source_end_index INTEGER;
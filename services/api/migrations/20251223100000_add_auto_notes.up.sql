@@ -0,0 +1,13 @@
+-- services/api/migrations/20251223100000_add_auto_notes.up.sql
+
+-- What each note was generated from: a question, a paragraph read with auto notes
+-- on, or the session's other notes.
+ALTER TABLE notes
+    ADD COLUMN source TEXT NOT NULL DEFAULT 'question'
+        CHECK (source IN ('question', 'auto', 'summary'));
+
+-- Summary notes were only recognizable by their prefix until now.
+UPDATE notes SET source = 'summary' WHERE generated_note_text LIKE 'Session summary: %';
+
+-- Opt-in, since every paragraph read costs an LLM call.
+ALTER TABLE user_preferences ADD COLUMN auto_notes BOOLEAN NOT NULL DEFAULT FALSE;
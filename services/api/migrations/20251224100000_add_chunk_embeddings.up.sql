@@ -0,0 +1,11 @@
+-- services/api/migrations/20251224100000_add_chunk_embeddings.up.sql
+
+-- One embedding per sentence chunk, so questions are answered from the most relevant
+-- parts of a document rather than only the passage being read. Filled in the
+-- background after upload (or when a session opens on an older document).
+-- The column is left without a dimension so the embedding model can be changed;
+-- searches are scoped to one document, so no vector index is needed.
+-- Note that embeddings are stored in the clear even for encrypted documents.
+CREATE EXTENSION IF NOT EXISTS vector;
+
+ALTER TABLE document_chunks ADD COLUMN embedding vector;
@@ -0,0 +1,6 @@
+ALTER TABLE user_preferences DROP COLUMN IF EXISTS weekly_recap;
+DROP TABLE IF EXISTS weekly_recaps;
+DELETE FROM notes WHERE source = 'weekly_recap';
+ALTER TABLE notes DROP CONSTRAINT notes_source_check;
+ALTER TABLE notes ADD CONSTRAINT notes_source_check
+    CHECK (source IN ('question', 'auto', 'summary'));
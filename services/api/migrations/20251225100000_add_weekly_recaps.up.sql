@@ -0,0 +1,19 @@
+-- services/api/migrations/20251225100000_add_weekly_recaps.up.sql
+
+-- Weekly recaps are notes too, shown on the session the user read last that week.
+ALTER TABLE notes DROP CONSTRAINT notes_source_check;
+ALTER TABLE notes ADD CONSTRAINT notes_source_check
+    CHECK (source IN ('question', 'auto', 'summary', 'weekly_recap'));
+
+-- One recap per user per week (starting Monday, UTC). `spoken_at` is set once the
+-- recap has been read out at the start of a session.
+CREATE TABLE weekly_recaps (
+    user_id UUID NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    week_start DATE NOT NULL,
+    note_id UUID NOT NULL REFERENCES notes(id) ON DELETE CASCADE,
+    spoken_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, week_start)
+);
+
+ALTER TABLE user_preferences ADD COLUMN weekly_recap BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,4 @@
+DELETE FROM notes WHERE source = 'imported';
+ALTER TABLE notes DROP CONSTRAINT notes_source_check;
+ALTER TABLE notes ADD CONSTRAINT notes_source_check
+    CHECK (source IN ('question', 'auto', 'summary', 'weekly_recap'));
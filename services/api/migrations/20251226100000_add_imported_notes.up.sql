@@ -0,0 +1,6 @@
+-- services/api/migrations/20251226100000_add_imported_notes.up.sql
+
+-- Highlights and notes imported from Kindle clippings or a Readwise export.
+ALTER TABLE notes DROP CONSTRAINT notes_source_check;
+ALTER TABLE notes ADD CONSTRAINT notes_source_check
+    CHECK (source IN ('question', 'auto', 'summary', 'weekly_recap', 'imported'));
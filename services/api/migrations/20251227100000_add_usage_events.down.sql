@@ -0,0 +1,2 @@
+ALTER TABLE user_preferences DROP COLUMN analytics_opt_out;
+DROP TABLE usage_events;
@@ -0,0 +1,18 @@
+-- services/api/migrations/20251227100000_add_usage_events.up.sql
+
+-- Feature usage for analytics. Events hold no content and no user or session ID:
+-- `visit_id` is random per connection, so events can be grouped by visit but not
+-- traced back to anyone.
+CREATE TABLE usage_events (
+    id BIGSERIAL PRIMARY KEY,
+    visit_id UUID NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN (
+        'session_opened', 'question_asked', 'paused', 'resumed', 'seeked', 'quiz_started',
+        'resume_command', 'seek_command', 'summary_command'
+    )),
+    occurred_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX usage_events_occurred_at_idx ON usage_events (occurred_at);
+
+ALTER TABLE user_preferences ADD COLUMN analytics_opt_out BOOLEAN NOT NULL DEFAULT FALSE;
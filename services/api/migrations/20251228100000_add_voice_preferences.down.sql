@@ -0,0 +1,3 @@
+ALTER TABLE user_preferences
+    DROP COLUMN speaking_speed,
+    DROP COLUMN voice;
@@ -0,0 +1,6 @@
+-- services/api/migrations/20251228100000_add_voice_preferences.up.sql
+
+-- NULL means the server's default voice and normal speed.
+ALTER TABLE user_preferences
+    ADD COLUMN voice TEXT,
+    ADD COLUMN speaking_speed REAL CHECK (speaking_speed BETWEEN 0.25 AND 4.0);
@@ -0,0 +1,10 @@
+-- services/api/migrations/20251229100000_add_session_ratings.up.sql
+
+-- Listeners' ratings of finished sessions, for quality tracking. One per session;
+-- rating again replaces the earlier rating.
+CREATE TABLE session_ratings (
+    session_id UUID PRIMARY KEY REFERENCES sessions(id) ON DELETE CASCADE,
+    rating SMALLINT NOT NULL CHECK (rating BETWEEN 1 AND 5),
+    feedback TEXT,
+    rated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,2 @@
+DROP TABLE listening_days;
+DROP TABLE reading_goals;
@@ -0,0 +1,21 @@
+-- services/api/migrations/20251230100000_add_reading_goals.up.sql
+
+-- Listening goals, e.g. 30 minutes a day on 4 days a week. One per user.
+CREATE TABLE reading_goals (
+    user_id UUID PRIMARY KEY REFERENCES users(user_id) ON DELETE CASCADE,
+    minutes_per_day INTEGER NOT NULL CHECK (minutes_per_day BETWEEN 1 AND 1440),
+    days_per_week SMALLINT NOT NULL CHECK (days_per_week BETWEEN 1 AND 7),
+    reminders BOOLEAN NOT NULL DEFAULT TRUE,
+    -- The last day (UTC) a streak reminder was sent, so at most one goes out a day.
+    last_reminded_on DATE,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Time spent listening per user and day (UTC), for goal progress. Kept apart from
+-- the anonymized usage events, which can't be tied back to a user.
+CREATE TABLE listening_days (
+    user_id UUID NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    day DATE NOT NULL,
+    seconds BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (user_id, day)
+);
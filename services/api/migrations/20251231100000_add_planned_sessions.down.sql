@@ -0,0 +1 @@
+DROP TABLE planned_sessions;
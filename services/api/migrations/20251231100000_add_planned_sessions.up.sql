@@ -0,0 +1,14 @@
+-- services/api/migrations/20251231100000_add_planned_sessions.up.sql
+
+-- Times users set aside to read or review a session, published in their calendar feed.
+CREATE TABLE planned_sessions (
+    id UUID PRIMARY KEY,
+    session_id UUID NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL CHECK (kind IN ('reading', 'review')),
+    title TEXT,
+    starts_at TIMESTAMPTZ NOT NULL,
+    duration_minutes INTEGER NOT NULL CHECK (duration_minutes BETWEEN 1 AND 1440),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX planned_sessions_session_id_idx ON planned_sessions (session_id);
@@ -0,0 +1 @@
+ALTER TABLE qa_pairs DROP COLUMN answer_interrupted;
@@ -0,0 +1,5 @@
+-- services/api/migrations/20260101100000_add_answer_interrupted.up.sql
+
+-- Answers the user stopped partway. Their `answer_text` is only the part sent before
+-- they did.
+ALTER TABLE qa_pairs ADD COLUMN answer_interrupted BOOLEAN NOT NULL DEFAULT FALSE;
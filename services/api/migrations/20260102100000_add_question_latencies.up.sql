@@ -0,0 +1,13 @@
+-- services/api/migrations/20260102100000_add_question_latencies.up.sql
+
+-- How long each stage of answering a question took, for latency reports.
+-- One row per stage; rows of the same question share a question_id.
+CREATE TABLE question_latencies (
+    question_id UUID NOT NULL,
+    stage TEXT NOT NULL CHECK (stage IN ('stt', 'llm', 'tts', 'total')),
+    duration_ms INTEGER NOT NULL CHECK (duration_ms >= 0),
+    recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (question_id, stage)
+);
+
+CREATE INDEX idx_question_latencies_recorded_at ON question_latencies (recorded_at);
@@ -0,0 +1,19 @@
+-- services/api/migrations/20260103100000_add_session_snapshots.up.sql
+
+-- What a session's last connection held in memory when it closed, so a client that
+-- reconnects with `resume` picks up in the same mode and conversation.
+-- One row per session, replaced each time a connection closes.
+CREATE TABLE session_snapshots (
+    session_id UUID PRIMARY KEY REFERENCES sessions(id) ON DELETE CASCADE,
+    mode TEXT NOT NULL CHECK (mode IN ('reading', 'interrupted_listening', 'processing_question', 'clarifying', 'paused', 'ended')),
+    reading_progress_index INTEGER NOT NULL,
+    last_question TEXT,
+    last_answer TEXT,
+    pending_clarification TEXT,
+    -- The answer the user stopped partway, when there is one: its saved Q&A pair and
+    -- the sentences heard and still to come.
+    interrupted_qa_pair_id UUID,
+    interrupted_heard TEXT[] NOT NULL DEFAULT '{}',
+    interrupted_remaining TEXT[] NOT NULL DEFAULT '{}',
+    saved_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
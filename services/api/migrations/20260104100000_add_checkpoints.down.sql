@@ -0,0 +1,5 @@
+DELETE FROM session_snapshots WHERE mode = 'checkpoint';
+ALTER TABLE session_snapshots DROP CONSTRAINT session_snapshots_mode_check;
+ALTER TABLE session_snapshots ADD CONSTRAINT session_snapshots_mode_check
+    CHECK (mode IN ('reading', 'interrupted_listening', 'processing_question', 'clarifying', 'paused', 'ended'));
+ALTER TABLE user_preferences DROP COLUMN IF EXISTS checkpoint_paragraphs;
@@ -0,0 +1,11 @@
+-- services/api/migrations/20260104100000_add_checkpoints.up.sql
+
+-- Active reading: every this many paragraphs, reading stops for one quick question
+-- about them. NULL leaves it off.
+ALTER TABLE user_preferences
+    ADD COLUMN checkpoint_paragraphs INTEGER CHECK (checkpoint_paragraphs BETWEEN 1 AND 50);
+
+-- A session can now be dropped while waiting on a checkpoint answer.
+ALTER TABLE session_snapshots DROP CONSTRAINT session_snapshots_mode_check;
+ALTER TABLE session_snapshots ADD CONSTRAINT session_snapshots_mode_check
+    CHECK (mode IN ('reading', 'interrupted_listening', 'processing_question', 'clarifying', 'checkpoint', 'paused', 'ended'));
@@ -0,0 +1,12 @@
+-- services/api/migrations/20260105100000_add_llm_usage.up.sql
+
+-- Tokens reported by each QA provider/model per UTC day, alongside `tts_usage`.
+CREATE TABLE llm_usage (
+    usage_date DATE NOT NULL,
+    provider TEXT NOT NULL,
+    model TEXT NOT NULL,
+    prompt_tokens BIGINT NOT NULL DEFAULT 0,
+    completion_tokens BIGINT NOT NULL DEFAULT 0,
+    requests BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (usage_date, provider, model)
+);
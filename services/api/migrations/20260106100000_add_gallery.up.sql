@@ -0,0 +1,10 @@
+-- services/api/migrations/20260106100000_add_gallery.up.sql
+
+-- Public-domain documents any user can start a session on. The documents themselves
+-- belong to the admin who uploaded them; the title shown in the gallery lives here.
+CREATE TABLE gallery_documents (
+    document_id UUID PRIMARY KEY REFERENCES documents(id) ON DELETE CASCADE,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    added_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
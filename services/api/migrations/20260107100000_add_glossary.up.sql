@@ -0,0 +1,13 @@
+-- services/api/migrations/20260107100000_add_glossary.up.sql
+
+-- Words the user asked to have defined while listening, per session.
+CREATE TABLE glossary_entries (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    session_id UUID NOT NULL REFERENCES sessions(id) ON DELETE CASCADE,
+    word TEXT NOT NULL,
+    part_of_speech TEXT,
+    meaning TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_glossary_entries_session_id ON glossary_entries(session_id);
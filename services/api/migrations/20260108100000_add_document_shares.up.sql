@@ -0,0 +1,11 @@
+-- services/api/migrations/20260108100000_add_document_shares.up.sql
+
+-- Documents shared by their owner with specific users, e.g. a study partner.
+CREATE TABLE document_shares (
+    document_id UUID NOT NULL REFERENCES documents(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(user_id) ON DELETE CASCADE,
+    shared_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (document_id, user_id)
+);
+
+CREATE INDEX idx_document_shares_user_id ON document_shares(user_id);
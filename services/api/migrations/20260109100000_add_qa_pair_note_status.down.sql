@@ -0,0 +1,5 @@
+DROP INDEX IF EXISTS idx_qa_pairs_pending_notes;
+ALTER TABLE qa_pairs DROP COLUMN note_education_mode;
+ALTER TABLE qa_pairs DROP COLUMN note_source_end;
+ALTER TABLE qa_pairs DROP COLUMN note_source_start;
+ALTER TABLE qa_pairs DROP COLUMN note_status;
@@ -0,0 +1,14 @@
+-- services/api/migrations/20260109100000_add_qa_pair_note_status.up.sql
+
+-- Questions are saved before their answer is spoken, and their note is written in the
+-- background afterwards. 'pending' rows still need a note, so the ones a crash or
+-- restart cut short are picked up again; 'done' rows have one or don't need one.
+ALTER TABLE qa_pairs ADD COLUMN note_status TEXT NOT NULL DEFAULT 'done'
+    CHECK (note_status IN ('pending', 'done', 'failed'));
+-- What the note is written with: the passage it links back to, and whether the
+-- session was in education mode.
+ALTER TABLE qa_pairs ADD COLUMN note_source_start INT;
+ALTER TABLE qa_pairs ADD COLUMN note_source_end INT;
+ALTER TABLE qa_pairs ADD COLUMN note_education_mode BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE INDEX idx_qa_pairs_pending_notes ON qa_pairs(created_at) WHERE note_status = 'pending';
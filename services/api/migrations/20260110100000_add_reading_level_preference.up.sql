@@ -0,0 +1,6 @@
+-- services/api/migrations/20260110100000_add_reading_level_preference.up.sql
+
+-- The level "explain this more simply" re-explains passages at.
+ALTER TABLE user_preferences
+    ADD COLUMN reading_level TEXT NOT NULL DEFAULT 'general'
+        CHECK (reading_level IN ('elementary', 'middle_school', 'general'));
@@ -0,0 +1,2 @@
+ALTER TABLE sessions DROP COLUMN title;
+ALTER TABLE documents DROP COLUMN title;
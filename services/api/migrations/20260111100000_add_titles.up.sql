@@ -0,0 +1,8 @@
+-- services/api/migrations/20260111100000_add_titles.up.sql
+
+-- The title a document was created with (its file name or article title), which the
+-- user can change. Documents from before this have none.
+ALTER TABLE documents ADD COLUMN title TEXT;
+
+-- A title the user gave one session, shown instead of its document's.
+ALTER TABLE sessions ADD COLUMN title TEXT;
@@ -0,0 +1,13 @@
+ALTER TABLE question_latencies DROP CONSTRAINT question_latencies_question_id_fkey;
+
+ALTER TABLE notes DROP CONSTRAINT notes_session_id_fkey;
+ALTER TABLE notes ADD CONSTRAINT notes_session_id_fkey
+    FOREIGN KEY (session_id) REFERENCES sessions(id);
+
+ALTER TABLE qa_pairs DROP CONSTRAINT qa_pairs_session_id_fkey;
+ALTER TABLE qa_pairs ADD CONSTRAINT qa_pairs_session_id_fkey
+    FOREIGN KEY (session_id) REFERENCES sessions(id);
+
+ALTER TABLE sessions DROP CONSTRAINT sessions_document_id_fkey;
+ALTER TABLE sessions ADD CONSTRAINT sessions_document_id_fkey
+    FOREIGN KEY (document_id) REFERENCES documents(id);
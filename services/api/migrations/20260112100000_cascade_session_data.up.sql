@@ -0,0 +1,21 @@
+-- services/api/migrations/20260112100000_cascade_session_data.up.sql
+
+-- Deleting a document deletes its sessions, and deleting a session its questions and
+-- notes, like every table added since the initial schema.
+ALTER TABLE sessions DROP CONSTRAINT sessions_document_id_fkey;
+ALTER TABLE sessions ADD CONSTRAINT sessions_document_id_fkey
+    FOREIGN KEY (document_id) REFERENCES documents(id) ON DELETE CASCADE;
+
+ALTER TABLE qa_pairs DROP CONSTRAINT qa_pairs_session_id_fkey;
+ALTER TABLE qa_pairs ADD CONSTRAINT qa_pairs_session_id_fkey
+    FOREIGN KEY (session_id) REFERENCES sessions(id) ON DELETE CASCADE;
+
+ALTER TABLE notes DROP CONSTRAINT notes_session_id_fkey;
+ALTER TABLE notes ADD CONSTRAINT notes_session_id_fkey
+    FOREIGN KEY (session_id) REFERENCES sessions(id) ON DELETE CASCADE;
+
+-- Latencies go with their question. NOT VALID, so latencies of questions deleted
+-- before this don't block it; GET /admin/integrity reports them and its repair
+-- removes them.
+ALTER TABLE question_latencies ADD CONSTRAINT question_latencies_question_id_fkey
+    FOREIGN KEY (question_id) REFERENCES qa_pairs(id) ON DELETE CASCADE NOT VALID;
@@ -0,0 +1,6 @@
+DELETE FROM usage_events WHERE kind = 'speed_command';
+ALTER TABLE usage_events DROP CONSTRAINT usage_events_kind_check;
+ALTER TABLE usage_events ADD CONSTRAINT usage_events_kind_check CHECK (kind IN (
+    'session_opened', 'question_asked', 'paused', 'resumed', 'seeked', 'quiz_started',
+    'resume_command', 'seek_command', 'summary_command'
+));
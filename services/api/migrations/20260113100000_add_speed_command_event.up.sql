@@ -0,0 +1,8 @@
+-- services/api/migrations/20260113100000_add_speed_command_event.up.sql
+
+-- Spoken "read faster" / "read slower" commands.
+ALTER TABLE usage_events DROP CONSTRAINT usage_events_kind_check;
+ALTER TABLE usage_events ADD CONSTRAINT usage_events_kind_check CHECK (kind IN (
+    'session_opened', 'question_asked', 'paused', 'resumed', 'seeked', 'quiz_started',
+    'resume_command', 'seek_command', 'summary_command', 'speed_command'
+));
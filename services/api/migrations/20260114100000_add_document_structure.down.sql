@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS document_chapters;
+ALTER TABLE documents DROP COLUMN IF EXISTS structure_version;
+ALTER TABLE documents DROP COLUMN IF EXISTS paragraph_starts;
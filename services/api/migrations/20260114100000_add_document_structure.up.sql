@@ -0,0 +1,17 @@
+-- services/api/migrations/20260114100000_add_document_structure.up.sql
+
+-- Paragraph and chapter starts are recorded next to the chunks at upload time, so
+-- opening a session doesn't need the original text. They were computed with the
+-- chunker version in `structure_version`, and only apply while it matches the
+-- chunks' `chunker_version`.
+ALTER TABLE documents
+    ADD COLUMN paragraph_starts INTEGER[] NOT NULL DEFAULT '{}',
+    ADD COLUMN structure_version INTEGER;
+
+CREATE TABLE document_chapters (
+    document_id UUID NOT NULL REFERENCES documents(id) ON DELETE CASCADE,
+    sentence_index INTEGER NOT NULL,
+    title TEXT NOT NULL,
+    encrypted BOOLEAN NOT NULL DEFAULT FALSE,
+    PRIMARY KEY (document_id, sentence_index)
+);
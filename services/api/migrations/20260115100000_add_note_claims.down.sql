@@ -0,0 +1,4 @@
+UPDATE qa_pairs SET note_status = 'failed' WHERE note_status IN ('answering', 'processing');
+ALTER TABLE qa_pairs DROP CONSTRAINT qa_pairs_note_status_check;
+ALTER TABLE qa_pairs ADD CONSTRAINT qa_pairs_note_status_check
+    CHECK (note_status IN ('pending', 'done', 'failed'));
@@ -0,0 +1,8 @@
+-- services/api/migrations/20260115100000_add_note_claims.up.sql
+
+-- 'answering' rows are saved but their answer hasn't been delivered yet, so they never
+-- get a note; they only become 'pending' once it has. 'processing' rows were claimed by
+-- a server resuming pending notes, so no other replica writes their note too.
+ALTER TABLE qa_pairs DROP CONSTRAINT qa_pairs_note_status_check;
+ALTER TABLE qa_pairs ADD CONSTRAINT qa_pairs_note_status_check
+    CHECK (note_status IN ('answering', 'pending', 'processing', 'done', 'failed'));
@@ -0,0 +1,29 @@
+//! services/api/src/adapters/analytics.rs
+//!
+//! An `AnalyticsService` that stores usage events in the application database, where
+//! the admin usage report reads them back.
+
+use async_trait::async_trait;
+use reading_assistant_core::domain::UsageEvent;
+use reading_assistant_core::ports::{AnalyticsService, DatabaseService, PortResult};
+use std::sync::Arc;
+
+/// Writes each usage event as one row of `usage_events`.
+#[derive(Clone)]
+pub struct DbAnalyticsSink {
+    db: Arc<dyn DatabaseService>,
+}
+
+impl DbAnalyticsSink {
+    /// Creates a new `DbAnalyticsSink`.
+    pub fn new(db: Arc<dyn DatabaseService>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl AnalyticsService for DbAnalyticsSink {
+    async fn record(&self, event: UsageEvent) -> PortResult<()> {
+        self.db.save_usage_event(&event).await
+    }
+}
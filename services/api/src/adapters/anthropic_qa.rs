@@ -0,0 +1,294 @@
+//! services/api/src/adapters/anthropic_qa.rs
+//!
+//! This module contains an adapter for Anthropic's Claude models as the
+//! Question-Answering LLM, using the Messages API. It implements the
+//! `QuestionAnsweringService` port from the `core` crate with the same prompts as
+//! the OpenAI adapter, so either can be selected with `QA_PROVIDER`.
+
+use async_trait::async_trait;
+use futures::Stream;
+use reading_assistant_core::{
+    domain::{AnswerVerbosity, GroundingVerdict, Metered, QaReply, QuizGrade, QuizQuestion, ReadingLevel},
+    ports::{PortError, PortResult, QaReplyStream, QuestionAnsweringService},
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+
+use crate::adapters::qa_prompts::{self, Prompt};
+
+const ANTHROPIC_API_BASE: &str = "https://api.anthropic.com/v1";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+/// The longest reply requested. Answers are a few sentences, quizzes a few lines.
+const MAX_TOKENS: u32 = 1024;
+
+//=========================================================================================
+// The Main Adapter Struct
+//=========================================================================================
+
+/// An adapter that implements `QuestionAnsweringService` using Anthropic's Messages API.
+#[derive(Clone)]
+pub struct AnthropicQaAdapter {
+    http: reqwest::Client,
+    api_key: String,
+    model: String,
+}
+
+#[derive(Deserialize)]
+struct MessageResponse {
+    content: Vec<ContentBlock>,
+}
+
+#[derive(Deserialize)]
+struct ContentBlock {
+    #[serde(rename = "type")]
+    block_type: String,
+    #[serde(default)]
+    text: String,
+}
+
+impl AnthropicQaAdapter {
+    /// Creates a new `AnthropicQaAdapter`.
+    pub fn new(api_key: String, model: String) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            api_key,
+            model,
+        }
+    }
+
+    /// Sends a prompt to the Messages API, optionally asking for a streamed reply.
+    async fn send(&self, prompt: Prompt, stream: bool) -> PortResult<reqwest::Response> {
+        let body = json!({
+            "model": self.model,
+            "max_tokens": MAX_TOKENS,
+            "system": prompt.system,
+            "messages": [{ "role": "user", "content": prompt.user }],
+            "stream": stream,
+        });
+
+        let response = self
+            .http
+            .post(format!("{}/messages", ANTHROPIC_API_BASE))
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        let status = response.status();
+        if status == reqwest::StatusCode::UNAUTHORIZED {
+            return Err(PortError::Unauthorized);
+        }
+        if !status.is_success() {
+            let detail = response.text().await.unwrap_or_default();
+            return Err(PortError::Unexpected(format!(
+                "Anthropic request failed with {}: {}",
+                status, detail
+            )));
+        }
+        Ok(response)
+    }
+
+    /// Runs one request and returns the text of the reply.
+    async fn complete(&self, prompt: Prompt, what: &str) -> PortResult<String> {
+        let message: MessageResponse = self
+            .send(prompt, false)
+            .await?
+            .json()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        let text: String = message
+            .content
+            .into_iter()
+            .filter(|block| block.block_type == "text")
+            .map(|block| block.text)
+            .collect();
+        if text.is_empty() {
+            return Err(PortError::Unexpected(format!(
+                "{} LLM response contained no text content.",
+                what
+            )));
+        }
+        Ok(text)
+    }
+
+    /// Runs one summarization request with the given system instructions.
+    async fn summarize(&self, instructions: &str, text: &str) -> PortResult<String> {
+        let prompt = Prompt {
+            system: instructions.to_string(),
+            user: format!("PASSAGE:\n---\n{}\n---", text),
+        };
+        self.complete(prompt, "Summary").await
+    }
+}
+
+//=========================================================================================
+// Streaming
+//=========================================================================================
+
+/// Turns a streamed Messages API response (server-sent events) into the text it
+/// carries. Only text deltas are kept; an `error` event ends the stream with an error.
+fn text_deltas(
+    response: reqwest::Response,
+) -> impl Stream<Item = PortResult<String>> + Send + Unpin + 'static {
+    struct State {
+        response: reqwest::Response,
+        /// Bytes received after the last complete line.
+        buffer: Vec<u8>,
+        ready: VecDeque<PortResult<String>>,
+        done: bool,
+    }
+
+    let state = State {
+        response,
+        buffer: Vec::new(),
+        ready: VecDeque::new(),
+        done: false,
+    };
+
+    Box::pin(futures::stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(item) = state.ready.pop_front() {
+                if item.is_err() {
+                    state.done = true;
+                    state.ready.clear();
+                }
+                return Some((item, state));
+            }
+            if state.done {
+                return None;
+            }
+
+            match state.response.chunk().await {
+                Ok(Some(bytes)) => state.buffer.extend_from_slice(&bytes),
+                Ok(None) => state.done = true,
+                Err(e) => {
+                    state.ready.push_back(Err(PortError::Unexpected(e.to_string())));
+                    continue;
+                }
+            }
+
+            // Lines are only decoded once complete, so multi-byte characters split
+            // across chunks stay intact.
+            while let Some(end) = state.buffer.iter().position(|&b| b == b'\n') {
+                let line: Vec<u8> = state.buffer.drain(..=end).collect();
+                let line = String::from_utf8_lossy(&line);
+                if let Some(event) = parse_event(line.trim()) {
+                    state.ready.push_back(event);
+                }
+            }
+        }
+    }))
+}
+
+/// Reads one `data:` line of the event stream.
+fn parse_event(line: &str) -> Option<PortResult<String>> {
+    let data = line.strip_prefix("data:")?.trim();
+    let event: Value = serde_json::from_str(data).ok()?;
+    match event["type"].as_str()? {
+        "content_block_delta" if event["delta"]["type"] == "text_delta" => {
+            Some(Ok(event["delta"]["text"].as_str()?.to_string()))
+        }
+        "error" => Some(Err(PortError::Unexpected(format!(
+            "Anthropic stream failed: {}",
+            event["error"]["message"].as_str().unwrap_or("unknown error")
+        )))),
+        _ => None,
+    }
+}
+
+//=========================================================================================
+// `QuestionAnsweringService` Trait Implementation
+//=========================================================================================
+
+#[async_trait]
+impl QuestionAnsweringService for AnthropicQaAdapter {
+    /// Answers a user's question based on a provided snippet of text (context).
+    async fn answer_question(
+        &self,
+        question: &str,
+        context: &str,
+        verbosity: AnswerVerbosity,
+        education_mode: bool,
+        allow_clarification: bool,
+    ) -> PortResult<Metered<QaReply>> {
+        let prompt =
+            qa_prompts::answer_prompt(question, context, verbosity, education_mode, allow_clarification);
+        let content = self.complete(prompt, "QA").await?;
+        Ok(Metered {
+            value: qa_prompts::parse_answer(&content, verbosity),
+            usage: None,
+        })
+    }
+
+    /// Streams the answer to a user's question.
+    async fn answer_question_streaming(
+        &self,
+        question: &str,
+        context: &str,
+        verbosity: AnswerVerbosity,
+        education_mode: bool,
+        allow_clarification: bool,
+    ) -> PortResult<QaReplyStream> {
+        let prompt =
+            qa_prompts::answer_prompt(question, context, verbosity, education_mode, allow_clarification);
+        let response = self.send(prompt, true).await?;
+        qa_prompts::parse_answer_stream(text_deltas(response), qa_prompts::unreported_usage()).await
+    }
+
+    /// Summarizes a passage for a spoken recap: short, no lists, no headings.
+    async fn summarize_passage(&self, text: &str) -> PortResult<String> {
+        self.summarize(qa_prompts::SUMMARY_INSTRUCTIONS, text).await
+    }
+
+    /// Summarizes a passage in one sentence that completes "Last time we covered...".
+    async fn summarize_passage_in_one_sentence(&self, text: &str) -> PortResult<String> {
+        self.summarize(qa_prompts::ONE_SENTENCE_SUMMARY_INSTRUCTIONS, text).await
+    }
+
+    /// Re-explains a passage in 2-4 plain sentences pitched at the reading level.
+    async fn simplify_passage(&self, text: &str, level: ReadingLevel) -> PortResult<String> {
+        self.summarize(&qa_prompts::simplify_instructions(level), text).await
+    }
+
+    /// Generates comprehension questions as `Q:`/`A:` line pairs and parses them.
+    async fn generate_quiz_questions(
+        &self,
+        context: &str,
+        count: usize,
+    ) -> PortResult<Vec<QuizQuestion>> {
+        let content = self.complete(qa_prompts::quiz_prompt(context, count), "Quiz").await?;
+        qa_prompts::parse_quiz_questions(&content, count)
+    }
+
+    /// Checks the answer against its context with a separate, non-streamed completion.
+    async fn verify_grounding(&self, answer: &str, context: &str) -> PortResult<GroundingVerdict> {
+        let content = self
+            .complete(qa_prompts::grounding_prompt(answer, context), "Grounding")
+            .await?;
+        Ok(qa_prompts::parse_grounding(&content))
+    }
+
+    /// Grades an answer leniently: paraphrases and partial wording count as correct.
+    async fn grade_quiz_answer(
+        &self,
+        question: &QuizQuestion,
+        user_answer: &str,
+    ) -> PortResult<QuizGrade> {
+        let content = self
+            .complete(qa_prompts::grade_prompt(question, user_answer), "Grading")
+            .await?;
+        Ok(qa_prompts::parse_grade(&content))
+    }
+
+    fn provider(&self) -> &str {
+        "anthropic"
+    }
+
+    fn model(&self) -> String {
+        self.model.clone()
+    }
+}
@@ -0,0 +1,59 @@
+//! services/api/src/adapters/audio_cache.rs
+//!
+//! This module contains a disk-backed adapter for the `AudioCacheService` port.
+//! Each entry is one file named after its key, so the cache survives restarts and
+//! can be shared by several API instances through a common volume.
+
+use async_trait::async_trait;
+use reading_assistant_core::ports::{AudioCacheService, PortError, PortResult};
+use std::{io::ErrorKind, path::PathBuf};
+use uuid::Uuid;
+
+/// Stores cached audio as files in a single directory.
+#[derive(Clone)]
+pub struct DiskAudioCache {
+    dir: PathBuf,
+}
+
+impl DiskAudioCache {
+    /// Creates a new `DiskAudioCache` in `dir`, creating the directory if needed.
+    pub async fn new(dir: PathBuf) -> PortResult<Self> {
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .map_err(|e| PortError::Unexpected(format!("Failed to create audio cache dir: {}", e)))?;
+        Ok(Self { dir })
+    }
+
+    /// Keys are hex digests, so they are always safe to use as file names.
+    fn path_for(&self, key: &str) -> PortResult<PathBuf> {
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(PortError::Unexpected(format!("Invalid audio cache key '{}'", key)));
+        }
+        Ok(self.dir.join(key))
+    }
+}
+
+#[async_trait]
+impl AudioCacheService for DiskAudioCache {
+    async fn get(&self, key: &str) -> PortResult<Option<Vec<u8>>> {
+        match tokio::fs::read(self.path_for(key)?).await {
+            Ok(audio) => Ok(Some(audio)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(PortError::Unexpected(e.to_string())),
+        }
+    }
+
+    async fn put(&self, key: &str, audio: &[u8]) -> PortResult<()> {
+        let path = self.path_for(key)?;
+        // Write to a temporary file and rename it, so readers never see a partial entry.
+        let tmp_path = self.dir.join(format!(".{}.{}.tmp", key, Uuid::new_v4()));
+        tokio::fs::write(&tmp_path, audio)
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        if let Err(e) = tokio::fs::rename(&tmp_path, &path).await {
+            let _ = tokio::fs::remove_file(&tmp_path).await;
+            return Err(PortError::Unexpected(e.to_string()));
+        }
+        Ok(())
+    }
+}
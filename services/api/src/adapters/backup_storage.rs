@@ -0,0 +1,113 @@
+//! services/api/src/adapters/backup_storage.rs
+//!
+//! This module contains a disk-backed adapter for the `BackupStorageService` port.
+//! Each backup is one file in a single directory, which can be a mounted volume or
+//! a bucket mounted as a filesystem.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reading_assistant_core::{
+    domain::BackupInfo,
+    ports::{BackupStorageService, PortError, PortResult},
+};
+use std::{io::ErrorKind, path::PathBuf};
+use uuid::Uuid;
+
+/// Stores backups as files in a single directory.
+#[derive(Clone)]
+pub struct DiskBackupStorage {
+    dir: PathBuf,
+}
+
+impl DiskBackupStorage {
+    /// Creates a new `DiskBackupStorage` in `dir`, creating the directory if needed.
+    pub async fn new(dir: PathBuf) -> PortResult<Self> {
+        tokio::fs::create_dir_all(&dir)
+            .await
+            .map_err(|e| PortError::Unexpected(format!("Failed to create backup dir: {}", e)))?;
+        Ok(Self { dir })
+    }
+
+    /// Names come from clients when downloading, so only plain file names are allowed.
+    /// Names starting with '.' are reserved for files still being written.
+    fn path_for(&self, name: &str) -> PortResult<PathBuf> {
+        let valid = !name.is_empty()
+            && !name.starts_with('.')
+            && name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+        if !valid {
+            return Err(PortError::NotFound(format!("Backup '{}' not found", name)));
+        }
+        Ok(self.dir.join(name))
+    }
+}
+
+#[async_trait]
+impl BackupStorageService for DiskBackupStorage {
+    async fn put(&self, name: &str, data: &[u8]) -> PortResult<()> {
+        let path = self.path_for(name)?;
+        // Write to a temporary file and rename it, so a listed backup is always complete.
+        let tmp_path = self.dir.join(format!(".{}.{}.tmp", name, Uuid::new_v4()));
+        tokio::fs::write(&tmp_path, data)
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        if let Err(e) = tokio::fs::rename(&tmp_path, &path).await {
+            let _ = tokio::fs::remove_file(&tmp_path).await;
+            return Err(PortError::Unexpected(e.to_string()));
+        }
+        Ok(())
+    }
+
+    async fn list(&self) -> PortResult<Vec<BackupInfo>> {
+        let mut entries = tokio::fs::read_dir(&self.dir)
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        let mut backups = Vec::new();
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?
+        {
+            let Ok(name) = entry.file_name().into_string() else {
+                continue;
+            };
+            if name.starts_with('.') {
+                continue;
+            }
+            let metadata = entry
+                .metadata()
+                .await
+                .map_err(|e| PortError::Unexpected(e.to_string()))?;
+            if !metadata.is_file() {
+                continue;
+            }
+            let created_at: DateTime<Utc> = metadata
+                .modified()
+                .map_err(|e| PortError::Unexpected(e.to_string()))?
+                .into();
+            backups.push(BackupInfo {
+                name,
+                size_bytes: metadata.len(),
+                created_at,
+            });
+        }
+
+        backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        Ok(backups)
+    }
+
+    async fn get(&self, name: &str) -> PortResult<Option<Vec<u8>>> {
+        let path = match self.path_for(name) {
+            Ok(path) => path,
+            Err(PortError::NotFound(_)) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        match tokio::fs::read(path).await {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(PortError::Unexpected(e.to_string())),
+        }
+    }
+}
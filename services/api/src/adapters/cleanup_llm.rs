@@ -0,0 +1,111 @@
+//! services/api/src/adapters/cleanup_llm.rs
+//!
+//! This module contains the adapter for the text-cleanup LLM pass.
+//! It implements the `TextCleanupService` port from the `core` crate.
+
+use async_openai::{
+    config::OpenAIConfig,
+    types::{
+        ChatCompletionRequestSystemMessageArgs, ChatCompletionRequestUserMessageArgs,
+        CreateChatCompletionRequestArgs,
+    },
+    Client, error::OpenAIError,
+};
+use async_trait::async_trait;
+use reading_assistant_core::ports::{PortError, PortResult, TextCleanupService};
+
+/// The approximate number of characters sent to the model per request.
+const CLEANUP_CHUNK_CHARS: usize = 6000;
+
+//=========================================================================================
+// The Main Adapter Struct
+//=========================================================================================
+
+/// An adapter that implements `TextCleanupService` using a cheap OpenAI-compatible LLM.
+#[derive(Clone)]
+pub struct OpenAiCleanupAdapter {
+    client: Client<OpenAIConfig>,
+    model: String,
+}
+
+impl OpenAiCleanupAdapter {
+    /// Creates a new `OpenAiCleanupAdapter`.
+    pub fn new(client: Client<OpenAIConfig>, model: String) -> Self {
+        Self { client, model }
+    }
+
+    /// Splits text into chunks on paragraph boundaries so each fits in one request.
+    fn split_into_chunks(text: &str) -> Vec<String> {
+        let mut chunks = Vec::new();
+        let mut current = String::new();
+
+        for paragraph in text.split("\n\n") {
+            if !current.is_empty() && current.len() + paragraph.len() > CLEANUP_CHUNK_CHARS {
+                chunks.push(std::mem::take(&mut current));
+            }
+            if !current.is_empty() {
+                current.push_str("\n\n");
+            }
+            current.push_str(paragraph);
+        }
+        if !current.trim().is_empty() {
+            chunks.push(current);
+        }
+
+        chunks
+    }
+
+    async fn clean_chunk(&self, chunk: &str) -> PortResult<String> {
+        let messages = vec![
+            ChatCompletionRequestSystemMessageArgs::default()
+                .content("You clean up text that was pasted or extracted from PDFs and scans. Join lines that were broken mid-sentence, rejoin words hyphenated across line breaks, remove page numbers, running headers/footers and obvious OCR garbage, and keep paragraph breaks. Do NOT summarize, rephrase, translate or add anything. Respond with the cleaned text only.")
+                .build()
+                .map_err(|e| PortError::Unexpected(e.to_string()))?
+                .into(),
+            ChatCompletionRequestUserMessageArgs::default()
+                .content(chunk)
+                .build()
+                .map_err(|e| PortError::Unexpected(e.to_string()))?
+                .into(),
+        ];
+
+        let request = CreateChatCompletionRequestArgs::default()
+            .model(&self.model)
+            .messages(messages)
+            .temperature(0.0)
+            .build()
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        let response = self
+            .client
+            .chat()
+            .create(request)
+            .await
+            .map_err(|e: OpenAIError| PortError::Unexpected(e.to_string()))?;
+
+        response
+            .choices
+            .into_iter()
+            .next()
+            .and_then(|choice| choice.message.content)
+            .ok_or_else(|| {
+                PortError::Unexpected("Cleanup LLM response contained no text content.".to_string())
+            })
+    }
+}
+
+//=========================================================================================
+// `TextCleanupService` Trait Implementation
+//=========================================================================================
+
+#[async_trait]
+impl TextCleanupService for OpenAiCleanupAdapter {
+    /// Cleans the text chunk by chunk and stitches the results back together.
+    async fn clean_text(&self, text: &str) -> PortResult<String> {
+        let mut cleaned = Vec::new();
+        for chunk in Self::split_into_chunks(text) {
+            cleaned.push(self.clean_chunk(&chunk).await?);
+        }
+        Ok(cleaned.join("\n\n"))
+    }
+}
@@ -5,12 +5,23 @@
 //! with the PostgreSQL database using `sqlx`.
 
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
-use reading_assistant_core::domain::{Document, Note, QAPair, Session, User, UserCredentials, AuthSession};
+use chrono::{DateTime, NaiveDate, NaiveTime, Utc};
+use reading_assistant_core::domain::{
+    AnswerVerbosity, AuthSession, BackupSnapshot, Definition, Document, DocumentLayout, DocumentShare, DocumentSummary, DocumentUsage, DocumentTag, Feed, GalleryDocument, GlossaryEntry, IntegrityReport, InterruptedAnswer, LatencyPercentiles, LatencyStage, ListVersion, ListeningDay, LlmUsage, Note, NoteSource, PendingNote, PlanKind, PlannedSession, QAPair, QueueItem, QueueItemStatus, QuizAttempt, ReadingGoal, ReadingLevel, RelatedPassage, Session, SessionRating, SessionSnapshot, StageLatency, Tenant, TokenUsage, TtsUsage, UsageEvent, UsageEventCount,
+    UsageEventKind, User, UserApiKey, UserCredentials, UserPreferences, WeeklyRecap, Workspace, WorkspaceDocument, WorkspaceRole,
+};
+use reading_assistant_core::chunker::{self, chunk_into_sentences, Chapter, DocumentStructure, CHUNKER_VERSION};
+use reading_assistant_core::session_machine::SessionMode;
 use reading_assistant_core::ports::{DatabaseService, PortError, PortResult};
-use sqlx::{FromRow, PgPool};
+use sqlx::{FromRow, PgPool, Postgres, Transaction};
+use std::{ops::Range, sync::Arc};
 use uuid::Uuid;
 
+use crate::crypto::SecretCipher;
+
+/// How many sentence chunks a document preview is built from.
+const PREVIEW_CHUNKS: i64 = 5;
+
 //=========================================================================================
 // The Main Adapter Struct
 //=========================================================================================
@@ -19,12 +30,57 @@ use uuid::Uuid;
 #[derive(Clone)]
 pub struct DbAdapter {
     pool: PgPool,
+    /// Encrypts new document text at rest, when configured.
+    document_cipher: Option<Arc<SecretCipher>>,
 }
 
 impl DbAdapter {
     /// Creates a new `DbAdapter`.
     pub fn new(pool: PgPool) -> Self {
-        Self { pool }
+        Self {
+            pool,
+            document_cipher: None,
+        }
+    }
+
+    /// Encrypts document text (and its sentence chunks) stored from now on.
+    /// Rows written before remain readable either way.
+    pub fn with_document_cipher(mut self, cipher: Arc<SecretCipher>) -> Self {
+        self.document_cipher = Some(cipher);
+        self
+    }
+
+    /// Encrypts document text if a cipher is configured. Returns the stored form
+    /// and whether it is encrypted.
+    fn seal_text(&self, text: &str) -> PortResult<(String, bool)> {
+        match &self.document_cipher {
+            Some(cipher) => cipher
+                .encrypt_str(text)
+                .map(|sealed| (sealed, true))
+                .map_err(|e| PortError::Unexpected(e.to_string())),
+            None => Ok((text.to_string(), false)),
+        }
+    }
+
+    /// Reverses `seal_text` for a stored value.
+    fn open_text(&self, stored: String, encrypted: bool) -> PortResult<String> {
+        if !encrypted {
+            return Ok(stored);
+        }
+        let cipher = self.document_cipher.as_ref().ok_or_else(|| {
+            PortError::Unexpected(
+                "Document is encrypted but no DOCUMENT_ENCRYPTION_KEY is configured".to_string(),
+            )
+        })?;
+        cipher
+            .decrypt_str(&stored)
+            .map_err(|e| PortError::Unexpected(e.to_string()))
+    }
+
+    /// Formats an embedding as a pgvector literal, e.g. `[0.1,0.2]`.
+    fn vector_literal(embedding: &[f32]) -> String {
+        let values: Vec<String> = embedding.iter().map(|v| v.to_string()).collect();
+        format!("[{}]", values.join(","))
     }
 
     /// A helper function to run database migrations at startup.
@@ -32,6 +88,98 @@ impl DbAdapter {
         sqlx::migrate!("./migrations").run(&self.pool).await?;
         Ok(())
     }
+
+    /// Inserts a document's sentence chunks, split by `chunker_version`, and their
+    /// structure inside an existing transaction, encrypting them if a document
+    /// cipher is configured.
+    async fn insert_chunks(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        document_id: Uuid,
+        chunker_version: i32,
+        chunks: &[String],
+        structure: &DocumentStructure,
+    ) -> PortResult<()> {
+        let indices: Vec<i32> = (0..chunks.len() as i32).collect();
+        let mut stored = Vec::with_capacity(chunks.len());
+        let mut encrypted = false;
+        for chunk in chunks {
+            let (text, sealed) = self.seal_text(chunk)?;
+            stored.push(text);
+            encrypted = sealed;
+        }
+        sqlx::query!(
+            "INSERT INTO document_chunks (document_id, chunk_index, text, encrypted)
+             SELECT $1, idx, txt, $4 FROM UNNEST($2::int4[], $3::text[]) AS t(idx, txt)",
+            document_id,
+            &indices[..],
+            &stored[..],
+            encrypted
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        sqlx::query!(
+            "UPDATE documents SET chunker_version = $1 WHERE id = $2",
+            chunker_version,
+            document_id
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        self.insert_structure(tx, document_id, structure).await
+    }
+
+    /// Replaces the recorded paragraph and chapter starts of a document inside an
+    /// existing transaction, marking them as computed with its chunks' chunker version.
+    async fn insert_structure(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        document_id: Uuid,
+        structure: &DocumentStructure,
+    ) -> PortResult<()> {
+        let paragraph_starts: Vec<i32> =
+            structure.paragraph_starts.iter().map(|&i| i as i32).collect();
+        sqlx::query!(
+            "UPDATE documents
+             SET paragraph_starts = $1, structure_version = COALESCE(chunker_version, 0)
+             WHERE id = $2",
+            &paragraph_starts[..],
+            document_id
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        sqlx::query!("DELETE FROM document_chapters WHERE document_id = $1", document_id)
+            .execute(&mut **tx)
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        let indices: Vec<i32> =
+            structure.chapters.iter().map(|c| c.sentence_index as i32).collect();
+        let mut titles = Vec::with_capacity(structure.chapters.len());
+        let mut encrypted = false;
+        for chapter in &structure.chapters {
+            let (title, sealed) = self.seal_text(&chapter.title)?;
+            titles.push(title);
+            encrypted = sealed;
+        }
+        sqlx::query!(
+            "INSERT INTO document_chapters (document_id, sentence_index, title, encrypted)
+             SELECT $1, idx, title, $4 FROM UNNEST($2::int4[], $3::text[]) AS t(idx, title)",
+            document_id,
+            &indices[..],
+            &titles[..],
+            encrypted
+        )
+        .execute(&mut **tx)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(())
+    }
 }
 
 //=========================================================================================
@@ -44,6 +192,8 @@ struct UserRecord {
     user_id: Uuid,
     email: Option<String>,      // Add this
     created_at: DateTime<Utc>,
+    is_guest: bool,
+    guest_expires_at: Option<DateTime<Utc>>,
 }
 
 impl UserRecord {
@@ -51,6 +201,8 @@ impl UserRecord {
         User {
             user_id: self.user_id,
             email: self.email,      // Add this
+            is_guest: self.is_guest,
+            guest_expires_at: self.guest_expires_at,
         }
     }
 }
@@ -91,18 +243,254 @@ impl AuthSessionRecord {
     }
 }
 
+#[derive(FromRow)]
+struct UserPreferencesRecord {
+    answer_verbosity: String,
+    resume_recap: bool,
+    sentence_gap_ms: i32,
+    crossfade_ms: i32,
+    auto_notes: bool,
+    checkpoint_paragraphs: Option<i32>,
+    weekly_recap: bool,
+    analytics_opt_out: bool,
+    voice: Option<String>,
+    speaking_speed: Option<f32>,
+    reading_level: String,
+}
+
+#[derive(FromRow)]
+struct PlannedSessionRecord {
+    id: Uuid,
+    session_id: Uuid,
+    kind: String,
+    title: Option<String>,
+    starts_at: DateTime<Utc>,
+    duration_minutes: i32,
+}
+
+impl PlannedSessionRecord {
+    fn to_domain(self) -> PlannedSession {
+        PlannedSession {
+            id: self.id,
+            session_id: self.session_id,
+            kind: match self.kind.as_str() {
+                "review" => PlanKind::Review,
+                _ => PlanKind::Reading,
+            },
+            title: self.title,
+            starts_at: self.starts_at,
+            duration_minutes: self.duration_minutes.max(0) as u32,
+        }
+    }
+}
+
+#[derive(FromRow)]
+struct ReadingGoalRecord {
+    minutes_per_day: i32,
+    days_per_week: i16,
+    reminders: bool,
+}
+
+impl ReadingGoalRecord {
+    fn to_domain(self) -> ReadingGoal {
+        ReadingGoal {
+            minutes_per_day: self.minutes_per_day.max(0) as u32,
+            days_per_week: self.days_per_week.clamp(0, 7) as u8,
+            reminders: self.reminders,
+        }
+    }
+}
+
+impl UserPreferencesRecord {
+    fn to_domain(self) -> UserPreferences {
+        let answer_verbosity = match self.answer_verbosity.as_str() {
+            "brief" => AnswerVerbosity::Brief,
+            "detailed" => AnswerVerbosity::Detailed,
+            _ => AnswerVerbosity::Normal,
+        };
+        UserPreferences {
+            answer_verbosity,
+            resume_recap: self.resume_recap,
+            sentence_gap_ms: self.sentence_gap_ms.max(0) as u32,
+            crossfade_ms: self.crossfade_ms.max(0) as u32,
+            auto_notes: self.auto_notes,
+            checkpoint_paragraphs: self.checkpoint_paragraphs.map(|paragraphs| paragraphs.max(1) as u32),
+            weekly_recap: self.weekly_recap,
+            analytics_opt_out: self.analytics_opt_out,
+            voice: self.voice,
+            speaking_speed: self.speaking_speed,
+            reading_level: match self.reading_level.as_str() {
+                "elementary" => ReadingLevel::Elementary,
+                "middle_school" => ReadingLevel::MiddleSchool,
+                _ => ReadingLevel::General,
+            },
+        }
+    }
+}
+
+#[derive(FromRow)]
+struct WorkspaceRecord {
+    id: Uuid,
+    name: String,
+    owner_id: Uuid,
+    join_code: String,
+    created_at: DateTime<Utc>,
+}
+
+impl WorkspaceRecord {
+    fn to_domain(self) -> Workspace {
+        Workspace {
+            id: self.id,
+            name: self.name,
+            owner_id: self.owner_id,
+            join_code: self.join_code,
+            created_at: self.created_at,
+        }
+    }
+}
+
+/// A workspace row joined with the requesting user's membership role.
+#[derive(FromRow)]
+struct WorkspaceMembershipRecord {
+    id: Uuid,
+    name: String,
+    owner_id: Uuid,
+    join_code: String,
+    created_at: DateTime<Utc>,
+    role: String,
+}
+
+impl WorkspaceMembershipRecord {
+    fn to_domain(self) -> (Workspace, WorkspaceRole) {
+        let role = parse_workspace_role(&self.role);
+        (
+            Workspace {
+                id: self.id,
+                name: self.name,
+                owner_id: self.owner_id,
+                join_code: self.join_code,
+                created_at: self.created_at,
+            },
+            role,
+        )
+    }
+}
+
+#[derive(FromRow)]
+struct WorkspaceDocumentRecord {
+    workspace_id: Uuid,
+    document_id: Uuid,
+    title: String,
+    added_at: DateTime<Utc>,
+}
+
+impl WorkspaceDocumentRecord {
+    fn to_domain(self) -> WorkspaceDocument {
+        WorkspaceDocument {
+            workspace_id: self.workspace_id,
+            document_id: self.document_id,
+            title: self.title,
+            added_at: self.added_at,
+        }
+    }
+}
+
+#[derive(FromRow)]
+struct DocumentShareRecord {
+    document_id: Uuid,
+    owner_id: Uuid,
+    owner_email: Option<String>,
+    user_id: Uuid,
+    user_email: String,
+    shared_at: DateTime<Utc>,
+}
+
+impl DocumentShareRecord {
+    fn to_domain(self) -> DocumentShare {
+        DocumentShare {
+            document_id: self.document_id,
+            owner_id: self.owner_id,
+            owner_email: self.owner_email,
+            user_id: self.user_id,
+            user_email: self.user_email,
+            shared_at: self.shared_at,
+        }
+    }
+}
+
+#[derive(FromRow)]
+struct GlossaryEntryRecord {
+    id: Uuid,
+    session_id: Uuid,
+    word: String,
+    part_of_speech: Option<String>,
+    meaning: String,
+    created_at: DateTime<Utc>,
+}
+
+impl GlossaryEntryRecord {
+    fn to_domain(self) -> GlossaryEntry {
+        GlossaryEntry {
+            id: self.id,
+            session_id: self.session_id,
+            word: self.word,
+            part_of_speech: self.part_of_speech,
+            meaning: self.meaning,
+            created_at: self.created_at,
+        }
+    }
+}
+
+#[derive(FromRow)]
+struct GalleryDocumentRecord {
+    document_id: Uuid,
+    title: String,
+    description: String,
+    added_at: DateTime<Utc>,
+}
+
+impl GalleryDocumentRecord {
+    fn to_domain(self) -> GalleryDocument {
+        GalleryDocument {
+            document_id: self.document_id,
+            title: self.title,
+            description: self.description,
+            added_at: self.added_at,
+        }
+    }
+}
+
+fn parse_workspace_role(role: &str) -> WorkspaceRole {
+    match role {
+        "teacher" => WorkspaceRole::Teacher,
+        _ => WorkspaceRole::Student,
+    }
+}
+
+fn workspace_role_str(role: WorkspaceRole) -> &'static str {
+    match role {
+        WorkspaceRole::Teacher => "teacher",
+        WorkspaceRole::Student => "student",
+    }
+}
+
 #[derive(FromRow)]
 struct DocumentRecord {
     id: Uuid,
     user_id: Uuid,
+    title: Option<String>,
     original_text: String,
+    encrypted: bool,
+    created_at: DateTime<Utc>,
 }
 impl DocumentRecord {
     fn to_domain(self) -> Document {
         Document {
             id: self.id,
             user_id: self.user_id,
+            title: self.title,
             original_text: self.original_text,
+            created_at: self.created_at,
         }
     }
 }
@@ -115,6 +503,8 @@ struct SessionRecord {
     reading_progress_index: i32,
     created_at: chrono::DateTime<chrono::Utc>,  // ✅ Add this
     last_accessed_at: chrono::DateTime<chrono::Utc>,  // ✅ Add this
+    education_mode: bool,
+    title: Option<String>,
 }
 
 impl SessionRecord {
@@ -126,6 +516,8 @@ impl SessionRecord {
             reading_progress_index: self.reading_progress_index as usize,
             created_at: self.created_at,  // ✅ Add this
             last_accessed_at: self.last_accessed_at,  // ✅ Add this
+            education_mode: self.education_mode,
+            title: self.title,
         }
     }
 }
@@ -136,6 +528,7 @@ struct QAPairRecord {
     session_id: Uuid,
     question_text: String,
     answer_text: String,
+    answer_interrupted: bool,
     created_at: DateTime<Utc>,
 }
 impl QAPairRecord {
@@ -145,6 +538,38 @@ impl QAPairRecord {
             session_id: self.session_id,
             question_text: self.question_text,
             answer_text: self.answer_text,
+            answer_interrupted: self.answer_interrupted,
+            created_at: self.created_at,
+        }
+    }
+}
+
+#[derive(FromRow)]
+struct PendingNoteRecord {
+    id: Uuid,
+    session_id: Uuid,
+    question_text: String,
+    answer_text: String,
+    answer_interrupted: bool,
+    created_at: DateTime<Utc>,
+    note_source_start: Option<i32>,
+    note_source_end: Option<i32>,
+    note_education_mode: bool,
+}
+impl PendingNoteRecord {
+    fn to_domain(self) -> PendingNote {
+        PendingNote {
+            qa_pair: QAPair {
+                id: self.id,
+                session_id: self.session_id,
+                question_text: self.question_text,
+                answer_text: self.answer_text,
+                answer_interrupted: self.answer_interrupted,
+                created_at: self.created_at,
+            },
+            source_start: self.note_source_start.unwrap_or(0) as usize,
+            source_end: self.note_source_end.unwrap_or(0) as usize,
+            education_mode: self.note_education_mode,
         }
     }
 }
@@ -155,6 +580,9 @@ struct NoteRecord {
     session_id: Uuid,
     generated_note_text: String,
     created_at: chrono::DateTime<chrono::Utc>, 
+    source_start_index: Option<i32>,
+    source_end_index: Option<i32>,
+    source: String,
 }
 impl NoteRecord {
     fn to_domain(self) -> Note {
@@ -163,6 +591,254 @@ impl NoteRecord {
             session_id: self.session_id,
             generated_note_text: self.generated_note_text,
             created_at: self.created_at,
+            source_start_index: self.source_start_index.map(|i| i as usize),
+            source_end_index: self.source_end_index.map(|i| i as usize),
+            source: match self.source.as_str() {
+                "auto" => NoteSource::Auto,
+                "summary" => NoteSource::Summary,
+                "weekly_recap" => NoteSource::WeeklyRecap,
+                "imported" => NoteSource::Imported,
+                _ => NoteSource::Question,
+            },
+        }
+    }
+}
+
+/// The value stored in `notes.source`.
+fn note_source_column(source: NoteSource) -> &'static str {
+    match source {
+        NoteSource::Question => "question",
+        NoteSource::Auto => "auto",
+        NoteSource::Summary => "summary",
+        NoteSource::WeeklyRecap => "weekly_recap",
+        NoteSource::Imported => "imported",
+    }
+}
+
+/// The value stored in `session_snapshots.mode`.
+fn session_mode_column(mode: SessionMode) -> &'static str {
+    match mode {
+        SessionMode::Reading => "reading",
+        SessionMode::InterruptedListening => "interrupted_listening",
+        SessionMode::ProcessingQuestion => "processing_question",
+        SessionMode::Clarifying => "clarifying",
+        SessionMode::Checkpoint => "checkpoint",
+        SessionMode::Paused => "paused",
+        SessionMode::Ended => "ended",
+    }
+}
+
+fn session_mode_from_column(mode: &str) -> Option<SessionMode> {
+    Some(match mode {
+        "reading" => SessionMode::Reading,
+        "interrupted_listening" => SessionMode::InterruptedListening,
+        "processing_question" => SessionMode::ProcessingQuestion,
+        "clarifying" => SessionMode::Clarifying,
+        "checkpoint" => SessionMode::Checkpoint,
+        "paused" => SessionMode::Paused,
+        "ended" => SessionMode::Ended,
+        _ => return None,
+    })
+}
+
+/// The value stored in `question_latencies.stage`.
+fn latency_stage_column(stage: LatencyStage) -> &'static str {
+    match stage {
+        LatencyStage::Stt => "stt",
+        LatencyStage::Llm => "llm",
+        LatencyStage::Tts => "tts",
+        LatencyStage::Total => "total",
+    }
+}
+
+fn latency_stage_from_column(stage: &str) -> Option<LatencyStage> {
+    Some(match stage {
+        "stt" => LatencyStage::Stt,
+        "llm" => LatencyStage::Llm,
+        "tts" => LatencyStage::Tts,
+        "total" => LatencyStage::Total,
+        _ => return None,
+    })
+}
+
+/// The value stored in `usage_events.kind`.
+fn usage_event_kind_column(kind: UsageEventKind) -> &'static str {
+    match kind {
+        UsageEventKind::SessionOpened => "session_opened",
+        UsageEventKind::QuestionAsked => "question_asked",
+        UsageEventKind::Paused => "paused",
+        UsageEventKind::Resumed => "resumed",
+        UsageEventKind::Seeked => "seeked",
+        UsageEventKind::QuizStarted => "quiz_started",
+        UsageEventKind::ResumeCommand => "resume_command",
+        UsageEventKind::SeekCommand => "seek_command",
+        UsageEventKind::SummaryCommand => "summary_command",
+        UsageEventKind::SpeedCommand => "speed_command",
+    }
+}
+
+fn usage_event_kind_from_column(kind: &str) -> Option<UsageEventKind> {
+    Some(match kind {
+        "session_opened" => UsageEventKind::SessionOpened,
+        "question_asked" => UsageEventKind::QuestionAsked,
+        "paused" => UsageEventKind::Paused,
+        "resumed" => UsageEventKind::Resumed,
+        "seeked" => UsageEventKind::Seeked,
+        "quiz_started" => UsageEventKind::QuizStarted,
+        "resume_command" => UsageEventKind::ResumeCommand,
+        "seek_command" => UsageEventKind::SeekCommand,
+        "summary_command" => UsageEventKind::SummaryCommand,
+        "speed_command" => UsageEventKind::SpeedCommand,
+        _ => return None,
+    })
+}
+
+#[derive(FromRow)]
+struct FeedRecord {
+    id: Uuid,
+    user_id: Uuid,
+    url: String,
+    last_fetched_at: Option<DateTime<Utc>>,
+}
+impl FeedRecord {
+    fn to_domain(self) -> Feed {
+        Feed {
+            id: self.id,
+            user_id: self.user_id,
+            url: self.url,
+            last_fetched_at: self.last_fetched_at,
+        }
+    }
+}
+
+#[derive(FromRow)]
+struct QueueItemRecord {
+    id: Uuid,
+    user_id: Uuid,
+    feed_id: Option<Uuid>,
+    url: String,
+    title: Option<String>,
+    status: String,
+    session_id: Option<Uuid>,
+    created_at: DateTime<Utc>,
+}
+impl QueueItemRecord {
+    fn to_domain(self) -> QueueItem {
+        let status = match self.status.as_str() {
+            "ready" => QueueItemStatus::Ready,
+            "failed" => QueueItemStatus::Failed,
+            _ => QueueItemStatus::Pending,
+        };
+        QueueItem {
+            id: self.id,
+            user_id: self.user_id,
+            feed_id: self.feed_id,
+            url: self.url,
+            title: self.title,
+            status,
+            session_id: self.session_id,
+            created_at: self.created_at,
+        }
+    }
+}
+
+#[derive(FromRow)]
+struct TenantRecord {
+    id: Uuid,
+    slug: String,
+    name: String,
+    hostname: Option<String>,
+}
+
+impl TenantRecord {
+    fn to_domain(self) -> Tenant {
+        Tenant {
+            id: self.id,
+            slug: self.slug,
+            name: self.name,
+            hostname: self.hostname,
+        }
+    }
+}
+
+#[derive(FromRow)]
+struct UserApiKeyRecord {
+    provider: String,
+    encrypted_key: String,
+    key_hint: String,
+    created_at: DateTime<Utc>,
+}
+
+impl UserApiKeyRecord {
+    fn to_domain(self) -> UserApiKey {
+        UserApiKey {
+            provider: self.provider,
+            encrypted_key: self.encrypted_key,
+            key_hint: self.key_hint,
+            created_at: self.created_at,
+        }
+    }
+}
+
+#[derive(FromRow)]
+struct TtsUsageRecord {
+    usage_date: NaiveDate,
+    provider: String,
+    model: String,
+    characters: i64,
+    requests: i64,
+}
+
+impl TtsUsageRecord {
+    fn to_domain(self) -> TtsUsage {
+        TtsUsage {
+            usage_date: self.usage_date,
+            provider: self.provider,
+            model: self.model,
+            characters: self.characters.max(0) as u64,
+            requests: self.requests.max(0) as u64,
+        }
+    }
+}
+
+#[derive(FromRow)]
+struct LlmUsageRecord {
+    usage_date: NaiveDate,
+    provider: String,
+    model: String,
+    prompt_tokens: i64,
+    completion_tokens: i64,
+    requests: i64,
+}
+
+impl LlmUsageRecord {
+    fn to_domain(self) -> LlmUsage {
+        LlmUsage {
+            usage_date: self.usage_date,
+            provider: self.provider,
+            model: self.model,
+            prompt_tokens: self.prompt_tokens.max(0) as u64,
+            completion_tokens: self.completion_tokens.max(0) as u64,
+            requests: self.requests.max(0) as u64,
+        }
+    }
+}
+
+#[derive(FromRow)]
+struct WeeklyRecapRecord {
+    user_id: Uuid,
+    week_start: NaiveDate,
+    text: String,
+    spoken_at: Option<DateTime<Utc>>,
+}
+
+impl WeeklyRecapRecord {
+    fn to_domain(self) -> WeeklyRecap {
+        WeeklyRecap {
+            user_id: self.user_id,
+            week_start: self.week_start,
+            text: self.text,
+            spoken_at: self.spoken_at,
         }
     }
 }
@@ -181,7 +857,7 @@ impl DatabaseService for DbAdapter {
 
         let record = sqlx::query_as!(
             UserRecord,
-            "SELECT user_id, email, created_at FROM users WHERE user_id = $1",  // Add email here
+            "SELECT user_id, email, created_at, is_guest, guest_expires_at FROM users WHERE user_id = $1",  // Add email here
             user_id
         )
         .fetch_one(&self.pool)
@@ -195,9 +871,9 @@ impl DatabaseService for DbAdapter {
   }
 
     async fn get_document_by_id(&self, document_id: Uuid) -> PortResult<Document> {
-        let record = sqlx::query_as!(
+        let mut record = sqlx::query_as!(
             DocumentRecord,
-            "SELECT id, user_id, original_text FROM documents WHERE id = $1",
+            "SELECT id, user_id, title, original_text, encrypted, created_at FROM documents WHERE id = $1",
             document_id
         )
         .fetch_one(&self.pool)
@@ -206,67 +882,2104 @@ impl DatabaseService for DbAdapter {
             sqlx::Error::RowNotFound => PortError::NotFound(format!("Document {} not found", document_id)),
             _ => PortError::Unexpected(e.to_string()),
         })?;
+        record.original_text = self.open_text(record.original_text, record.encrypted)?;
         Ok(record.to_domain())
     }
 
-    async fn create_document(&self, user_id: Uuid, _title: &str, original_text: &str) -> PortResult<Document> {
-        let record = sqlx::query_as!(
-            DocumentRecord,
-            "INSERT INTO documents (id, user_id, original_text) VALUES ($1, $2, $3) RETURNING id, user_id, original_text",
-            Uuid::new_v4(),
+    async fn get_document_summaries_by_user(
+        &self,
+        user_id: Uuid,
+        offset: usize,
+        limit: Option<usize>,
+        preview_chars: usize,
+    ) -> PortResult<Vec<DocumentSummary>> {
+        // The preview is built from the first few sentence chunks rather than the text,
+        // which can be a whole book. Encrypted chunks can only be cut once opened.
+        let records = sqlx::query!(
+            r#"SELECT d.id, d.title, d.created_at,
+                      COALESCE(p.texts, '{}'::text[]) AS "preview_texts!",
+                      COALESCE(p.encrypted, '{}'::bool[]) AS "preview_encrypted!"
+               FROM documents d
+               LEFT JOIN LATERAL (
+                   SELECT array_agg(CASE WHEN c.encrypted THEN c.text ELSE LEFT(c.text, $4) END
+                                    ORDER BY c.chunk_index) AS texts,
+                          array_agg(c.encrypted ORDER BY c.chunk_index) AS encrypted
+                   FROM (SELECT text, encrypted, chunk_index FROM document_chunks
+                         WHERE document_id = d.id
+                         ORDER BY chunk_index
+                         LIMIT $5) c
+               ) p ON true
+               WHERE d.user_id = $1
+               ORDER BY d.created_at DESC, d.id
+               OFFSET $2
+               LIMIT $3"#,
             user_id,
-            original_text
+            offset as i64,
+            limit.map(|limit| limit as i64),
+            preview_chars as i32,
+            PREVIEW_CHUNKS
         )
-        .fetch_one(&self.pool)
+        .fetch_all(&self.pool)
         .await
         .map_err(|e| PortError::Unexpected(e.to_string()))?;
-        Ok(record.to_domain())
+
+        records
+            .into_iter()
+            .map(|r| {
+                let sentences = r
+                    .preview_texts
+                    .into_iter()
+                    .zip(r.preview_encrypted)
+                    .map(|(text, encrypted)| self.open_text(text, encrypted))
+                    .collect::<PortResult<Vec<_>>>()?;
+                Ok(DocumentSummary {
+                    id: r.id,
+                    title: r.title,
+                    preview: sentences.join(" ").chars().take(preview_chars).collect(),
+                    created_at: r.created_at,
+                })
+            })
+            .collect()
     }
 
-    async fn get_session_by_id(&self, session_id: Uuid) -> PortResult<Session> {
-        let record = sqlx::query_as!(
-            SessionRecord,
-            "SELECT id, user_id, document_id, reading_progress_index, created_at, last_accessed_at 
-            FROM sessions 
-            WHERE id = $1",
+    async fn audit_integrity(&self, repair: bool) -> PortResult<IntegrityReport> {
+        // One transaction, so the report is a consistent snapshot; it is only
+        // committed when repairing.
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        // Orphans first, so what they leave behind isn't reported again below.
+        let sessions_without_document: Vec<Uuid> = sqlx::query_scalar!(
+            "SELECT s.id FROM sessions s
+             WHERE NOT EXISTS (SELECT 1 FROM documents d WHERE d.id = s.document_id)
+             ORDER BY s.created_at"
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        let notes_without_session: Vec<Uuid> = sqlx::query_scalar!(
+            "SELECT n.id FROM notes n
+             WHERE NOT EXISTS (SELECT 1 FROM sessions s WHERE s.id = n.session_id)
+             ORDER BY n.created_at"
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        let latencies_without_question: Vec<Uuid> = sqlx::query_scalar!(
+            r#"SELECT DISTINCT l.question_id AS "question_id!" FROM question_latencies l
+               WHERE NOT EXISTS (SELECT 1 FROM qa_pairs q WHERE q.id = l.question_id)
+               ORDER BY 1"#
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        if repair {
+            // Questions and notes of the deleted sessions cascade with them.
+            sqlx::query!("DELETE FROM sessions WHERE id = ANY($1)", &sessions_without_document)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| PortError::Unexpected(e.to_string()))?;
+            sqlx::query!("DELETE FROM notes WHERE id = ANY($1)", &notes_without_session)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| PortError::Unexpected(e.to_string()))?;
+            sqlx::query!(
+                "DELETE FROM question_latencies WHERE question_id = ANY($1)",
+                &latencies_without_question
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        }
+
+        let documents_without_chunks: Vec<Uuid> = sqlx::query!(
+            "SELECT d.id FROM documents d
+             WHERE NOT EXISTS (SELECT 1 FROM document_chunks c WHERE c.document_id = d.id)
+             ORDER BY d.created_at"
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?
+        .into_iter()
+        .map(|r| r.id)
+        .collect();
+
+        if repair {
+            for &document_id in &documents_without_chunks {
+                let record = sqlx::query!(
+                    "SELECT original_text, encrypted FROM documents WHERE id = $1",
+                    document_id
+                )
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(|e| PortError::Unexpected(e.to_string()))?;
+                let text = self.open_text(record.original_text, record.encrypted)?;
+                self.insert_chunks(
+                    &mut tx,
+                    document_id,
+                    CHUNKER_VERSION,
+                    &chunk_into_sentences(&text),
+                    &chunker::structure(&text),
+                )
+                .await?;
+            }
+        }
+
+        let sessions_past_end: Vec<Uuid> = sqlx::query!(
+            "SELECT s.id FROM sessions s
+             WHERE s.reading_progress_index >
+                 (SELECT COUNT(*) FROM document_chunks c WHERE c.document_id = s.document_id)
+             ORDER BY s.created_at"
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?
+        .into_iter()
+        .map(|r| r.id)
+        .collect();
+
+        let queue_items_without_session: Vec<Uuid> = sqlx::query!(
+            "SELECT id FROM queue_items WHERE status = 'ready' AND session_id IS NULL ORDER BY created_at"
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?
+        .into_iter()
+        .map(|r| r.id)
+        .collect();
+
+        if repair {
+            sqlx::query!(
+                "UPDATE sessions s SET reading_progress_index =
+                     (SELECT COUNT(*) FROM document_chunks c WHERE c.document_id = s.document_id)::int
+                 WHERE s.id = ANY($1)",
+                &sessions_past_end
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+            sqlx::query!(
+                "DELETE FROM queue_items WHERE id = ANY($1)",
+                &queue_items_without_session
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+            tx.commit()
+                .await
+                .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        }
+
+        Ok(IntegrityReport {
+            sessions_without_document,
+            notes_without_session,
+            latencies_without_question,
+            documents_without_chunks,
+            sessions_past_end,
+            queue_items_without_session,
+            repaired: repair,
+        })
+    }
+
+    async fn get_backup_snapshot(&self) -> PortResult<BackupSnapshot> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        // One snapshot for all three reads, so no note refers to a session the
+        // backup doesn't have.
+        sqlx::query("SET TRANSACTION ISOLATION LEVEL REPEATABLE READ, READ ONLY")
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        let documents = sqlx::query_as!(
+            DocumentRecord,
+            "SELECT id, user_id, title, original_text, encrypted, created_at FROM documents ORDER BY created_at"
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        let sessions = sqlx::query_as!(
+            SessionRecord,
+            "SELECT id, user_id, document_id, reading_progress_index, created_at, last_accessed_at, education_mode, title
+             FROM sessions ORDER BY created_at"
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        let notes = sqlx::query_as!(
+            NoteRecord,
+            "SELECT id, session_id, generated_note_text, created_at, source_start_index, source_end_index, source
+             FROM notes ORDER BY created_at"
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        let documents = documents
+            .into_iter()
+            .map(|mut record| {
+                record.original_text = self.open_text(record.original_text, record.encrypted)?;
+                Ok(record.to_domain())
+            })
+            .collect::<PortResult<Vec<_>>>()?;
+
+        Ok(BackupSnapshot {
+            documents,
+            sessions: sessions.into_iter().map(|r| r.to_domain()).collect(),
+            notes: notes.into_iter().map(|r| r.to_domain()).collect(),
+        })
+    }
+
+    async fn delete_document(&self, document_id: Uuid) -> PortResult<()> {
+        // Sessions, with their questions and notes, cascade with the document.
+        let deleted = sqlx::query!("DELETE FROM documents WHERE id = $1", document_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?
+            .rows_affected();
+        if deleted == 0 {
+            return Err(PortError::NotFound(format!("Document {} not found", document_id)));
+        }
+        Ok(())
+    }
+
+    async fn set_document_title(&self, document_id: Uuid, title: Option<&str>) -> PortResult<()> {
+        let updated = sqlx::query!(
+            "UPDATE documents SET title = $1 WHERE id = $2",
+            title,
+            document_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?
+        .rows_affected();
+        if updated == 0 {
+            return Err(PortError::NotFound(format!("Document {} not found", document_id)));
+        }
+        Ok(())
+    }
+
+    async fn create_document(&self, user_id: Uuid, title: &str, original_text: &str) -> PortResult<Document> {
+        let chunks = chunk_into_sentences(original_text);
+        let (stored_text, encrypted) = self.seal_text(original_text)?;
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        let mut record = sqlx::query_as!(
+            DocumentRecord,
+            "INSERT INTO documents (id, user_id, original_text, encrypted, tenant_id, title)
+             SELECT $1, $2, $3, $4, tenant_id, $5 FROM users WHERE user_id = $2
+             RETURNING id, user_id, title, original_text, encrypted, created_at",
+            Uuid::new_v4(),
+            user_id,
+            stored_text,
+            encrypted,
+            Some(title).filter(|title| !title.trim().is_empty())
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        let structure = chunker::structure(original_text);
+        self.insert_chunks(&mut tx, record.id, CHUNKER_VERSION, &chunks, &structure)
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        record.original_text = original_text.to_string();
+        Ok(record.to_domain())
+    }
+
+    async fn get_document_chunks(&self, document_id: Uuid) -> PortResult<Vec<String>> {
+        let records = sqlx::query!(
+            "SELECT text, encrypted FROM document_chunks WHERE document_id = $1 ORDER BY chunk_index ASC",
+            document_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        records
+            .into_iter()
+            .map(|r| self.open_text(r.text, r.encrypted))
+            .collect()
+    }
+
+    async fn count_document_chunks(&self, document_id: Uuid) -> PortResult<usize> {
+        let record = sqlx::query!(
+            r#"SELECT COUNT(*) as "count!" FROM document_chunks WHERE document_id = $1"#,
+            document_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        Ok(record.count as usize)
+    }
+
+    async fn get_document_layout(&self, document_id: Uuid) -> PortResult<DocumentLayout> {
+        let record = sqlx::query!(
+            r#"SELECT COALESCE(d.chunker_version, 0) AS "chunker_version!",
+                      d.structure_version, d.paragraph_starts,
+                      (SELECT COUNT(*) FROM document_chunks c WHERE c.document_id = d.id) AS "sentence_count!"
+               FROM documents d WHERE d.id = $1"#,
+            document_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?
+        .ok_or_else(|| PortError::NotFound(format!("Document {} not found", document_id)))?;
+
+        let structure = if record.structure_version == Some(record.chunker_version) {
+            let chapters = sqlx::query!(
+                "SELECT sentence_index, title, encrypted FROM document_chapters
+                 WHERE document_id = $1
+                 ORDER BY sentence_index ASC",
+                document_id
+            )
+            .fetch_all(&self.pool)
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?
+            .into_iter()
+            .map(|r| {
+                Ok(Chapter {
+                    title: self.open_text(r.title, r.encrypted)?,
+                    sentence_index: r.sentence_index as usize,
+                })
+            })
+            .collect::<PortResult<Vec<_>>>()?;
+            Some(DocumentStructure {
+                paragraph_starts: record.paragraph_starts.into_iter().map(|i| i as usize).collect(),
+                chapters,
+            })
+        } else {
+            None
+        };
+
+        Ok(DocumentLayout {
+            sentence_count: record.sentence_count as usize,
+            chunker_version: record.chunker_version,
+            structure,
+        })
+    }
+
+    async fn get_document_chunk_range(
+        &self,
+        document_id: Uuid,
+        range: Range<usize>,
+    ) -> PortResult<Vec<String>> {
+        let records = sqlx::query!(
+            "SELECT text, encrypted FROM document_chunks
+             WHERE document_id = $1 AND chunk_index >= $2 AND chunk_index < $3
+             ORDER BY chunk_index ASC",
+            document_id,
+            range.start as i32,
+            range.end as i32
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        records
+            .into_iter()
+            .map(|r| self.open_text(r.text, r.encrypted))
+            .collect()
+    }
+
+    async fn get_document_chunks_at(
+        &self,
+        document_id: Uuid,
+        indices: &[usize],
+    ) -> PortResult<Vec<(usize, String)>> {
+        let indices: Vec<i32> = indices.iter().map(|&i| i as i32).collect();
+        let records = sqlx::query!(
+            "SELECT chunk_index, text, encrypted FROM document_chunks
+             WHERE document_id = $1 AND chunk_index = ANY($2)
+             ORDER BY chunk_index ASC",
+            document_id,
+            &indices
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        records
+            .into_iter()
+            .map(|r| Ok((r.chunk_index as usize, self.open_text(r.text, r.encrypted)?)))
+            .collect()
+    }
+
+    async fn save_document_chunks(
+        &self,
+        document_id: Uuid,
+        chunker_version: i32,
+        chunks: &[String],
+        structure: &DocumentStructure,
+    ) -> PortResult<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        sqlx::query!("DELETE FROM document_chunks WHERE document_id = $1", document_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        self.insert_chunks(&mut tx, document_id, chunker_version, chunks, structure)
+            .await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn save_document_structure(
+        &self,
+        document_id: Uuid,
+        structure: &DocumentStructure,
+    ) -> PortResult<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        self.insert_structure(&mut tx, document_id, structure).await?;
+
+        tx.commit()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get_unembedded_chunks(&self, document_id: Uuid) -> PortResult<Vec<(usize, String)>> {
+        let records = sqlx::query!(
+            "SELECT chunk_index, text, encrypted FROM document_chunks
+             WHERE document_id = $1 AND embedding IS NULL
+             ORDER BY chunk_index ASC",
+            document_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        records
+            .into_iter()
+            .map(|r| Ok((r.chunk_index as usize, self.open_text(r.text, r.encrypted)?)))
+            .collect()
+    }
+
+    async fn save_chunk_embeddings(
+        &self,
+        document_id: Uuid,
+        embeddings: &[(usize, Vec<f32>)],
+    ) -> PortResult<()> {
+        let indices: Vec<i32> = embeddings.iter().map(|(i, _)| *i as i32).collect();
+        let vectors: Vec<String> = embeddings
+            .iter()
+            .map(|(_, embedding)| Self::vector_literal(embedding))
+            .collect();
+        sqlx::query!(
+            "UPDATE document_chunks c SET embedding = t.emb::vector
+             FROM UNNEST($2::int4[], $3::text[]) AS t(idx, emb)
+             WHERE c.document_id = $1 AND c.chunk_index = t.idx",
+            document_id,
+            &indices[..],
+            &vectors[..]
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn search_document_chunks(
+        &self,
+        document_id: Uuid,
+        embedding: &[f32],
+        limit: usize,
+    ) -> PortResult<Vec<(usize, f32)>> {
+        let records = sqlx::query!(
+            r#"SELECT chunk_index, (embedding <=> $2::text::vector) AS "distance!"
+               FROM document_chunks
+               WHERE document_id = $1 AND embedding IS NOT NULL
+               ORDER BY embedding <=> $2::text::vector
+               LIMIT $3"#,
+            document_id,
+            Self::vector_literal(embedding),
+            limit as i64
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(records
+            .into_iter()
+            .map(|r| (r.chunk_index as usize, (1.0 - r.distance) as f32))
+            .collect())
+    }
+
+    async fn find_related_passage(
+        &self,
+        user_id: Uuid,
+        exclude_document_id: Uuid,
+        embedding: &[f32],
+    ) -> PortResult<Option<RelatedPassage>> {
+        // Documents embedded with a model of another size can't be compared, so they are skipped.
+        let record = sqlx::query!(
+            r#"SELECT c.document_id, c.chunk_index, c.text, c.encrypted,
+                      (c.embedding <=> $3::text::vector) AS "distance!",
+                      (SELECT s.id FROM sessions s
+                       WHERE s.document_id = c.document_id AND s.user_id = $1
+                       ORDER BY s.last_accessed_at DESC
+                       LIMIT 1) AS session_id
+               FROM document_chunks c
+               JOIN documents d ON d.id = c.document_id
+               WHERE d.user_id = $1 AND c.document_id <> $2 AND c.embedding IS NOT NULL
+                 AND vector_dims(c.embedding) = vector_dims($3::text::vector)
+               ORDER BY c.embedding <=> $3::text::vector
+               LIMIT 1"#,
+            user_id,
+            exclude_document_id,
+            Self::vector_literal(embedding)
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        record
+            .map(|r| {
+                Ok(RelatedPassage {
+                    document_id: r.document_id,
+                    session_id: r.session_id,
+                    chunk_index: r.chunk_index as usize,
+                    text: self.open_text(r.text, r.encrypted)?,
+                    // pgvector's `<=>` is the cosine distance.
+                    similarity: (1.0 - r.distance) as f32,
+                })
+            })
+            .transpose()
+    }
+
+    async fn get_session_by_id(&self, session_id: Uuid) -> PortResult<Session> {
+        let record = sqlx::query_as!(
+            SessionRecord,
+            "SELECT id, user_id, document_id, reading_progress_index, created_at, last_accessed_at, education_mode, title 
+            FROM sessions 
+            WHERE id = $1",
+            session_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => PortError::NotFound("Session not found".to_string()),
+            _ => PortError::Unexpected(e.to_string()),
+        })?;
+        
+        Ok(record.to_domain())
+    }
+
+    async fn create_session(
+        &self,
+        user_id: Uuid,
+        document_id: Uuid,
+        education_mode: bool,
+    ) -> PortResult<Session> {
+    let record = sqlx::query_as!(
+        SessionRecord,
+        "INSERT INTO sessions (id, user_id, document_id, education_mode, tenant_id)
+         SELECT $1, $2, $3, $4, tenant_id FROM users WHERE user_id = $2
+         RETURNING id, user_id, document_id, reading_progress_index, created_at, last_accessed_at, education_mode, title",
+        Uuid::new_v4(),  // ✅ Generate ID here
+        user_id,
+        document_id,
+        education_mode
+    )
+    .fetch_one(&self.pool)
+    .await
+    .map_err(|e| PortError::Unexpected(e.to_string()))?;
+    
+    Ok(record.to_domain())
+    }
+
+    async fn delete_session(&self, session_id: Uuid) -> PortResult<()> {
+        // Questions, notes and the session's other rows cascade with it.
+        let deleted = sqlx::query!("DELETE FROM sessions WHERE id = $1", session_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?
+            .rows_affected();
+        if deleted == 0 {
+            return Err(PortError::NotFound("Session not found".to_string()));
+        }
+        Ok(())
+    }
+
+    async fn set_session_title(&self, session_id: Uuid, title: Option<&str>) -> PortResult<()> {
+        let updated = sqlx::query!(
+            "UPDATE sessions SET title = $1 WHERE id = $2",
+            title,
+            session_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?
+        .rows_affected();
+        if updated == 0 {
+            return Err(PortError::NotFound("Session not found".to_string()));
+        }
+        Ok(())
+    }
+
+    async fn update_session_progress(
+        &self,
+        session_id: Uuid,
+        device_id: Option<&str>,
+        new_progress_index: usize,
+    ) -> PortResult<()> {
+        // Merge policy: the session row only ever moves forward, so one device
+        // replaying an earlier passage can't roll back another device's progress.
+        sqlx::query!(
+            "UPDATE sessions
+             SET reading_progress_index = GREATEST(reading_progress_index, $1), last_accessed_at = NOW()
+             WHERE id = $2",
+            new_progress_index as i32,
+            session_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        if let Some(device_id) = device_id {
+            sqlx::query!(
+                "INSERT INTO device_positions (session_id, device_id, reading_progress_index)
+                 VALUES ($1, $2, $3)
+                 ON CONFLICT (session_id, device_id)
+                 DO UPDATE SET reading_progress_index = EXCLUDED.reading_progress_index, updated_at = NOW()",
+                session_id,
+                device_id,
+                new_progress_index as i32
+            )
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    async fn get_device_position(
+        &self,
+        session_id: Uuid,
+        device_id: &str,
+    ) -> PortResult<Option<usize>> {
+        let record = sqlx::query!(
+            "SELECT reading_progress_index FROM device_positions WHERE session_id = $1 AND device_id = $2",
+            session_id,
+            device_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        Ok(record.map(|r| r.reading_progress_index as usize))
+    }
+
+    async fn save_session_snapshot(&self, snapshot: &SessionSnapshot) -> PortResult<()> {
+        let interrupted = snapshot.interrupted_answer.as_ref();
+        sqlx::query!(
+            "INSERT INTO session_snapshots
+                (session_id, mode, reading_progress_index, last_question, last_answer, pending_clarification,
+                 interrupted_qa_pair_id, interrupted_heard, interrupted_remaining, saved_at)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+             ON CONFLICT (session_id) DO UPDATE SET
+                mode = EXCLUDED.mode,
+                reading_progress_index = EXCLUDED.reading_progress_index,
+                last_question = EXCLUDED.last_question,
+                last_answer = EXCLUDED.last_answer,
+                pending_clarification = EXCLUDED.pending_clarification,
+                interrupted_qa_pair_id = EXCLUDED.interrupted_qa_pair_id,
+                interrupted_heard = EXCLUDED.interrupted_heard,
+                interrupted_remaining = EXCLUDED.interrupted_remaining,
+                saved_at = EXCLUDED.saved_at",
+            snapshot.session_id,
+            session_mode_column(snapshot.mode),
+            snapshot.reading_progress_index as i32,
+            snapshot.last_question,
+            snapshot.last_answer,
+            snapshot.pending_clarification,
+            interrupted.map(|answer| answer.qa_pair_id),
+            interrupted.map(|answer| answer.heard.as_slice()).unwrap_or_default(),
+            interrupted.map(|answer| answer.remaining.as_slice()).unwrap_or_default(),
+            snapshot.saved_at
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn take_session_snapshot(&self, session_id: Uuid) -> PortResult<Option<SessionSnapshot>> {
+        let record = sqlx::query!(
+            "DELETE FROM session_snapshots WHERE session_id = $1
+             RETURNING mode, reading_progress_index, last_question, last_answer, pending_clarification,
+                       interrupted_qa_pair_id, interrupted_heard, interrupted_remaining, saved_at",
+            session_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        let Some(r) = record else {
+            return Ok(None);
+        };
+        let mode = session_mode_from_column(&r.mode)
+            .ok_or_else(|| PortError::Unexpected(format!("Unknown session mode '{}'", r.mode)))?;
+        Ok(Some(SessionSnapshot {
+            session_id,
+            mode,
+            reading_progress_index: r.reading_progress_index as usize,
+            last_question: r.last_question,
+            last_answer: r.last_answer,
+            pending_clarification: r.pending_clarification,
+            interrupted_answer: r.interrupted_qa_pair_id.map(|qa_pair_id| InterruptedAnswer {
+                qa_pair_id,
+                heard: r.interrupted_heard,
+                remaining: r.interrupted_remaining,
+            }),
+            saved_at: r.saved_at,
+        }))
+    }
+
+    async fn save_pending_qa_pair(&self, pending: &PendingNote) -> PortResult<()> {
+        let qa_pair = &pending.qa_pair;
+        sqlx::query!(
+            "INSERT INTO qa_pairs (id, session_id, question_text, answer_text, answer_interrupted,
+                                   note_status, note_source_start, note_source_end, note_education_mode)
+             VALUES ($1, $2, $3, $4, $5, 'answering', $6, $7, $8)",
+            qa_pair.id,
+            qa_pair.session_id,
+            qa_pair.question_text,
+            qa_pair.answer_text,
+            qa_pair.answer_interrupted,
+            pending.source_start as i32,
+            pending.source_end as i32,
+            pending.education_mode
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn mark_answer_delivered(&self, qa_pair_id: Uuid) -> PortResult<()> {
+        sqlx::query!(
+            "UPDATE qa_pairs SET note_status = 'pending' WHERE id = $1 AND note_status = 'answering'",
+            qa_pair_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn mark_note_done(&self, qa_pair_id: Uuid) -> PortResult<()> {
+        sqlx::query!("UPDATE qa_pairs SET note_status = 'done' WHERE id = $1", qa_pair_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn mark_note_failed(&self, qa_pair_id: Uuid) -> PortResult<()> {
+        sqlx::query!("UPDATE qa_pairs SET note_status = 'failed' WHERE id = $1", qa_pair_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn claim_pending_notes(&self, before: DateTime<Utc>, limit: i64) -> PortResult<Vec<PendingNote>> {
+        let mut records = sqlx::query_as!(
+            PendingNoteRecord,
+            "UPDATE qa_pairs SET note_status = 'processing'
+             WHERE id IN (
+                 SELECT id FROM qa_pairs
+                 WHERE note_status = 'pending' AND created_at < $1
+                 ORDER BY created_at ASC
+                 LIMIT $2
+                 FOR UPDATE SKIP LOCKED
+             )
+             RETURNING id, session_id, question_text, answer_text, answer_interrupted, created_at,
+                       note_source_start, note_source_end, note_education_mode",
+            before,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        // RETURNING doesn't keep the subquery's order.
+        records.sort_by_key(|r| r.created_at);
+
+        Ok(records.into_iter().map(|r| r.to_domain()).collect())
+    }
+
+    async fn delete_qa_pair(&self, qa_pair_id: Uuid) -> PortResult<()> {
+        sqlx::query!("DELETE FROM qa_pairs WHERE id = $1", qa_pair_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn update_qa_pair_answer(
+        &self,
+        qa_pair_id: Uuid,
+        answer_text: &str,
+        answer_interrupted: bool,
+    ) -> PortResult<()> {
+        let result = sqlx::query!(
+            "UPDATE qa_pairs SET answer_text = $2, answer_interrupted = $3 WHERE id = $1",
+            qa_pair_id,
+            answer_text,
+            answer_interrupted
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(PortError::NotFound(format!("QA pair {} not found", qa_pair_id)));
+        }
+        Ok(())
+    }
+
+    async fn get_qa_pairs_for_session(&self, session_id: Uuid) -> PortResult<Vec<QAPair>> {
+        let records = sqlx::query_as!(
+            QAPairRecord,
+            "SELECT id, session_id, question_text, answer_text, answer_interrupted, created_at FROM qa_pairs WHERE session_id = $1 ORDER BY created_at ASC",
+            session_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        let qa_pairs = records.into_iter().map(|r| r.to_domain()).collect();
+        Ok(qa_pairs)
+    }
+
+    async fn save_note(&self, note: Note) -> PortResult<()> {
+        sqlx::query!(
+            "INSERT INTO notes (id, session_id, generated_note_text, source_start_index, source_end_index, source)
+             VALUES ($1, $2, $3, $4, $5, $6)",
+            note.id,
+            note.session_id,
+            note.generated_note_text,
+            note.source_start_index.map(|i| i as i32),
+            note.source_end_index.map(|i| i as i32),
+            note_source_column(note.source)
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn save_glossary_entry(&self, session_id: Uuid, definition: &Definition) -> PortResult<GlossaryEntry> {
+        let record = sqlx::query_as!(
+            GlossaryEntryRecord,
+            "INSERT INTO glossary_entries (session_id, word, part_of_speech, meaning) VALUES ($1, $2, $3, $4)
+             RETURNING id, session_id, word, part_of_speech, meaning, created_at",
+            session_id,
+            definition.word,
+            definition.part_of_speech,
+            definition.meaning
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(record.to_domain())
+    }
+
+    async fn get_glossary_for_session(&self, session_id: Uuid) -> PortResult<Vec<GlossaryEntry>> {
+        let records = sqlx::query_as!(
+            GlossaryEntryRecord,
+            "SELECT id, session_id, word, part_of_speech, meaning, created_at
+             FROM glossary_entries
+             WHERE session_id = $1
+             ORDER BY created_at ASC",
+            session_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(records.into_iter().map(|r| r.to_domain()).collect())
+    }
+
+    async fn save_quiz_attempt(&self, attempt: QuizAttempt) -> PortResult<()> {
+        sqlx::query!(
+            "INSERT INTO quiz_attempts (id, session_id, question_text, expected_answer, user_answer, correct, feedback)
+             VALUES ($1, $2, $3, $4, $5, $6, $7)",
+            attempt.id,
+            attempt.session_id,
+            attempt.question_text,
+            attempt.expected_answer,
+            attempt.user_answer,
+            attempt.correct,
+            attempt.feedback
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get_notes_for_session(&self, session_id: Uuid) -> PortResult<Vec<Note>> {
+    let records = sqlx::query_as!(
+        NoteRecord,
+        "SELECT id, session_id, generated_note_text, created_at, source_start_index, source_end_index, source
+         FROM notes 
+         WHERE session_id = $1 
+         ORDER BY created_at ASC",
+        session_id
+    )
+    .fetch_all(&self.pool)
+    .await
+    .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+    Ok(records.into_iter().map(|r| r.to_domain()).collect())
+    }
+
+    async fn get_tenant_by_slug(&self, slug: &str) -> PortResult<Tenant> {
+        let record = sqlx::query_as!(
+            TenantRecord,
+            "SELECT id, slug, name, hostname FROM tenants WHERE slug = $1",
+            slug
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => PortError::NotFound(format!("Tenant '{}' not found", slug)),
+            _ => PortError::Unexpected(e.to_string()),
+        })?;
+        Ok(record.to_domain())
+    }
+
+    async fn get_tenant_by_hostname(&self, hostname: &str) -> PortResult<Tenant> {
+        let record = sqlx::query_as!(
+            TenantRecord,
+            "SELECT id, slug, name, hostname FROM tenants WHERE hostname = $1",
+            hostname
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => {
+                PortError::NotFound(format!("No tenant for hostname '{}'", hostname))
+            }
+            _ => PortError::Unexpected(e.to_string()),
+        })?;
+        Ok(record.to_domain())
+    }
+
+    async fn create_user_with_email(
+        &self,
+        tenant_id: Uuid,
+        email: &str,
+        hashed_password: &str,
+    ) -> PortResult<User> {
+        let user_id = Uuid::new_v4();
+        sqlx::query!(
+            "INSERT INTO users (user_id, tenant_id, email, hashed_password) VALUES ($1, $2, $3, $4)",
+            user_id,
+            tenant_id,
+            email,
+            hashed_password
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        
+        Ok(User { 
+            user_id,
+            email: Some(email.to_string()),
+            is_guest: false,
+            guest_expires_at: None,
+        })
+    }
+    
+    async fn get_user_by_email(&self, tenant_id: Uuid, email: &str) -> PortResult<UserCredentials> {
+    let record = sqlx::query!(
+        "SELECT user_id, email, hashed_password FROM users WHERE tenant_id = $1 AND email = $2",
+        tenant_id,
+        email
+    )
+    .fetch_one(&self.pool)
+    .await
+    .map_err(|e| match e {
+        sqlx::Error::RowNotFound => PortError::NotFound("User not found".to_string()),
+        _ => PortError::Unexpected(e.to_string()),
+    })?;
+    
+    // Handle optional email and password
+    let email = record.email.ok_or_else(|| {
+        PortError::Unexpected("User has no email".to_string())
+    })?;
+    
+    let hashed_password = record.hashed_password.ok_or_else(|| {
+        PortError::Unexpected("User has no password".to_string())
+    })?;
+    
+    Ok(UserCredentials {
+        user_id: record.user_id,
+        email,
+        hashed_password,
+    })
+  }
+    
+    async fn create_auth_session(
+        &self,
+        session_id: &str,
+        user_id: Uuid,
+        expires_at: DateTime<Utc>,
+    ) -> PortResult<()> {
+        sqlx::query!(
+            "INSERT INTO auth_sessions (id, user_id, expires_at) VALUES ($1, $2, $3)",
+            session_id,
+            user_id,
+            expires_at
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(())
+    }
+    
+    async fn validate_auth_session(&self, session_id: &str, tenant_id: Uuid) -> PortResult<Uuid> {
+        let record = sqlx::query!(
+            "SELECT a.user_id FROM auth_sessions a
+             JOIN users u ON u.user_id = a.user_id
+             WHERE a.id = $1 AND a.expires_at > NOW() AND u.tenant_id = $2",
+            session_id,
+            tenant_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => PortError::Unauthorized,
+            _ => PortError::Unexpected(e.to_string()),
+        })?;
+        Ok(record.user_id)
+    }
+    
+    async fn delete_auth_session(&self, session_id: &str) -> PortResult<()> {
+        sqlx::query!("DELETE FROM auth_sessions WHERE id = $1", session_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get_user(&self, user_id: Uuid) -> PortResult<User> {
+        let record = sqlx::query_as!(
+            UserRecord,
+            "SELECT user_id, email, created_at, is_guest, guest_expires_at FROM users WHERE user_id = $1",
+            user_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => PortError::NotFound(format!("User {} not found", user_id)),
+            _ => PortError::Unexpected(e.to_string()),
+        })?;
+        Ok(record.to_domain())
+    }
+
+    async fn create_guest_user(
+        &self,
+        tenant_id: Uuid,
+        expires_at: DateTime<Utc>,
+        client_ip: &str,
+    ) -> PortResult<User> {
+        let record = sqlx::query_as!(
+            UserRecord,
+            "INSERT INTO users (user_id, tenant_id, is_guest, guest_expires_at, guest_ip)
+             VALUES ($1, $2, TRUE, $3, $4)
+             RETURNING user_id, email, created_at, is_guest, guest_expires_at",
+            Uuid::new_v4(),
+            tenant_id,
+            expires_at,
+            client_ip
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(record.to_domain())
+    }
+
+    async fn count_guests_created_since(&self, client_ip: &str, since: DateTime<Utc>) -> PortResult<usize> {
+        let record = sqlx::query!(
+            "SELECT COUNT(*) AS \"count!\" FROM users
+             WHERE is_guest AND guest_ip = $1 AND created_at >= $2",
+            client_ip,
+            since
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(record.count as usize)
+    }
+
+    async fn convert_guest_user(
+        &self,
+        user_id: Uuid,
+        email: &str,
+        hashed_password: &str,
+    ) -> PortResult<User> {
+        let record = sqlx::query_as!(
+            UserRecord,
+            "UPDATE users
+             SET email = $2, hashed_password = $3, is_guest = FALSE, guest_expires_at = NULL, guest_ip = NULL
+             WHERE user_id = $1 AND is_guest
+             RETURNING user_id, email, created_at, is_guest, guest_expires_at",
+            user_id,
+            email,
+            hashed_password
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => PortError::NotFound(format!("Guest {} not found", user_id)),
+            _ => PortError::Unexpected(e.to_string()),
+        })?;
+
+        // Guest auth sessions were short-lived; the new account gets a fresh one.
+        sqlx::query!("DELETE FROM auth_sessions WHERE user_id = $1", user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        Ok(record.to_domain())
+    }
+
+    async fn delete_expired_guests(&self) -> PortResult<u64> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        let expired: Vec<Uuid> = sqlx::query_scalar!(
+            "SELECT user_id FROM users WHERE is_guest AND guest_expires_at < NOW() FOR UPDATE"
+        )
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        if expired.is_empty() {
+            return Ok(0);
+        }
+
+        // Sessions and documents don't cascade with their user, so they are removed
+        // explicitly; sessions on the guests' documents, and every session's questions
+        // and notes, cascade with them.
+        sqlx::query!("DELETE FROM sessions WHERE user_id = ANY($1)", &expired[..])
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        sqlx::query!("DELETE FROM documents WHERE user_id = ANY($1)", &expired[..])
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        let deleted = sqlx::query!("DELETE FROM users WHERE user_id = ANY($1)", &expired[..])
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?
+            .rows_affected();
+
+        tx.commit()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(deleted)
+    }
+
+    async fn get_user_preferences(&self, user_id: Uuid) -> PortResult<UserPreferences> {
+        let record = sqlx::query_as!(
+            UserPreferencesRecord,
+            "SELECT answer_verbosity, resume_recap, sentence_gap_ms, crossfade_ms, auto_notes, checkpoint_paragraphs,
+                    weekly_recap, analytics_opt_out, voice, speaking_speed, reading_level
+             FROM user_preferences WHERE user_id = $1",
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        Ok(record.map(|r| r.to_domain()).unwrap_or_default())
+    }
+
+    async fn save_user_preferences(
+        &self,
+        user_id: Uuid,
+        preferences: &UserPreferences,
+    ) -> PortResult<()> {
+        let answer_verbosity = match preferences.answer_verbosity {
+            AnswerVerbosity::Brief => "brief",
+            AnswerVerbosity::Normal => "normal",
+            AnswerVerbosity::Detailed => "detailed",
+        };
+        let reading_level = match preferences.reading_level {
+            ReadingLevel::Elementary => "elementary",
+            ReadingLevel::MiddleSchool => "middle_school",
+            ReadingLevel::General => "general",
+        };
+        sqlx::query!(
+            "INSERT INTO user_preferences (user_id, answer_verbosity, resume_recap, sentence_gap_ms, crossfade_ms, auto_notes, weekly_recap, analytics_opt_out, voice, speaking_speed, checkpoint_paragraphs, reading_level)
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+             ON CONFLICT (user_id) DO UPDATE
+             SET answer_verbosity = EXCLUDED.answer_verbosity, resume_recap = EXCLUDED.resume_recap,
+                 sentence_gap_ms = EXCLUDED.sentence_gap_ms, crossfade_ms = EXCLUDED.crossfade_ms,
+                 auto_notes = EXCLUDED.auto_notes, checkpoint_paragraphs = EXCLUDED.checkpoint_paragraphs,
+                 weekly_recap = EXCLUDED.weekly_recap,
+                 analytics_opt_out = EXCLUDED.analytics_opt_out, voice = EXCLUDED.voice,
+                 speaking_speed = EXCLUDED.speaking_speed, reading_level = EXCLUDED.reading_level,
+                 updated_at = NOW()",
+            user_id,
+            answer_verbosity,
+            preferences.resume_recap,
+            preferences.sentence_gap_ms as i32,
+            preferences.crossfade_ms as i32,
+            preferences.auto_notes,
+            preferences.weekly_recap,
+            preferences.analytics_opt_out,
+            preferences.voice,
+            preferences.speaking_speed,
+            preferences.checkpoint_paragraphs.map(|paragraphs| paragraphs as i32),
+            reading_level
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get_sessions_by_user(
+        &self,
+        user_id: Uuid,
+        tag: Option<&str>,
+    ) -> PortResult<Vec<Session>> {
+    let records = sqlx::query_as!(
+        SessionRecord,
+        "SELECT id, user_id, document_id, reading_progress_index, created_at, last_accessed_at, education_mode, title
+         FROM sessions 
+         WHERE user_id = $1 
+           AND ($2::text IS NULL OR document_id IN (SELECT document_id FROM document_tags WHERE tag = $2 AND NOT suggested))
+         ORDER BY last_accessed_at DESC",
+        user_id,
+        tag
+    )
+    .fetch_all(&self.pool)
+    .await
+    .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+    Ok(records.into_iter().map(|r| r.to_domain()).collect())
+    }
+
+    async fn get_sessions_version(&self, user_id: Uuid, tag: Option<&str>) -> PortResult<ListVersion> {
+        let record = sqlx::query!(
+            r#"SELECT COUNT(*) AS "count!", MAX(last_accessed_at) AS last_changed_at
+               FROM sessions
+               WHERE user_id = $1
+                 AND ($2::text IS NULL OR document_id IN (SELECT document_id FROM document_tags WHERE tag = $2 AND NOT suggested))"#,
+            user_id,
+            tag
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        Ok(ListVersion {
+            count: record.count as usize,
+            last_changed_at: record.last_changed_at,
+        })
+    }
+
+    async fn get_notes_version(&self, session_id: Uuid) -> PortResult<ListVersion> {
+        let record = sqlx::query!(
+            r#"SELECT COUNT(*) AS "count!", MAX(created_at) AS last_changed_at
+               FROM notes
+               WHERE session_id = $1"#,
+            session_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        Ok(ListVersion {
+            count: record.count as usize,
+            last_changed_at: record.last_changed_at,
+        })
+    }
+
+    async fn add_document_tags(
+        &self,
+        document_id: Uuid,
+        user_id: Uuid,
+        tags: &[String],
+        suggested: bool,
+    ) -> PortResult<()> {
+        // A suggestion never downgrades a confirmed tag; a confirmation always upgrades a suggestion.
+        sqlx::query!(
+            "INSERT INTO document_tags (document_id, user_id, tag, suggested)
+             SELECT $1, $2, tag, $4 FROM UNNEST($3::text[]) AS tag
+             ON CONFLICT (document_id, tag)
+             DO UPDATE SET suggested = document_tags.suggested AND EXCLUDED.suggested",
+            document_id,
+            user_id,
+            tags,
+            suggested
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn remove_document_tag(&self, document_id: Uuid, tag: &str) -> PortResult<()> {
+        sqlx::query!(
+            "DELETE FROM document_tags WHERE document_id = $1 AND tag = $2",
+            document_id,
+            tag
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get_document_tags(&self, document_id: Uuid) -> PortResult<Vec<DocumentTag>> {
+        let records = sqlx::query!(
+            "SELECT tag, suggested FROM document_tags WHERE document_id = $1 ORDER BY suggested, tag",
+            document_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        Ok(records
+            .into_iter()
+            .map(|r| DocumentTag {
+                tag: r.tag,
+                suggested: r.suggested,
+            })
+            .collect())
+    }
+
+    async fn get_tags_by_user(&self, user_id: Uuid, prefix: Option<&str>) -> PortResult<Vec<String>> {
+        let records = sqlx::query!(
+            "SELECT DISTINCT tag FROM document_tags
+             WHERE user_id = $1 AND NOT suggested AND ($2::text IS NULL OR tag LIKE $2 || '%')
+             ORDER BY tag
+             LIMIT 20",
+            user_id,
+            prefix
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        Ok(records.into_iter().map(|r| r.tag).collect())
+    }
+
+    async fn save_integration_token(
+        &self,
+        user_id: Uuid,
+        provider: &str,
+        access_token: &str,
+    ) -> PortResult<()> {
+        sqlx::query!(
+            "INSERT INTO integration_tokens (user_id, provider, access_token) VALUES ($1, $2, $3)
+             ON CONFLICT (user_id, provider) DO UPDATE SET access_token = EXCLUDED.access_token, created_at = NOW()",
+            user_id,
+            provider,
+            access_token
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get_integration_token(&self, user_id: Uuid, provider: &str) -> PortResult<String> {
+        let record = sqlx::query!(
+            "SELECT access_token FROM integration_tokens WHERE user_id = $1 AND provider = $2",
+            user_id,
+            provider
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => {
+                PortError::NotFound(format!("No {} integration for user {}", provider, user_id))
+            }
+            _ => PortError::Unexpected(e.to_string()),
+        })?;
+        Ok(record.access_token)
+    }
+
+    async fn save_user_api_key(
+        &self,
+        user_id: Uuid,
+        provider: &str,
+        encrypted_key: &str,
+        key_hint: &str,
+    ) -> PortResult<UserApiKey> {
+        let record = sqlx::query_as!(
+            UserApiKeyRecord,
+            "INSERT INTO user_api_keys (user_id, provider, encrypted_key, key_hint) VALUES ($1, $2, $3, $4)
+             ON CONFLICT (user_id, provider) DO UPDATE
+             SET encrypted_key = EXCLUDED.encrypted_key, key_hint = EXCLUDED.key_hint, created_at = NOW()
+             RETURNING provider, encrypted_key, key_hint, created_at",
+            user_id,
+            provider,
+            encrypted_key,
+            key_hint
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(record.to_domain())
+    }
+
+    async fn get_user_api_key(&self, user_id: Uuid, provider: &str) -> PortResult<Option<UserApiKey>> {
+        let record = sqlx::query_as!(
+            UserApiKeyRecord,
+            "SELECT provider, encrypted_key, key_hint, created_at
+             FROM user_api_keys WHERE user_id = $1 AND provider = $2",
+            user_id,
+            provider
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(record.map(|r| r.to_domain()))
+    }
+
+    async fn delete_user_api_key(&self, user_id: Uuid, provider: &str) -> PortResult<()> {
+        sqlx::query!(
+            "DELETE FROM user_api_keys WHERE user_id = $1 AND provider = $2",
+            user_id,
+            provider
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn create_feed(&self, user_id: Uuid, url: &str) -> PortResult<Feed> {
+        let record = sqlx::query_as!(
+            FeedRecord,
+            "INSERT INTO feeds (user_id, url) VALUES ($1, $2)
+             ON CONFLICT (user_id, url) DO UPDATE SET url = EXCLUDED.url
+             RETURNING id, user_id, url, last_fetched_at",
+            user_id,
+            url
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(record.to_domain())
+    }
+
+    async fn get_feeds_by_user(&self, user_id: Uuid) -> PortResult<Vec<Feed>> {
+        let records = sqlx::query_as!(
+            FeedRecord,
+            "SELECT id, user_id, url, last_fetched_at FROM feeds WHERE user_id = $1 ORDER BY created_at ASC",
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(records.into_iter().map(|r| r.to_domain()).collect())
+    }
+
+    async fn get_feeds_due(&self, fetched_before: DateTime<Utc>) -> PortResult<Vec<Feed>> {
+        let records = sqlx::query_as!(
+            FeedRecord,
+            "SELECT id, user_id, url, last_fetched_at FROM feeds
+             WHERE last_fetched_at IS NULL OR last_fetched_at < $1",
+            fetched_before
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(records.into_iter().map(|r| r.to_domain()).collect())
+    }
+
+    async fn mark_feed_fetched(&self, feed_id: Uuid) -> PortResult<()> {
+        sqlx::query!("UPDATE feeds SET last_fetched_at = NOW() WHERE id = $1", feed_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn enqueue_link(
+        &self,
+        user_id: Uuid,
+        feed_id: Option<Uuid>,
+        url: &str,
+        title: Option<&str>,
+    ) -> PortResult<()> {
+        sqlx::query!(
+            "INSERT INTO queue_items (user_id, feed_id, url, title) VALUES ($1, $2, $3, $4)
+             ON CONFLICT (user_id, url) DO NOTHING",
+            user_id,
+            feed_id,
+            url,
+            title
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get_pending_queue_items(&self, limit: i64) -> PortResult<Vec<QueueItem>> {
+        let records = sqlx::query_as!(
+            QueueItemRecord,
+            "SELECT id, user_id, feed_id, url, title, status, session_id, created_at
+             FROM queue_items
+             WHERE status = 'pending'
+             ORDER BY created_at ASC
+             LIMIT $1",
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(records.into_iter().map(|r| r.to_domain()).collect())
+    }
+
+    async fn mark_queue_item_ready(
+        &self,
+        item_id: Uuid,
+        title: &str,
+        session_id: Uuid,
+    ) -> PortResult<()> {
+        sqlx::query!(
+            "UPDATE queue_items SET status = 'ready', title = COALESCE(title, $2), session_id = $3 WHERE id = $1",
+            item_id,
+            title,
             session_id
         )
-        .fetch_one(&self.pool)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn mark_queue_item_failed(&self, item_id: Uuid) -> PortResult<()> {
+        sqlx::query!("UPDATE queue_items SET status = 'failed' WHERE id = $1", item_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get_queue_for_user(&self, user_id: Uuid) -> PortResult<Vec<QueueItem>> {
+        let records = sqlx::query_as!(
+            QueueItemRecord,
+            "SELECT id, user_id, feed_id, url, title, status, session_id, created_at
+             FROM queue_items
+             WHERE user_id = $1
+             ORDER BY created_at ASC",
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(records.into_iter().map(|r| r.to_domain()).collect())
+    }
+
+    async fn create_workspace(
+        &self,
+        owner_id: Uuid,
+        name: &str,
+        join_code: &str,
+    ) -> PortResult<Workspace> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        let record = sqlx::query_as!(
+            WorkspaceRecord,
+            "INSERT INTO workspaces (name, owner_id, join_code) VALUES ($1, $2, $3)
+             RETURNING id, name, owner_id, join_code, created_at",
+            name,
+            owner_id,
+            join_code
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        sqlx::query!(
+            "INSERT INTO workspace_members (workspace_id, user_id, role) VALUES ($1, $2, 'teacher')",
+            record.id,
+            owner_id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        tx.commit()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(record.to_domain())
+    }
+
+    async fn get_workspace_by_join_code(&self, tenant_id: Uuid, join_code: &str) -> PortResult<Workspace> {
+        let record = sqlx::query_as!(
+            WorkspaceRecord,
+            "SELECT w.id, w.name, w.owner_id, w.join_code, w.created_at
+             FROM workspaces w
+             JOIN users u ON u.user_id = w.owner_id
+             WHERE w.join_code = $1 AND u.tenant_id = $2",
+            join_code,
+            tenant_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => PortError::NotFound("Workspace not found".to_string()),
+            _ => PortError::Unexpected(e.to_string()),
+        })?;
+        Ok(record.to_domain())
+    }
+
+    async fn get_workspaces_for_user(
+        &self,
+        user_id: Uuid,
+    ) -> PortResult<Vec<(Workspace, WorkspaceRole)>> {
+        let records = sqlx::query_as!(
+            WorkspaceMembershipRecord,
+            "SELECT w.id, w.name, w.owner_id, w.join_code, w.created_at, m.role
+             FROM workspaces w
+             JOIN workspace_members m ON m.workspace_id = w.id
+             WHERE m.user_id = $1
+             ORDER BY w.created_at DESC",
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(records.into_iter().map(|r| r.to_domain()).collect())
+    }
+
+    async fn get_workspace_role(
+        &self,
+        workspace_id: Uuid,
+        user_id: Uuid,
+    ) -> PortResult<Option<WorkspaceRole>> {
+        let record = sqlx::query!(
+            "SELECT role FROM workspace_members WHERE workspace_id = $1 AND user_id = $2",
+            workspace_id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(record.map(|r| parse_workspace_role(&r.role)))
+    }
+
+    async fn add_workspace_member(
+        &self,
+        workspace_id: Uuid,
+        user_id: Uuid,
+        role: WorkspaceRole,
+    ) -> PortResult<()> {
+        sqlx::query!(
+            "INSERT INTO workspace_members (workspace_id, user_id, role) VALUES ($1, $2, $3)
+             ON CONFLICT (workspace_id, user_id) DO NOTHING",
+            workspace_id,
+            user_id,
+            workspace_role_str(role)
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn add_workspace_document(
+        &self,
+        workspace_id: Uuid,
+        document_id: Uuid,
+        title: &str,
+    ) -> PortResult<WorkspaceDocument> {
+        let record = sqlx::query_as!(
+            WorkspaceDocumentRecord,
+            "INSERT INTO workspace_documents (workspace_id, document_id, title) VALUES ($1, $2, $3)
+             ON CONFLICT (workspace_id, document_id) DO UPDATE SET title = EXCLUDED.title
+             RETURNING workspace_id, document_id, title, added_at",
+            workspace_id,
+            document_id,
+            title
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(record.to_domain())
+    }
+
+    async fn get_workspace_documents(&self, workspace_id: Uuid) -> PortResult<Vec<WorkspaceDocument>> {
+        let records = sqlx::query_as!(
+            WorkspaceDocumentRecord,
+            "SELECT workspace_id, document_id, title, added_at
+             FROM workspace_documents
+             WHERE workspace_id = $1
+             ORDER BY added_at DESC",
+            workspace_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(records.into_iter().map(|r| r.to_domain()).collect())
+    }
+
+    async fn get_workspace_document(
+        &self,
+        workspace_id: Uuid,
+        document_id: Uuid,
+    ) -> PortResult<WorkspaceDocument> {
+        let record = sqlx::query_as!(
+            WorkspaceDocumentRecord,
+            "SELECT workspace_id, document_id, title, added_at
+             FROM workspace_documents
+             WHERE workspace_id = $1 AND document_id = $2",
+            workspace_id,
+            document_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => {
+                PortError::NotFound("Document not found in workspace".to_string())
+            }
+            _ => PortError::Unexpected(e.to_string()),
+        })?;
+        Ok(record.to_domain())
+    }
+
+    async fn add_gallery_document(
+        &self,
+        document_id: Uuid,
+        title: &str,
+        description: &str,
+    ) -> PortResult<GalleryDocument> {
+        let record = sqlx::query_as!(
+            GalleryDocumentRecord,
+            "INSERT INTO gallery_documents (document_id, title, description) VALUES ($1, $2, $3)
+             ON CONFLICT (document_id) DO UPDATE SET title = EXCLUDED.title, description = EXCLUDED.description
+             RETURNING document_id, title, description, added_at",
+            document_id,
+            title,
+            description
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(record.to_domain())
+    }
+
+    async fn get_gallery_documents(&self) -> PortResult<Vec<GalleryDocument>> {
+        let records = sqlx::query_as!(
+            GalleryDocumentRecord,
+            "SELECT document_id, title, description, added_at
+             FROM gallery_documents
+             ORDER BY added_at DESC"
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(records.into_iter().map(|r| r.to_domain()).collect())
+    }
+
+    async fn get_gallery_document(&self, document_id: Uuid) -> PortResult<GalleryDocument> {
+        let record = sqlx::query_as!(
+            GalleryDocumentRecord,
+            "SELECT document_id, title, description, added_at
+             FROM gallery_documents
+             WHERE document_id = $1",
+            document_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => {
+                PortError::NotFound("Document not found in gallery".to_string())
+            }
+            _ => PortError::Unexpected(e.to_string()),
+        })?;
+        Ok(record.to_domain())
+    }
+
+    async fn get_qa_pairs_for_document(&self, document_id: Uuid) -> PortResult<Vec<QAPair>> {
+        let records = sqlx::query_as!(
+            QAPairRecord,
+            "SELECT q.id, q.session_id, q.question_text, q.answer_text, q.answer_interrupted, q.created_at
+             FROM qa_pairs q
+             JOIN sessions s ON s.id = q.session_id
+             WHERE s.document_id = $1
+             ORDER BY q.created_at ASC",
+            document_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(records.into_iter().map(|r| r.to_domain()).collect())
+    }
+
+    async fn get_notes_for_document(&self, document_id: Uuid) -> PortResult<Vec<Note>> {
+        let records = sqlx::query_as!(
+            NoteRecord,
+            "SELECT n.id, n.session_id, n.generated_note_text, n.created_at,
+                    n.source_start_index, n.source_end_index, n.source
+             FROM notes n
+             JOIN sessions s ON s.id = n.session_id
+             WHERE s.document_id = $1
+             ORDER BY n.created_at ASC",
+            document_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(records.into_iter().map(|r| r.to_domain()).collect())
+    }
+
+    async fn get_notes_for_document_by_users(
+        &self,
+        document_id: Uuid,
+        user_ids: &[Uuid],
+    ) -> PortResult<Vec<Note>> {
+        let records = sqlx::query_as!(
+            NoteRecord,
+            "SELECT n.id, n.session_id, n.generated_note_text, n.created_at,
+                    n.source_start_index, n.source_end_index, n.source
+             FROM notes n
+             JOIN sessions s ON s.id = n.session_id
+             WHERE s.document_id = $1 AND s.user_id = ANY($2)
+             ORDER BY n.created_at ASC",
+            document_id,
+            user_ids
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(records.into_iter().map(|r| r.to_domain()).collect())
+    }
+
+    async fn share_document(&self, document_id: Uuid, user_id: Uuid) -> PortResult<DocumentShare> {
+        sqlx::query!(
+            "INSERT INTO document_shares (document_id, user_id) VALUES ($1, $2)
+             ON CONFLICT (document_id, user_id) DO NOTHING",
+            document_id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        let record = sqlx::query_as!(
+            DocumentShareRecord,
+            r#"SELECT ds.document_id, d.user_id AS owner_id, o.email AS owner_email,
+                      ds.user_id, u.email AS "user_email!", ds.shared_at
+               FROM document_shares ds
+               JOIN documents d ON d.id = ds.document_id
+               JOIN users o ON o.user_id = d.user_id
+               JOIN users u ON u.user_id = ds.user_id
+               WHERE ds.document_id = $1 AND ds.user_id = $2"#,
+            document_id,
+            user_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(record.to_domain())
+    }
+
+    async fn unshare_document(&self, document_id: Uuid, user_id: Uuid) -> PortResult<()> {
+        let result = sqlx::query!(
+            "DELETE FROM document_shares WHERE document_id = $1 AND user_id = $2",
+            document_id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        if result.rows_affected() == 0 {
+            return Err(PortError::NotFound("Document is not shared with this user".to_string()));
+        }
+        Ok(())
+    }
+
+    async fn get_document_shares(&self, document_id: Uuid) -> PortResult<Vec<DocumentShare>> {
+        let records = sqlx::query_as!(
+            DocumentShareRecord,
+            r#"SELECT ds.document_id, d.user_id AS owner_id, o.email AS owner_email,
+                      ds.user_id, u.email AS "user_email!", ds.shared_at
+               FROM document_shares ds
+               JOIN documents d ON d.id = ds.document_id
+               JOIN users o ON o.user_id = d.user_id
+               JOIN users u ON u.user_id = ds.user_id
+               WHERE ds.document_id = $1
+               ORDER BY ds.shared_at ASC"#,
+            document_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(records.into_iter().map(|r| r.to_domain()).collect())
+    }
+
+    async fn get_document_usage(&self, document_id: Uuid) -> PortResult<DocumentUsage> {
+        let record = sqlx::query!(
+            r#"SELECT
+                 (SELECT COUNT(*) FROM document_shares WHERE document_id = d.id) AS "shares!",
+                 (SELECT COUNT(*) FROM sessions s
+                  WHERE s.document_id = d.id AND s.user_id <> d.user_id) AS "other_users_sessions!",
+                 (SELECT COUNT(*) FROM workspace_documents WHERE document_id = d.id) AS "workspaces!",
+                 EXISTS (SELECT 1 FROM gallery_documents WHERE document_id = d.id) AS "in_gallery!"
+               FROM documents d WHERE d.id = $1"#,
+            document_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?
+        .ok_or_else(|| PortError::NotFound(format!("Document {} not found", document_id)))?;
+
+        Ok(DocumentUsage {
+            shares: record.shares as usize,
+            other_users_sessions: record.other_users_sessions as usize,
+            workspaces: record.workspaces as usize,
+            in_gallery: record.in_gallery,
+        })
+    }
+
+    async fn get_documents_shared_with(&self, user_id: Uuid) -> PortResult<Vec<DocumentShare>> {
+        let records = sqlx::query_as!(
+            DocumentShareRecord,
+            r#"SELECT ds.document_id, d.user_id AS owner_id, o.email AS owner_email,
+                      ds.user_id, u.email AS "user_email!", ds.shared_at
+               FROM document_shares ds
+               JOIN documents d ON d.id = ds.document_id
+               JOIN users o ON o.user_id = d.user_id
+               JOIN users u ON u.user_id = ds.user_id
+               WHERE ds.user_id = $1
+               ORDER BY ds.shared_at DESC"#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(records.into_iter().map(|r| r.to_domain()).collect())
+    }
+
+    async fn record_tts_usage(&self, provider: &str, model: &str, characters: usize) -> PortResult<()> {
+        sqlx::query!(
+            "INSERT INTO tts_usage (usage_date, provider, model, characters, requests)
+             VALUES ((NOW() AT TIME ZONE 'UTC')::date, $1, $2, $3, 1)
+             ON CONFLICT (usage_date, provider, model) DO UPDATE
+             SET characters = tts_usage.characters + EXCLUDED.characters,
+                 requests = tts_usage.requests + 1",
+            provider,
+            model,
+            characters as i64
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn get_tts_usage(&self, from: NaiveDate, to: NaiveDate) -> PortResult<Vec<TtsUsage>> {
+        let records = sqlx::query_as!(
+            TtsUsageRecord,
+            "SELECT usage_date, provider, model, characters, requests
+             FROM tts_usage
+             WHERE usage_date BETWEEN $1 AND $2
+             ORDER BY usage_date ASC, provider ASC, model ASC",
+            from,
+            to
+        )
+        .fetch_all(&self.pool)
         .await
-        .map_err(|e| match e {
-            sqlx::Error::RowNotFound => PortError::NotFound("Session not found".to_string()),
-            _ => PortError::Unexpected(e.to_string()),
-        })?;
-        
-        Ok(record.to_domain())
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(records.into_iter().map(|r| r.to_domain()).collect())
     }
 
-    async fn create_session(&self, user_id: Uuid, document_id: Uuid) -> PortResult<Session> {
-    let record = sqlx::query_as!(
-        SessionRecord,
-        "INSERT INTO sessions (id, user_id, document_id) 
-         VALUES ($1, $2, $3) 
-         RETURNING id, user_id, document_id, reading_progress_index, created_at, last_accessed_at",
-        Uuid::new_v4(),  // ✅ Generate ID here
-        user_id,
-        document_id
-    )
-    .fetch_one(&self.pool)
-    .await
-    .map_err(|e| PortError::Unexpected(e.to_string()))?;
-    
-    Ok(record.to_domain())
+    async fn record_llm_usage(&self, provider: &str, model: &str, usage: TokenUsage) -> PortResult<()> {
+        sqlx::query!(
+            "INSERT INTO llm_usage (usage_date, provider, model, prompt_tokens, completion_tokens, requests)
+             VALUES ((NOW() AT TIME ZONE 'UTC')::date, $1, $2, $3, $4, 1)
+             ON CONFLICT (usage_date, provider, model) DO UPDATE
+             SET prompt_tokens = llm_usage.prompt_tokens + EXCLUDED.prompt_tokens,
+                 completion_tokens = llm_usage.completion_tokens + EXCLUDED.completion_tokens,
+                 requests = llm_usage.requests + 1",
+            provider,
+            model,
+            i64::from(usage.prompt_tokens),
+            i64::from(usage.completion_tokens)
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(())
     }
 
-    async fn update_session_progress(
-        &self,
-        session_id: Uuid,
-        new_progress_index: usize,
-    ) -> PortResult<()> {
+    async fn get_llm_usage(&self, from: NaiveDate, to: NaiveDate) -> PortResult<Vec<LlmUsage>> {
+        let records = sqlx::query_as!(
+            LlmUsageRecord,
+            "SELECT usage_date, provider, model, prompt_tokens, completion_tokens, requests
+             FROM llm_usage
+             WHERE usage_date BETWEEN $1 AND $2
+             ORDER BY usage_date ASC, provider ASC, model ASC",
+            from,
+            to
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(records.into_iter().map(|r| r.to_domain()).collect())
+    }
+
+    async fn save_usage_event(&self, event: &UsageEvent) -> PortResult<()> {
         sqlx::query!(
-            "UPDATE sessions SET reading_progress_index = $1 WHERE id = $2",
-            new_progress_index as i32,
-            session_id
+            "INSERT INTO usage_events (visit_id, kind, occurred_at) VALUES ($1, $2, $3)",
+            event.visit_id,
+            usage_event_kind_column(event.kind),
+            event.occurred_at
         )
         .execute(&self.pool)
         .await
@@ -274,13 +2987,57 @@ impl DatabaseService for DbAdapter {
         Ok(())
     }
 
-    async fn save_qa_pair(&self, qa_pair: QAPair) -> PortResult<()> {
+    async fn get_usage_event_counts(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> PortResult<Vec<UsageEventCount>> {
+        let records = sqlx::query!(
+            r#"SELECT (occurred_at AT TIME ZONE 'UTC')::date AS "day!", kind,
+                      COUNT(*) AS "events!", COUNT(DISTINCT visit_id) AS "visits!"
+               FROM usage_events
+               WHERE (occurred_at AT TIME ZONE 'UTC')::date BETWEEN $1 AND $2
+               GROUP BY 1, kind
+               ORDER BY 1 ASC, kind ASC"#,
+            from,
+            to
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        Ok(records
+            .into_iter()
+            .filter_map(|r| {
+                Some(UsageEventCount {
+                    day: r.day,
+                    kind: usage_event_kind_from_column(&r.kind)?,
+                    events: r.events.max(0) as u64,
+                    visits: r.visits.max(0) as u64,
+                })
+            })
+            .collect())
+    }
+
+    async fn save_question_latencies(
+        &self,
+        question_id: Uuid,
+        latencies: &[StageLatency],
+    ) -> PortResult<()> {
+        let stages: Vec<String> = latencies
+            .iter()
+            .map(|latency| latency_stage_column(latency.stage).to_string())
+            .collect();
+        let durations: Vec<i32> = latencies
+            .iter()
+            .map(|latency| latency.duration_ms.min(i32::MAX as u32) as i32)
+            .collect();
         sqlx::query!(
-            "INSERT INTO qa_pairs (id, session_id, question_text, answer_text) VALUES ($1, $2, $3, $4)",
-            qa_pair.id,
-            qa_pair.session_id,
-            qa_pair.question_text,
-            qa_pair.answer_text
+            "INSERT INTO question_latencies (question_id, stage, duration_ms)
+             SELECT $1, stage, ms FROM UNNEST($2::text[], $3::int4[]) AS t(stage, ms)",
+            question_id,
+            &stages[..],
+            &durations[..]
         )
         .execute(&self.pool)
         .await
@@ -288,26 +3045,53 @@ impl DatabaseService for DbAdapter {
         Ok(())
     }
 
-    async fn get_qa_pairs_for_session(&self, session_id: Uuid) -> PortResult<Vec<QAPair>> {
-        let records = sqlx::query_as!(
-            QAPairRecord,
-            "SELECT id, session_id, question_text, answer_text, created_at FROM qa_pairs WHERE session_id = $1 ORDER BY created_at ASC",
-            session_id
+    async fn get_latency_percentiles(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> PortResult<Vec<LatencyPercentiles>> {
+        let records = sqlx::query!(
+            r#"SELECT (recorded_at AT TIME ZONE 'UTC')::date AS "day!", stage,
+                      COUNT(*) AS "samples!",
+                      percentile_disc(0.5) WITHIN GROUP (ORDER BY duration_ms) AS "p50!",
+                      percentile_disc(0.9) WITHIN GROUP (ORDER BY duration_ms) AS "p90!",
+                      percentile_disc(0.99) WITHIN GROUP (ORDER BY duration_ms) AS "p99!"
+               FROM question_latencies
+               WHERE (recorded_at AT TIME ZONE 'UTC')::date BETWEEN $1 AND $2
+               GROUP BY 1, stage
+               ORDER BY 1 ASC, stage ASC"#,
+            from,
+            to
         )
         .fetch_all(&self.pool)
         .await
         .map_err(|e| PortError::Unexpected(e.to_string()))?;
 
-        let qa_pairs = records.into_iter().map(|r| r.to_domain()).collect();
-        Ok(qa_pairs)
+        Ok(records
+            .into_iter()
+            .filter_map(|r| {
+                Some(LatencyPercentiles {
+                    day: r.day,
+                    stage: latency_stage_from_column(&r.stage)?,
+                    samples: r.samples.max(0) as u64,
+                    p50_ms: r.p50.max(0) as u32,
+                    p90_ms: r.p90.max(0) as u32,
+                    p99_ms: r.p99.max(0) as u32,
+                })
+            })
+            .collect())
     }
 
-    async fn save_note(&self, note: Note) -> PortResult<()> {
+    async fn save_session_rating(&self, rating: &SessionRating) -> PortResult<()> {
         sqlx::query!(
-            "INSERT INTO notes (id, session_id, generated_note_text) VALUES ($1, $2, $3)",
-            note.id,
-            note.session_id,
-            note.generated_note_text
+            r#"INSERT INTO session_ratings (session_id, rating, feedback, rated_at)
+               VALUES ($1, $2, $3, $4)
+               ON CONFLICT (session_id) DO UPDATE
+               SET rating = EXCLUDED.rating, feedback = EXCLUDED.feedback, rated_at = EXCLUDED.rated_at"#,
+            rating.session_id,
+            i16::from(rating.rating),
+            rating.feedback,
+            rating.rated_at
         )
         .execute(&self.pool)
         .await
@@ -315,126 +3099,307 @@ impl DatabaseService for DbAdapter {
         Ok(())
     }
 
-    async fn get_notes_for_session(&self, session_id: Uuid) -> PortResult<Vec<Note>> {
-    let records = sqlx::query_as!(
-        NoteRecord,
-        "SELECT id, session_id, generated_note_text, created_at 
-         FROM notes 
-         WHERE session_id = $1 
-         ORDER BY created_at ASC",
-        session_id
-    )
-    .fetch_all(&self.pool)
-    .await
-    .map_err(|e| PortError::Unexpected(e.to_string()))?;
+    async fn get_reading_goal(&self, user_id: Uuid) -> PortResult<Option<ReadingGoal>> {
+        let record = sqlx::query_as!(
+            ReadingGoalRecord,
+            "SELECT minutes_per_day, days_per_week, reminders FROM reading_goals WHERE user_id = $1",
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
 
-    Ok(records.into_iter().map(|r| r.to_domain()).collect())
+        Ok(record.map(|r| r.to_domain()))
     }
 
-    async fn create_user_with_email(
-        &self,
-        email: &str,
-        hashed_password: &str,
-    ) -> PortResult<User> {
-        let user_id = Uuid::new_v4();
+    async fn save_reading_goal(&self, user_id: Uuid, goal: &ReadingGoal) -> PortResult<()> {
         sqlx::query!(
-            "INSERT INTO users (user_id, email, hashed_password) VALUES ($1, $2, $3)",
+            "INSERT INTO reading_goals (user_id, minutes_per_day, days_per_week, reminders)
+             VALUES ($1, $2, $3, $4)
+             ON CONFLICT (user_id) DO UPDATE
+             SET minutes_per_day = EXCLUDED.minutes_per_day, days_per_week = EXCLUDED.days_per_week,
+                 reminders = EXCLUDED.reminders, updated_at = NOW()",
             user_id,
-            email,
-            hashed_password
+            goal.minutes_per_day as i32,
+            i16::from(goal.days_per_week),
+            goal.reminders
         )
         .execute(&self.pool)
         .await
         .map_err(|e| PortError::Unexpected(e.to_string()))?;
-        
-        Ok(User { 
+        Ok(())
+    }
+
+    async fn delete_reading_goal(&self, user_id: Uuid) -> PortResult<()> {
+        sqlx::query!("DELETE FROM reading_goals WHERE user_id = $1", user_id)
+            .execute(&self.pool)
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(())
+    }
+
+    async fn add_listening_time(&self, user_id: Uuid, day: NaiveDate, seconds: u64) -> PortResult<()> {
+        sqlx::query!(
+            "INSERT INTO listening_days (user_id, day, seconds) VALUES ($1, $2, $3)
+             ON CONFLICT (user_id, day) DO UPDATE SET seconds = listening_days.seconds + EXCLUDED.seconds",
             user_id,
-            email: Some(email.to_string()),
-        })
+            day,
+            seconds as i64
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(())
     }
-    
-    async fn get_user_by_email(&self, email: &str) -> PortResult<UserCredentials> {
-    let record = sqlx::query!(
-        "SELECT user_id, email, hashed_password FROM users WHERE email = $1",
-        email
-    )
-    .fetch_one(&self.pool)
-    .await
-    .map_err(|e| match e {
-        sqlx::Error::RowNotFound => PortError::NotFound("User not found".to_string()),
-        _ => PortError::Unexpected(e.to_string()),
-    })?;
-    
-    // Handle optional email and password
-    let email = record.email.ok_or_else(|| {
-        PortError::Unexpected("User has no email".to_string())
-    })?;
-    
-    let hashed_password = record.hashed_password.ok_or_else(|| {
-        PortError::Unexpected("User has no password".to_string())
-    })?;
-    
-    Ok(UserCredentials {
-        user_id: record.user_id,
-        email,
-        hashed_password,
-    })
-  }
-    
-    async fn create_auth_session(
+
+    async fn get_listening_days(
         &self,
-        session_id: &str,
         user_id: Uuid,
-        expires_at: DateTime<Utc>,
-    ) -> PortResult<()> {
+        from: NaiveDate,
+        to: NaiveDate,
+    ) -> PortResult<Vec<ListeningDay>> {
+        let records = sqlx::query!(
+            "SELECT day, seconds FROM listening_days
+             WHERE user_id = $1 AND day BETWEEN $2 AND $3
+             ORDER BY day ASC",
+            user_id,
+            from,
+            to
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        Ok(records
+            .into_iter()
+            .map(|r| ListeningDay {
+                day: r.day,
+                seconds: r.seconds.max(0) as u64,
+            })
+            .collect())
+    }
+
+    async fn get_users_due_goal_reminder(&self, day: NaiveDate) -> PortResult<Vec<(Uuid, ReadingGoal)>> {
+        let records = sqlx::query!(
+            "SELECT user_id, minutes_per_day, days_per_week, reminders FROM reading_goals
+             WHERE reminders AND (last_reminded_on IS NULL OR last_reminded_on < $1)",
+            day
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        Ok(records
+            .into_iter()
+            .map(|r| {
+                let goal = ReadingGoalRecord {
+                    minutes_per_day: r.minutes_per_day,
+                    days_per_week: r.days_per_week,
+                    reminders: r.reminders,
+                };
+                (r.user_id, goal.to_domain())
+            })
+            .collect())
+    }
+
+    async fn mark_goal_reminded(&self, user_id: Uuid, day: NaiveDate) -> PortResult<()> {
         sqlx::query!(
-            "INSERT INTO auth_sessions (id, user_id, expires_at) VALUES ($1, $2, $3)",
-            session_id,
+            "UPDATE reading_goals SET last_reminded_on = $2 WHERE user_id = $1",
             user_id,
-            expires_at
+            day
         )
         .execute(&self.pool)
         .await
         .map_err(|e| PortError::Unexpected(e.to_string()))?;
         Ok(())
     }
-    
-    async fn validate_auth_session(&self, session_id: &str) -> PortResult<Uuid> {
-        let record = sqlx::query!(
-            "SELECT user_id FROM auth_sessions 
-             WHERE id = $1 AND expires_at > NOW()",
-            session_id
+
+    async fn create_planned_session(
+        &self,
+        session_id: Uuid,
+        kind: PlanKind,
+        title: Option<&str>,
+        starts_at: DateTime<Utc>,
+        duration_minutes: u32,
+    ) -> PortResult<PlannedSession> {
+        let kind = match kind {
+            PlanKind::Reading => "reading",
+            PlanKind::Review => "review",
+        };
+        let record = sqlx::query_as!(
+            PlannedSessionRecord,
+            "INSERT INTO planned_sessions (id, session_id, kind, title, starts_at, duration_minutes)
+             VALUES ($1, $2, $3, $4, $5, $6)
+             RETURNING id, session_id, kind, title, starts_at, duration_minutes",
+            Uuid::new_v4(),
+            session_id,
+            kind,
+            title,
+            starts_at,
+            duration_minutes as i32
         )
         .fetch_one(&self.pool)
         .await
-        .map_err(|e| match e {
-            sqlx::Error::RowNotFound => PortError::Unauthorized,
-            _ => PortError::Unexpected(e.to_string()),
-        })?;
-        Ok(record.user_id)
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        Ok(record.to_domain())
     }
-    
-    async fn delete_auth_session(&self, session_id: &str) -> PortResult<()> {
-        sqlx::query!("DELETE FROM auth_sessions WHERE id = $1", session_id)
-            .execute(&self.pool)
+
+    async fn get_planned_sessions(&self, user_id: Uuid, after: DateTime<Utc>) -> PortResult<Vec<PlannedSession>> {
+        let records = sqlx::query_as!(
+            PlannedSessionRecord,
+            "SELECT p.id, p.session_id, p.kind, p.title, p.starts_at, p.duration_minutes
+             FROM planned_sessions p
+             JOIN sessions s ON s.id = p.session_id
+             WHERE s.user_id = $1
+               AND p.starts_at + make_interval(mins => p.duration_minutes) > $2
+             ORDER BY p.starts_at ASC",
+            user_id,
+            after
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        Ok(records.into_iter().map(|r| r.to_domain()).collect())
+    }
+
+    async fn delete_planned_session(&self, user_id: Uuid, plan_id: Uuid) -> PortResult<()> {
+        let result = sqlx::query!(
+            "DELETE FROM planned_sessions p
+             USING sessions s
+             WHERE p.id = $1 AND s.id = p.session_id AND s.user_id = $2",
+            plan_id,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        if result.rows_affected() == 0 {
+            return Err(PortError::NotFound(format!("Planned session {} not found", plan_id)));
+        }
+        Ok(())
+    }
+
+    async fn get_users_due_weekly_recap(&self, week_start: NaiveDate) -> PortResult<Vec<Uuid>> {
+        let from = week_start.and_time(NaiveTime::MIN).and_utc();
+        let to = from + chrono::Duration::days(7);
+        let records = sqlx::query!(
+            "SELECT DISTINCT s.user_id
+             FROM notes n
+             JOIN sessions s ON s.id = n.session_id
+             JOIN user_preferences p ON p.user_id = s.user_id
+             WHERE p.weekly_recap
+               AND n.created_at >= $1 AND n.created_at < $2
+               AND n.source <> 'weekly_recap'
+               AND NOT EXISTS (
+                   SELECT 1 FROM weekly_recaps r WHERE r.user_id = s.user_id AND r.week_start = $3
+               )",
+            from,
+            to,
+            week_start
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(records.into_iter().map(|r| r.user_id).collect())
+    }
+
+    async fn get_user_notes_between(
+        &self,
+        user_id: Uuid,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> PortResult<Vec<Note>> {
+        let records = sqlx::query_as!(
+            NoteRecord,
+            "SELECT n.id, n.session_id, n.generated_note_text, n.created_at,
+                    n.source_start_index, n.source_end_index, n.source
+             FROM notes n
+             JOIN sessions s ON s.id = n.session_id
+             WHERE s.user_id = $1 AND n.created_at >= $2 AND n.created_at < $3
+               AND n.source <> 'weekly_recap'
+             ORDER BY n.created_at ASC",
+            user_id,
+            from,
+            to
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(records.into_iter().map(|r| r.to_domain()).collect())
+    }
+
+    async fn save_weekly_recap(&self, recap: &WeeklyRecap, note: Note) -> PortResult<()> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        sqlx::query!(
+            "INSERT INTO notes (id, session_id, generated_note_text, source)
+             VALUES ($1, $2, $3, $4)",
+            note.id,
+            note.session_id,
+            note.generated_note_text,
+            note_source_column(note.source)
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        let inserted = sqlx::query!(
+            "INSERT INTO weekly_recaps (user_id, week_start, note_id)
+             VALUES ($1, $2, $3)
+             ON CONFLICT (user_id, week_start) DO NOTHING",
+            recap.user_id,
+            recap.week_start,
+            note.id
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?
+        .rows_affected();
+
+        // Another run got there first; dropping the transaction discards our note.
+        if inserted == 0 {
+            return Ok(());
+        }
+        tx.commit()
             .await
             .map_err(|e| PortError::Unexpected(e.to_string()))?;
         Ok(())
     }
 
-    async fn get_sessions_by_user(&self, user_id: Uuid) -> PortResult<Vec<Session>> {
-    let records = sqlx::query_as!(
-        SessionRecord,
-        "SELECT id, user_id, document_id, reading_progress_index, created_at, last_accessed_at
-         FROM sessions 
-         WHERE user_id = $1 
-         ORDER BY last_accessed_at DESC",
-        user_id
-    )
-    .fetch_all(&self.pool)
-    .await
-    .map_err(|e| PortError::Unexpected(e.to_string()))?;
+    async fn get_unspoken_weekly_recap(&self, user_id: Uuid) -> PortResult<Option<WeeklyRecap>> {
+        let record = sqlx::query_as!(
+            WeeklyRecapRecord,
+            "SELECT r.user_id, r.week_start, n.generated_note_text AS text, r.spoken_at
+             FROM weekly_recaps r
+             JOIN notes n ON n.id = r.note_id
+             WHERE r.user_id = $1 AND r.spoken_at IS NULL
+             ORDER BY r.week_start DESC
+             LIMIT 1",
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(record.map(|r| r.to_domain()))
+    }
 
-    Ok(records.into_iter().map(|r| r.to_domain()).collect())
+    async fn mark_weekly_recap_spoken(&self, user_id: Uuid, week_start: NaiveDate) -> PortResult<()> {
+        sqlx::query!(
+            "UPDATE weekly_recaps SET spoken_at = NOW()
+             WHERE user_id = $1 AND week_start <= $2 AND spoken_at IS NULL",
+            user_id,
+            week_start
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(())
     }
 }
@@ -0,0 +1,130 @@
+//! services/api/src/adapters/dictionary.rs
+//!
+//! This module contains the adapter for looking words up in the Free Dictionary API
+//! (dictionaryapi.dev), or a server with the same response format.
+//! It implements the `DictionaryService` port from the `core` crate.
+
+use async_trait::async_trait;
+use reading_assistant_core::{
+    domain::Definition,
+    ports::{DictionaryService, PortError, PortResult},
+};
+use serde::Deserialize;
+
+//=========================================================================================
+// Response Types
+//=========================================================================================
+
+#[derive(Deserialize)]
+struct Entry {
+    word: String,
+    #[serde(default)]
+    phonetic: Option<String>,
+    #[serde(default)]
+    phonetics: Vec<Phonetic>,
+    #[serde(default)]
+    meanings: Vec<Meaning>,
+}
+
+#[derive(Deserialize)]
+struct Phonetic {
+    #[serde(default)]
+    text: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Meaning {
+    #[serde(default)]
+    part_of_speech: Option<String>,
+    #[serde(default)]
+    definitions: Vec<Sense>,
+}
+
+#[derive(Deserialize)]
+struct Sense {
+    definition: String,
+}
+
+//=========================================================================================
+// The Main Adapter Struct
+//=========================================================================================
+
+/// An adapter that implements `DictionaryService` over the Free Dictionary API.
+#[derive(Clone)]
+pub struct FreeDictionaryAdapter {
+    http: reqwest::Client,
+    /// The entries endpoint for one language, e.g. `https://api.dictionaryapi.dev/api/v2/entries/en`.
+    base_url: String,
+}
+
+impl FreeDictionaryAdapter {
+    /// Creates a new `FreeDictionaryAdapter`.
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// The first sense of the first entry that has one.
+    fn first_definition(entries: Vec<Entry>) -> Option<Definition> {
+        entries.into_iter().find_map(|entry| {
+            let phonetic = entry
+                .phonetic
+                .or_else(|| entry.phonetics.into_iter().find_map(|phonetic| phonetic.text))
+                .filter(|phonetic| !phonetic.is_empty());
+            let (part_of_speech, sense) = entry.meanings.into_iter().find_map(|meaning| {
+                let sense = meaning.definitions.into_iter().next()?;
+                Some((meaning.part_of_speech, sense))
+            })?;
+            Some(Definition {
+                word: entry.word,
+                phonetic,
+                part_of_speech,
+                meaning: sense.definition,
+            })
+        })
+    }
+}
+
+//=========================================================================================
+// `DictionaryService` Trait Implementation
+//=========================================================================================
+
+#[async_trait]
+impl DictionaryService for FreeDictionaryAdapter {
+    async fn define(&self, word: &str) -> PortResult<Definition> {
+        let mut url = reqwest::Url::parse(&self.base_url)
+            .map_err(|e| PortError::Unexpected(format!("Invalid dictionary URL: {}", e)))?;
+        url.path_segments_mut()
+            .map_err(|_| PortError::Unexpected("Invalid dictionary URL".to_string()))?
+            .push(word);
+
+        let response = self
+            .http
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        let entries: Vec<Entry> = match response.status() {
+            reqwest::StatusCode::NOT_FOUND => {
+                return Err(PortError::NotFound(format!("No definition for '{}'", word)));
+            }
+            status if !status.is_success() => {
+                return Err(PortError::Unexpected(format!(
+                    "Dictionary returned {} for '{}'",
+                    status, word
+                )));
+            }
+            _ => response
+                .json()
+                .await
+                .map_err(|e| PortError::Unexpected(e.to_string()))?,
+        };
+
+        Self::first_definition(entries)
+            .ok_or_else(|| PortError::NotFound(format!("No definition for '{}'", word)))
+    }
+}
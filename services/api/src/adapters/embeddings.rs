@@ -0,0 +1,74 @@
+//! services/api/src/adapters/embeddings.rs
+//!
+//! This module contains the adapter for OpenAI's embeddings API.
+//! It implements the `EmbeddingService` port from the `core` crate.
+
+use async_openai::{
+    config::OpenAIConfig,
+    types::CreateEmbeddingRequestArgs,
+    Client, error::OpenAIError,
+};
+use async_trait::async_trait;
+use reading_assistant_core::ports::{EmbeddingService, PortError, PortResult};
+
+/// The maximum number of inputs sent in a single embeddings request.
+const EMBEDDING_BATCH_SIZE: usize = 256;
+
+//=========================================================================================
+// The Main Adapter Struct
+//=========================================================================================
+
+/// An adapter that implements `EmbeddingService` using the OpenAI embeddings API.
+#[derive(Clone)]
+pub struct OpenAiEmbeddingAdapter {
+    client: Client<OpenAIConfig>,
+    model: String,
+}
+
+impl OpenAiEmbeddingAdapter {
+    /// Creates a new `OpenAiEmbeddingAdapter`.
+    pub fn new(client: Client<OpenAIConfig>, model: String) -> Self {
+        Self { client, model }
+    }
+}
+
+//=========================================================================================
+// `EmbeddingService` Trait Implementation
+//=========================================================================================
+
+#[async_trait]
+impl EmbeddingService for OpenAiEmbeddingAdapter {
+    /// Embeds the texts in batches, preserving input order.
+    async fn embed(&self, texts: &[String]) -> PortResult<Vec<Vec<f32>>> {
+        let mut embeddings = Vec::with_capacity(texts.len());
+
+        for batch in texts.chunks(EMBEDDING_BATCH_SIZE) {
+            let request = CreateEmbeddingRequestArgs::default()
+                .model(&self.model)
+                .input(batch.to_vec())
+                .build()
+                .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+            let response = self
+                .client
+                .embeddings()
+                .create(request)
+                .await
+                .map_err(|e: OpenAIError| PortError::Unexpected(e.to_string()))?;
+
+            let mut data = response.data;
+            data.sort_by_key(|d| d.index);
+            embeddings.extend(data.into_iter().map(|d| d.embedding));
+        }
+
+        if embeddings.len() != texts.len() {
+            return Err(PortError::Unexpected(format!(
+                "Embedding API returned {} vectors for {} inputs",
+                embeddings.len(),
+                texts.len()
+            )));
+        }
+
+        Ok(embeddings)
+    }
+}
@@ -0,0 +1,145 @@
+//! services/api/src/adapters/fetcher.rs
+//!
+//! This module contains the adapter for fetching feeds and web articles.
+//! It implements the `ContentFetchService` port from the `core` crate.
+
+use async_trait::async_trait;
+use reading_assistant_core::{
+    domain::FeedEntry,
+    ports::{ContentFetchService, PortError, PortResult},
+};
+use regex::Regex;
+
+//=========================================================================================
+// The Main Adapter Struct
+//=========================================================================================
+
+/// An adapter that implements `ContentFetchService` over plain HTTP.
+#[derive(Clone)]
+pub struct HttpContentFetcher {
+    http: reqwest::Client,
+}
+
+impl HttpContentFetcher {
+    /// Creates a new `HttpContentFetcher`.
+    pub fn new() -> Self {
+        Self {
+            http: reqwest::Client::new(),
+        }
+    }
+
+    async fn get_bytes(&self, url: &str) -> PortResult<Vec<u8>> {
+        let response = self
+            .http
+            .get(url)
+            .send()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        match response.status() {
+            reqwest::StatusCode::NOT_FOUND => Err(PortError::NotFound(url.to_string())),
+            status if !status.is_success() => Err(PortError::Unexpected(format!(
+                "{} returned {}",
+                url, status
+            ))),
+            _ => Ok(response
+                .bytes()
+                .await
+                .map_err(|e| PortError::Unexpected(e.to_string()))?
+                .to_vec()),
+        }
+    }
+
+    /// Extracts the page title and visible text from an HTML document.
+    fn extract_text(html: &str) -> (Option<String>, String) {
+        let title_regex = Regex::new(r"(?is)<title[^>]*>(.*?)</title>").unwrap();
+        let title = title_regex
+            .captures(html)
+            .map(|c| Self::decode_entities(c[1].trim()));
+
+        // Prefer the <article> body when the page has one.
+        let article_regex = Regex::new(r"(?is)<article[^>]*>(.*?)</article>").unwrap();
+        let body = article_regex
+            .captures(html)
+            .map(|c| c[1].to_string())
+            .unwrap_or_else(|| html.to_string());
+
+        let noise_regex =
+            Regex::new(r"(?is)<(script|style|nav|header|footer|aside)[^>]*>.*?</(script|style|nav|header|footer|aside)>")
+                .unwrap();
+        let without_noise = noise_regex.replace_all(&body, " ");
+
+        // Paragraph-level tags become line breaks so sentences don't run together.
+        let block_regex = Regex::new(r"(?i)</?(p|br|div|h[1-6]|li)[^>]*>").unwrap();
+        let with_breaks = block_regex.replace_all(&without_noise, "\n");
+
+        let tag_regex = Regex::new(r"(?s)<[^>]*>").unwrap();
+        let text = tag_regex.replace_all(&with_breaks, "");
+
+        let text = Self::decode_entities(&text)
+            .lines()
+            .map(|l| l.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|l| !l.is_empty())
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        (title, text)
+    }
+
+    fn decode_entities(text: &str) -> String {
+        text.replace("&nbsp;", " ")
+            .replace("&amp;", "&")
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&#39;", "'")
+    }
+}
+
+impl Default for HttpContentFetcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+//=========================================================================================
+// `ContentFetchService` Trait Implementation
+//=========================================================================================
+
+#[async_trait]
+impl ContentFetchService for HttpContentFetcher {
+    /// Parses an RSS or Atom feed and returns each entry's link and title.
+    async fn fetch_feed(&self, url: &str) -> PortResult<Vec<FeedEntry>> {
+        let bytes = self.get_bytes(url).await?;
+        let feed = feed_rs::parser::parse(bytes.as_slice())
+            .map_err(|e| PortError::Unexpected(format!("Failed to parse feed {}: {}", url, e)))?;
+
+        Ok(feed
+            .entries
+            .into_iter()
+            .filter_map(|entry| {
+                let link = entry.links.into_iter().next()?;
+                Some(FeedEntry {
+                    url: link.href,
+                    title: entry.title.map(|t| t.content),
+                })
+            })
+            .collect())
+    }
+
+    /// Downloads a web page and strips it down to readable text.
+    async fn fetch_article(&self, url: &str) -> PortResult<(String, String)> {
+        let bytes = self.get_bytes(url).await?;
+        let html = String::from_utf8_lossy(&bytes);
+        let (title, text) = Self::extract_text(&html);
+
+        if text.trim().is_empty() {
+            return Err(PortError::Unexpected(format!(
+                "No readable text found at {}",
+                url
+            )));
+        }
+
+        Ok((title.unwrap_or_else(|| url.to_string()), text))
+    }
+}
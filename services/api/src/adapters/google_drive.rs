@@ -0,0 +1,197 @@
+//! services/api/src/adapters/google_drive.rs
+//!
+//! This module contains the adapter for importing Google Docs from Google Drive.
+//! It implements the `DocumentImportService` port from the `core` crate.
+
+use async_trait::async_trait;
+use reading_assistant_core::{
+    domain::ExternalDocument,
+    ports::{DocumentImportService, PortError, PortResult},
+};
+use serde::Deserialize;
+
+const GOOGLE_AUTH_URL: &str = "https://accounts.google.com/o/oauth2/v2/auth";
+const GOOGLE_TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
+const DRIVE_FILES_URL: &str = "https://www.googleapis.com/drive/v3/files";
+const DRIVE_SCOPE: &str = "https://www.googleapis.com/auth/drive.readonly";
+const GOOGLE_DOC_MIME_TYPE: &str = "application/vnd.google-apps.document";
+
+//=========================================================================================
+// The Main Adapter Struct
+//=========================================================================================
+
+/// An adapter that implements `DocumentImportService` using the Google Drive API.
+#[derive(Clone)]
+pub struct GoogleDriveAdapter {
+    http: reqwest::Client,
+    client_id: String,
+    client_secret: String,
+    redirect_uri: String,
+}
+
+impl GoogleDriveAdapter {
+    /// Creates a new `GoogleDriveAdapter`.
+    pub fn new(client_id: String, client_secret: String, redirect_uri: String) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            client_id,
+            client_secret,
+            redirect_uri,
+        }
+    }
+
+    /// Maps a non-success Drive response status onto a `PortError`.
+    fn status_error(status: reqwest::StatusCode, document_id: &str) -> PortError {
+        match status {
+            reqwest::StatusCode::UNAUTHORIZED => PortError::Unauthorized,
+            reqwest::StatusCode::NOT_FOUND => {
+                PortError::NotFound(format!("Google document {} not found", document_id))
+            }
+            _ => PortError::Unexpected(format!("Google Drive API returned {}", status)),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+}
+
+#[derive(Deserialize)]
+struct DriveFile {
+    id: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct DriveFileList {
+    files: Vec<DriveFile>,
+}
+
+//=========================================================================================
+// `DocumentImportService` Trait Implementation
+//=========================================================================================
+
+#[async_trait]
+impl DocumentImportService for GoogleDriveAdapter {
+    fn authorize_url(&self, state: &str) -> String {
+        format!(
+            "{}?client_id={}&redirect_uri={}&response_type=code&scope={}&access_type=online&state={}",
+            GOOGLE_AUTH_URL, self.client_id, self.redirect_uri, DRIVE_SCOPE, state
+        )
+    }
+
+    /// Exchanges an authorization code for a Drive access token.
+    async fn exchange_code(&self, code: &str) -> PortResult<String> {
+        let response = self
+            .http
+            .post(GOOGLE_TOKEN_URL)
+            .form(&[
+                ("code", code),
+                ("client_id", self.client_id.as_str()),
+                ("client_secret", self.client_secret.as_str()),
+                ("redirect_uri", self.redirect_uri.as_str()),
+                ("grant_type", "authorization_code"),
+            ])
+            .send()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        if !response.status().is_success() {
+            return Err(PortError::Unauthorized);
+        }
+
+        let token: TokenResponse = response
+            .json()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(token.access_token)
+    }
+
+    /// Lists the user's Google Docs, most recently modified first.
+    async fn list_documents(&self, access_token: &str) -> PortResult<Vec<ExternalDocument>> {
+        let query = format!("mimeType='{}' and trashed=false", GOOGLE_DOC_MIME_TYPE);
+        let response = self
+            .http
+            .get(DRIVE_FILES_URL)
+            .bearer_auth(access_token)
+            .query(&[
+                ("q", query.as_str()),
+                ("fields", "files(id,name)"),
+                ("orderBy", "modifiedTime desc"),
+                ("pageSize", "100"),
+            ])
+            .send()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        if !response.status().is_success() {
+            return Err(Self::status_error(response.status(), ""));
+        }
+
+        let list: DriveFileList = response
+            .json()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        Ok(list
+            .files
+            .into_iter()
+            .map(|f| ExternalDocument {
+                id: f.id,
+                title: f.name,
+            })
+            .collect())
+    }
+
+    /// Fetches a Google Doc's metadata and exports its body as plain text.
+    async fn fetch_document(
+        &self,
+        access_token: &str,
+        document_id: &str,
+    ) -> PortResult<(ExternalDocument, String)> {
+        let meta_response = self
+            .http
+            .get(format!("{}/{}", DRIVE_FILES_URL, document_id))
+            .bearer_auth(access_token)
+            .query(&[("fields", "id,name")])
+            .send()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        if !meta_response.status().is_success() {
+            return Err(Self::status_error(meta_response.status(), document_id));
+        }
+
+        let file: DriveFile = meta_response
+            .json()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        let export_response = self
+            .http
+            .get(format!("{}/{}/export", DRIVE_FILES_URL, document_id))
+            .bearer_auth(access_token)
+            .query(&[("mimeType", "text/plain")])
+            .send()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        if !export_response.status().is_success() {
+            return Err(Self::status_error(export_response.status(), document_id));
+        }
+
+        let text = export_response
+            .text()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        Ok((
+            ExternalDocument {
+                id: file.id,
+                title: file.name,
+            },
+            text,
+        ))
+    }
+}
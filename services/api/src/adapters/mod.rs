@@ -1,11 +1,46 @@
+pub mod analytics;
+pub mod anthropic_qa;
+pub mod audio_cache;
+pub mod backup_storage;
+pub mod cleanup_llm;
 pub mod db;
+pub mod dictionary;
+pub mod embeddings;
+pub mod fetcher;
+pub mod google_drive;
+pub mod moderation;
 pub mod notes_llm;
+pub mod notes_prompts;
+pub mod notion;
+pub mod ollama;
+pub mod openai;
+pub mod piper_tts;
 pub mod qa_llm;
+pub mod qa_prompts;
 pub mod sst;
 pub mod tts;
+pub mod tts_cache;
+pub mod tts_usage;
+pub mod webhook_notifier;
 
+pub use analytics::DbAnalyticsSink;
+pub use anthropic_qa::AnthropicQaAdapter;
+pub use audio_cache::DiskAudioCache;
+pub use backup_storage::DiskBackupStorage;
+pub use cleanup_llm::OpenAiCleanupAdapter;
 pub use db::DbAdapter;
+pub use dictionary::FreeDictionaryAdapter;
+pub use embeddings::OpenAiEmbeddingAdapter;
+pub use fetcher::HttpContentFetcher;
+pub use google_drive::GoogleDriveAdapter;
+pub use moderation::OpenAiModerationAdapter;
 pub use notes_llm::OpenAiNotesAdapter;
+pub use notion::NotionAdapter;
+pub use ollama::OllamaAdapter;
+pub use openai::OpenAiAdapters;
+pub use piper_tts::PiperTtsAdapter;
 pub use qa_llm::OpenAiQaAdapter;
 pub use sst::OpenAiSstAdapter;
-pub use tts::OpenAiTtsAdapter;
\ No newline at end of file
+pub use tts::OpenAiTtsAdapter;
+pub use tts_cache::CachedTtsAdapter;
+pub use tts_usage::MeteredTtsAdapter;
\ No newline at end of file
@@ -0,0 +1,76 @@
+//! services/api/src/adapters/moderation.rs
+//!
+//! This module contains the adapter for OpenAI's moderation API.
+//! It implements the `ModerationService` port from the `core` crate.
+
+use async_openai::{
+    config::OpenAIConfig,
+    types::CreateModerationRequestArgs,
+    Client, error::OpenAIError,
+};
+use async_trait::async_trait;
+use reading_assistant_core::{
+    domain::ModerationResult,
+    ports::{ModerationService, PortError, PortResult},
+};
+
+//=========================================================================================
+// The Main Adapter Struct
+//=========================================================================================
+
+/// An adapter that implements `ModerationService` using the OpenAI moderation endpoint.
+#[derive(Clone)]
+pub struct OpenAiModerationAdapter {
+    client: Client<OpenAIConfig>,
+}
+
+impl OpenAiModerationAdapter {
+    /// Creates a new `OpenAiModerationAdapter`.
+    pub fn new(client: Client<OpenAIConfig>) -> Self {
+        Self { client }
+    }
+}
+
+//=========================================================================================
+// `ModerationService` Trait Implementation
+//=========================================================================================
+
+#[async_trait]
+impl ModerationService for OpenAiModerationAdapter {
+    /// Returns the provider's verdict plus the highest category score, so callers
+    /// can apply a stricter threshold than the provider's default.
+    async fn moderate(&self, text: &str) -> PortResult<ModerationResult> {
+        let request = CreateModerationRequestArgs::default()
+            .input(text)
+            .build()
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        let response = self
+            .client
+            .moderations()
+            .create(request)
+            .await
+            .map_err(|e: OpenAIError| PortError::Unexpected(e.to_string()))?;
+
+        let result = response.results.into_iter().next().ok_or_else(|| {
+            PortError::Unexpected("Moderation response contained no results.".to_string())
+        })?;
+
+        // The set of categories grows over time, so read the scores generically.
+        let scores = serde_json::to_value(&result.category_scores)
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        let max_category_score = scores
+            .as_object()
+            .map(|o| {
+                o.values()
+                    .filter_map(|v| v.as_f64())
+                    .fold(0.0_f64, f64::max) as f32
+            })
+            .unwrap_or(0.0);
+
+        Ok(ModerationResult {
+            flagged: result.flagged,
+            max_category_score,
+        })
+    }
+}
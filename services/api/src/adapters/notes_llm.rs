@@ -13,10 +13,12 @@ use async_openai::{
 };
 use async_trait::async_trait;
 use reading_assistant_core::{
-    domain::QAPair,
+    domain::{Note, QAPair},
     ports::{NoteGenerationService, PortError, PortResult},
 };
 
+use crate::adapters::{notes_prompts, qa_prompts::Prompt};
+
 //=========================================================================================
 // The Main Adapter Struct
 //=========================================================================================
@@ -33,28 +35,17 @@ impl OpenAiNotesAdapter {
     pub fn new(client: Client<OpenAIConfig>, model: String) -> Self {
         Self { client, model }
     }
-}
-
-//=========================================================================================
-// `NoteGenerationService` Trait Implementation
-//=========================================================================================
 
-#[async_trait]
-impl NoteGenerationService for OpenAiNotesAdapter {
-    /// Generates a concise note by summarizing a question and its corresponding answer.
-    async fn generate_note_from_qapair(&self, qapair: &QAPair) -> PortResult<String> {
+    /// Runs one chat completion and returns the text of its first choice.
+    async fn complete(&self, prompt: Prompt, what: &str) -> PortResult<String> {
         let messages = vec![
             ChatCompletionRequestSystemMessageArgs::default()
-                .content(
-                "You are a note-taking assistant. Your task is to summarize the following question and answer into a single, concise note. IMPORTANT: If the answer indicates the question was unrelated to the context (e.g., contains phrases like 'I didn't understand your question given the context' or 'Could you please try asking again'), respond with EXACTLY: 'SKIP_NOTE' and nothing else. Otherwise, create a single bullet point or short sentence that captures the key insight from the exchange.")
+                .content(prompt.system)
                 .build()
                 .map_err(|e| PortError::Unexpected(e.to_string()))?
                 .into(),
             ChatCompletionRequestUserMessageArgs::default()
-                .content(format!(
-                    "QUESTION: {}\n\nANSWER: {}",
-                    qapair.question_text, qapair.answer_text
-                ))
+                .content(prompt.user)
                 .build()
                 .map_err(|e| PortError::Unexpected(e.to_string()))?
                 .into(),
@@ -76,18 +67,68 @@ impl NoteGenerationService for OpenAiNotesAdapter {
             .map_err(|e: OpenAIError| PortError::Unexpected(e.to_string()))?;
 
         // Extract the text content from the first choice in the response.
-        if let Some(choice) = response.choices.into_iter().next() {
-            if let Some(content) = choice.message.content {
-                Ok(content)
-            } else {
-                Err(PortError::Unexpected(
-                    "Note generation LLM response contained no text content.".to_string(),
-                ))
-            }
-        } else {
-            Err(PortError::Unexpected(
-                "Note generation LLM returned no choices in its response.".to_string(),
-            ))
+        response
+            .choices
+            .into_iter()
+            .next()
+            .and_then(|choice| choice.message.content)
+            .ok_or_else(|| {
+                PortError::Unexpected(format!("{} LLM response contained no text content.", what))
+            })
+    }
+}
+
+//=========================================================================================
+// `NoteGenerationService` Trait Implementation
+//=========================================================================================
+
+#[async_trait]
+impl NoteGenerationService for OpenAiNotesAdapter {
+    /// Generates a concise note by summarizing a question and its corresponding answer.
+    async fn generate_note_from_qapair(
+        &self,
+        qapair: &QAPair,
+        education_mode: bool,
+    ) -> PortResult<String> {
+        self.complete(notes_prompts::qa_note_prompt(qapair, education_mode), "Note generation")
+            .await
+    }
+
+    /// Extracts up to three key points from a passage, one per line of the response.
+    async fn extract_key_points(
+        &self,
+        passage: &str,
+        education_mode: bool,
+    ) -> PortResult<Vec<String>> {
+        let content = self
+            .complete(notes_prompts::key_points_prompt(passage, education_mode), "Key point")
+            .await?;
+        Ok(notes_prompts::parse_key_points(&content))
+    }
+
+    /// Recaps a week's notes in a few sentences meant to be spoken to the user.
+    async fn generate_weekly_recap(&self, notes: &[Note]) -> PortResult<String> {
+        if notes.is_empty() {
+            return Ok(String::new());
         }
+        self.complete(notes_prompts::weekly_recap_prompt(notes), "Weekly recap")
+            .await
+    }
+
+    /// Summarizes a session's notes into a short paragraph suitable for exports.
+    async fn summarize_notes(&self, notes: &[Note]) -> PortResult<String> {
+        if notes.is_empty() {
+            return Ok(String::new());
+        }
+        self.complete(notes_prompts::notes_summary_prompt(notes), "Summary")
+            .await
+    }
+
+    /// Suggests up to five lowercase topic tags for a document excerpt.
+    async fn suggest_tags(&self, text: &str, existing_tags: &[String]) -> PortResult<Vec<String>> {
+        let content = self
+            .complete(notes_prompts::tags_prompt(text, existing_tags), "Tagging")
+            .await?;
+        Ok(notes_prompts::parse_tags(&content))
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,102 @@
+//! services/api/src/adapters/notes_prompts.rs
+//!
+//! The prompts behind `NoteGenerationService` and the parsing of their replies,
+//! shared by every LLM vendor's adapter so notes, recaps and tags read the same
+//! whichever model writes them.
+
+use reading_assistant_core::domain::{Note, QAPair};
+
+use crate::adapters::qa_prompts::Prompt;
+
+/// Extra system instructions for notes taken in child/education-mode sessions.
+const EDUCATION_MODE_INSTRUCTIONS: &str = " The notes are for a school-age student: use simple, age-appropriate vocabulary and leave out any mature content.";
+
+/// The characters of a document read to suggest its tags. The opening of the
+/// document is enough to identify its topic.
+const TAG_EXCERPT_CHARS: usize = 4000;
+
+fn audience(education_mode: bool) -> &'static str {
+    if education_mode { EDUCATION_MODE_INSTRUCTIONS } else { "" }
+}
+
+/// The notes as a bulleted list, for the prompts that work from a set of notes.
+fn note_list(notes: &[Note]) -> String {
+    notes
+        .iter()
+        .map(|n| format!("- {}", n.generated_note_text))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The prompt for turning a question and its answer into one note. Unrelated
+/// questions get `SKIP_NOTE` back, which the caller drops.
+pub fn qa_note_prompt(qapair: &QAPair, education_mode: bool) -> Prompt {
+    Prompt {
+        system: format!("You are a note-taking assistant. Your task is to summarize the following question and answer into a single, concise note. IMPORTANT: If the answer indicates the question was unrelated to the context (e.g., contains phrases like 'I didn't understand your question given the context' or 'Could you please try asking again'), respond with EXACTLY: 'SKIP_NOTE' and nothing else. Otherwise, create a single bullet point or short sentence that captures the key insight from the exchange.{}", audience(education_mode)),
+        user: format!(
+            "QUESTION: {}\n\nANSWER: {}",
+            qapair.question_text, qapair.answer_text
+        ),
+    }
+}
+
+/// The prompt for the key points of a passage, at most three, one per line.
+pub fn key_points_prompt(passage: &str, education_mode: bool) -> Prompt {
+    Prompt {
+        system: format!("You are a note-taking assistant. Write the key points of the following passage as at most 3 short notes, one per line, with no numbering or bullet characters. Only include ideas stated in the passage. If the passage has no substantive content (e.g. it is only a heading, a caption or a transition), respond with EXACTLY: 'SKIP_NOTE' and nothing else.{}", audience(education_mode)),
+        user: format!("PASSAGE:\n{}", passage),
+    }
+}
+
+/// Reads the key points out of a reply to `key_points_prompt`.
+pub fn parse_key_points(content: &str) -> Vec<String> {
+    if content.trim() == "SKIP_NOTE" {
+        return Vec::new();
+    }
+    content
+        .lines()
+        .map(|line| line.trim().trim_start_matches(['-', '*', '•']).trim())
+        .filter(|line| !line.is_empty())
+        .take(3)
+        .map(str::to_string)
+        .collect()
+}
+
+/// The prompt for a spoken recap of a week's notes.
+pub fn weekly_recap_prompt(notes: &[Note]) -> Prompt {
+    Prompt {
+        system: "You are a friendly reading assistant. The following are the notes a reader took this week, across several reading sessions. Write a short recap (3-5 sentences) addressed to the reader that starts with 'This week you read about' and connects the main themes. It will be read aloud, so use plain sentences without lists or formatting. Do not add information that is not in the notes.".to_string(),
+        user: format!("NOTES:\n{}", note_list(notes)),
+    }
+}
+
+/// The prompt for a one-paragraph summary of a session's notes, for exports.
+pub fn notes_summary_prompt(notes: &[Note]) -> Prompt {
+    Prompt {
+        system: "You are a note-taking assistant. Summarize the following study notes into one short paragraph (3-4 sentences) that captures the main ideas. Do not add information that is not in the notes.".to_string(),
+        user: format!("NOTES:\n{}", note_list(notes)),
+    }
+}
+
+/// The prompt for up to five topic tags for a document, reusing the user's own.
+pub fn tags_prompt(text: &str, existing_tags: &[String]) -> Prompt {
+    let excerpt: String = text.chars().take(TAG_EXCERPT_CHARS).collect();
+    Prompt {
+        system: "You label documents for a personal reading library. Respond with up to 5 short, lowercase topic tags separated by commas and nothing else. Reuse tags from the user's existing list when they fit.".to_string(),
+        user: format!(
+            "EXISTING TAGS: {}\n\nDOCUMENT:\n{}",
+            existing_tags.join(", "),
+            excerpt
+        ),
+    }
+}
+
+/// Reads the tags out of a reply to `tags_prompt`.
+pub fn parse_tags(content: &str) -> Vec<String> {
+    content
+        .split(',')
+        .map(|t| t.trim().trim_matches('.').to_lowercase())
+        .filter(|t| !t.is_empty() && t.len() <= 40)
+        .take(5)
+        .collect()
+}
@@ -0,0 +1,159 @@
+//! services/api/src/adapters/notion.rs
+//!
+//! This module contains the adapter for exporting notes to Notion.
+//! It implements the `NoteExportService` port from the `core` crate.
+
+use async_trait::async_trait;
+use reading_assistant_core::{
+    domain::Note,
+    ports::{NoteExportService, PortError, PortResult},
+};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+const NOTION_API_BASE: &str = "https://api.notion.com/v1";
+const NOTION_VERSION: &str = "2022-06-28";
+
+//=========================================================================================
+// The Main Adapter Struct
+//=========================================================================================
+
+/// An adapter that implements `NoteExportService` using the Notion public API.
+#[derive(Clone)]
+pub struct NotionAdapter {
+    http: reqwest::Client,
+    client_id: String,
+    client_secret: String,
+    redirect_uri: String,
+}
+
+impl NotionAdapter {
+    /// Creates a new `NotionAdapter`.
+    pub fn new(client_id: String, client_secret: String, redirect_uri: String) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            client_id,
+            client_secret,
+            redirect_uri,
+        }
+    }
+
+    /// Builds a Notion rich-text block of the given type.
+    fn text_block(block_type: &str, text: &str) -> Value {
+        json!({
+            "object": "block",
+            "type": block_type,
+            block_type: {
+                "rich_text": [{ "type": "text", "text": { "content": text } }]
+            }
+        })
+    }
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+}
+
+#[derive(Deserialize)]
+struct PageResponse {
+    url: String,
+}
+
+//=========================================================================================
+// `NoteExportService` Trait Implementation
+//=========================================================================================
+
+#[async_trait]
+impl NoteExportService for NotionAdapter {
+    fn authorize_url(&self, state: &str) -> String {
+        format!(
+            "{}/oauth/authorize?client_id={}&response_type=code&owner=user&redirect_uri={}&state={}",
+            NOTION_API_BASE, self.client_id, self.redirect_uri, state
+        )
+    }
+
+    /// Exchanges an authorization code for a workspace access token.
+    async fn exchange_code(&self, code: &str) -> PortResult<String> {
+        let response = self
+            .http
+            .post(format!("{}/oauth/token", NOTION_API_BASE))
+            .basic_auth(&self.client_id, Some(&self.client_secret))
+            .json(&json!({
+                "grant_type": "authorization_code",
+                "code": code,
+                "redirect_uri": self.redirect_uri,
+            }))
+            .send()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        if !response.status().is_success() {
+            return Err(PortError::Unauthorized);
+        }
+
+        let token: TokenResponse = response
+            .json()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        Ok(token.access_token)
+    }
+
+    /// Creates a new page under `parent_id` containing the summary and a bullet per note.
+    async fn export_notes(
+        &self,
+        access_token: &str,
+        parent_id: &str,
+        title: &str,
+        summary: &str,
+        notes: &[Note],
+    ) -> PortResult<String> {
+        let mut children = vec![Self::text_block("heading_2", "Summary")];
+        if !summary.is_empty() {
+            children.push(Self::text_block("paragraph", summary));
+        }
+        children.push(Self::text_block("heading_2", "Notes"));
+        children.extend(
+            notes
+                .iter()
+                .map(|n| Self::text_block("bulleted_list_item", &n.generated_note_text)),
+        );
+
+        let body = json!({
+            "parent": { "page_id": parent_id },
+            "properties": {
+                "title": { "title": [{ "text": { "content": title } }] }
+            },
+            "children": children,
+        });
+
+        let response = self
+            .http
+            .post(format!("{}/pages", NOTION_API_BASE))
+            .bearer_auth(access_token)
+            .header("Notion-Version", NOTION_VERSION)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        match response.status() {
+            reqwest::StatusCode::UNAUTHORIZED => Err(PortError::Unauthorized),
+            reqwest::StatusCode::NOT_FOUND => Err(PortError::NotFound(format!(
+                "Notion page {} not found or not shared with the integration",
+                parent_id
+            ))),
+            status if !status.is_success() => Err(PortError::Unexpected(format!(
+                "Notion API returned {}",
+                status
+            ))),
+            _ => {
+                let page: PageResponse = response
+                    .json()
+                    .await
+                    .map_err(|e| PortError::Unexpected(e.to_string()))?;
+                Ok(page.url)
+            }
+        }
+    }
+}
@@ -0,0 +1,353 @@
+//! services/api/src/adapters/ollama.rs
+//!
+//! This module contains an adapter for models served by Ollama, or any server
+//! speaking its `/api/chat` protocol, so questions and notes can be handled by a
+//! local model with no cloud LLM involved. It implements the
+//! `QuestionAnsweringService` and `NoteGenerationService` ports from the `core`
+//! crate with the same prompts as the OpenAI adapters.
+
+use async_trait::async_trait;
+use futures::Stream;
+use reading_assistant_core::{
+    domain::{AnswerVerbosity, GroundingVerdict, Metered, Note, QAPair, QaReply, QuizGrade, QuizQuestion, ReadingLevel},
+    ports::{
+        NoteGenerationService, PortError, PortResult, QaReplyStream, QuestionAnsweringService,
+    },
+};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::VecDeque;
+
+use crate::adapters::{
+    notes_prompts,
+    qa_prompts::{self, Prompt},
+};
+
+//=========================================================================================
+// The Main Adapter Struct
+//=========================================================================================
+
+/// An adapter that implements the LLM ports using an Ollama-compatible chat endpoint.
+#[derive(Clone)]
+pub struct OllamaAdapter {
+    http: reqwest::Client,
+    base_url: String,
+    model: String,
+}
+
+/// One chat response, or one line of a streamed one.
+#[derive(Deserialize)]
+struct ChatResponse {
+    #[serde(default)]
+    message: Option<ChatMessage>,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct ChatMessage {
+    #[serde(default)]
+    content: String,
+}
+
+impl OllamaAdapter {
+    /// Creates a new `OllamaAdapter` for `model` on the server at `base_url`,
+    /// e.g. `http://localhost:11434`.
+    pub fn new(base_url: String, model: String) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+            model,
+        }
+    }
+
+    /// Sends a prompt to the chat endpoint, optionally asking for a streamed reply.
+    async fn send(&self, prompt: Prompt, stream: bool) -> PortResult<reqwest::Response> {
+        let body = json!({
+            "model": self.model,
+            "messages": [
+                { "role": "system", "content": prompt.system },
+                { "role": "user", "content": prompt.user },
+            ],
+            "stream": stream,
+        });
+
+        let response = self
+            .http
+            .post(format!("{}/api/chat", self.base_url))
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let detail = response.text().await.unwrap_or_default();
+            return Err(PortError::Unexpected(format!(
+                "Ollama request failed with {}: {}",
+                status, detail
+            )));
+        }
+        Ok(response)
+    }
+
+    /// Runs one request and returns the text of the reply.
+    async fn complete(&self, prompt: Prompt, what: &str) -> PortResult<String> {
+        let response: ChatResponse = self
+            .send(prompt, false)
+            .await?
+            .json()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        if let Some(error) = response.error {
+            return Err(PortError::Unexpected(format!("Ollama request failed: {}", error)));
+        }
+        match response.message {
+            Some(message) if !message.content.is_empty() => Ok(message.content),
+            _ => Err(PortError::Unexpected(format!(
+                "{} LLM response contained no text content.",
+                what
+            ))),
+        }
+    }
+
+    /// Runs one summarization request with the given system instructions.
+    async fn summarize(&self, instructions: &str, text: &str) -> PortResult<String> {
+        let prompt = Prompt {
+            system: instructions.to_string(),
+            user: format!("PASSAGE:\n---\n{}\n---", text),
+        };
+        self.complete(prompt, "Summary").await
+    }
+}
+
+//=========================================================================================
+// Streaming
+//=========================================================================================
+
+/// Turns a streamed chat response (one JSON object per line) into the text it
+/// carries. A line with an `error` ends the stream with an error.
+fn message_deltas(
+    response: reqwest::Response,
+) -> impl Stream<Item = PortResult<String>> + Send + Unpin + 'static {
+    struct State {
+        response: reqwest::Response,
+        /// Bytes received after the last complete line.
+        buffer: Vec<u8>,
+        ready: VecDeque<PortResult<String>>,
+        done: bool,
+    }
+
+    let state = State {
+        response,
+        buffer: Vec::new(),
+        ready: VecDeque::new(),
+        done: false,
+    };
+
+    Box::pin(futures::stream::unfold(state, |mut state| async move {
+        loop {
+            if let Some(item) = state.ready.pop_front() {
+                if item.is_err() {
+                    state.done = true;
+                    state.ready.clear();
+                }
+                return Some((item, state));
+            }
+            if state.done {
+                return None;
+            }
+
+            match state.response.chunk().await {
+                Ok(Some(bytes)) => state.buffer.extend_from_slice(&bytes),
+                Ok(None) => {
+                    // The last line may not end in a newline
+                    state.buffer.push(b'\n');
+                    state.done = true;
+                }
+                Err(e) => {
+                    state.ready.push_back(Err(PortError::Unexpected(e.to_string())));
+                    continue;
+                }
+            }
+
+            // Lines are only decoded once complete, so multi-byte characters split
+            // across chunks stay intact.
+            while let Some(end) = state.buffer.iter().position(|&b| b == b'\n') {
+                let line: Vec<u8> = state.buffer.drain(..=end).collect();
+                if let Some(delta) = parse_line(&line) {
+                    state.ready.push_back(delta);
+                }
+            }
+        }
+    }))
+}
+
+/// Reads one line of the streamed response.
+fn parse_line(line: &[u8]) -> Option<PortResult<String>> {
+    let line = String::from_utf8_lossy(line);
+    let line = line.trim();
+    if line.is_empty() {
+        return None;
+    }
+    match serde_json::from_str::<ChatResponse>(line) {
+        Ok(ChatResponse { error: Some(error), .. }) => Some(Err(PortError::Unexpected(format!(
+            "Ollama stream failed: {}",
+            error
+        )))),
+        Ok(ChatResponse { message, .. }) => message
+            .map(|message| message.content)
+            .filter(|content| !content.is_empty())
+            .map(Ok),
+        Err(e) => Some(Err(PortError::Unexpected(e.to_string()))),
+    }
+}
+
+//=========================================================================================
+// `QuestionAnsweringService` Trait Implementation
+//=========================================================================================
+
+#[async_trait]
+impl QuestionAnsweringService for OllamaAdapter {
+    /// Answers a user's question based on a provided snippet of text (context).
+    async fn answer_question(
+        &self,
+        question: &str,
+        context: &str,
+        verbosity: AnswerVerbosity,
+        education_mode: bool,
+        allow_clarification: bool,
+    ) -> PortResult<Metered<QaReply>> {
+        let prompt =
+            qa_prompts::answer_prompt(question, context, verbosity, education_mode, allow_clarification);
+        let content = self.complete(prompt, "QA").await?;
+        Ok(Metered {
+            value: qa_prompts::parse_answer(&content, verbosity),
+            usage: None,
+        })
+    }
+
+    /// Streams the answer to a user's question.
+    async fn answer_question_streaming(
+        &self,
+        question: &str,
+        context: &str,
+        verbosity: AnswerVerbosity,
+        education_mode: bool,
+        allow_clarification: bool,
+    ) -> PortResult<QaReplyStream> {
+        let prompt =
+            qa_prompts::answer_prompt(question, context, verbosity, education_mode, allow_clarification);
+        let response = self.send(prompt, true).await?;
+        qa_prompts::parse_answer_stream(message_deltas(response), qa_prompts::unreported_usage()).await
+    }
+
+    /// Summarizes a passage for a spoken recap: short, no lists, no headings.
+    async fn summarize_passage(&self, text: &str) -> PortResult<String> {
+        self.summarize(qa_prompts::SUMMARY_INSTRUCTIONS, text).await
+    }
+
+    /// Summarizes a passage in one sentence that completes "Last time we covered...".
+    async fn summarize_passage_in_one_sentence(&self, text: &str) -> PortResult<String> {
+        self.summarize(qa_prompts::ONE_SENTENCE_SUMMARY_INSTRUCTIONS, text).await
+    }
+
+    /// Re-explains a passage in 2-4 plain sentences pitched at the reading level.
+    async fn simplify_passage(&self, text: &str, level: ReadingLevel) -> PortResult<String> {
+        self.summarize(&qa_prompts::simplify_instructions(level), text).await
+    }
+
+    /// Generates comprehension questions as `Q:`/`A:` line pairs and parses them.
+    async fn generate_quiz_questions(
+        &self,
+        context: &str,
+        count: usize,
+    ) -> PortResult<Vec<QuizQuestion>> {
+        let content = self.complete(qa_prompts::quiz_prompt(context, count), "Quiz").await?;
+        qa_prompts::parse_quiz_questions(&content, count)
+    }
+
+    /// Checks the answer against its context with a separate, non-streamed completion.
+    async fn verify_grounding(&self, answer: &str, context: &str) -> PortResult<GroundingVerdict> {
+        let content = self
+            .complete(qa_prompts::grounding_prompt(answer, context), "Grounding")
+            .await?;
+        Ok(qa_prompts::parse_grounding(&content))
+    }
+
+    /// Grades an answer leniently: paraphrases and partial wording count as correct.
+    async fn grade_quiz_answer(
+        &self,
+        question: &QuizQuestion,
+        user_answer: &str,
+    ) -> PortResult<QuizGrade> {
+        let content = self
+            .complete(qa_prompts::grade_prompt(question, user_answer), "Grading")
+            .await?;
+        Ok(qa_prompts::parse_grade(&content))
+    }
+
+    fn provider(&self) -> &str {
+        "ollama"
+    }
+
+    fn model(&self) -> String {
+        self.model.clone()
+    }
+}
+
+//=========================================================================================
+// `NoteGenerationService` Trait Implementation
+//=========================================================================================
+
+#[async_trait]
+impl NoteGenerationService for OllamaAdapter {
+    /// Generates a concise note by summarizing a question and its corresponding answer.
+    async fn generate_note_from_qapair(
+        &self,
+        qapair: &QAPair,
+        education_mode: bool,
+    ) -> PortResult<String> {
+        self.complete(notes_prompts::qa_note_prompt(qapair, education_mode), "Note generation")
+            .await
+    }
+
+    /// Extracts up to three key points from a passage, one per line of the response.
+    async fn extract_key_points(
+        &self,
+        passage: &str,
+        education_mode: bool,
+    ) -> PortResult<Vec<String>> {
+        let content = self
+            .complete(notes_prompts::key_points_prompt(passage, education_mode), "Key point")
+            .await?;
+        Ok(notes_prompts::parse_key_points(&content))
+    }
+
+    /// Recaps a week's notes in a few sentences meant to be spoken to the user.
+    async fn generate_weekly_recap(&self, notes: &[Note]) -> PortResult<String> {
+        if notes.is_empty() {
+            return Ok(String::new());
+        }
+        self.complete(notes_prompts::weekly_recap_prompt(notes), "Weekly recap")
+            .await
+    }
+
+    /// Summarizes a session's notes into a short paragraph suitable for exports.
+    async fn summarize_notes(&self, notes: &[Note]) -> PortResult<String> {
+        if notes.is_empty() {
+            return Ok(String::new());
+        }
+        self.complete(notes_prompts::notes_summary_prompt(notes), "Summary")
+            .await
+    }
+
+    /// Suggests up to five lowercase topic tags for a document excerpt.
+    async fn suggest_tags(&self, text: &str, existing_tags: &[String]) -> PortResult<Vec<String>> {
+        let content = self
+            .complete(notes_prompts::tags_prompt(text, existing_tags), "Tagging")
+            .await?;
+        Ok(notes_prompts::parse_tags(&content))
+    }
+}
@@ -0,0 +1,52 @@
+//! services/api/src/adapters/openai.rs
+//!
+//! Builds every OpenAI-backed adapter from a single client, so the same set can
+//! be created for the operator's key at startup or for a user's own key per session.
+
+use async_openai::{
+    config::OpenAIConfig,
+    types::{SpeechModel, Voice},
+    Client,
+};
+use std::sync::Arc;
+
+use crate::adapters::{
+    OpenAiCleanupAdapter, OpenAiEmbeddingAdapter, OpenAiModerationAdapter, OpenAiNotesAdapter,
+    OpenAiQaAdapter, OpenAiSstAdapter, OpenAiTtsAdapter,
+};
+use crate::config::Config;
+
+/// The TTS model used for all narration.
+pub const TTS_MODEL: SpeechModel = SpeechModel::Tts1Hd;
+
+/// The OpenAI-backed adapters, all sharing one client (and therefore one API key).
+pub struct OpenAiAdapters {
+    pub sst: Arc<OpenAiSstAdapter>,
+    pub tts: Arc<OpenAiTtsAdapter>,
+    pub qa: Arc<OpenAiQaAdapter>,
+    pub notes: Arc<OpenAiNotesAdapter>,
+    pub cleanup: Arc<OpenAiCleanupAdapter>,
+    pub embedding: Arc<OpenAiEmbeddingAdapter>,
+    pub moderation: Arc<OpenAiModerationAdapter>,
+}
+
+impl OpenAiAdapters {
+    /// Creates the adapters with the models from `config`.
+    pub fn new(client: Client<OpenAIConfig>, config: &Config, tts_voice: Voice) -> Self {
+        Self {
+            sst: Arc::new(OpenAiSstAdapter::new(client.clone(), config.sst_model.clone())),
+            tts: Arc::new(OpenAiTtsAdapter::new(client.clone(), TTS_MODEL, tts_voice)),
+            qa: Arc::new(OpenAiQaAdapter::new(client.clone(), config.qa_model.clone())),
+            notes: Arc::new(OpenAiNotesAdapter::new(client.clone(), config.note_model.clone())),
+            cleanup: Arc::new(OpenAiCleanupAdapter::new(
+                client.clone(),
+                config.cleanup_model.clone(),
+            )),
+            embedding: Arc::new(OpenAiEmbeddingAdapter::new(
+                client.clone(),
+                config.embedding_model.clone(),
+            )),
+            moderation: Arc::new(OpenAiModerationAdapter::new(client)),
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! services/api/src/adapters/piper_tts.rs
+//!
+//! This module contains an adapter for Piper, a local neural TTS engine, so
+//! self-hosted deployments can narrate without paying per sentence. Each clip is
+//! synthesized by running the `piper` binary on one voice model; it implements the
+//! `TextToSpeechService` port from the `core` crate.
+
+use async_trait::async_trait;
+use reading_assistant_core::{
+    domain::{AudioEncoding, AudioFormat, SpeechSettings},
+    ports::{PortError, PortResult, TextToSpeechService},
+};
+use std::{path::PathBuf, process::Stdio};
+use tokio::{io::AsyncWriteExt, process::Command};
+
+use crate::audio::process_speech;
+
+//=========================================================================================
+// The Main Adapter Struct
+//=========================================================================================
+
+/// An adapter that implements the `TextToSpeechService` port by running Piper.
+#[derive(Clone)]
+pub struct PiperTtsAdapter {
+    binary: PathBuf,
+    model_path: PathBuf,
+    /// The sample rate of the model's output, from its `.onnx.json` config.
+    sample_rate: u32,
+    /// The model's only voice, named after its file (e.g. "en_US-lessac-medium").
+    voice: String,
+}
+
+impl PiperTtsAdapter {
+    /// Creates a new `PiperTtsAdapter` that runs `binary` with the model at `model_path`.
+    pub fn new(binary: PathBuf, model_path: PathBuf, sample_rate: u32) -> Self {
+        let voice = model_path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .map(|name| name.trim_end_matches(".onnx").to_string())
+            .unwrap_or_else(|| "piper".to_string());
+        Self {
+            binary,
+            model_path,
+            sample_rate,
+            voice,
+        }
+    }
+
+    async fn synthesize(&self, text: &str, speed: f32, format: AudioFormat) -> PortResult<Vec<u8>> {
+        // Piper only writes raw PCM, and nothing here encodes MP3 or Opus.
+        if format.encoding.is_compressed() {
+            return Err(PortError::Unexpected(format!(
+                "Piper can't produce {:?} audio",
+                format.encoding
+            )));
+        }
+        // Piper reads one utterance per line; a sentence is read as one utterance.
+        let text = text.replace(['\r', '\n'], " ");
+
+        let mut child = Command::new(&self.binary)
+            .arg("--model")
+            .arg(&self.model_path)
+            .arg("--output-raw")
+            // Piper's pace is a duration multiplier, the inverse of speed.
+            .arg("--length_scale")
+            .arg((1.0 / speed).to_string())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| PortError::Unexpected(format!("Failed to start Piper: {}", e)))?;
+
+        let mut stdin = child
+            .stdin
+            .take()
+            .ok_or_else(|| PortError::Unexpected("Piper's stdin was not captured".to_string()))?;
+        stdin
+            .write_all(text.as_bytes())
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        // Closing stdin tells Piper the input is complete.
+        drop(stdin);
+
+        let output = child
+            .wait_with_output()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+        if !output.status.success() {
+            return Err(PortError::Unexpected(format!(
+                "Piper exited with {}: {}",
+                output.status,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        // Trim silence and even out loudness so consecutive sentences play back smoothly.
+        process_speech(&output.stdout, self.sample_rate, format)
+            .map_err(|e| PortError::Unexpected(e.to_string()))
+    }
+}
+
+//=========================================================================================
+// `TextToSpeechService` Trait Implementation
+//=========================================================================================
+
+#[async_trait]
+impl TextToSpeechService for PiperTtsAdapter {
+    /// Generates a normalized WAV or raw PCM clip from the given text. The model has
+    /// one voice, so only the speed is taken from `speech`.
+    async fn generate_audio(&self, text: &str, speech: &SpeechSettings) -> PortResult<Vec<u8>> {
+        self.synthesize(text, speech.speed.unwrap_or(1.0), speech.format).await
+    }
+
+    async fn generate_audio_with_voice(
+        &self,
+        text: &str,
+        voice: &str,
+        speed: f32,
+    ) -> PortResult<Vec<u8>> {
+        if voice != self.voice {
+            return Err(PortError::NotFound(format!("Unknown voice '{}'", voice)));
+        }
+        self.synthesize(text, speed, AudioFormat::default()).await
+    }
+
+    fn provider(&self) -> &str {
+        "piper"
+    }
+
+    fn available_voices(&self) -> Vec<String> {
+        vec![self.voice.clone()]
+    }
+
+    fn default_voice(&self) -> String {
+        self.voice.clone()
+    }
+
+    fn model(&self) -> String {
+        self.voice.clone()
+    }
+
+    fn supported_encodings(&self) -> Vec<AudioEncoding> {
+        vec![AudioEncoding::Wav, AudioEncoding::Pcm16]
+    }
+
+    fn native_sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+}
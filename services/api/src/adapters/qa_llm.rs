@@ -6,16 +6,21 @@
 use async_openai::{
     config::OpenAIConfig,
     types::{
-        ChatCompletionRequestSystemMessageArgs, ChatCompletionRequestUserMessageArgs,
+        ChatCompletionRequestMessage, ChatCompletionRequestSystemMessageArgs,
+        ChatCompletionRequestUserMessageArgs, ChatCompletionStreamOptions, CompletionUsage,
         CreateChatCompletionRequestArgs,
     },
     Client, error::OpenAIError,
 };
 use async_trait::async_trait;
-use reading_assistant_core::ports::{PortError, PortResult, QuestionAnsweringService};
-use regex::Regex;
-use futures::{Stream, StreamExt};
-use std::pin::Pin;
+use reading_assistant_core::{
+    domain::{AnswerVerbosity, GroundingVerdict, Metered, QaReply, QuizGrade, QuizQuestion, ReadingLevel, TokenUsage},
+    ports::{PortError, PortResult, QaReplyStream, QuestionAnsweringService},
+};
+use futures::StreamExt;
+
+use crate::adapters::qa_prompts::{self, Prompt};
+
 //=========================================================================================
 // The Main Adapter Struct
 //=========================================================================================
@@ -32,66 +37,33 @@ impl OpenAiQaAdapter {
     pub fn new(client: Client<OpenAIConfig>, model: String) -> Self {
         Self { client, model }
     }
-    fn remove_citations(text: &str) -> String {
-        // Remove markdown citations like ([url.com](link))
-        let citation_regex = Regex::new(r"\(\[.*?\]\(.*?\)\)").unwrap();
-        let without_citations = citation_regex.replace_all(text, "");
-        
-        // Remove any lines that start with ## or - (sections/bullet points)
-        let lines: Vec<&str> = without_citations
-            .lines()
-            .filter(|line| {
-                let trimmed = line.trim();
-                !trimmed.starts_with("##") && 
-                !trimmed.starts_with("- [") &&
-                !trimmed.is_empty()
-            })
-            .collect();
-        
-        // Take only the first 1-2 sentences (before the citations section)
-        let result = lines.join(" ").trim().to_string();
-        
-        // Find the first occurrence of multiple sentences and cut off
-        if let Some(pos) = result.find(". ") {
-            // Look for second sentence
-            if let Some(second_pos) = result[pos+2..].find(". ") {
-                return result[..pos + second_pos + 3].to_string();
-            }
-        }
-        
-        result
-    }
-
-}
-
-//=========================================================================================
-// `QuestionAnsweringService` Trait Implementation
-//=========================================================================================
 
-#[async_trait]
-impl QuestionAnsweringService for OpenAiQaAdapter {
-    /// Answers a user's question based on a provided snippet of text (context).
-    async fn answer_question(&self, question: &str, context: &str) -> PortResult<String> {
+    /// The chat messages for a prompt.
+    fn messages(prompt: Prompt) -> PortResult<Vec<ChatCompletionRequestMessage>> {
+        Ok(vec![
+            ChatCompletionRequestSystemMessageArgs::default()
+                .content(prompt.system)
+                .build()
+                .map_err(|e| PortError::Unexpected(e.to_string()))?
+                .into(),
+            ChatCompletionRequestUserMessageArgs::default()
+                .content(prompt.user)
+                .build()
+                .map_err(|e| PortError::Unexpected(e.to_string()))?
+                .into(),
+        ])
+    }
 
-        let messages = vec![
-        ChatCompletionRequestSystemMessageArgs::default()
-            .content("You are a strict validation assistant. Your ONLY job is to check if the question relates to the provided context. The context is about a specific topic. If the question asks about ANYTHING not mentioned in the context, you MUST respond with EXACTLY: 'I'm sorry, I didn't understand your question given the context of what we've read so far. Could you please try asking again?' Do NOT answer unrelated questions. Do NOT use your general knowledge. ONLY answer if the question is directly about something in the context.")
-            .build()
-            .map_err(|e| PortError::Unexpected(e.to_string()))?
-            .into(),
-        ChatCompletionRequestUserMessageArgs::default()
-            .content(format!(
-                "CONTEXT:\n---\n{}\n---\n\nQUESTION: {}\n\nIs this question about something in the context? If NO, respond with the exact rejection message. If YES, answer briefly in 1-2 sentences using ONLY information from the context.",
-                context, question
-            ))
-            .build()
-            .map_err(|e| PortError::Unexpected(e.to_string()))?
-            .into(),
-    ];
+    /// Runs one chat completion and returns the text of its first choice.
+    async fn complete(&self, prompt: Prompt, what: &str) -> PortResult<String> {
+        self.complete_metered(prompt, what).await.map(|completion| completion.value)
+    }
 
+    /// Like `complete`, along with the tokens the completion used.
+    async fn complete_metered(&self, prompt: Prompt, what: &str) -> PortResult<Metered<String>> {
         let request = CreateChatCompletionRequestArgs::default()
             .model(&self.model)
-            .messages(messages)
+            .messages(Self::messages(prompt)?)
             .build()
             .map_err(|e| PortError::Unexpected(e.to_string()))?;
 
@@ -102,48 +74,68 @@ impl QuestionAnsweringService for OpenAiQaAdapter {
             .await
             .map_err(|e: OpenAIError| PortError::Unexpected(e.to_string()))?;
 
-        if let Some(choice) = response.choices.into_iter().next() {
-            if let Some(content) = choice.message.content {
-                // ✅ Clean up the response by removing citations and extra content
-                let cleaned = Self::remove_citations(&content);
-                Ok(cleaned)
-            } else {
-                Err(PortError::Unexpected(
-                    "LLM response contained no text content.".to_string(),
-                ))
-            }
-        } else {
-            Err(PortError::Unexpected(
-                "LLM returned no choices in its response.".to_string(),
-            ))
-        }
-    }
-
-     async fn answer_question_streaming(
+        let usage = response.usage.map(token_usage);
+        let value = response
+            .choices
+            .into_iter()
+            .next()
+            .and_then(|choice| choice.message.content)
+            .ok_or_else(|| {
+                PortError::Unexpected(format!("{} LLM response contained no text content.", what))
+            })?;
+        Ok(Metered { value, usage })
+    }
+
+    /// Runs one summarization request with the given system instructions.
+    async fn summarize(&self, instructions: &str, text: &str) -> PortResult<String> {
+        let prompt = Prompt {
+            system: instructions.to_string(),
+            user: format!("PASSAGE:\n---\n{}\n---", text),
+        };
+        self.complete(prompt, "Summary").await
+    }
+}
+
+//=========================================================================================
+// `QuestionAnsweringService` Trait Implementation
+//=========================================================================================
+
+#[async_trait]
+impl QuestionAnsweringService for OpenAiQaAdapter {
+    /// Answers a user's question based on a provided snippet of text (context).
+    async fn answer_question(
         &self,
         question: &str,
         context: &str,
-    ) -> PortResult<Pin<Box<dyn Stream<Item = Result<String, PortError>> + Send>>> {
-        let messages = vec![
-            ChatCompletionRequestSystemMessageArgs::default()
-                .content("You are an expert tutor. Answer the user's question based on the provided context and any recent information. Be concise and clear. Keep your response limited to 1-2 sentences. Do NOT include any URLs, citations, or references in your answer - only provide the information in natural conversational language.")
-                .build()
-                .map_err(|e| PortError::Unexpected(e.to_string()))?
-                .into(),
-            ChatCompletionRequestUserMessageArgs::default()
-                .content(format!(
-                    "CONTEXT:\n---\n{}\n---\n\nQUESTION: {}",
-                    context, question
-                ))
-                .build()
-                .map_err(|e| PortError::Unexpected(e.to_string()))?
-                .into(),
-        ];
+        verbosity: AnswerVerbosity,
+        education_mode: bool,
+        allow_clarification: bool,
+    ) -> PortResult<Metered<QaReply>> {
+        let prompt =
+            qa_prompts::answer_prompt(question, context, verbosity, education_mode, allow_clarification);
+        let completion = self.complete_metered(prompt, "QA").await?;
+        Ok(Metered {
+            value: qa_prompts::parse_answer(&completion.value, verbosity),
+            usage: completion.usage,
+        })
+    }
 
+    /// Streams the answer to a user's question.
+    async fn answer_question_streaming(
+        &self,
+        question: &str,
+        context: &str,
+        verbosity: AnswerVerbosity,
+        education_mode: bool,
+        allow_clarification: bool,
+    ) -> PortResult<QaReplyStream> {
+        let prompt =
+            qa_prompts::answer_prompt(question, context, verbosity, education_mode, allow_clarification);
         let request = CreateChatCompletionRequestArgs::default()
             .model(&self.model)
-            .messages(messages)
+            .messages(Self::messages(prompt)?)
             .stream(true)
+            .stream_options(ChatCompletionStreamOptions { include_usage: true })
             .build()
             .map_err(|e| PortError::Unexpected(e.to_string()))?;
 
@@ -154,28 +146,88 @@ impl QuestionAnsweringService for OpenAiQaAdapter {
             .await
             .map_err(|e: OpenAIError| PortError::Unexpected(e.to_string()))?;
 
-        // Convert the stream to our result type
-        let mapped_stream = stream.map(|result| {
+        // Each event carries the next few characters of the reply; the last one, which
+        // has no choices, carries the usage
+        let (usage_tx, usage_rx) = futures::channel::oneshot::channel();
+        let mut usage_tx = Some(usage_tx);
+        let deltas = stream.map(move |result| {
             result
                 .map_err(|e| PortError::Unexpected(e.to_string()))
-                .and_then(|response| {
-                    let content: String = response
+                .map(|response| {
+                    if let Some(usage) = response.usage {
+                        if let Some(usage_tx) = usage_tx.take() {
+                            // The answer may have been abandoned, dropping the receiver
+                            let _ = usage_tx.send(token_usage(usage));
+                        }
+                    }
+                    response
                         .choices
                         .into_iter()
                         .filter_map(|choice| choice.delta.content)
-                        .collect();
-                    Ok(content)
-                })
-                .and_then(|content| {
-                    if content.is_empty() {
-                        Ok(String::new())
-                    } else {
-                        Ok(content)
-                    }
+                        .collect::<String>()
                 })
         });
 
-        Ok(Box::pin(mapped_stream))
+        qa_prompts::parse_answer_stream(deltas, usage_rx).await
+    }
+
+    /// Summarizes a passage for a spoken recap: short, no lists, no headings.
+    async fn summarize_passage(&self, text: &str) -> PortResult<String> {
+        self.summarize(qa_prompts::SUMMARY_INSTRUCTIONS, text).await
+    }
+
+    /// Summarizes a passage in one sentence that completes "Last time we covered...".
+    async fn summarize_passage_in_one_sentence(&self, text: &str) -> PortResult<String> {
+        self.summarize(qa_prompts::ONE_SENTENCE_SUMMARY_INSTRUCTIONS, text).await
+    }
+
+    /// Re-explains a passage in 2-4 plain sentences pitched at the reading level.
+    async fn simplify_passage(&self, text: &str, level: ReadingLevel) -> PortResult<String> {
+        self.summarize(&qa_prompts::simplify_instructions(level), text).await
+    }
+
+    /// Generates comprehension questions as `Q:`/`A:` line pairs and parses them.
+    async fn generate_quiz_questions(
+        &self,
+        context: &str,
+        count: usize,
+    ) -> PortResult<Vec<QuizQuestion>> {
+        let content = self.complete(qa_prompts::quiz_prompt(context, count), "Quiz").await?;
+        qa_prompts::parse_quiz_questions(&content, count)
+    }
+
+    /// Checks the answer against its context with a separate, non-streamed completion.
+    async fn verify_grounding(&self, answer: &str, context: &str) -> PortResult<GroundingVerdict> {
+        let content = self
+            .complete(qa_prompts::grounding_prompt(answer, context), "Grounding")
+            .await?;
+        Ok(qa_prompts::parse_grounding(&content))
+    }
+
+    /// Grades an answer leniently: paraphrases and partial wording count as correct.
+    async fn grade_quiz_answer(
+        &self,
+        question: &QuizQuestion,
+        user_answer: &str,
+    ) -> PortResult<QuizGrade> {
+        let content = self
+            .complete(qa_prompts::grade_prompt(question, user_answer), "Grading")
+            .await?;
+        Ok(qa_prompts::parse_grade(&content))
+    }
+
+    fn provider(&self) -> &str {
+        "openai"
+    }
+
+    fn model(&self) -> String {
+        self.model.clone()
     }
 }
 
+fn token_usage(usage: CompletionUsage) -> TokenUsage {
+    TokenUsage {
+        prompt_tokens: usage.prompt_tokens,
+        completion_tokens: usage.completion_tokens,
+    }
+}
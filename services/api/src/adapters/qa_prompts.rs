@@ -0,0 +1,281 @@
+//! services/api/src/adapters/qa_prompts.rs
+//!
+//! The prompts behind `QuestionAnsweringService` and the parsing of their replies,
+//! shared by every LLM vendor's adapter. Keeping them in one place means each vendor
+//! answers under the same contract: off-topic questions get the exact rejection
+//! message, ambiguous ones a `CLARIFY:` reply, and so on.
+
+use futures::{Stream, StreamExt};
+use reading_assistant_core::{
+    domain::{AnswerVerbosity, GroundingVerdict, QaReply, QuizGrade, QuizQuestion, ReadingLevel},
+    ports::{PortError, PortResult, QaReplyStream, UsageReceiver},
+};
+use regex::Regex;
+
+/// A prompt as a system instruction plus the user's message.
+pub struct Prompt {
+    pub system: String,
+    pub user: String,
+}
+
+/// Extra system instructions for child/education-mode sessions.
+const EDUCATION_MODE_INSTRUCTIONS: &str = " The listener is a school-age child. Use simple, age-appropriate words and short sentences, keep a warm and encouraging tone, and never describe violent, sexual, or otherwise mature content in detail.";
+
+/// Lets the model ask one clarifying question instead of guessing at an ambiguous one.
+const CLARIFICATION_INSTRUCTIONS: &str = " If the question is about the context but is ambiguous (for example, it is unclear which person, term, or passage it refers to), do not guess: reply with 'CLARIFY:' followed by one short question asking what the listener meant.";
+
+/// Has the model cite the numbered context sentences an answer is based on.
+const CITATION_INSTRUCTIONS: &str = " Each sentence of the context starts with its number in square brackets. When you answer, start your reply with 'ANSWER' followed by the numbers of the sentences that support the answer in square brackets and a colon, e.g. 'ANSWER [12, 14]:', then the answer itself without any numbers.";
+
+/// The longest answer prefix read, e.g. "ANSWER [12, 14, 15]:", before giving up on
+/// finding its end.
+const MAX_ANSWER_PREFIX_CHARS: usize = 120;
+
+pub const SUMMARY_INSTRUCTIONS: &str = "You summarize passages that are read aloud to a listener. Write 3-5 plain sentences covering the main points in the order they appear. Do NOT use lists, headings, markdown, or information that is not in the passage.";
+
+pub const ONE_SENTENCE_SUMMARY_INSTRUCTIONS: &str = "You remind a listener what they heard in their last reading session. Write ONE short plain sentence that completes \"Last time we covered...\" without repeating those words, e.g. \"how the treaty ended the war and what it cost both sides.\" Do NOT use lists, markdown, or information that is not in the passage.";
+
+/// The instructions for re-explaining a passage more simply, at `level`.
+pub fn simplify_instructions(level: ReadingLevel) -> String {
+    let audience = match level {
+        ReadingLevel::Elementary => "a child of about 8: use short sentences and only everyday words",
+        ReadingLevel::MiddleSchool => "a reader of about 12: use short sentences and explain any hard words",
+        ReadingLevel::General => "an adult who found it hard going: use plain everyday language instead of jargon",
+    };
+    format!(
+        "You re-explain passages that were just read aloud to a listener who asked for them more simply. Explain what the passage says in 2-4 sentences for {}. Keep its meaning, and do NOT use lists, headings, markdown, or information that is not in the passage.",
+        audience
+    )
+}
+
+/// The length instruction for the prompt and the maximum sentences kept from the reply.
+fn length_for(verbosity: AnswerVerbosity) -> (&'static str, usize) {
+    match verbosity {
+        AnswerVerbosity::Brief => ("in one short sentence", 1),
+        AnswerVerbosity::Normal => ("briefly in 1-2 sentences", 2),
+        AnswerVerbosity::Detailed => ("in 3-5 sentences", 5),
+    }
+}
+
+/// The prompt for answering `question` from `context`. Questions that aren't about the
+/// context get a fixed rejection message instead of an answer. Answers come from the
+/// context alone: no adapter gives the model a web search tool, so every session is
+/// document-only and there is no search policy to configure.
+pub fn answer_prompt(
+    question: &str,
+    context: &str,
+    verbosity: AnswerVerbosity,
+    education_mode: bool,
+    allow_clarification: bool,
+) -> Prompt {
+    let (length, _) = length_for(verbosity);
+    let audience = if education_mode { EDUCATION_MODE_INSTRUCTIONS } else { "" };
+    let clarification = if allow_clarification { CLARIFICATION_INSTRUCTIONS } else { "" };
+
+    Prompt {
+        system: format!("You are a strict validation assistant. Your ONLY job is to check if the question relates to the provided context. The context is about a specific topic. If the question asks about ANYTHING not mentioned in the context, you MUST respond with EXACTLY: 'I'm sorry, I didn't understand your question given the context of what we've read so far. Could you please try asking again?' Do NOT answer unrelated questions. Do NOT use your general knowledge. ONLY answer if the question is directly about something in the context.{}{}{}", CITATION_INSTRUCTIONS, clarification, audience),
+        user: format!(
+            "CONTEXT:\n---\n{}\n---\n\nQUESTION: {}\n\nIs this question about something in the context? If NO, respond with the exact rejection message. If YES, answer {} using ONLY information from the context.",
+            context, question, length
+        ),
+    }
+}
+
+/// Reads a complete reply to `answer_prompt`.
+pub fn parse_answer(content: &str, verbosity: AnswerVerbosity) -> QaReply {
+    let content = content.trim();
+    if let Some(clarifying) = content.strip_prefix("CLARIFY:") {
+        return QaReply::Clarification(clarifying.trim().to_string());
+    }
+    // The rejection message comes back without a prefix
+    let (sources, answer) = split_answer_prefix(content).unwrap_or((Vec::new(), content));
+    // ✅ Clean up the response by removing citations and extra content
+    let (_, max_sentences) = length_for(verbosity);
+    QaReply::Answer {
+        text: remove_citations(answer, max_sentences),
+        sources,
+    }
+}
+
+/// Splits an "ANSWER [12, 14]:" or "ANSWER:" prefix off a reply, returning the cited
+/// sentence numbers and the rest. Returns `None` if the reply doesn't start with one.
+fn split_answer_prefix(reply: &str) -> Option<(Vec<usize>, &str)> {
+    let after_keyword = reply.strip_prefix("ANSWER")?;
+    let (cited, rest) = after_keyword.split_once(':')?;
+    let cited = cited.trim();
+    let sources = if cited.is_empty() {
+        Vec::new()
+    } else {
+        cited
+            .strip_prefix('[')?
+            .strip_suffix(']')?
+            .split(',')
+            .filter_map(|number| number.trim().parse().ok())
+            .collect()
+    };
+    Some((sources, rest.trim_start()))
+}
+
+/// Reads a reply to `answer_prompt` as it streams in. The first few characters are
+/// read before returning, to tell an answer from a clarifying question. `usage`
+/// resolves once the stream has ended, if the provider reports it.
+pub async fn parse_answer_stream<S>(mut deltas: S, usage: UsageReceiver) -> PortResult<QaReplyStream>
+where
+    S: Stream<Item = PortResult<String>> + Send + Unpin + 'static,
+{
+    // Read until the "CLARIFY:" or "ANSWER [...]:" prefix can be told apart, and to
+    // the end of an answer's prefix, which lists the sentences it cites
+    let mut head = String::new();
+    loop {
+        let trimmed = head.trim_start();
+        let undecided = trimmed.len() < "CLARIFY:".len()
+            || (trimmed.starts_with("ANSWER")
+                && !trimmed.contains(':')
+                && trimmed.len() < MAX_ANSWER_PREFIX_CHARS);
+        if !undecided {
+            break;
+        }
+        match deltas.next().await {
+            Some(delta) => head.push_str(&delta?),
+            None => break,
+        }
+    }
+    let head = head.trim_start();
+
+    if let Some(clarifying) = head.strip_prefix("CLARIFY:") {
+        let mut clarifying = clarifying.to_string();
+        while let Some(delta) = deltas.next().await {
+            clarifying.push_str(&delta?);
+        }
+        return Ok(QaReplyStream::Clarification(clarifying.trim().to_string()));
+    }
+    // The rejection message comes back without a prefix
+    let (sources, first) = split_answer_prefix(head).unwrap_or((Vec::new(), head));
+    let first = first.to_string();
+    Ok(QaReplyStream::Answer {
+        stream: Box::pin(futures::stream::once(async { Ok(first) }).chain(deltas)),
+        sources,
+        usage,
+    })
+}
+
+/// The usage of a streamed answer from a provider that doesn't report it.
+pub fn unreported_usage() -> UsageReceiver {
+    futures::channel::oneshot::channel().1
+}
+
+fn remove_citations(text: &str, max_sentences: usize) -> String {
+    // Remove markdown citations like ([url.com](link))
+    let citation_regex = Regex::new(r"\(\[.*?\]\(.*?\)\)").unwrap();
+    let without_citations = citation_regex.replace_all(text, "");
+
+    // Remove any lines that start with ## or - (sections/bullet points)
+    let lines: Vec<&str> = without_citations
+        .lines()
+        .filter(|line| {
+            let trimmed = line.trim();
+            !trimmed.starts_with("##") &&
+            !trimmed.starts_with("- [") &&
+            !trimmed.is_empty()
+        })
+        .collect();
+
+    // Take only the first `max_sentences` sentences (before the citations section)
+    let result = lines.join(" ").trim().to_string();
+
+    // Cut off after the `max_sentences`-th sentence break, if there is one
+    let mut end = 0;
+    for _ in 0..max_sentences {
+        match result[end..].find(". ") {
+            Some(pos) => end += pos + 2,
+            None => return result,
+        }
+    }
+    result[..end].trim_end().to_string()
+}
+
+/// The prompt for checking an answer against the context it was written from.
+pub fn grounding_prompt(answer: &str, context: &str) -> Prompt {
+    Prompt {
+        system: "You fact-check answers about a document. Decide whether every claim in the answer is stated in or directly follows from the context; general knowledge does not count. An answer saying the question couldn't be understood is supported. Respond with 'SUPPORTED' or 'UNSUPPORTED:' followed by one short sentence naming the claim the context doesn't back up.".to_string(),
+        user: format!("CONTEXT:\n---\n{}\n---\n\nANSWER: {}", context, answer),
+    }
+}
+
+/// Reads the verdict out of a reply to `grounding_prompt`. Replies that are neither
+/// verdict count as supported, so a confused checker never casts doubt on an answer.
+pub fn parse_grounding(content: &str) -> GroundingVerdict {
+    match content.trim().strip_prefix("UNSUPPORTED") {
+        Some(reason) => GroundingVerdict {
+            supported: false,
+            reason: reason.trim_start_matches(':').trim().to_string(),
+        },
+        None => GroundingVerdict {
+            supported: true,
+            reason: String::new(),
+        },
+    }
+}
+
+/// The prompt for `count` comprehension questions as `Q:`/`A:` line pairs.
+pub fn quiz_prompt(context: &str, count: usize) -> Prompt {
+    Prompt {
+        system: "You are a tutor quizzing a listener on a passage they just heard. Write short comprehension questions that can be answered in one spoken sentence using ONLY the passage. Format each as two lines: 'Q: <question>' then 'A: <expected answer>'. Output nothing else.".to_string(),
+        user: format!("PASSAGE:\n---\n{}\n---\n\nWrite {} questions.", context, count),
+    }
+}
+
+/// Reads the questions out of a reply to `quiz_prompt`.
+pub fn parse_quiz_questions(content: &str, count: usize) -> PortResult<Vec<QuizQuestion>> {
+    let mut questions = Vec::new();
+    let mut pending_question: Option<String> = None;
+    for line in content.lines().map(str::trim) {
+        if let Some(q) = line.strip_prefix("Q:") {
+            pending_question = Some(q.trim().to_string());
+        } else if let Some(a) = line.strip_prefix("A:") {
+            if let Some(question) = pending_question.take() {
+                questions.push(QuizQuestion {
+                    question,
+                    expected_answer: a.trim().to_string(),
+                });
+            }
+        }
+    }
+    questions.truncate(count);
+
+    if questions.is_empty() {
+        return Err(PortError::Unexpected(
+            "Quiz LLM response contained no parsable questions.".to_string(),
+        ));
+    }
+    Ok(questions)
+}
+
+/// The prompt for grading an answer leniently: paraphrases and partial wording count
+/// as correct.
+pub fn grade_prompt(question: &QuizQuestion, user_answer: &str) -> Prompt {
+    Prompt {
+        system: "You grade spoken answers to quiz questions. The answer was transcribed from speech, so ignore filler words and transcription errors, and accept paraphrases that capture the key idea. Respond with 'CORRECT:' or 'INCORRECT:' followed by one short, encouraging sentence of feedback that states the right answer when the user was wrong.".to_string(),
+        user: format!(
+            "QUESTION: {}\nEXPECTED ANSWER: {}\nUSER ANSWER: {}",
+            question.question, question.expected_answer, user_answer
+        ),
+    }
+}
+
+/// Reads the verdict out of a reply to `grade_prompt`.
+pub fn parse_grade(content: &str) -> QuizGrade {
+    let content = content.trim();
+    let (correct, feedback) = if let Some(rest) = content.strip_prefix("INCORRECT:") {
+        (false, rest)
+    } else if let Some(rest) = content.strip_prefix("CORRECT:") {
+        (true, rest)
+    } else {
+        (false, content)
+    };
+
+    QuizGrade {
+        correct,
+        feedback: feedback.trim().to_string(),
+    }
+}
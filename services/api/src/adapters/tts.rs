@@ -5,11 +5,16 @@
 
 use async_openai::{
     config::OpenAIConfig,
-    types::{CreateSpeechRequest, SpeechModel, Voice},
+    types::{CreateSpeechRequest, SpeechModel, SpeechResponseFormat, Voice},
     Client, error::OpenAIError,
 };
 use async_trait::async_trait;
-use reading_assistant_core::ports::{PortError, PortResult, TextToSpeechService};
+use reading_assistant_core::{
+    domain::{AudioEncoding, AudioFormat, SpeechSettings},
+    ports::{PortError, PortResult, TextToSpeechService},
+};
+
+use crate::audio::{process_speech, TTS_SAMPLE_RATE};
 
 //=========================================================================================
 // The Main Adapter Struct
@@ -23,6 +28,16 @@ pub struct OpenAiTtsAdapter {
     voice: Voice,
 }
 
+/// The voices offered by the OpenAI TTS API, by identifier.
+const VOICES: &[(&str, Voice)] = &[
+    ("alloy", Voice::Alloy),
+    ("echo", Voice::Echo),
+    ("fable", Voice::Fable),
+    ("onyx", Voice::Onyx),
+    ("nova", Voice::Nova),
+    ("shimmer", Voice::Shimmer),
+];
+
 impl OpenAiTtsAdapter {
     /// Creates a new `OpenAiTtsAdapter`.
     pub fn new(client: Client<OpenAIConfig>, model: SpeechModel, voice: Voice) -> Self {
@@ -32,20 +47,36 @@ impl OpenAiTtsAdapter {
             voice,
         }
     }
-}
 
-//=========================================================================================
-// `TextToSpeechService` Trait Implementation
-//=========================================================================================
+    /// Looks up a voice by its (case-insensitive) identifier.
+    pub fn parse_voice(name: &str) -> Option<Voice> {
+        let name = name.to_lowercase();
+        VOICES
+            .iter()
+            .find(|(id, _)| *id == name)
+            .map(|(_, voice)| voice.clone())
+    }
 
-#[async_trait]
-impl TextToSpeechService for OpenAiTtsAdapter {
-    /// Generates a vector of audio data (`Vec<u8>`) from the given text.
-    async fn generate_audio(&self, text: &str) -> PortResult<Vec<u8>> {
+    async fn synthesize(
+        &self,
+        text: &str,
+        voice: Voice,
+        speed: Option<f32>,
+        format: AudioFormat,
+    ) -> PortResult<Vec<u8>> {
+        // Uncompressed clips are fetched as raw PCM so they can be post-processed;
+        // compressed ones are encoded by the API and sent on as they are.
+        let response_format = match format.encoding {
+            AudioEncoding::Wav | AudioEncoding::Pcm16 => SpeechResponseFormat::Pcm,
+            AudioEncoding::Mp3 => SpeechResponseFormat::Mp3,
+            AudioEncoding::Opus => SpeechResponseFormat::Opus,
+        };
         let request = CreateSpeechRequest {
             model: self.model.clone(),
             input: text.to_string(),
-            voice: self.voice.clone(),
+            voice,
+            speed,
+            response_format: Some(response_format),
             ..Default::default()
         };
 
@@ -57,7 +88,71 @@ impl TextToSpeechService for OpenAiTtsAdapter {
             .await
             .map_err(|e: OpenAIError| PortError::Unexpected(e.to_string()))?;
 
-        // The response contains a `bytes` field. We call `.to_vec()` on that field.
-        Ok(response.bytes.to_vec())
+        if format.encoding.is_compressed() {
+            return Ok(response.bytes.to_vec());
+        }
+        // Trim silence and even out loudness so consecutive sentences play back smoothly.
+        process_speech(&response.bytes, TTS_SAMPLE_RATE, format)
+            .map_err(|e| PortError::Unexpected(e.to_string()))
+    }
+}
+
+//=========================================================================================
+// `TextToSpeechService` Trait Implementation
+//=========================================================================================
+
+#[async_trait]
+impl TextToSpeechService for OpenAiTtsAdapter {
+    /// Generates a clip from the given text, normalized unless it is compressed.
+    async fn generate_audio(&self, text: &str, speech: &SpeechSettings) -> PortResult<Vec<u8>> {
+        let voice = speech
+            .voice
+            .as_deref()
+            .and_then(Self::parse_voice)
+            .unwrap_or_else(|| self.voice.clone());
+        self.synthesize(text, voice, speech.speed, speech.format).await
+    }
+
+    async fn generate_audio_with_voice(
+        &self,
+        text: &str,
+        voice: &str,
+        speed: f32,
+    ) -> PortResult<Vec<u8>> {
+        let voice = Self::parse_voice(voice)
+            .ok_or_else(|| PortError::NotFound(format!("Unknown voice '{}'", voice)))?;
+        self.synthesize(text, voice, Some(speed), AudioFormat::default()).await
+    }
+
+    fn provider(&self) -> &str {
+        "openai"
+    }
+
+    fn available_voices(&self) -> Vec<String> {
+        VOICES.iter().map(|(id, _)| id.to_string()).collect()
+    }
+
+    fn default_voice(&self) -> String {
+        VOICES
+            .iter()
+            .find(|(_, voice)| *voice == self.voice)
+            .map(|(id, _)| id.to_string())
+            .unwrap_or_default()
+    }
+
+    fn model(&self) -> String {
+        // The API identifier ("tts-1", "tts-1-hd", ...) is the model's serialized form.
+        serde_json::to_value(&self.model)
+            .ok()
+            .and_then(|v| v.as_str().map(str::to_string))
+            .unwrap_or_else(|| format!("{:?}", self.model))
+    }
+
+    fn supported_encodings(&self) -> Vec<AudioEncoding> {
+        vec![AudioEncoding::Wav, AudioEncoding::Pcm16, AudioEncoding::Mp3, AudioEncoding::Opus]
+    }
+
+    fn native_sample_rate(&self) -> u32 {
+        TTS_SAMPLE_RATE
     }
 }
@@ -0,0 +1,117 @@
+//! services/api/src/adapters/tts_cache.rs
+//!
+//! A `TextToSpeechService` decorator that looks synthesized audio up in an
+//! `AudioCacheService` before calling the wrapped adapter, so the same sentence
+//! read in the same voice is only ever synthesized once.
+
+use async_trait::async_trait;
+use reading_assistant_core::domain::{AudioEncoding, AudioFormat, SpeechSettings};
+use reading_assistant_core::ports::{AudioCacheService, PortResult, TextToSpeechService};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use tracing::warn;
+
+/// Wraps another TTS adapter and caches everything it synthesizes.
+#[derive(Clone)]
+pub struct CachedTtsAdapter {
+    inner: Arc<dyn TextToSpeechService>,
+    cache: Arc<dyn AudioCacheService>,
+}
+
+impl CachedTtsAdapter {
+    /// Creates a new `CachedTtsAdapter` around `inner`.
+    pub fn new(inner: Arc<dyn TextToSpeechService>, cache: Arc<dyn AudioCacheService>) -> Self {
+        Self { inner, cache }
+    }
+
+    /// The cache key: a digest of everything that determines the audio.
+    fn key(&self, text: &str, voice: &str, speed: Option<f32>, format: AudioFormat) -> String {
+        let mut hasher = Sha256::new();
+        for part in [self.inner.provider(), &self.inner.model(), voice, text] {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
+        if let Some(speed) = speed {
+            hasher.update(speed.to_le_bytes());
+        }
+        // WAV at the provider's own rate predates formats, so it keeps its old keys.
+        let sample_rate = format
+            .sample_rate
+            .filter(|rate| *rate != self.inner.native_sample_rate());
+        if format.encoding != AudioEncoding::Wav || sample_rate.is_some() {
+            hasher.update(format!("{:?}", format.encoding).as_bytes());
+            hasher.update(sample_rate.unwrap_or(0).to_le_bytes());
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// Returns the cached audio for `key`, or synthesizes and caches it. A failing
+    /// cache only costs a synthesis, so its errors are logged rather than returned.
+    async fn get_or_generate<F>(&self, key: String, generate: F) -> PortResult<Vec<u8>>
+    where
+        F: std::future::Future<Output = PortResult<Vec<u8>>>,
+    {
+        match self.cache.get(&key).await {
+            Ok(Some(audio)) => return Ok(audio),
+            Ok(None) => {}
+            Err(e) => warn!("Failed to read TTS audio cache: {:?}", e),
+        }
+
+        let audio = generate.await?;
+        if let Err(e) = self.cache.put(&key, &audio).await {
+            warn!("Failed to write TTS audio cache: {:?}", e);
+        }
+        Ok(audio)
+    }
+}
+
+#[async_trait]
+impl TextToSpeechService for CachedTtsAdapter {
+    async fn generate_audio(&self, text: &str, speech: &SpeechSettings) -> PortResult<Vec<u8>> {
+        // Keyed by the voice actually used, so an unknown voice shares the default's audio
+        // and normal speed shares the keys of audio from before speeds could be chosen.
+        let voice = speech
+            .voice
+            .clone()
+            .filter(|voice| self.inner.available_voices().contains(voice))
+            .unwrap_or_else(|| self.inner.default_voice());
+        let speed = speech.speed.filter(|speed| *speed != 1.0);
+        let key = self.key(text, &voice, speed, speech.format);
+        self.get_or_generate(key, self.inner.generate_audio(text, speech)).await
+    }
+
+    async fn generate_audio_with_voice(
+        &self,
+        text: &str,
+        voice: &str,
+        speed: f32,
+    ) -> PortResult<Vec<u8>> {
+        let key = self.key(text, voice, Some(speed), AudioFormat::default());
+        self.get_or_generate(key, self.inner.generate_audio_with_voice(text, voice, speed))
+            .await
+    }
+
+    fn provider(&self) -> &str {
+        self.inner.provider()
+    }
+
+    fn available_voices(&self) -> Vec<String> {
+        self.inner.available_voices()
+    }
+
+    fn default_voice(&self) -> String {
+        self.inner.default_voice()
+    }
+
+    fn model(&self) -> String {
+        self.inner.model()
+    }
+
+    fn supported_encodings(&self) -> Vec<AudioEncoding> {
+        self.inner.supported_encodings()
+    }
+
+    fn native_sample_rate(&self) -> u32 {
+        self.inner.native_sample_rate()
+    }
+}
@@ -0,0 +1,81 @@
+//! services/api/src/adapters/tts_usage.rs
+//!
+//! A `TextToSpeechService` decorator that records how many characters are
+//! synthesized per provider and model, so operators can reconcile provider
+//! invoices against actual application usage.
+
+use async_trait::async_trait;
+use reading_assistant_core::domain::{AudioEncoding, SpeechSettings};
+use reading_assistant_core::ports::{DatabaseService, PortResult, TextToSpeechService};
+use std::sync::Arc;
+use tracing::warn;
+
+/// Wraps another TTS adapter and records usage for every successful synthesis.
+#[derive(Clone)]
+pub struct MeteredTtsAdapter {
+    inner: Arc<dyn TextToSpeechService>,
+    db: Arc<dyn DatabaseService>,
+}
+
+impl MeteredTtsAdapter {
+    /// Creates a new `MeteredTtsAdapter` around `inner`.
+    pub fn new(inner: Arc<dyn TextToSpeechService>, db: Arc<dyn DatabaseService>) -> Self {
+        Self { inner, db }
+    }
+
+    /// Records usage without failing the synthesis: a missed row only skews the report.
+    async fn record(&self, text: &str) {
+        let characters = text.chars().count();
+        if let Err(e) = self
+            .db
+            .record_tts_usage(self.inner.provider(), &self.inner.model(), characters)
+            .await
+        {
+            warn!("Failed to record TTS usage: {:?}", e);
+        }
+    }
+}
+
+#[async_trait]
+impl TextToSpeechService for MeteredTtsAdapter {
+    async fn generate_audio(&self, text: &str, speech: &SpeechSettings) -> PortResult<Vec<u8>> {
+        let audio = self.inner.generate_audio(text, speech).await?;
+        self.record(text).await;
+        Ok(audio)
+    }
+
+    async fn generate_audio_with_voice(
+        &self,
+        text: &str,
+        voice: &str,
+        speed: f32,
+    ) -> PortResult<Vec<u8>> {
+        let audio = self.inner.generate_audio_with_voice(text, voice, speed).await?;
+        self.record(text).await;
+        Ok(audio)
+    }
+
+    fn provider(&self) -> &str {
+        self.inner.provider()
+    }
+
+    fn available_voices(&self) -> Vec<String> {
+        self.inner.available_voices()
+    }
+
+    fn default_voice(&self) -> String {
+        self.inner.default_voice()
+    }
+
+    fn model(&self) -> String {
+        self.inner.model()
+    }
+
+    fn supported_encodings(&self) -> Vec<AudioEncoding> {
+        self.inner.supported_encodings()
+    }
+
+    fn native_sample_rate(&self) -> u32 {
+        self.inner.native_sample_rate()
+    }
+}
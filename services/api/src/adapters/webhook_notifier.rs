@@ -0,0 +1,61 @@
+//! services/api/src/adapters/webhook_notifier.rs
+//!
+//! This module contains an adapter that delivers notifications by posting them to a
+//! webhook, which passes them on by email, push or chat. It implements the
+//! `NotificationService` port from the `core` crate.
+
+use async_trait::async_trait;
+use reading_assistant_core::{
+    domain::Notification,
+    ports::{NotificationService, PortError, PortResult},
+};
+use serde_json::json;
+use uuid::Uuid;
+
+//=========================================================================================
+// The Main Adapter Struct
+//=========================================================================================
+
+/// An adapter that implements `NotificationService` by posting JSON to a webhook.
+#[derive(Clone)]
+pub struct WebhookNotifier {
+    http: reqwest::Client,
+    url: String,
+}
+
+impl WebhookNotifier {
+    /// Creates a new `WebhookNotifier` posting to `url`.
+    pub fn new(url: String) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            url,
+        }
+    }
+}
+
+#[async_trait]
+impl NotificationService for WebhookNotifier {
+    async fn notify(&self, user_id: Uuid, notification: &Notification) -> PortResult<()> {
+        let body = json!({
+            "user_id": user_id,
+            "title": notification.title,
+            "body": notification.body,
+        });
+        let response = self
+            .http
+            .post(&self.url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            return Err(PortError::Unexpected(format!(
+                "Notification webhook failed with {}",
+                status
+            )));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,222 @@
+//! services/api/src/audio.rs
+//!
+//! A small DSP pipeline for synthesized speech. Each TTS clip is trimmed of
+//! leading/trailing silence and normalized to a common loudness, so that
+//! consecutive sentences play back smoothly and at an even level. Per-user
+//! narration pacing (gaps and fades between sentences) is applied on top.
+//!
+//! Clips come out as WAV or raw PCM at the session's sample rate. Compressed clips
+//! (MP3, Opus) are encoded by the TTS provider and pass through untouched.
+
+use hound::{WavReader, WavSpec, WavWriter};
+use reading_assistant_core::domain::{AudioEncoding, AudioFormat};
+
+/// The sample rate of the raw PCM produced by the OpenAI TTS API.
+pub const TTS_SAMPLE_RATE: u32 = 24_000;
+
+/// Windows quieter than this RMS level (about -40 dBFS) count as silence.
+const SILENCE_THRESHOLD: f32 = 0.01;
+/// The length of the windows used to detect silence and measure loudness.
+const WINDOW_MS: u32 = 10;
+/// Silence kept at each edge after trimming, so word onsets aren't clipped.
+const EDGE_PADDING_MS: u32 = 30;
+/// The loudness every clip is normalized to, as the RMS of its non-silent windows.
+const TARGET_RMS_DBFS: f32 = -20.0;
+/// Normalization never boosts quiet clips by more than this.
+const MAX_GAIN_DB: f32 = 12.0;
+/// Normalization never pushes a sample peak above this level.
+const PEAK_CEILING: f32 = 0.97;
+
+/// The longest pause a user can put between narrated sentences.
+pub const MAX_SENTENCE_GAP_MS: u32 = 2000;
+/// The longest fade a user can put on each end of a narrated sentence.
+pub const MAX_CROSSFADE_MS: u32 = 50;
+
+/// Runs the full pipeline over 16-bit little-endian mono PCM sampled at `sample_rate`
+/// and returns it as a WAV file or raw PCM, resampled to the format's sample rate.
+/// Compressed encodings can't be produced here.
+pub fn process_speech(
+    pcm: &[u8],
+    sample_rate: u32,
+    format: AudioFormat,
+) -> Result<Vec<u8>, hound::Error> {
+    let samples = pcm16_to_samples(pcm);
+    let mut samples = trim_silence(&samples, sample_rate).to_vec();
+    normalize_loudness(&mut samples, sample_rate);
+
+    let output_rate = format.sample_rate.unwrap_or(sample_rate);
+    let samples = resample(&samples, sample_rate, output_rate);
+    match format.encoding {
+        AudioEncoding::Wav => samples_to_wav(&samples, output_rate),
+        AudioEncoding::Pcm16 => Ok(samples_to_pcm16(&samples)),
+        AudioEncoding::Mp3 | AudioEncoding::Opus => Err(hound::Error::Unsupported),
+    }
+}
+
+/// Converts 16-bit little-endian PCM bytes into samples in the range [-1.0, 1.0].
+pub fn pcm16_to_samples(pcm: &[u8]) -> Vec<f32> {
+    pcm.chunks_exact(2)
+        .map(|b| i16::from_le_bytes([b[0], b[1]]) as f32 / i16::MAX as f32)
+        .collect()
+}
+
+/// Converts samples back into 16-bit little-endian PCM bytes.
+pub fn samples_to_pcm16(samples: &[f32]) -> Vec<u8> {
+    samples
+        .iter()
+        .flat_map(|sample| ((sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16).to_le_bytes())
+        .collect()
+}
+
+/// Resamples by linear interpolation, which is plenty for speech.
+pub fn resample(samples: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
+    if from_rate == to_rate || samples.is_empty() {
+        return samples.to_vec();
+    }
+    let len = (samples.len() as u64 * to_rate as u64 / from_rate as u64) as usize;
+    let step = from_rate as f64 / to_rate as f64;
+    (0..len)
+        .map(|i| {
+            let position = i as f64 * step;
+            let index = position as usize;
+            let fraction = (position - index as f64) as f32;
+            let current = samples[index.min(samples.len() - 1)];
+            let next = samples[(index + 1).min(samples.len() - 1)];
+            current + (next - current) * fraction
+        })
+        .collect()
+}
+
+/// Encodes samples as a 16-bit mono WAV file.
+pub fn samples_to_wav(samples: &[f32], sample_rate: u32) -> Result<Vec<u8>, hound::Error> {
+    let mut cursor = std::io::Cursor::new(Vec::new());
+
+    let spec = WavSpec {
+        channels: 1,
+        sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+
+    let mut writer = WavWriter::new(&mut cursor, spec)?;
+    for sample in samples {
+        writer.write_sample((sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16)?;
+    }
+    writer.finalize()?;
+
+    Ok(cursor.into_inner())
+}
+
+/// Applies narration pacing to a clip from `process_speech`: fades each end over
+/// `crossfade_ms` so joins between sentences blend instead of clicking, then appends
+/// `sentence_gap_ms` of silence. Works on the finished clip, so changing the pacing
+/// never requires resynthesizing audio. Raw PCM is read at `format`'s sample rate;
+/// compressed clips are returned unpaced.
+pub fn apply_pacing(
+    clip: &[u8],
+    format: AudioFormat,
+    sentence_gap_ms: u32,
+    crossfade_ms: u32,
+) -> Result<Vec<u8>, hound::Error> {
+    if (sentence_gap_ms == 0 && crossfade_ms == 0) || format.encoding.is_compressed() {
+        return Ok(clip.to_vec());
+    }
+
+    let (mut samples, sample_rate) = match format.encoding {
+        AudioEncoding::Pcm16 => (
+            pcm16_to_samples(clip),
+            format.sample_rate.unwrap_or(TTS_SAMPLE_RATE),
+        ),
+        _ => {
+            let mut reader = WavReader::new(std::io::Cursor::new(clip))?;
+            let sample_rate = reader.spec().sample_rate;
+            let samples = reader
+                .samples::<i16>()
+                .map(|s| s.map(|s| s as f32 / i16::MAX as f32))
+                .collect::<Result<Vec<f32>, _>>()?;
+            (samples, sample_rate)
+        }
+    };
+
+    let fade_len = ((sample_rate * crossfade_ms.min(MAX_CROSSFADE_MS) / 1000) as usize)
+        .min(samples.len() / 2);
+    let len = samples.len();
+    let fade_gain = |i: usize| i as f32 / fade_len as f32;
+    for (i, sample) in samples[..fade_len].iter_mut().enumerate() {
+        *sample *= fade_gain(i);
+    }
+    for (i, sample) in samples[len - fade_len..].iter_mut().rev().enumerate() {
+        *sample *= fade_gain(i);
+    }
+
+    let gap_len = (sample_rate * sentence_gap_ms.min(MAX_SENTENCE_GAP_MS) / 1000) as usize;
+    samples.resize(len + gap_len, 0.0);
+
+    match format.encoding {
+        AudioEncoding::Pcm16 => Ok(samples_to_pcm16(&samples)),
+        _ => samples_to_wav(&samples, sample_rate),
+    }
+}
+
+/// Returns the samples between the first and last non-silent windows, plus a
+/// little padding. A clip that is silent throughout is returned unchanged.
+pub fn trim_silence(samples: &[f32], sample_rate: u32) -> &[f32] {
+    let window = window_len(sample_rate);
+    let loud = |chunk: &[f32]| rms(chunk) >= SILENCE_THRESHOLD;
+
+    let Some(first) = samples.chunks(window).position(loud) else {
+        return samples;
+    };
+    let last = samples.chunks(window).rposition(loud).unwrap_or(first);
+
+    let padding = (sample_rate * EDGE_PADDING_MS / 1000) as usize;
+    let start = (first * window).saturating_sub(padding);
+    let end = ((last + 1) * window + padding).min(samples.len());
+
+    &samples[start..end]
+}
+
+/// Scales the clip so its speech-active RMS hits `TARGET_RMS_DBFS`, limited by
+/// `MAX_GAIN_DB` and by the headroom below `PEAK_CEILING`.
+pub fn normalize_loudness(samples: &mut [f32], sample_rate: u32) {
+    let window = window_len(sample_rate);
+    let (sum_squares, count) = samples
+        .chunks(window)
+        .filter(|chunk| rms(chunk) >= SILENCE_THRESHOLD)
+        .fold((0.0f64, 0usize), |(sum, count), chunk| {
+            let chunk_sum: f64 = chunk.iter().map(|s| (*s as f64) * (*s as f64)).sum();
+            (sum + chunk_sum, count + chunk.len())
+        });
+    if count == 0 {
+        return;
+    }
+
+    let speech_rms = (sum_squares / count as f64).sqrt() as f32;
+    let peak = samples.iter().fold(0.0f32, |max, s| max.max(s.abs()));
+
+    let mut gain = db_to_linear(TARGET_RMS_DBFS) / speech_rms;
+    gain = gain.min(db_to_linear(MAX_GAIN_DB));
+    if peak > 0.0 {
+        gain = gain.min(PEAK_CEILING / peak);
+    }
+
+    for sample in samples.iter_mut() {
+        *sample *= gain;
+    }
+}
+
+fn window_len(sample_rate: u32) -> usize {
+    ((sample_rate * WINDOW_MS / 1000) as usize).max(1)
+}
+
+fn rms(samples: &[f32]) -> f32 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    let sum: f32 = samples.iter().map(|s| s * s).sum();
+    (sum / samples.len() as f32).sqrt()
+}
+
+fn db_to_linear(db: f32) -> f32 {
+    10f32.powf(db / 20.0)
+}
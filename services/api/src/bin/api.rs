@@ -2,37 +2,93 @@
 
 use api_lib::{
     adapters::{
-        db::DbAdapter, notes_llm::OpenAiNotesAdapter, sst::OpenAiSstAdapter,
-        tts::OpenAiTtsAdapter, qa_llm::OpenAiQaAdapter,
+        db::DbAdapter, fetcher::HttpContentFetcher, google_drive::GoogleDriveAdapter, notion::NotionAdapter,
+        audio_cache::DiskAudioCache, backup_storage::DiskBackupStorage, openai::OpenAiAdapters, tts::OpenAiTtsAdapter,
+        tts_cache::CachedTtsAdapter, tts_usage::MeteredTtsAdapter, anthropic_qa::AnthropicQaAdapter, ollama::OllamaAdapter,
+        piper_tts::PiperTtsAdapter, analytics::DbAnalyticsSink, webhook_notifier::WebhookNotifier,
+        dictionary::FreeDictionaryAdapter,
     },
-    config::Config,
+    config::{Config, NoteProvider, QaProvider, TtsProvider},
+    crypto::SecretCipher,
     error::ApiError,
     web::{
         auth::{signup_handler, login_handler, logout_handler},
-        create_session_handler, rest::ApiDoc, state::AppState, ws_handler,
-        middleware::require_auth, list_sessions_handler,list_notes_handler
+        guest::{create_guest_handler, guest_cleanup_process, reject_guests},
+        highlights::import_highlights_handler,
+        create_session_handler, delete_document_handler, delete_session_handler, get_document_handler,
+        rest::ApiDoc, state::AppState, update_document_handler, update_session_handler, ws_handler,
+        middleware::{require_admin, require_auth, resolve_tenant, TENANT_HEADER}, list_sessions_handler,list_notes_handler,
+        list_documents_handler, list_qa_pairs_handler,
+        admin::{
+            integrity_report_handler, latency_report_handler, llm_usage_report_handler,
+            repair_integrity_handler, tts_usage_report_handler,
+        },
+        analytics::usage_report_handler,
+        announcements::warm_welcome_audio,
+        backups::{backup_process, create_backup_handler, download_backup_handler, list_backups_handler},
+        calendar::{
+            calendar_feed_handler, calendar_feed_url_handler, create_plan_handler, delete_plan_handler,
+            list_plans_handler,
+        },
+        compression::compression_layer,
+        gallery::{add_gallery_document_handler, create_gallery_session_handler, list_gallery_handler},
+        glossary::list_glossary_handler,
+        sharing::{
+            create_shared_session_handler, list_document_notes_handler, list_document_shares_handler,
+            list_shared_documents_handler, share_document_handler, unshare_document_handler,
+        },
+        api_keys::{get_openai_key_handler, save_openai_key_handler, delete_openai_key_handler},
+        ask::ask_question_handler,
+        goals::{delete_goal_handler, get_goal_handler, goal_reminder_process, update_goal_handler},
+        integrations::{
+            notion_authorize_handler, notion_callback_handler, export_notion_handler,
+            google_authorize_handler, google_callback_handler, list_google_documents_handler,
+            import_google_document_handler,
+        },
+        preferences::{
+            get_my_preferences_handler, get_preferences_handler, patch_preferences_handler,
+            update_preferences_handler,
+        },
+        voices::{get_voice_preview_handler, list_voices_handler, preview_voice_handler},
+        workspaces::{
+            create_workspace_handler, list_workspaces_handler, join_workspace_handler,
+            upload_workspace_document_handler, list_workspace_documents_handler,
+            create_workspace_session_handler, document_activity_handler,
+        },
+        queue::{add_feed_handler, list_feeds_handler, save_link_handler, list_queue_handler},
+        note_task::resume_pending_notes,
+        queue_task::queue_ingest_process,
+        ratings::rate_session_handler,
+        recap_task::weekly_recap_process,
+        tags::{
+            list_tags_handler, get_document_tags_handler, add_document_tags_handler,
+            remove_document_tag_handler,
+        },
     },
 };
-use async_openai::{
-    config::OpenAIConfig,
-    types::{SpeechModel, Voice},
-    Client,
-};
+use async_openai::{config::OpenAIConfig, Client};
 use axum::{
     extract::DefaultBodyLimit,
-    routing::{get, post},
+    routing::{delete, get, patch, post, put},
     Router,
     middleware as axum_middleware,
 };
+use reading_assistant_core::ports::{
+    AudioCacheService, BackupStorageService, DocumentImportService, NoteExportService, NoteGenerationService,
+    NotificationService, QuestionAnsweringService, TextToSpeechService,
+};
 use sqlx::postgres::PgPoolOptions;
-use std::sync::Arc;
+use std::{net::SocketAddr, sync::Arc};
 use tracing::info;
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
 // ✅ Add these imports
-use tower_http::cors::CorsLayer;
-use axum::http::{Method, HeaderValue, header::{AUTHORIZATION, CONTENT_TYPE, ACCEPT}};
+use tower_http::{
+    cors::CorsLayer,
+    services::{ServeDir, ServeFile},
+};
+use axum::http::{Method, HeaderName, HeaderValue, header::{AUTHORIZATION, CONTENT_TYPE, ACCEPT, RANGE}};
 
 #[tokio::main]
 async fn main() -> Result<(), ApiError> {
@@ -50,7 +106,15 @@ async fn main() -> Result<(), ApiError> {
         .max_connections(5)
         .connect(&config.database_url)
         .await?;
-    let db_adapter = Arc::new(DbAdapter::new(db_pool.clone()));
+    let mut db_adapter = DbAdapter::new(db_pool.clone());
+    if let Some(key) = &config.document_encryption_key {
+        let cipher = SecretCipher::from_base64_key(key).map_err(|e| {
+            ApiError::Internal(format!("Invalid DOCUMENT_ENCRYPTION_KEY: {}", e))
+        })?;
+        info!("Document encryption at rest enabled.");
+        db_adapter = db_adapter.with_document_cipher(Arc::new(cipher));
+    }
+    let db_adapter = Arc::new(db_adapter);
     info!("Running database migrations...");
     db_adapter.run_migrations().await?;
     info!("Database migrations complete.");
@@ -64,68 +128,284 @@ async fn main() -> Result<(), ApiError> {
     );
     let openai_client = Client::with_config(openai_config);
 
-    let sst_adapter = Arc::new(OpenAiSstAdapter::new(
-        openai_client.clone(),
-        config.sst_model.clone(),
-    ));
-
-    let tts_voice = match config.tts_voice.to_lowercase().as_str() {
-        "alloy" => Voice::Alloy,
-        "echo" => Voice::Echo,
-        "fable" => Voice::Fable,
-        "onyx" => Voice::Onyx,
-        "nova" => Voice::Nova,
-        "shimmer" => Voice::Shimmer,
-        _ => {
-            return Err(ApiError::Internal(format!(
-                "Invalid TTS voice specified in config: '{}'",
-                config.tts_voice
+    let tts_voice = OpenAiTtsAdapter::parse_voice(&config.tts_voice).ok_or_else(|| {
+        ApiError::Internal(format!(
+            "Invalid TTS voice specified in config: '{}'",
+            config.tts_voice
+        ))
+    })?;
+    let openai = OpenAiAdapters::new(openai_client, &config, tts_voice);
+    // Every OpenAI synthesis is metered so TTS invoices can be reconciled against usage;
+    // Piper runs locally and has no invoice. Cache hits are never sent to the provider,
+    // so the cache wraps the meter.
+    let provider_tts: Arc<dyn TextToSpeechService> = match config.tts_provider {
+        TtsProvider::OpenAi => Arc::new(MeteredTtsAdapter::new(openai.tts, db_adapter.clone())),
+        TtsProvider::Piper => {
+            // The model path's presence is checked when the config is loaded.
+            let model_path = config.piper_model_path.clone().unwrap_or_default();
+            info!("Narrating with Piper model {}.", model_path.display());
+            Arc::new(PiperTtsAdapter::new(
+                config.piper_binary.clone(),
+                model_path,
+                config.piper_sample_rate,
+            ))
+        }
+    };
+    let audio_cache: Option<Arc<dyn AudioCacheService>> = match &config.audio_cache_dir {
+        Some(dir) => {
+            info!("TTS audio cache enabled in {}.", dir.display());
+            Some(Arc::new(DiskAudioCache::new(dir.clone()).await.map_err(|e| {
+                ApiError::Internal(format!("Invalid AUDIO_CACHE_DIR: {:?}", e))
+            })?))
+        }
+        None => None,
+    };
+    let tts_adapter: Arc<dyn TextToSpeechService> = match &audio_cache {
+        Some(cache) => Arc::new(CachedTtsAdapter::new(provider_tts, cache.clone())),
+        None => provider_tts,
+    };
+    let content_fetcher = Arc::new(HttpContentFetcher::new());
+    let dictionary_adapter = Arc::new(FreeDictionaryAdapter::new(&config.dictionary_api_url));
+
+    let qa_adapter: Arc<dyn QuestionAnsweringService> = match config.qa_provider {
+        QaProvider::OpenAi => openai.qa,
+        QaProvider::Anthropic => {
+            info!("Answering questions with Anthropic model '{}'.", config.qa_model);
+            // The key's presence is checked when the config is loaded.
+            let api_key = config.anthropic_api_key.clone().unwrap_or_default();
+            Arc::new(AnthropicQaAdapter::new(api_key, config.qa_model.clone()))
+        }
+        QaProvider::Ollama => {
+            info!(
+                "Answering questions with Ollama model '{}' at {}.",
+                config.qa_model, config.ollama_base_url
+            );
+            Arc::new(OllamaAdapter::new(config.ollama_base_url.clone(), config.qa_model.clone()))
+        }
+    };
+    let notes_adapter: Arc<dyn NoteGenerationService> = match config.note_provider {
+        NoteProvider::OpenAi => openai.notes,
+        NoteProvider::Ollama => {
+            info!(
+                "Taking notes with Ollama model '{}' at {}.",
+                config.note_model, config.ollama_base_url
+            );
+            Arc::new(OllamaAdapter::new(config.ollama_base_url.clone(), config.note_model.clone()))
+        }
+    };
+
+    // Users can only store their own API keys when a secrets key is configured.
+    let secret_cipher = match &config.secrets_encryption_key {
+        Some(key) => {
+            info!("Secrets encryption enabled.");
+            Some(Arc::new(SecretCipher::from_base64_key(key).map_err(|e| {
+                ApiError::Internal(format!("Invalid SECRETS_ENCRYPTION_KEY: {}", e))
+            })?))
+        }
+        None => None,
+    };
+
+    // Optional integrations are only enabled when fully configured.
+    let notion_adapter: Option<Arc<dyn NoteExportService>> = match (
+        &config.notion_client_id,
+        &config.notion_client_secret,
+        &config.notion_redirect_uri,
+    ) {
+        (Some(id), Some(secret), Some(redirect)) => {
+            info!("Notion integration enabled.");
+            Some(Arc::new(NotionAdapter::new(
+                id.clone(),
+                secret.clone(),
+                redirect.clone(),
             )))
         }
+        _ => None,
+    };
+    let google_drive_adapter: Option<Arc<dyn DocumentImportService>> = match (
+        &config.google_client_id,
+        &config.google_client_secret,
+        &config.google_redirect_uri,
+    ) {
+        (Some(id), Some(secret), Some(redirect)) => {
+            info!("Google Drive integration enabled.");
+            Some(Arc::new(GoogleDriveAdapter::new(
+                id.clone(),
+                secret.clone(),
+                redirect.clone(),
+            )))
+        }
+        _ => None,
+    };
+
+    let backup_storage: Option<Arc<dyn BackupStorageService>> = match &config.backup_dir {
+        Some(dir) => {
+            info!("Backups enabled in {}.", dir.display());
+            Some(Arc::new(DiskBackupStorage::new(dir.clone()).await.map_err(|e| {
+                ApiError::Internal(format!("Invalid BACKUP_DIR: {:?}", e))
+            })?))
+        }
+        None => None,
+    };
+
+    let notifier: Option<Arc<dyn NotificationService>> = match &config.notification_webhook_url {
+        Some(url) => {
+            info!("Goal reminders enabled.");
+            Some(Arc::new(WebhookNotifier::new(url.clone())))
+        }
+        None => None,
     };
-    let tts_adapter = Arc::new(OpenAiTtsAdapter::new(
-        openai_client.clone(),
-        SpeechModel::Tts1Hd,
-        tts_voice,
-    ));
-
-    let qa_adapter = Arc::new(OpenAiQaAdapter::new(
-        openai_client.clone(),
-        config.qa_model.clone(),
-    ));
-    let notes_adapter = Arc::new(OpenAiNotesAdapter::new(
-        openai_client.clone(),
-        config.note_model.clone(),
-    ));
 
     // --- 4. Build the Shared AppState ---
     let app_state = Arc::new(AppState {
+        analytics: Arc::new(DbAnalyticsSink::new(db_adapter.clone())),
         db: db_adapter,
         config: config.clone(),
-        sst_adapter,
+        sst_adapter: openai.sst,
         tts_adapter,
         qa_adapter,
         notes_adapter,
+        cleanup_adapter: openai.cleanup,
+        content_fetcher,
+        embedding_adapter: openai.embedding,
+        moderation_adapter: openai.moderation,
+        dictionary_adapter,
+        notion_adapter,
+        google_drive_adapter,
+        secret_cipher,
+        audio_cache,
+        backup_storage: backup_storage.clone(),
+        handoffs: Arc::default(),
+        announcements: Arc::default(),
+        sentence_cache: Arc::default(),
     });
 
+    // --- 5. Start Background Workers ---
+    tokio::spawn(warm_welcome_audio(app_state.clone()));
+    tokio::spawn(resume_pending_notes(app_state.clone()));
+    tokio::spawn(queue_ingest_process(app_state.clone()));
+    tokio::spawn(weekly_recap_process(app_state.clone()));
+    if config.guest_mode_enabled {
+        tokio::spawn(guest_cleanup_process(app_state.clone()));
+    }
+    if let Some(storage) = backup_storage {
+        tokio::spawn(backup_process(app_state.clone(), storage));
+    }
+    if let Some(notifier) = notifier {
+        tokio::spawn(goal_reminder_process(app_state.clone(), notifier));
+    }
+
     let cors = CorsLayer::new()
     .allow_origin("http://localhost:3002".parse::<HeaderValue>().unwrap())
     .allow_credentials(true)
-    .allow_methods([Method::GET, Method::POST, Method::PUT, Method::DELETE, Method::OPTIONS])
-    .allow_headers([AUTHORIZATION, CONTENT_TYPE, ACCEPT]);
+    .allow_methods([Method::GET, Method::POST, Method::PUT, Method::PATCH, Method::DELETE, Method::OPTIONS])
+    .allow_headers([
+        AUTHORIZATION,
+        CONTENT_TYPE,
+        ACCEPT,
+        RANGE,
+        HeaderName::from_static(TENANT_HEADER),
+    ]);
     // --- 6. Create the Web Router ---
   // Public routes (no auth required)
     let public_routes = Router::new()
         .route("/auth/signup", post(signup_handler))
         .route("/auth/login", post(login_handler))
-        .route("/auth/logout", post(logout_handler));
+        .route("/auth/logout", post(logout_handler))
+        .route("/auth/guest", post(create_guest_handler))
+        .route("/calendar/{user_id}/feed.ics", get(calendar_feed_handler));
+
+    // Admin routes (auth + admin allowlist required)
+    let admin_routes = Router::new()
+        .route("/admin/tts-usage", get(tts_usage_report_handler))
+        .route("/admin/llm-usage", get(llm_usage_report_handler))
+        .route("/admin/latency", get(latency_report_handler))
+        .route("/admin/integrity", get(integrity_report_handler))
+        .route("/admin/integrity/repair", post(repair_integrity_handler))
+        .route("/admin/usage", get(usage_report_handler))
+        .route("/admin/backups", post(create_backup_handler))
+        .route("/admin/backups", get(list_backups_handler))
+        .route("/admin/backups/{name}", get(download_backup_handler))
+        .route("/admin/gallery", post(add_gallery_document_handler))
+        .route_layer(axum_middleware::from_fn_with_state(
+            app_state.clone(),
+            require_admin,
+        ));
+
+    // Routes that bring in documents or share them (auth required, no guests)
+    let member_routes = Router::new()
+        .route("/integrations/google/import", post(import_google_document_handler))
+        .route("/imports/highlights", post(import_highlights_handler))
+        .route("/queue/feeds", post(add_feed_handler))
+        .route("/queue/links", post(save_link_handler))
+        .route("/workspaces", post(create_workspace_handler))
+        .route("/workspaces/join", post(join_workspace_handler))
+        .route("/workspaces/{workspace_id}/documents", post(upload_workspace_document_handler))
+        .route("/workspaces/{workspace_id}/documents/{document_id}/sessions", post(create_workspace_session_handler))
+        .route("/documents/{document_id}/share", post(share_document_handler))
+        .route("/documents/{document_id}/shares/{user_id}", delete(unshare_document_handler))
+        .route("/api-keys/openai", put(save_openai_key_handler))
+        .route_layer(axum_middleware::from_fn_with_state(
+            app_state.clone(),
+            reject_guests,
+        ));
 
     // Protected routes (auth required)
     let protected_routes = Router::new()
         .route("/sessions", post(create_session_handler))
         .route("/sessions", get(list_sessions_handler))
+        .route("/sessions/{session_id}", delete(delete_session_handler))
+        .route("/sessions/{session_id}", patch(update_session_handler))
         .route("/sessions/{session_id}/notes", get(list_notes_handler))  
+        .route("/sessions/{session_id}/qa-pairs", get(list_qa_pairs_handler))
+        // The conversation transcript, under the path the frontend asks for it by.
+        .route("/sessions/{session_id}/qa", get(list_qa_pairs_handler))
+        .route("/sessions/{session_id}/glossary", get(list_glossary_handler))
+        .route("/sessions/{session_id}/rating", post(rate_session_handler))
+        .route("/sessions/{session_id}/plans", post(create_plan_handler))
+        .route("/documents", get(list_documents_handler))
+        .route("/documents/{document_id}", get(get_document_handler))
+        .route("/documents/{document_id}", delete(delete_document_handler))
+        .route("/documents/{document_id}", patch(update_document_handler))
+        .route("/gallery", get(list_gallery_handler))
+        .route("/gallery/{document_id}/sessions", post(create_gallery_session_handler))
+        .route("/sessions/{session_id}/ask", post(ask_question_handler))
+        .route("/sessions/{session_id}/export/notion", post(export_notion_handler))
+        .route("/integrations/notion/authorize", get(notion_authorize_handler))
+        .route("/integrations/notion/callback", get(notion_callback_handler))
+        .route("/integrations/google/authorize", get(google_authorize_handler))
+        .route("/integrations/google/callback", get(google_callback_handler))
+        .route("/integrations/google/documents", get(list_google_documents_handler))
+        .route("/queue", get(list_queue_handler))
+        .route("/queue/feeds", get(list_feeds_handler))
+        .route("/tags", get(list_tags_handler))
+        .route("/documents/{document_id}/tags", get(get_document_tags_handler))
+        .route("/documents/{document_id}/tags", post(add_document_tags_handler))
+        .route("/documents/{document_id}/tags/{tag}", delete(remove_document_tag_handler))
+        .route("/documents/shared", get(list_shared_documents_handler))
+        .route("/documents/{document_id}/shares", get(list_document_shares_handler))
+        .route("/documents/{document_id}/sessions", post(create_shared_session_handler))
+        .route("/documents/{document_id}/notes", get(list_document_notes_handler))
+        .route("/preferences", get(get_preferences_handler))
+        .route("/preferences", put(update_preferences_handler))
+        .route("/me/preferences", get(get_my_preferences_handler))
+        .route("/me/preferences", patch(patch_preferences_handler))
+        .route("/me/goal", get(get_goal_handler))
+        .route("/me/goal", put(update_goal_handler))
+        .route("/me/goal", delete(delete_goal_handler))
+        .route("/me/plans", get(list_plans_handler))
+        .route("/me/plans/{plan_id}", delete(delete_plan_handler))
+        .route("/me/calendar", get(calendar_feed_url_handler))
+        .route("/api-keys/openai", get(get_openai_key_handler))
+        .route("/api-keys/openai", delete(delete_openai_key_handler))
+        .route("/voices", get(list_voices_handler))
+        .route("/voices/preview", post(preview_voice_handler))
+        .route("/voices/preview", get(get_voice_preview_handler))
+        .route("/workspaces", get(list_workspaces_handler))
+        .route("/workspaces/{workspace_id}/documents", get(list_workspace_documents_handler))
+        .route("/workspaces/{workspace_id}/documents/{document_id}/activity", get(document_activity_handler))
         .route("/ws", get(ws_handler))
+        .merge(admin_routes)
+        .merge(member_routes)
         .layer(axum_middleware::from_fn_with_state(
             app_state.clone(),
             require_auth,
@@ -135,7 +415,12 @@ async fn main() -> Result<(), ApiError> {
 let api_router = Router::new()
     .merge(public_routes)
     .merge(protected_routes)
+    .layer(axum_middleware::from_fn_with_state(
+        app_state.clone(),
+        resolve_tenant,
+    ))
     .layer(DefaultBodyLimit::max(10 * 1024 * 1024))
+    .layer(compression_layer(&config))
     .layer(cors)
     .with_state(app_state);
 
@@ -144,6 +429,19 @@ let api_router = Router::new()
         .merge(api_router)
         .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()));
 
+    // Serve the built frontend for everything else. Unknown paths get `index.html`
+    // so the client-side router can handle them.
+    let app = match &config.frontend_dir {
+        Some(dir) => {
+            info!("Serving the frontend from {}.", dir.display());
+            let frontend = Router::new()
+                .fallback_service(ServeDir::new(dir).fallback(ServeFile::new(dir.join("index.html"))))
+                .layer(compression_layer(&config));
+            app.merge(frontend)
+        }
+        None => app,
+    };
+
     // --- 7. Start the Server ---
     info!("Starting server on {}", config.bind_address);
     info!(
@@ -151,7 +449,8 @@ let api_router = Router::new()
         config.bind_address
     );
     let listener = tokio::net::TcpListener::bind(&config.bind_address).await?;
-    axum::serve(listener, app).await?;
+    // Guest rate limiting keys on the client address.
+    axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>()).await?;
 
     Ok(())
 }
\ No newline at end of file
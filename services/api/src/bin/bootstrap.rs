@@ -0,0 +1,129 @@
+//! services/api/src/bin/bootstrap.rs
+//!
+//! First-run setup in one command: creates the database if it doesn't exist, runs
+//! the migrations, creates an initial admin user and optionally loads a sample
+//! document for them. Every step is skipped when already done, so it is safe to
+//! run before each start of the API.
+//!
+//! Reads the same environment as the API, plus:
+//! - `BOOTSTRAP_ADMIN_EMAIL` / `BOOTSTRAP_ADMIN_PASSWORD`: the admin to create.
+//! - `BOOTSTRAP_SAMPLE_DOCUMENT`: a text file to load for the admin (optional).
+
+use api_lib::{adapters::db::DbAdapter, config::Config, crypto::SecretCipher, error::ApiError};
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHasher, SaltString},
+    Argon2,
+};
+use reading_assistant_core::{
+    domain::DEFAULT_TENANT_ID,
+    ports::{DatabaseService, PortError},
+};
+use sqlx::{migrate::MigrateDatabase, postgres::PgPoolOptions, Postgres};
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+#[tokio::main]
+async fn main() -> Result<(), ApiError> {
+    let config = Config::from_env()?;
+
+    // --- 1. Create the Database ---
+    if !Postgres::database_exists(&config.database_url).await? {
+        println!("Creating database...");
+        Postgres::create_database(&config.database_url).await?;
+    }
+
+    // --- 2. Run Migrations ---
+    let db_pool = PgPoolOptions::new()
+        .max_connections(1)
+        .connect(&config.database_url)
+        .await?;
+    let mut db = DbAdapter::new(db_pool);
+    if let Some(key) = &config.document_encryption_key {
+        let cipher = SecretCipher::from_base64_key(key).map_err(|e| {
+            ApiError::Internal(format!("Invalid DOCUMENT_ENCRYPTION_KEY: {}", e))
+        })?;
+        db = db.with_document_cipher(std::sync::Arc::new(cipher));
+    }
+    println!("Running database migrations...");
+    db.run_migrations().await?;
+
+    // --- 3. Create the Admin User ---
+    let admin_email = std::env::var("BOOTSTRAP_ADMIN_EMAIL").ok();
+    let admin_password = std::env::var("BOOTSTRAP_ADMIN_PASSWORD").ok();
+    let admin_id = match (admin_email, admin_password) {
+        (Some(email), Some(password)) => Some(ensure_admin(&db, &config, &email, &password).await?),
+        (None, None) => {
+            println!("No BOOTSTRAP_ADMIN_EMAIL set; skipping the admin user.");
+            None
+        }
+        _ => {
+            return Err(ApiError::Internal(
+                "BOOTSTRAP_ADMIN_EMAIL and BOOTSTRAP_ADMIN_PASSWORD must be set together".to_string(),
+            ))
+        }
+    };
+
+    // --- 4. Load the Sample Document ---
+    if let Some(path) = std::env::var("BOOTSTRAP_SAMPLE_DOCUMENT").ok().map(PathBuf::from) {
+        let admin_id = admin_id.ok_or_else(|| {
+            ApiError::Internal("BOOTSTRAP_SAMPLE_DOCUMENT needs an admin user to own it".to_string())
+        })?;
+        load_sample_document(&db, admin_id, &path).await?;
+    }
+
+    println!("✅ Bootstrap complete.");
+    Ok(())
+}
+
+/// Creates the admin user unless a user with that email exists, and returns their ID.
+async fn ensure_admin(
+    db: &DbAdapter,
+    config: &Config,
+    email: &str,
+    password: &str,
+) -> Result<Uuid, ApiError> {
+    let user_id = match db.get_user_by_email(DEFAULT_TENANT_ID, email).await {
+        Ok(existing) => {
+            println!("Admin user {} already exists.", email);
+            existing.user_id
+        }
+        Err(PortError::NotFound(_)) => {
+            let salt = SaltString::generate(&mut OsRng);
+            let password_hash = Argon2::default()
+                .hash_password(password.as_bytes(), &salt)
+                .map_err(|e| ApiError::Internal(format!("Failed to hash password: {}", e)))?
+                .to_string();
+            let user = db
+                .create_user_with_email(DEFAULT_TENANT_ID, email, &password_hash)
+                .await?;
+            println!("Created admin user {}.", email);
+            user.user_id
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    // Admin rights come from the allowlist the API reads at startup.
+    if !config.admin_user_ids.contains(&user_id) {
+        println!("Add {} to ADMIN_USER_IDS to give this user admin rights.", user_id);
+    }
+    Ok(user_id)
+}
+
+/// Loads a text file as a document with a session for `user_id`, unless they
+/// already have documents.
+async fn load_sample_document(db: &DbAdapter, user_id: Uuid, path: &Path) -> Result<(), ApiError> {
+    if !db.get_document_summaries_by_user(user_id, 0, Some(1), 0).await?.is_empty() {
+        println!("The admin user already has documents; skipping the sample document.");
+        return Ok(());
+    }
+
+    let text = tokio::fs::read_to_string(path).await?;
+    let title = path
+        .file_stem()
+        .and_then(|stem| stem.to_str())
+        .unwrap_or("Sample document");
+    let document = db.create_document(user_id, title, &text).await?;
+    db.create_session(user_id, document.id, false).await?;
+    println!("Loaded the sample document from {}.", path.display());
+    Ok(())
+}
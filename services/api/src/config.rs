@@ -8,6 +8,7 @@
 use std::net::SocketAddr;
 use std::path::PathBuf;
 use tracing::Level;
+use uuid::Uuid;
 
 /// A custom error type for configuration loading failures.
 #[derive(Debug, thiserror::Error)]
@@ -18,6 +19,37 @@ pub enum ConfigError {
     InvalidValue(String, String),
 }
 
+/// The vendor whose models answer questions, summarize and quiz.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum QaProvider {
+    OpenAi,
+    Anthropic,
+    /// A local model served by Ollama at `ollama_base_url`.
+    Ollama,
+}
+
+/// The vendor whose models take notes, write recaps and suggest tags.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NoteProvider {
+    OpenAi,
+    /// A local model served by Ollama at `ollama_base_url`.
+    Ollama,
+}
+
+/// The engine that narrates documents and speaks answers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TtsProvider {
+    OpenAi,
+    /// A local Piper binary running the voice model at `piper_model_path`.
+    Piper,
+}
+
+/// The model Ollama deployments use unless `QA_MODEL`/`NOTE_MODEL` say otherwise.
+const DEFAULT_OLLAMA_MODEL: &str = "llama3.1";
+
+/// The greeting spoken as a session opens unless `WELCOME_MESSAGE` says otherwise.
+const DEFAULT_WELCOME_MESSAGE: &str = "Hi there! I am looking forward to discussing the information you have provided today! If at any point you have a question, please feel free to interrupt me, or if you need to pause our session, just click pause! I will now begin reading the information!";
+
 /// Holds all configuration loaded from the environment at startup.
 #[derive(Clone, Debug)]
 pub struct Config {
@@ -27,10 +59,95 @@ pub struct Config {
     pub prompts_path: PathBuf,
     pub openai_api_key: Option<String>,
     pub gemini_api_key: Option<String>,
+    /// Required when `qa_provider` is `Anthropic`.
+    pub anthropic_api_key: Option<String>,
+    pub qa_provider: QaProvider,
+    pub note_provider: NoteProvider,
+    /// The Ollama server used when either provider is `Ollama`.
+    pub ollama_base_url: String,
     pub sst_model: String,
+    pub tts_provider: TtsProvider,
     pub tts_voice: String,
+    /// Spoken to the listener when a session opens.
+    pub welcome_message: String,
+    /// The Piper executable, found on `PATH` unless a path is given.
+    pub piper_binary: PathBuf,
+    /// The Piper voice model (`.onnx`). Required when `tts_provider` is `Piper`.
+    pub piper_model_path: Option<PathBuf>,
+    /// The sample rate the Piper model outputs, from its `.onnx.json` config.
+    pub piper_sample_rate: u32,
     pub qa_model: String,
+    /// Check each answer against its context with a second QA model call, and hedge
+    /// aloud when it isn't supported. Costs an extra call per question.
+    pub verify_answer_grounding: bool,
     pub note_model: String,
+    pub cleanup_model: String,
+    pub embedding_model: String,
+    /// The Free Dictionary API entries endpoint spoken word definitions come from.
+    pub dictionary_api_url: String,
+    pub notion_client_id: Option<String>,
+    pub notion_client_secret: Option<String>,
+    pub notion_redirect_uri: Option<String>,
+    pub google_client_id: Option<String>,
+    pub google_client_secret: Option<String>,
+    pub google_redirect_uri: Option<String>,
+    pub queue_poll_interval_secs: u64,
+    pub feed_refresh_interval_secs: u64,
+    /// The most question audio, in bytes, buffered for one question. Audio beyond it
+    /// is dropped and the question has to be asked again.
+    pub max_question_audio_bytes: usize,
+    /// How much new question audio, in bytes, triggers another partial transcript while
+    /// the user is speaking. 0 turns partial transcripts off.
+    pub partial_transcript_step_bytes: usize,
+    /// How often, in seconds, an open session's in-memory state is saved so a crash loses
+    /// at most this much of it. 0 only saves it when the connection closes.
+    pub session_autosave_interval_secs: u64,
+    /// The longest document, in characters, that can be uploaded or imported.
+    pub max_document_chars: usize,
+    /// How many sentences of a document an open session holds in memory. Longer
+    /// documents are loaded a window at a time as reading moves through them.
+    pub session_sentence_window: usize,
+    /// Lowercase phrases that trigger a hands-free interrupt.
+    pub hotword_phrases: Vec<String>,
+    /// How long a session must sit untouched before reopening it starts with a recap.
+    pub resume_recap_min_gap_secs: u64,
+    /// Base64-encoded 256-bit key for encrypting user secrets (e.g. their own OpenAI
+    /// API keys) at rest. Users can only store secrets when this is set.
+    pub secrets_encryption_key: Option<String>,
+    /// Base64-encoded 256-bit key for encrypting document text at rest, e.g. a data key
+    /// issued by the deployment's KMS. Documents are stored in plaintext when unset.
+    pub document_encryption_key: Option<String>,
+    /// Serve multiple isolated tenants, resolved per request from the `X-Tenant`
+    /// header or the hostname. When off, everything belongs to the default tenant.
+    pub multi_tenant: bool,
+    /// Users allowed to call the `/admin` endpoints.
+    pub admin_user_ids: Vec<Uuid>,
+    /// Directory for cached TTS audio, shared by all sessions. Caching is off when unset.
+    pub audio_cache_dir: Option<PathBuf>,
+    /// Allow anonymous trial sessions via `POST /auth/guest`.
+    pub guest_mode_enabled: bool,
+    /// How long a guest account lives before it and its data are deleted.
+    pub guest_session_minutes: i64,
+    /// The longest document, in characters, a guest may read.
+    pub guest_max_document_chars: usize,
+    /// How many guest accounts one IP address may create per hour.
+    pub guest_max_per_ip_per_hour: usize,
+    /// Content-type prefixes sent uncompressed, e.g. audio that is already compressed.
+    pub compression_excluded_content_types: Vec<String>,
+    /// Directory backups are written to. Backups are off when unset.
+    pub backup_dir: Option<PathBuf>,
+    /// How often a backup is written.
+    pub backup_interval_secs: u64,
+    /// Webhook notifications (e.g. goal reminders) are posted to. Reminders are off
+    /// when unset.
+    pub notification_webhook_url: Option<String>,
+    /// The hour (UTC) from which reading goal reminders go out each day.
+    pub goal_reminder_hour_utc: u32,
+    /// Key calendar feed URLs are signed with. Calendar feeds are off when unset.
+    pub calendar_feed_secret: Option<String>,
+    /// The built web frontend, served for every path the API doesn't handle so a
+    /// small deployment is a single process. Not served when unset.
+    pub frontend_dir: Option<PathBuf>,
 }
 
 impl Config {
@@ -69,14 +186,266 @@ impl Config {
         // --- Load API Keys (as optional) ---
         let openai_api_key = std::env::var("OPENAI_API_KEY").ok();
         let gemini_api_key = std::env::var("GEMINI_API_KEY").ok();
+        let anthropic_api_key = std::env::var("ANTHROPIC_API_KEY").ok();
+
+        // --- Load the QA Provider ---
+        let qa_provider = match std::env::var("QA_PROVIDER")
+            .unwrap_or_else(|_| "openai".to_string())
+            .to_lowercase()
+            .as_str()
+        {
+            "openai" => QaProvider::OpenAi,
+            "anthropic" => QaProvider::Anthropic,
+            "ollama" => QaProvider::Ollama,
+            other => {
+                return Err(ConfigError::InvalidValue(
+                    "QA_PROVIDER".to_string(),
+                    format!("'{}' is not one of 'openai', 'anthropic' or 'ollama'", other),
+                ))
+            }
+        };
+        if qa_provider == QaProvider::Anthropic && anthropic_api_key.is_none() {
+            return Err(ConfigError::MissingVar("ANTHROPIC_API_KEY".to_string()));
+        }
+
+        // --- Load the Note Provider ---
+        let note_provider = match std::env::var("NOTE_PROVIDER")
+            .unwrap_or_else(|_| "openai".to_string())
+            .to_lowercase()
+            .as_str()
+        {
+            "openai" => NoteProvider::OpenAi,
+            "ollama" => NoteProvider::Ollama,
+            other => {
+                return Err(ConfigError::InvalidValue(
+                    "NOTE_PROVIDER".to_string(),
+                    format!("'{}' is not one of 'openai' or 'ollama'", other),
+                ))
+            }
+        };
+        let ollama_base_url = std::env::var("OLLAMA_BASE_URL")
+            .unwrap_or_else(|_| "http://localhost:11434".to_string());
 
         // --- Load Adapter-specific Settings ---
         let sst_model =
             std::env::var("SST_MODEL").unwrap_or_else(|_| "whisper-1".to_string());
         let tts_voice = std::env::var("TTS_VOICE").unwrap_or_else(|_| "alloy".to_string());
-        let qa_model = std::env::var("QA_MODEL").unwrap_or_else(|_| "gpt-4o".to_string());
-        let note_model =
-            std::env::var("NOTE_MODEL").unwrap_or_else(|_| "gpt-4o-mini".to_string());
+        let welcome_message = std::env::var("WELCOME_MESSAGE")
+            .ok()
+            .map(|message| message.trim().to_string())
+            .filter(|message| !message.is_empty())
+            .unwrap_or_else(|| DEFAULT_WELCOME_MESSAGE.to_string());
+
+        // --- Load the TTS Provider ---
+        let tts_provider = match std::env::var("TTS_PROVIDER")
+            .unwrap_or_else(|_| "openai".to_string())
+            .to_lowercase()
+            .as_str()
+        {
+            "openai" => TtsProvider::OpenAi,
+            "piper" => TtsProvider::Piper,
+            other => {
+                return Err(ConfigError::InvalidValue(
+                    "TTS_PROVIDER".to_string(),
+                    format!("'{}' is not one of 'openai' or 'piper'", other),
+                ))
+            }
+        };
+        let piper_binary = std::env::var("PIPER_BINARY")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from("piper"));
+        let piper_model_path = std::env::var("PIPER_MODEL_PATH").ok().map(PathBuf::from);
+        if tts_provider == TtsProvider::Piper && piper_model_path.is_none() {
+            return Err(ConfigError::MissingVar("PIPER_MODEL_PATH".to_string()));
+        }
+        let piper_sample_rate = std::env::var("PIPER_SAMPLE_RATE")
+            .unwrap_or_else(|_| "22050".to_string())
+            .parse::<u32>()
+            .map_err(|e| ConfigError::InvalidValue("PIPER_SAMPLE_RATE".to_string(), e.to_string()))?;
+        let qa_model = std::env::var("QA_MODEL").unwrap_or_else(|_| match qa_provider {
+            QaProvider::OpenAi => "gpt-4o".to_string(),
+            QaProvider::Anthropic => "claude-sonnet-4-5".to_string(),
+            QaProvider::Ollama => DEFAULT_OLLAMA_MODEL.to_string(),
+        });
+        let note_model = std::env::var("NOTE_MODEL").unwrap_or_else(|_| match note_provider {
+            NoteProvider::OpenAi => "gpt-4o-mini".to_string(),
+            NoteProvider::Ollama => DEFAULT_OLLAMA_MODEL.to_string(),
+        });
+        let cleanup_model =
+            std::env::var("CLEANUP_MODEL").unwrap_or_else(|_| "gpt-4o-mini".to_string());
+        let embedding_model = std::env::var("EMBEDDING_MODEL")
+            .unwrap_or_else(|_| "text-embedding-3-small".to_string());
+
+        // --- Load Integration Settings (as optional) ---
+        let notion_client_id = std::env::var("NOTION_CLIENT_ID").ok();
+        let notion_client_secret = std::env::var("NOTION_CLIENT_SECRET").ok();
+        let notion_redirect_uri = std::env::var("NOTION_REDIRECT_URI").ok();
+        let google_client_id = std::env::var("GOOGLE_CLIENT_ID").ok();
+        let google_client_secret = std::env::var("GOOGLE_CLIENT_SECRET").ok();
+        let google_redirect_uri = std::env::var("GOOGLE_REDIRECT_URI").ok();
+
+        // --- Load Reading Queue Settings ---
+        let queue_poll_interval_secs = std::env::var("QUEUE_POLL_INTERVAL_SECS")
+            .unwrap_or_else(|_| "60".to_string())
+            .parse::<u64>()
+            .map_err(|e| {
+                ConfigError::InvalidValue("QUEUE_POLL_INTERVAL_SECS".to_string(), e.to_string())
+            })?;
+        let feed_refresh_interval_secs = std::env::var("FEED_REFRESH_INTERVAL_SECS")
+            .unwrap_or_else(|_| "3600".to_string())
+            .parse::<u64>()
+            .map_err(|e| {
+                ConfigError::InvalidValue("FEED_REFRESH_INTERVAL_SECS".to_string(), e.to_string())
+            })?;
+
+        // --- Load Hot-word Settings ---
+        let hotword_phrases = std::env::var("HOTWORD_PHRASES")
+            .unwrap_or_else(|_| "hey assistant".to_string())
+            .split(',')
+            .map(|p| p.trim().to_lowercase())
+            .filter(|p| !p.is_empty())
+            .collect();
+
+        // --- Load Resume Recap Settings ---
+        let resume_recap_min_gap_secs = std::env::var("RESUME_RECAP_MIN_GAP_SECS")
+            .unwrap_or_else(|_| "14400".to_string())
+            .parse::<u64>()
+            .map_err(|e| {
+                ConfigError::InvalidValue("RESUME_RECAP_MIN_GAP_SECS".to_string(), e.to_string())
+            })?;
+
+        let secrets_encryption_key = std::env::var("SECRETS_ENCRYPTION_KEY").ok();
+        let document_encryption_key = std::env::var("DOCUMENT_ENCRYPTION_KEY").ok();
+
+        let max_question_audio_bytes = std::env::var("MAX_QUESTION_AUDIO_BYTES")
+            .unwrap_or_else(|_| "10485760".to_string())
+            .parse::<usize>()
+            .map_err(|e| {
+                ConfigError::InvalidValue("MAX_QUESTION_AUDIO_BYTES".to_string(), e.to_string())
+            })?;
+        let dictionary_api_url = std::env::var("DICTIONARY_API_URL")
+            .unwrap_or_else(|_| "https://api.dictionaryapi.dev/api/v2/entries/en".to_string());
+        let partial_transcript_step_bytes = std::env::var("PARTIAL_TRANSCRIPT_STEP_BYTES")
+            .unwrap_or_else(|_| "32768".to_string())
+            .parse::<usize>()
+            .map_err(|e| {
+                ConfigError::InvalidValue("PARTIAL_TRANSCRIPT_STEP_BYTES".to_string(), e.to_string())
+            })?;
+        let session_autosave_interval_secs = std::env::var("SESSION_AUTOSAVE_INTERVAL_SECS")
+            .unwrap_or_else(|_| "5".to_string())
+            .parse::<u64>()
+            .map_err(|e| {
+                ConfigError::InvalidValue("SESSION_AUTOSAVE_INTERVAL_SECS".to_string(), e.to_string())
+            })?;
+        let max_document_chars = std::env::var("MAX_DOCUMENT_CHARS")
+            .unwrap_or_else(|_| "2000000".to_string())
+            .parse::<usize>()
+            .map_err(|e| {
+                ConfigError::InvalidValue("MAX_DOCUMENT_CHARS".to_string(), e.to_string())
+            })?;
+        let session_sentence_window = std::env::var("SESSION_SENTENCE_WINDOW")
+            .unwrap_or_else(|_| "2000".to_string())
+            .parse::<usize>()
+            .map_err(|e| {
+                ConfigError::InvalidValue("SESSION_SENTENCE_WINDOW".to_string(), e.to_string())
+            })?;
+        if session_sentence_window == 0 {
+            return Err(ConfigError::InvalidValue(
+                "SESSION_SENTENCE_WINDOW".to_string(),
+                "must be at least 1".to_string(),
+            ));
+        }
+        let verify_answer_grounding = std::env::var("VERIFY_ANSWER_GROUNDING")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse::<bool>()
+            .map_err(|e| {
+                ConfigError::InvalidValue("VERIFY_ANSWER_GROUNDING".to_string(), e.to_string())
+            })?;
+
+        // --- Load Tenancy Settings ---
+        let multi_tenant = std::env::var("MULTI_TENANT")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse::<bool>()
+            .map_err(|e| ConfigError::InvalidValue("MULTI_TENANT".to_string(), e.to_string()))?;
+
+        // --- Load Admin Settings ---
+        let admin_user_ids = std::env::var("ADMIN_USER_IDS")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(|id| {
+                id.parse::<Uuid>().map_err(|e| {
+                    ConfigError::InvalidValue("ADMIN_USER_IDS".to_string(), e.to_string())
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // --- Load Audio Cache Settings (as optional) ---
+        let audio_cache_dir = std::env::var("AUDIO_CACHE_DIR").ok().map(PathBuf::from);
+
+        // --- Load Guest Mode Settings ---
+        let guest_mode_enabled = std::env::var("GUEST_MODE_ENABLED")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse::<bool>()
+            .map_err(|e| {
+                ConfigError::InvalidValue("GUEST_MODE_ENABLED".to_string(), e.to_string())
+            })?;
+        let guest_session_minutes = std::env::var("GUEST_SESSION_MINUTES")
+            .unwrap_or_else(|_| "60".to_string())
+            .parse::<i64>()
+            .map_err(|e| {
+                ConfigError::InvalidValue("GUEST_SESSION_MINUTES".to_string(), e.to_string())
+            })?;
+        let guest_max_document_chars = std::env::var("GUEST_MAX_DOCUMENT_CHARS")
+            .unwrap_or_else(|_| "5000".to_string())
+            .parse::<usize>()
+            .map_err(|e| {
+                ConfigError::InvalidValue("GUEST_MAX_DOCUMENT_CHARS".to_string(), e.to_string())
+            })?;
+        let guest_max_per_ip_per_hour = std::env::var("GUEST_MAX_PER_IP_PER_HOUR")
+            .unwrap_or_else(|_| "5".to_string())
+            .parse::<usize>()
+            .map_err(|e| {
+                ConfigError::InvalidValue("GUEST_MAX_PER_IP_PER_HOUR".to_string(), e.to_string())
+            })?;
+
+        // --- Load Response Compression Settings ---
+        let compression_excluded_content_types = std::env::var("COMPRESSION_EXCLUDED_CONTENT_TYPES")
+            .unwrap_or_else(|_| "audio/".to_string())
+            .split(',')
+            .map(|t| t.trim().to_lowercase())
+            .filter(|t| !t.is_empty())
+            .collect();
+
+        // --- Load Backup Settings ---
+        let backup_dir = std::env::var("BACKUP_DIR").ok().map(PathBuf::from);
+        let backup_interval_secs = std::env::var("BACKUP_INTERVAL_SECS")
+            .unwrap_or_else(|_| "86400".to_string())
+            .parse::<u64>()
+            .map_err(|e| {
+                ConfigError::InvalidValue("BACKUP_INTERVAL_SECS".to_string(), e.to_string())
+            })?;
+
+        // --- Load Notification Settings ---
+        let notification_webhook_url = std::env::var("NOTIFICATION_WEBHOOK_URL").ok();
+        let goal_reminder_hour_utc = std::env::var("GOAL_REMINDER_HOUR_UTC")
+            .unwrap_or_else(|_| "17".to_string())
+            .parse::<u32>()
+            .ok()
+            .filter(|hour| *hour < 24)
+            .ok_or_else(|| {
+                ConfigError::InvalidValue(
+                    "GOAL_REMINDER_HOUR_UTC".to_string(),
+                    "must be an hour from 0 to 23".to_string(),
+                )
+            })?;
+
+        // --- Load Calendar Feed Settings (as optional) ---
+        let calendar_feed_secret = std::env::var("CALENDAR_FEED_SECRET").ok();
+
+        // --- Load Frontend Settings (as optional) ---
+        let frontend_dir = std::env::var("FRONTEND_DIR").ok().map(PathBuf::from);
 
         Ok(Self {
             bind_address,
@@ -85,10 +454,54 @@ impl Config {
             prompts_path,
             openai_api_key,
             gemini_api_key,
+            anthropic_api_key,
+            qa_provider,
+            note_provider,
+            ollama_base_url,
             sst_model,
+            tts_provider,
             tts_voice,
+            welcome_message,
+            piper_binary,
+            piper_model_path,
+            piper_sample_rate,
             qa_model,
+            verify_answer_grounding,
             note_model,
+            cleanup_model,
+            embedding_model,
+            dictionary_api_url,
+            notion_client_id,
+            notion_client_secret,
+            notion_redirect_uri,
+            google_client_id,
+            google_client_secret,
+            google_redirect_uri,
+            queue_poll_interval_secs,
+            feed_refresh_interval_secs,
+            max_question_audio_bytes,
+            partial_transcript_step_bytes,
+            session_autosave_interval_secs,
+            max_document_chars,
+            session_sentence_window,
+            hotword_phrases,
+            resume_recap_min_gap_secs,
+            secrets_encryption_key,
+            document_encryption_key,
+            multi_tenant,
+            admin_user_ids,
+            audio_cache_dir,
+            guest_mode_enabled,
+            guest_session_minutes,
+            guest_max_document_chars,
+            guest_max_per_ip_per_hour,
+            compression_excluded_content_types,
+            backup_dir,
+            backup_interval_secs,
+            notification_webhook_url,
+            goal_reminder_hour_utc,
+            calendar_feed_secret,
+            frontend_dir,
         })
     }
 }
\ No newline at end of file
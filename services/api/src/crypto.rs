@@ -0,0 +1,84 @@
+//! services/api/src/crypto.rs
+//!
+//! Application-level encryption for secrets stored in the database. Values are
+//! sealed with AES-256-GCM under a key from the environment and stored as
+//! base64 of `nonce || ciphertext`.
+
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    Aes256Gcm, Key, Nonce,
+};
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+
+/// The AES-GCM nonce length in bytes.
+const NONCE_LEN: usize = 12;
+
+#[derive(Debug, thiserror::Error)]
+pub enum CryptoError {
+    #[error("Invalid encryption key: {0}")]
+    InvalidKey(String),
+    #[error("Encryption failed")]
+    Encrypt,
+    #[error("Decryption failed: the value is corrupt or was sealed with another key")]
+    Decrypt,
+}
+
+/// Seals and opens secrets with a single configured key.
+#[derive(Clone)]
+pub struct SecretCipher {
+    cipher: Aes256Gcm,
+}
+
+impl SecretCipher {
+    /// Creates a cipher from a base64-encoded 32-byte key.
+    pub fn from_base64_key(key: &str) -> Result<Self, CryptoError> {
+        let bytes = STANDARD
+            .decode(key.trim())
+            .map_err(|e| CryptoError::InvalidKey(e.to_string()))?;
+        if bytes.len() != 32 {
+            return Err(CryptoError::InvalidKey(format!(
+                "expected 32 bytes, got {}",
+                bytes.len()
+            )));
+        }
+        let key = Key::<Aes256Gcm>::from_slice(&bytes);
+        Ok(Self {
+            cipher: Aes256Gcm::new(key),
+        })
+    }
+
+    /// Encrypts `plaintext` under a fresh random nonce.
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<String, CryptoError> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, plaintext)
+            .map_err(|_| CryptoError::Encrypt)?;
+
+        let mut sealed = nonce.to_vec();
+        sealed.extend_from_slice(&ciphertext);
+        Ok(STANDARD.encode(sealed))
+    }
+
+    /// Decrypts a value produced by `encrypt`.
+    pub fn decrypt(&self, sealed: &str) -> Result<Vec<u8>, CryptoError> {
+        let bytes = STANDARD.decode(sealed).map_err(|_| CryptoError::Decrypt)?;
+        if bytes.len() < NONCE_LEN {
+            return Err(CryptoError::Decrypt);
+        }
+        let (nonce, ciphertext) = bytes.split_at(NONCE_LEN);
+        self.cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| CryptoError::Decrypt)
+    }
+
+    /// Encrypts a UTF-8 string.
+    pub fn encrypt_str(&self, plaintext: &str) -> Result<String, CryptoError> {
+        self.encrypt(plaintext.as_bytes())
+    }
+
+    /// Decrypts a value produced by `encrypt_str`.
+    pub fn decrypt_str(&self, sealed: &str) -> Result<String, CryptoError> {
+        String::from_utf8(self.decrypt(sealed)?).map_err(|_| CryptoError::Decrypt)
+    }
+}
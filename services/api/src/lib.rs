@@ -1,4 +1,6 @@
 pub mod adapters;
+pub mod audio;
 pub mod config;
+pub mod crypto;
 pub mod error;
 pub mod web;
\ No newline at end of file
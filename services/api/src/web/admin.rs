@@ -0,0 +1,445 @@
+//! services/api/src/web/admin.rs
+//!
+//! Operator-only endpoints. Routes here sit behind `require_admin`.
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use chrono::{Duration, NaiveDate, Utc};
+use reading_assistant_core::domain::{
+    IntegrityReport, LatencyPercentiles, LatencyStage, LlmUsage, TtsUsage,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::web::state::AppState;
+
+/// How far back the usage report goes when no `from` date is given.
+const DEFAULT_REPORT_DAYS: i64 = 30;
+
+//=========================================================================================
+// Request/Response Types
+//=========================================================================================
+
+#[derive(Deserialize, IntoParams)]
+pub struct TtsUsageQuery {
+    /// First day of the report (UTC, inclusive). Defaults to 30 days before `to`.
+    pub from: Option<NaiveDate>,
+    /// Last day of the report (UTC, inclusive). Defaults to today.
+    pub to: Option<NaiveDate>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct TtsUsageDay {
+    pub date: NaiveDate,
+    pub provider: String,
+    pub model: String,
+    pub characters: u64,
+    pub requests: u64,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct TtsUsageTotal {
+    pub provider: String,
+    pub model: String,
+    pub characters: u64,
+    pub requests: u64,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct TtsUsageReport {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// One row per day, provider and model, oldest first.
+    pub days: Vec<TtsUsageDay>,
+    /// Totals over the whole range, per provider and model.
+    pub totals: Vec<TtsUsageTotal>,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct LlmUsageQuery {
+    /// First day of the report (UTC, inclusive). Defaults to 30 days before `to`.
+    pub from: Option<NaiveDate>,
+    /// Last day of the report (UTC, inclusive). Defaults to today.
+    pub to: Option<NaiveDate>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct LlmUsageDay {
+    pub date: NaiveDate,
+    pub provider: String,
+    pub model: String,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub requests: u64,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct LlmUsageTotal {
+    pub provider: String,
+    pub model: String,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub requests: u64,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct LlmUsageReport {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// One row per day, provider and model, oldest first.
+    pub days: Vec<LlmUsageDay>,
+    /// Totals over the whole range, per provider and model.
+    pub totals: Vec<LlmUsageTotal>,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct LatencyQuery {
+    /// First day of the report (UTC, inclusive). Defaults to 30 days before `to`.
+    pub from: Option<NaiveDate>,
+    /// Last day of the report (UTC, inclusive). Defaults to today.
+    pub to: Option<NaiveDate>,
+}
+
+/// A timed stage of answering a question.
+#[derive(Serialize, ToSchema, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum LatencyStageBody {
+    /// Transcribing a spoken question.
+    Stt,
+    /// Until the model's reply starts.
+    Llm,
+    /// From the reply to the answer's last audio, including the rest of a streamed reply.
+    Tts,
+    /// The whole question.
+    Total,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct LatencyDay {
+    pub date: NaiveDate,
+    pub stage: LatencyStageBody,
+    /// Answered questions timed at this stage.
+    pub samples: u64,
+    pub p50_ms: u32,
+    pub p90_ms: u32,
+    pub p99_ms: u32,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct LatencyReport {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// One row per day and stage, oldest first.
+    pub days: Vec<LatencyDay>,
+}
+
+/// Data left inconsistent, e.g. by a failure between two writes. See `IntegrityReport`.
+#[derive(Serialize, ToSchema)]
+pub struct IntegrityReportBody {
+    /// Sessions whose document no longer exists.
+    pub sessions_without_document: Vec<Uuid>,
+    /// Notes whose session no longer exists.
+    pub notes_without_session: Vec<Uuid>,
+    /// Deleted questions whose latencies are still recorded.
+    pub latencies_without_question: Vec<Uuid>,
+    /// Documents without sentence chunks, which can't be read.
+    pub documents_without_chunks: Vec<Uuid>,
+    /// Sessions positioned past the end of their document.
+    pub sessions_past_end: Vec<Uuid>,
+    /// Read-later links marked ready whose session was deleted.
+    pub queue_items_without_session: Vec<Uuid>,
+    /// Whether what was found has been repaired.
+    pub repaired: bool,
+}
+
+impl From<IntegrityReport> for IntegrityReportBody {
+    fn from(report: IntegrityReport) -> Self {
+        Self {
+            sessions_without_document: report.sessions_without_document,
+            notes_without_session: report.notes_without_session,
+            latencies_without_question: report.latencies_without_question,
+            documents_without_chunks: report.documents_without_chunks,
+            sessions_past_end: report.sessions_past_end,
+            queue_items_without_session: report.queue_items_without_session,
+            repaired: report.repaired,
+        }
+    }
+}
+
+impl From<LatencyStage> for LatencyStageBody {
+    fn from(stage: LatencyStage) -> Self {
+        match stage {
+            LatencyStage::Stt => LatencyStageBody::Stt,
+            LatencyStage::Llm => LatencyStageBody::Llm,
+            LatencyStage::Tts => LatencyStageBody::Tts,
+            LatencyStage::Total => LatencyStageBody::Total,
+        }
+    }
+}
+
+impl From<LatencyPercentiles> for LatencyDay {
+    fn from(percentiles: LatencyPercentiles) -> Self {
+        Self {
+            date: percentiles.day,
+            stage: percentiles.stage.into(),
+            samples: percentiles.samples,
+            p50_ms: percentiles.p50_ms,
+            p90_ms: percentiles.p90_ms,
+            p99_ms: percentiles.p99_ms,
+        }
+    }
+}
+
+impl From<TtsUsage> for TtsUsageDay {
+    fn from(usage: TtsUsage) -> Self {
+        Self {
+            date: usage.usage_date,
+            provider: usage.provider,
+            model: usage.model,
+            characters: usage.characters,
+            requests: usage.requests,
+        }
+    }
+}
+
+impl From<LlmUsage> for LlmUsageDay {
+    fn from(usage: LlmUsage) -> Self {
+        Self {
+            date: usage.usage_date,
+            provider: usage.provider,
+            model: usage.model,
+            prompt_tokens: usage.prompt_tokens,
+            completion_tokens: usage.completion_tokens,
+            requests: usage.requests,
+        }
+    }
+}
+
+//=========================================================================================
+// Handlers
+//=========================================================================================
+
+/// GET /admin/tts-usage - Characters synthesized per TTS provider/model per day
+#[utoipa::path(
+    get,
+    path = "/admin/tts-usage",
+    params(TtsUsageQuery),
+    responses(
+        (status = 200, description = "TTS usage report", body = TtsUsageReport),
+        (status = 400, description = "`from` is after `to`"),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 403, description = "Forbidden - not an admin"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn tts_usage_report_handler(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<TtsUsageQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let to = query.to.unwrap_or_else(|| Utc::now().date_naive());
+    let from = query
+        .from
+        .unwrap_or_else(|| to - Duration::days(DEFAULT_REPORT_DAYS));
+    if from > to {
+        return Err((StatusCode::BAD_REQUEST, "`from` must not be after `to`".to_string()));
+    }
+
+    let usage = state.db.get_tts_usage(from, to).await.map_err(|e| {
+        error!("Failed to fetch TTS usage: {:?}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch TTS usage".to_string())
+    })?;
+
+    let mut totals: BTreeMap<(String, String), (u64, u64)> = BTreeMap::new();
+    for day in &usage {
+        let total = totals
+            .entry((day.provider.clone(), day.model.clone()))
+            .or_default();
+        total.0 += day.characters;
+        total.1 += day.requests;
+    }
+
+    Ok(Json(TtsUsageReport {
+        from,
+        to,
+        days: usage.into_iter().map(TtsUsageDay::from).collect(),
+        totals: totals
+            .into_iter()
+            .map(|((provider, model), (characters, requests))| TtsUsageTotal {
+                provider,
+                model,
+                characters,
+                requests,
+            })
+            .collect(),
+    }))
+}
+
+/// GET /admin/llm-usage - Tokens used per QA provider/model per day
+///
+/// Counts come from the provider's own usage figures on each answer, so only providers
+/// that report them (currently OpenAI) appear.
+#[utoipa::path(
+    get,
+    path = "/admin/llm-usage",
+    params(LlmUsageQuery),
+    responses(
+        (status = 200, description = "LLM usage report", body = LlmUsageReport),
+        (status = 400, description = "`from` is after `to`"),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 403, description = "Forbidden - not an admin"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn llm_usage_report_handler(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<LlmUsageQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let to = query.to.unwrap_or_else(|| Utc::now().date_naive());
+    let from = query
+        .from
+        .unwrap_or_else(|| to - Duration::days(DEFAULT_REPORT_DAYS));
+    if from > to {
+        return Err((StatusCode::BAD_REQUEST, "`from` must not be after `to`".to_string()));
+    }
+
+    let usage = state.db.get_llm_usage(from, to).await.map_err(|e| {
+        error!("Failed to fetch LLM usage: {:?}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch LLM usage".to_string())
+    })?;
+
+    let mut totals: BTreeMap<(String, String), (u64, u64, u64)> = BTreeMap::new();
+    for day in &usage {
+        let total = totals
+            .entry((day.provider.clone(), day.model.clone()))
+            .or_default();
+        total.0 += day.prompt_tokens;
+        total.1 += day.completion_tokens;
+        total.2 += day.requests;
+    }
+
+    Ok(Json(LlmUsageReport {
+        from,
+        to,
+        days: usage.into_iter().map(LlmUsageDay::from).collect(),
+        totals: totals
+            .into_iter()
+            .map(|((provider, model), (prompt_tokens, completion_tokens, requests))| LlmUsageTotal {
+                provider,
+                model,
+                prompt_tokens,
+                completion_tokens,
+                requests,
+            })
+            .collect(),
+    }))
+}
+
+/// GET /admin/latency - Daily percentiles of how long each stage of answering took
+#[utoipa::path(
+    get,
+    path = "/admin/latency",
+    params(LatencyQuery),
+    responses(
+        (status = 200, description = "Latency report", body = LatencyReport),
+        (status = 400, description = "`from` is after `to`"),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 403, description = "Forbidden - not an admin"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn latency_report_handler(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<LatencyQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let to = query.to.unwrap_or_else(|| Utc::now().date_naive());
+    let from = query
+        .from
+        .unwrap_or_else(|| to - Duration::days(DEFAULT_REPORT_DAYS));
+    if from > to {
+        return Err((StatusCode::BAD_REQUEST, "`from` must not be after `to`".to_string()));
+    }
+
+    let percentiles = state.db.get_latency_percentiles(from, to).await.map_err(|e| {
+        error!("Failed to fetch latency percentiles: {:?}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch latency report".to_string())
+    })?;
+
+    Ok(Json(LatencyReport {
+        from,
+        to,
+        days: percentiles.into_iter().map(LatencyDay::from).collect(),
+    }))
+}
+
+/// GET /admin/integrity - Report data left inconsistent, without changing anything
+#[utoipa::path(
+    get,
+    path = "/admin/integrity",
+    responses(
+        (status = 200, description = "Integrity report", body = IntegrityReportBody),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 403, description = "Forbidden - not an admin"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn integrity_report_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let report = state.db.audit_integrity(false).await.map_err(|e| {
+        error!("Failed to audit data integrity: {:?}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to audit data integrity".to_string())
+    })?;
+    if !report.is_clean() {
+        warn!("Integrity audit found inconsistent data: {:?}", report);
+    }
+
+    Ok(Json(IntegrityReportBody::from(report)))
+}
+
+/// POST /admin/integrity/repair - Repair data left inconsistent and report what was repaired
+#[utoipa::path(
+    post,
+    path = "/admin/integrity/repair",
+    responses(
+        (status = 200, description = "What was found and repaired", body = IntegrityReportBody),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 403, description = "Forbidden - not an admin"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn repair_integrity_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let report = state.db.audit_integrity(true).await.map_err(|e| {
+        error!("Failed to repair data integrity: {:?}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to repair data integrity".to_string())
+    })?;
+    if !report.is_clean() {
+        info!("Integrity repair fixed inconsistent data: {:?}", report);
+    }
+
+    Ok(Json(IntegrityReportBody::from(report)))
+}
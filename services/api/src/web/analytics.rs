@@ -0,0 +1,182 @@
+//! services/api/src/web/analytics.rs
+//!
+//! Anonymized usage analytics: which features listeners use and how often, never what
+//! they read or asked. Handlers call `track` as features are used; operators read the
+//! daily counts back from `GET /admin/usage`.
+
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use chrono::{Duration, NaiveDate, Utc};
+use reading_assistant_core::domain::{UsageEvent, UsageEventCount, UsageEventKind};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{error, warn};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::web::state::AppState;
+
+/// How far back the usage report goes when no `from` date is given.
+const DEFAULT_REPORT_DAYS: i64 = 30;
+
+/// Records that a feature was used on the visit `visit_id`, in the background so the
+/// listener never waits on analytics. Does nothing when the user opted out, i.e. the
+/// session has no visit ID.
+pub fn track(app_state: &Arc<AppState>, visit_id: Option<Uuid>, kind: UsageEventKind) {
+    let Some(visit_id) = visit_id else {
+        return;
+    };
+    let analytics = app_state.analytics.clone();
+    tokio::spawn(async move {
+        let event = UsageEvent {
+            visit_id,
+            kind,
+            occurred_at: Utc::now(),
+        };
+        if let Err(e) = analytics.record(event).await {
+            warn!("Failed to record usage event {:?}: {:?}", kind, e);
+        }
+    });
+}
+
+//=========================================================================================
+// Request/Response Types
+//=========================================================================================
+
+#[derive(Deserialize, IntoParams)]
+pub struct UsageQuery {
+    /// First day of the report (UTC, inclusive). Defaults to 30 days before `to`.
+    pub from: Option<NaiveDate>,
+    /// Last day of the report (UTC, inclusive). Defaults to today.
+    pub to: Option<NaiveDate>,
+}
+
+/// A feature, as reported by the usage endpoint.
+#[derive(Serialize, ToSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UsageFeature {
+    SessionOpened,
+    QuestionAsked,
+    Paused,
+    Resumed,
+    Seeked,
+    QuizStarted,
+    /// Spoken "resume reading" commands.
+    ResumeCommand,
+    /// Spoken "skip to the part about..." commands.
+    SeekCommand,
+    /// Spoken "summarize so far" commands.
+    SummaryCommand,
+    /// Spoken "read faster" and "read slower" commands.
+    SpeedCommand,
+}
+
+impl From<UsageEventKind> for UsageFeature {
+    fn from(kind: UsageEventKind) -> Self {
+        match kind {
+            UsageEventKind::SessionOpened => UsageFeature::SessionOpened,
+            UsageEventKind::QuestionAsked => UsageFeature::QuestionAsked,
+            UsageEventKind::Paused => UsageFeature::Paused,
+            UsageEventKind::Resumed => UsageFeature::Resumed,
+            UsageEventKind::Seeked => UsageFeature::Seeked,
+            UsageEventKind::QuizStarted => UsageFeature::QuizStarted,
+            UsageEventKind::ResumeCommand => UsageFeature::ResumeCommand,
+            UsageEventKind::SeekCommand => UsageFeature::SeekCommand,
+            UsageEventKind::SummaryCommand => UsageFeature::SummaryCommand,
+            UsageEventKind::SpeedCommand => UsageFeature::SpeedCommand,
+        }
+    }
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct UsageDay {
+    pub date: NaiveDate,
+    pub feature: UsageFeature,
+    /// How many times the feature was used.
+    pub events: u64,
+    /// On how many visits (one per opened session connection) it was used.
+    pub visits: u64,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct UsageReport {
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    /// One row per day and feature, oldest first.
+    pub days: Vec<UsageDay>,
+    /// Sessions opened over the whole range.
+    pub sessions_opened: u64,
+    /// Questions asked per opened session over the whole range; 0 with no sessions.
+    pub questions_per_session: f64,
+}
+
+impl From<UsageEventCount> for UsageDay {
+    fn from(count: UsageEventCount) -> Self {
+        Self {
+            date: count.day,
+            feature: count.kind.into(),
+            events: count.events,
+            visits: count.visits,
+        }
+    }
+}
+
+//=========================================================================================
+// Handlers
+//=========================================================================================
+
+/// GET /admin/usage - Anonymized feature usage per day
+#[utoipa::path(
+    get,
+    path = "/admin/usage",
+    params(UsageQuery),
+    responses(
+        (status = 200, description = "Feature usage report", body = UsageReport),
+        (status = 400, description = "`from` is after `to`"),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 403, description = "Forbidden - not an admin"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn usage_report_handler(
+    State(state): State<Arc<AppState>>,
+    Query(query): Query<UsageQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let to = query.to.unwrap_or_else(|| Utc::now().date_naive());
+    let from = query
+        .from
+        .unwrap_or_else(|| to - Duration::days(DEFAULT_REPORT_DAYS));
+    if from > to {
+        return Err((StatusCode::BAD_REQUEST, "`from` must not be after `to`".to_string()));
+    }
+
+    let counts = state.db.get_usage_event_counts(from, to).await.map_err(|e| {
+        error!("Failed to fetch usage events: {:?}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch usage".to_string())
+    })?;
+
+    let total = |kind: UsageEventKind| -> u64 {
+        counts.iter().filter(|count| count.kind == kind).map(|count| count.events).sum()
+    };
+    let sessions_opened = total(UsageEventKind::SessionOpened);
+    let questions_per_session = if sessions_opened == 0 {
+        0.0
+    } else {
+        total(UsageEventKind::QuestionAsked) as f64 / sessions_opened as f64
+    };
+
+    Ok(Json(UsageReport {
+        from,
+        to,
+        days: counts.into_iter().map(UsageDay::from).collect(),
+        sessions_opened,
+        questions_per_session,
+    }))
+}
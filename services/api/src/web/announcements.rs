@@ -0,0 +1,88 @@
+//! services/api/src/web/announcements.rs
+//!
+//! Audio for the announcements every session hears, like the welcome message. Each is
+//! synthesized once per voice, speed and format and then kept in memory, so opening a
+//! session doesn't wait on the same speech every time. Audio is keyed by its text as
+//! well as the voice, so changing `WELCOME_MESSAGE` or a listener's voice never plays
+//! stale audio.
+
+use reading_assistant_core::{
+    domain::{AudioFormat, SpeechSettings},
+    ports::{PortResult, TextToSpeechService},
+};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tracing::{info, warn};
+
+use crate::web::{state::AppState, ws_handler::negotiate_audio_format};
+
+/// The most announcements kept at once. Speeds are free-form, so the set of keys is
+/// unbounded; when it fills up it is emptied and refilled by the sessions that follow.
+const MAX_CACHED_ANNOUNCEMENTS: usize = 64;
+
+#[derive(PartialEq, Eq, Hash)]
+struct AnnouncementKey {
+    text: String,
+    voice: Option<String>,
+    /// The speed's bits, since floats aren't hashable.
+    speed: Option<u32>,
+    format: AudioFormat,
+}
+
+impl AnnouncementKey {
+    fn new(text: &str, speech: &SpeechSettings) -> Self {
+        Self {
+            text: text.to_string(),
+            voice: speech.voice.clone(),
+            speed: speech.speed.map(f32::to_bits),
+            format: speech.format,
+        }
+    }
+}
+
+/// Synthesized announcements, shared by all sessions.
+#[derive(Default)]
+pub struct AnnouncementAudio {
+    audio: Mutex<HashMap<AnnouncementKey, Vec<u8>>>,
+}
+
+impl AnnouncementAudio {
+    /// The audio of `text` spoken with `speech`, synthesized by `tts` the first time.
+    pub async fn get_or_synthesize(
+        &self,
+        tts: &dyn TextToSpeechService,
+        text: &str,
+        speech: &SpeechSettings,
+    ) -> PortResult<Vec<u8>> {
+        let key = AnnouncementKey::new(text, speech);
+        if let Some(audio) = self.audio.lock().unwrap().get(&key) {
+            return Ok(audio.clone());
+        }
+
+        // Sessions opening together may both synthesize it; the last one wins.
+        let audio = tts.generate_audio(text, speech).await?;
+        let mut cached = self.audio.lock().unwrap();
+        if cached.len() >= MAX_CACHED_ANNOUNCEMENTS {
+            cached.clear();
+        }
+        cached.insert(key, audio.clone());
+        Ok(audio)
+    }
+}
+
+/// Synthesizes the welcome message at startup in the default voice and format, which
+/// most sessions use, so even the first session gets it without waiting.
+pub async fn warm_welcome_audio(app_state: Arc<AppState>) {
+    let speech = SpeechSettings {
+        format: negotiate_audio_format(&app_state, None),
+        ..SpeechSettings::default()
+    };
+    match app_state
+        .announcements
+        .get_or_synthesize(app_state.tts_adapter.as_ref(), &app_state.config.welcome_message, &speech)
+        .await
+    {
+        Ok(_) => info!("Welcome message audio cached."),
+        Err(e) => warn!("Failed to synthesize the welcome message at startup: {:?}", e),
+    }
+}
@@ -0,0 +1,199 @@
+//! services/api/src/web/api_keys.rs
+//!
+//! Endpoints for users who bring their own OpenAI API key. A stored key is
+//! encrypted at rest, and the user's reading sessions bill to it instead of
+//! the operator's key.
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use reading_assistant_core::{
+    domain::UserApiKey,
+    ports::{PortError, PortResult},
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{error, info};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::web::state::AppState;
+
+/// The provider name under which OpenAI keys are stored.
+const OPENAI_PROVIDER: &str = "openai";
+/// How many trailing characters of a key are kept in the clear as a hint.
+const KEY_HINT_LEN: usize = 4;
+
+//=========================================================================================
+// Request/Response Types
+//=========================================================================================
+
+#[derive(Deserialize, ToSchema)]
+pub struct SaveApiKeyRequest {
+    pub api_key: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ApiKeyStatusResponse {
+    /// Whether the user has a key stored.
+    pub configured: bool,
+    /// The last few characters of the stored key, e.g. "…a1b2".
+    pub key_hint: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl From<Option<UserApiKey>> for ApiKeyStatusResponse {
+    fn from(key: Option<UserApiKey>) -> Self {
+        match key {
+            Some(key) => Self {
+                configured: true,
+                key_hint: Some(format!("…{}", key.key_hint)),
+                created_at: Some(key.created_at),
+            },
+            None => Self {
+                configured: false,
+                key_hint: None,
+                created_at: None,
+            },
+        }
+    }
+}
+
+//=========================================================================================
+// Handlers
+//=========================================================================================
+
+/// GET /api-keys/openai - Whether the user has their own OpenAI key stored
+#[utoipa::path(
+    get,
+    path = "/api-keys/openai",
+    responses(
+        (status = 200, description = "Key status", body = ApiKeyStatusResponse),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn get_openai_key_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let key = state
+        .db
+        .get_user_api_key(user_id, OPENAI_PROVIDER)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch API key: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch API key".to_string())
+        })?;
+
+    Ok(Json(ApiKeyStatusResponse::from(key)))
+}
+
+/// PUT /api-keys/openai - Store (or replace) the user's own OpenAI key
+#[utoipa::path(
+    put,
+    path = "/api-keys/openai",
+    request_body = SaveApiKeyRequest,
+    responses(
+        (status = 200, description = "Key stored", body = ApiKeyStatusResponse),
+        (status = 400, description = "The key is not a valid OpenAI key"),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 501, description = "This deployment does not accept user keys"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn save_openai_key_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Json(req): Json<SaveApiKeyRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let cipher = state.secret_cipher.as_ref().ok_or((
+        StatusCode::NOT_IMPLEMENTED,
+        "This deployment does not accept user API keys".to_string(),
+    ))?;
+
+    let api_key = req.api_key.trim();
+    if !api_key.is_ascii() || !api_key.starts_with("sk-") || api_key.len() <= KEY_HINT_LEN + 3 {
+        return Err((StatusCode::BAD_REQUEST, "Not a valid OpenAI API key".to_string()));
+    }
+
+    let encrypted_key = cipher.encrypt_str(api_key).map_err(|e| {
+        error!("Failed to encrypt API key: {:?}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to store API key".to_string())
+    })?;
+    let key_hint = &api_key[api_key.len() - KEY_HINT_LEN..];
+
+    let key = state
+        .db
+        .save_user_api_key(user_id, OPENAI_PROVIDER, &encrypted_key, key_hint)
+        .await
+        .map_err(|e| {
+            error!("Failed to save API key: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to store API key".to_string())
+        })?;
+
+    info!("User {} stored their own OpenAI API key.", user_id);
+    Ok(Json(ApiKeyStatusResponse::from(Some(key))))
+}
+
+/// DELETE /api-keys/openai - Remove the user's own OpenAI key
+#[utoipa::path(
+    delete,
+    path = "/api-keys/openai",
+    responses(
+        (status = 204, description = "Key removed; sessions use the operator's key again"),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn delete_openai_key_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    state
+        .db
+        .delete_user_api_key(user_id, OPENAI_PROVIDER)
+        .await
+        .map_err(|e| {
+            error!("Failed to delete API key: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to delete API key".to_string())
+        })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+//=========================================================================================
+// Session Credentials
+//=========================================================================================
+
+/// Returns the app state a user's session should run with: adapters billing to the
+/// user's own OpenAI key if they stored one, otherwise the shared state.
+pub async fn app_state_for_user(
+    app_state: &Arc<AppState>,
+    user_id: Uuid,
+) -> PortResult<Arc<AppState>> {
+    let Some(cipher) = &app_state.secret_cipher else {
+        return Ok(app_state.clone());
+    };
+    let Some(key) = app_state.db.get_user_api_key(user_id, OPENAI_PROVIDER).await? else {
+        return Ok(app_state.clone());
+    };
+
+    let api_key = cipher
+        .decrypt_str(&key.encrypted_key)
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+    Ok(Arc::new(app_state.with_openai_key(&api_key)))
+}
@@ -0,0 +1,121 @@
+//! services/api/src/web/ask.rs
+//!
+//! Text Q&A about a session's document, for places that don't open the audio
+//! WebSocket, such as the notes page or a mobile widget. Answers use the same
+//! context, preferences and note-taking as spoken questions.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use reading_assistant_core::ports::PortError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::web::{
+    api_keys::app_state_for_user,
+    qa_task::answer_text_question,
+    state::{AppState, SessionState},
+};
+
+/// The longest question accepted, in characters.
+const MAX_QUESTION_CHARS: usize = 1000;
+
+//=========================================================================================
+// Request/Response Types
+//=========================================================================================
+
+#[derive(Deserialize, ToSchema)]
+pub struct AskRequest {
+    pub question: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct AskResponse {
+    pub question: String,
+    pub answer: String,
+}
+
+//=========================================================================================
+// Handlers
+//=========================================================================================
+
+/// POST /sessions/{session_id}/ask - Answer a typed question about the session's document
+#[utoipa::path(
+    post,
+    path = "/sessions/{session_id}/ask",
+    params(
+        ("session_id" = Uuid, Path, description = "Session ID")
+    ),
+    request_body = AskRequest,
+    responses(
+        (status = 200, description = "Question answered", body = AskResponse),
+        (status = 400, description = "Empty or overly long question"),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 403, description = "Access denied"),
+        (status = 404, description = "Session not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn ask_question_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Path(session_id): Path<Uuid>,
+    Json(payload): Json<AskRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let question = payload.question.trim().to_string();
+    if question.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "Question must not be empty".to_string()));
+    }
+    if question.chars().count() > MAX_QUESTION_CHARS {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Questions are limited to {} characters", MAX_QUESTION_CHARS),
+        ));
+    }
+
+    // 1. Verify the session belongs to this user
+    let session = state.db.get_session_by_id(session_id).await.map_err(|e| match e {
+        PortError::NotFound(_) => (StatusCode::NOT_FOUND, "Session not found".to_string()),
+        e => {
+            error!("Failed to get session: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to load session".to_string())
+        }
+    })?;
+    if session.user_id != user_id {
+        return Err((StatusCode::FORBIDDEN, "Access denied".to_string()));
+    }
+
+    // 2. Load the session as a live connection would, billed to the user's own key if any
+    let state = app_state_for_user(&state, user_id).await.map_err(|e| {
+        error!("Failed to load user API key: {:?}", e);
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            "Your saved OpenAI API key could not be used".to_string(),
+        )
+    })?;
+    let mut session_state = SessionState::new(state.clone(), session_id, None, None)
+        .await
+        .map_err(|e| {
+            error!("Failed to load session state: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to load session".to_string())
+        })?;
+
+    // 3. Answer it
+    let answer = answer_text_question(state, &mut session_state, question.clone())
+        .await
+        .map_err(|e| {
+            error!("Failed to answer question: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to answer question".to_string())
+        })?;
+
+    Ok(Json(AskResponse { question, answer }))
+}
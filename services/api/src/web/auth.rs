@@ -6,7 +6,7 @@ use axum::{
     extract::State,
     http::{header, StatusCode},
     response::IntoResponse,
-    Json,
+    Extension, Json,
 };
 use argon2::{
     password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
@@ -18,7 +18,7 @@ use std::sync::Arc;
 use tracing::error;
 use uuid::Uuid;
 use utoipa::ToSchema;
-use crate::web::state::AppState;
+use crate::web::{middleware::TenantId, state::AppState};
 
 //=========================================================================================
 // Request/Response Types
@@ -59,6 +59,8 @@ pub struct AuthResponse {
 )]
 pub async fn signup_handler(
     State(state): State<Arc<AppState>>,
+    Extension(TenantId(tenant_id)): Extension<TenantId>,
+    headers: axum::http::HeaderMap,
     Json(req): Json<SignupRequest>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
     // 1. Hash the password
@@ -72,15 +74,31 @@ pub async fn signup_handler(
         })?
         .to_string();
 
-    // 2. Create user in database
-    let user = state
-        .db
-        .create_user_with_email(&req.email, &password_hash)
-        .await
-        .map_err(|e| {
-            error!("Failed to create user: {:?}", e);
-            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to create user".to_string())
-        })?;
+    // 2. Create user in database, or convert the caller's guest account so it
+    //    keeps its trial session
+    let guest = match session_cookie(&headers) {
+        Some(auth_session_id) => match state.db.validate_auth_session(auth_session_id, tenant_id).await {
+            Ok(user_id) => state.db.get_user(user_id).await.ok(),
+            Err(_) => None,
+        },
+        None => None,
+    }
+    .filter(|user| user.is_guest);
+
+    let user = match guest {
+        Some(guest) => state
+            .db
+            .convert_guest_user(guest.user_id, &req.email, &password_hash)
+            .await,
+        None => state
+            .db
+            .create_user_with_email(tenant_id, &req.email, &password_hash)
+            .await,
+    }
+    .map_err(|e| {
+        error!("Failed to create user: {:?}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to create user".to_string())
+    })?;
 
     // 3. Generate auth session ID
     let auth_session_id = Uuid::new_v4().to_string();
@@ -131,12 +149,13 @@ pub async fn signup_handler(
 )]
 pub async fn login_handler(
     State(state): State<Arc<AppState>>,
+    Extension(TenantId(tenant_id)): Extension<TenantId>,
     Json(req): Json<LoginRequest>,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
     // 1. Get user by email
     let user_creds = state
         .db
-        .get_user_by_email(&req.email)
+        .get_user_by_email(tenant_id, &req.email)
         .await
         .map_err(|e| {
             error!("Failed to get user: {:?}", e);
@@ -206,19 +225,8 @@ pub async fn logout_handler(
     State(state): State<Arc<AppState>>,
     headers: axum::http::HeaderMap,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
-    // 1. Extract session cookie
-    let cookie_header = headers
-        .get(header::COOKIE)
-        .and_then(|v| v.to_str().ok())
-        .ok_or((StatusCode::UNAUTHORIZED, "No session found".to_string()))?;
-
-    // 2. Parse session ID from cookie
-    let auth_session_id = cookie_header
-        .split(';')
-        .find_map(|c| {
-            let c = c.trim();
-            c.strip_prefix("session=")
-        })
+    // 1-2. Extract the session ID from the cookie
+    let auth_session_id = session_cookie(&headers)
         .ok_or((StatusCode::UNAUTHORIZED, "No session found".to_string()))?;
 
     // 3. Delete auth session from database
@@ -235,4 +243,12 @@ pub async fn logout_handler(
     let cookie = "session=; HttpOnly; SameSite=Lax; Path=/; Max-Age=0";
 
     Ok((StatusCode::OK, [(header::SET_COOKIE, cookie.to_string())]))
-}
\ No newline at end of file
+}
+/// Returns the auth session ID from the request's `session` cookie, if any.
+fn session_cookie(headers: &axum::http::HeaderMap) -> Option<&str> {
+    headers
+        .get(header::COOKIE)
+        .and_then(|v| v.to_str().ok())?
+        .split(';')
+        .find_map(|c| c.trim().strip_prefix("session="))
+}
@@ -0,0 +1,325 @@
+//! services/api/src/web/backups.rs
+//!
+//! Backups of user-facing data for deployments without managed Postgres backups.
+//! A background worker writes every document, session and note to backup storage
+//! on a schedule, and admins can trigger, list and download backups. Backups are
+//! encrypted with the document key when one is configured, so they are never less
+//! protected than the database.
+
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use reading_assistant_core::{
+    domain::{BackupInfo, BackupSnapshot},
+    ports::{BackupStorageService, PortError, PortResult},
+};
+use serde::Serialize;
+use std::sync::Arc;
+use tracing::{error, info};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::crypto::SecretCipher;
+use crate::web::{rest::NoteSourceKind, state::AppState};
+
+/// How often the worker checks whether a backup is due.
+const BACKUP_CHECK_INTERVAL_SECS: u64 = 60 * 60;
+/// The layout of the backup file, bumped when it changes.
+const BACKUP_FORMAT_VERSION: u32 = 1;
+
+//=========================================================================================
+// Backup File Format
+//=========================================================================================
+
+#[derive(Serialize)]
+struct BackupFile {
+    format_version: u32,
+    created_at: DateTime<Utc>,
+    documents: Vec<BackupDocument>,
+    sessions: Vec<BackupSession>,
+    notes: Vec<BackupNote>,
+}
+
+#[derive(Serialize)]
+struct BackupDocument {
+    id: Uuid,
+    user_id: Uuid,
+    title: Option<String>,
+    text: String,
+    created_at: DateTime<Utc>,
+}
+
+#[derive(Serialize)]
+struct BackupSession {
+    id: Uuid,
+    user_id: Uuid,
+    document_id: Uuid,
+    reading_progress_index: usize,
+    education_mode: bool,
+    title: Option<String>,
+    created_at: DateTime<Utc>,
+    last_accessed_at: DateTime<Utc>,
+}
+
+#[derive(Serialize)]
+struct BackupNote {
+    id: Uuid,
+    session_id: Uuid,
+    text: String,
+    source: NoteSourceKind,
+    source_start_index: Option<usize>,
+    source_end_index: Option<usize>,
+    created_at: DateTime<Utc>,
+}
+
+impl BackupFile {
+    fn new(snapshot: BackupSnapshot, created_at: DateTime<Utc>) -> Self {
+        Self {
+            format_version: BACKUP_FORMAT_VERSION,
+            created_at,
+            documents: snapshot
+                .documents
+                .into_iter()
+                .map(|d| BackupDocument {
+                    id: d.id,
+                    user_id: d.user_id,
+                    title: d.title,
+                    text: d.original_text,
+                    created_at: d.created_at,
+                })
+                .collect(),
+            sessions: snapshot
+                .sessions
+                .into_iter()
+                .map(|s| BackupSession {
+                    id: s.id,
+                    user_id: s.user_id,
+                    document_id: s.document_id,
+                    reading_progress_index: s.reading_progress_index,
+                    education_mode: s.education_mode,
+                    title: s.title,
+                    created_at: s.created_at,
+                    last_accessed_at: s.last_accessed_at,
+                })
+                .collect(),
+            notes: snapshot
+                .notes
+                .into_iter()
+                .map(|n| BackupNote {
+                    id: n.id,
+                    session_id: n.session_id,
+                    text: n.generated_note_text,
+                    source: n.source.into(),
+                    source_start_index: n.source_start_index,
+                    source_end_index: n.source_end_index,
+                    created_at: n.created_at,
+                })
+                .collect(),
+        }
+    }
+}
+
+//=========================================================================================
+// Background Worker
+//=========================================================================================
+
+/// The main loop of the backup worker. Runs for the lifetime of the server and
+/// writes a backup whenever the newest one is older than the configured interval,
+/// so restarts neither skip nor repeat backups.
+pub async fn backup_process(app_state: Arc<AppState>, storage: Arc<dyn BackupStorageService>) {
+    info!("Backup worker started.");
+    let interval = Duration::seconds(app_state.config.backup_interval_secs as i64);
+    let mut ticker =
+        tokio::time::interval(std::time::Duration::from_secs(BACKUP_CHECK_INTERVAL_SECS));
+
+    loop {
+        ticker.tick().await;
+
+        let due = match storage.list().await {
+            Ok(backups) => backups
+                .first()
+                .is_none_or(|newest| Utc::now() - newest.created_at >= interval),
+            Err(e) => {
+                error!("Failed to list backups: {:?}", e);
+                continue;
+            }
+        };
+        if due {
+            match write_backup(&app_state, storage.as_ref()).await {
+                Ok(backup) => info!("Wrote backup {} ({} bytes).", backup.name, backup.size_bytes),
+                Err(e) => error!("Failed to write backup: {:?}", e),
+            }
+        }
+    }
+}
+
+/// Snapshots the database and stores it as a new backup.
+async fn write_backup(
+    app_state: &AppState,
+    storage: &dyn BackupStorageService,
+) -> PortResult<BackupInfo> {
+    let created_at = Utc::now();
+    let snapshot = app_state.db.get_backup_snapshot().await?;
+    let json = serde_json::to_vec(&BackupFile::new(snapshot, created_at))
+        .map_err(|e| PortError::Unexpected(e.to_string()))?;
+
+    let stamp = created_at.format("%Y%m%dT%H%M%SZ");
+    let (name, data) = match &app_state.config.document_encryption_key {
+        Some(key) => {
+            // The key was validated at startup.
+            let cipher = SecretCipher::from_base64_key(key)
+                .map_err(|e| PortError::Unexpected(e.to_string()))?;
+            let sealed = cipher
+                .encrypt(&json)
+                .map_err(|e| PortError::Unexpected(e.to_string()))?;
+            (format!("backup-{}.json.enc", stamp), sealed.into_bytes())
+        }
+        None => (format!("backup-{}.json", stamp), json),
+    };
+
+    storage.put(&name, &data).await?;
+    Ok(BackupInfo {
+        name,
+        size_bytes: data.len() as u64,
+        created_at,
+    })
+}
+
+//=========================================================================================
+// Request/Response Types
+//=========================================================================================
+
+#[derive(Serialize, ToSchema)]
+pub struct BackupItem {
+    pub name: String,
+    pub size_bytes: u64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ListBackupsResponse {
+    /// Newest first.
+    pub backups: Vec<BackupItem>,
+}
+
+impl From<BackupInfo> for BackupItem {
+    fn from(info: BackupInfo) -> Self {
+        Self {
+            name: info.name,
+            size_bytes: info.size_bytes,
+            created_at: info.created_at,
+        }
+    }
+}
+
+//=========================================================================================
+// Handlers
+//=========================================================================================
+
+fn backup_storage(
+    state: &AppState,
+) -> Result<&Arc<dyn BackupStorageService>, (StatusCode, String)> {
+    state.backup_storage.as_ref().ok_or((
+        StatusCode::NOT_IMPLEMENTED,
+        "Backups are not configured".to_string(),
+    ))
+}
+
+/// POST /admin/backups - Write a backup now
+#[utoipa::path(
+    post,
+    path = "/admin/backups",
+    responses(
+        (status = 201, description = "Backup written", body = BackupItem),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 403, description = "Forbidden - not an admin"),
+        (status = 500, description = "Internal server error"),
+        (status = 501, description = "Backups are not configured")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn create_backup_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let storage = backup_storage(&state)?;
+    let backup = write_backup(&state, storage.as_ref()).await.map_err(|e| {
+        error!("Failed to write backup: {:?}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to write backup".to_string())
+    })?;
+    Ok((StatusCode::CREATED, Json(BackupItem::from(backup))))
+}
+
+/// GET /admin/backups - List stored backups
+#[utoipa::path(
+    get,
+    path = "/admin/backups",
+    responses(
+        (status = 200, description = "Stored backups", body = ListBackupsResponse),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 403, description = "Forbidden - not an admin"),
+        (status = 500, description = "Internal server error"),
+        (status = 501, description = "Backups are not configured")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn list_backups_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let backups = backup_storage(&state)?.list().await.map_err(|e| {
+        error!("Failed to list backups: {:?}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to list backups".to_string())
+    })?;
+    Ok(Json(ListBackupsResponse {
+        backups: backups.into_iter().map(BackupItem::from).collect(),
+    }))
+}
+
+/// GET /admin/backups/{name} - Download a backup
+#[utoipa::path(
+    get,
+    path = "/admin/backups/{name}",
+    params(
+        ("name" = String, Path, description = "Backup file name")
+    ),
+    responses(
+        (status = 200, description = "The backup file", content_type = "application/octet-stream"),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 403, description = "Forbidden - not an admin"),
+        (status = 404, description = "Backup not found"),
+        (status = 500, description = "Internal server error"),
+        (status = 501, description = "Backups are not configured")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn download_backup_handler(
+    State(state): State<Arc<AppState>>,
+    Path(name): Path<String>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let data = backup_storage(&state)?
+        .get(&name)
+        .await
+        .map_err(|e| {
+            error!("Failed to read backup: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to read backup".to_string())
+        })?
+        .ok_or((StatusCode::NOT_FOUND, "Backup not found".to_string()))?;
+
+    // Stored names are plain file names, so they are safe in the header.
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/octet-stream".to_string()),
+            (header::CONTENT_DISPOSITION, format!("attachment; filename=\"{}\"", name)),
+        ],
+        data,
+    ))
+}
@@ -0,0 +1,332 @@
+//! services/api/src/web/calendar.rs
+//!
+//! Planned reading and review sessions, and the calendar feed that publishes them.
+//! Calendar apps can't log in, so the feed is public and its URL carries a signature
+//! of the user ID under `CALENDAR_FEED_SECRET` instead.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    Extension, Json,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use chrono::{DateTime, Duration, Utc};
+use hmac::{Hmac, Mac};
+use reading_assistant_core::{
+    calendar::render_ics,
+    domain::{PlanKind, PlannedSession},
+    ports::PortError,
+};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::sync::Arc;
+use tracing::error;
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::web::state::AppState;
+
+/// Past plans stay in the feed this long, so calendars keep recent history.
+const FEED_HISTORY_DAYS: i64 = 30;
+/// The longest title accepted for a plan, in characters.
+const MAX_TITLE_CHARS: usize = 200;
+
+//=========================================================================================
+// Request/Response Types
+//=========================================================================================
+
+/// What a planned session is for.
+#[derive(Serialize, Deserialize, ToSchema, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanKindBody {
+    /// Reading on from where the session left off.
+    Reading,
+    /// Going back over the session's notes and questions.
+    Review,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreatePlanRequest {
+    pub kind: PlanKindBody,
+    /// Shown in the calendar. Defaults to "Reading session" or "Review session".
+    #[serde(default)]
+    pub title: Option<String>,
+    pub starts_at: DateTime<Utc>,
+    /// 1 to 1440 minutes.
+    pub duration_minutes: u32,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct PlanItem {
+    pub plan_id: Uuid,
+    pub session_id: Uuid,
+    pub kind: PlanKindBody,
+    pub title: Option<String>,
+    pub starts_at: DateTime<Utc>,
+    pub duration_minutes: u32,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ListPlansResponse {
+    pub plans: Vec<PlanItem>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct CalendarFeedResponse {
+    /// The feed's path, relative to the API's base URL, for subscribing in Google or
+    /// Apple Calendar. Anyone with it can read the user's plans.
+    pub feed_url: String,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct FeedQuery {
+    /// The feed signature from `GET /me/calendar`.
+    pub sig: String,
+}
+
+impl From<PlanKindBody> for PlanKind {
+    fn from(kind: PlanKindBody) -> Self {
+        match kind {
+            PlanKindBody::Reading => PlanKind::Reading,
+            PlanKindBody::Review => PlanKind::Review,
+        }
+    }
+}
+
+impl From<PlanKind> for PlanKindBody {
+    fn from(kind: PlanKind) -> Self {
+        match kind {
+            PlanKind::Reading => PlanKindBody::Reading,
+            PlanKind::Review => PlanKindBody::Review,
+        }
+    }
+}
+
+impl From<PlannedSession> for PlanItem {
+    fn from(plan: PlannedSession) -> Self {
+        Self {
+            plan_id: plan.id,
+            session_id: plan.session_id,
+            kind: plan.kind.into(),
+            title: plan.title,
+            starts_at: plan.starts_at,
+            duration_minutes: plan.duration_minutes,
+        }
+    }
+}
+
+//=========================================================================================
+// Feed Signatures
+//=========================================================================================
+
+fn feed_mac(secret: &str, user_id: Uuid) -> Hmac<Sha256> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(b"calendar-feed:");
+    mac.update(user_id.as_bytes());
+    mac
+}
+
+fn feed_secret(state: &AppState) -> Result<&str, (StatusCode, String)> {
+    state.config.calendar_feed_secret.as_deref().ok_or((
+        StatusCode::NOT_IMPLEMENTED,
+        "Calendar feeds are not configured".to_string(),
+    ))
+}
+
+//=========================================================================================
+// Handlers
+//=========================================================================================
+
+/// POST /sessions/{session_id}/plans - Plan time to read or review a session
+#[utoipa::path(
+    post,
+    path = "/sessions/{session_id}/plans",
+    params(
+        ("session_id" = Uuid, Path, description = "Session ID")
+    ),
+    request_body = CreatePlanRequest,
+    responses(
+        (status = 201, description = "Plan created", body = PlanItem),
+        (status = 400, description = "Duration out of range or title too long"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Access denied"),
+        (status = 404, description = "Session not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn create_plan_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Path(session_id): Path<Uuid>,
+    Json(req): Json<CreatePlanRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let session = state.db.get_session_by_id(session_id).await.map_err(|e| {
+        error!("Failed to get session: {:?}", e);
+        (StatusCode::NOT_FOUND, "Session not found".to_string())
+    })?;
+    if session.user_id != user_id {
+        return Err((StatusCode::FORBIDDEN, "Access denied".to_string()));
+    }
+
+    if !(1..=24 * 60).contains(&req.duration_minutes) {
+        return Err((StatusCode::BAD_REQUEST, "duration_minutes must be between 1 and 1440".to_string()));
+    }
+    let title = req
+        .title
+        .map(|title| title.trim().to_string())
+        .filter(|title| !title.is_empty());
+    if title.as_ref().is_some_and(|title| title.chars().count() > MAX_TITLE_CHARS) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("title must be at most {} characters", MAX_TITLE_CHARS),
+        ));
+    }
+
+    let plan = state
+        .db
+        .create_planned_session(session_id, req.kind.into(), title.as_deref(), req.starts_at, req.duration_minutes)
+        .await
+        .map_err(|e| {
+            error!("Failed to create planned session: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to create plan".to_string())
+        })?;
+
+    Ok((StatusCode::CREATED, Json(PlanItem::from(plan))))
+}
+
+/// GET /me/plans - The user's upcoming planned sessions
+#[utoipa::path(
+    get,
+    path = "/me/plans",
+    responses(
+        (status = 200, description = "Upcoming plans, soonest first", body = ListPlansResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn list_plans_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let plans = state.db.get_planned_sessions(user_id, Utc::now()).await.map_err(|e| {
+        error!("Failed to fetch planned sessions: {:?}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch plans".to_string())
+    })?;
+
+    Ok(Json(ListPlansResponse {
+        plans: plans.into_iter().map(PlanItem::from).collect(),
+    }))
+}
+
+/// DELETE /me/plans/{plan_id} - Cancel a planned session
+#[utoipa::path(
+    delete,
+    path = "/me/plans/{plan_id}",
+    params(
+        ("plan_id" = Uuid, Path, description = "Plan ID")
+    ),
+    responses(
+        (status = 204, description = "Plan cancelled"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Plan not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn delete_plan_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Path(plan_id): Path<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    state
+        .db
+        .delete_planned_session(user_id, plan_id)
+        .await
+        .map_err(|e| match e {
+            PortError::NotFound(_) => (StatusCode::NOT_FOUND, "Plan not found".to_string()),
+            e => {
+                error!("Failed to delete planned session: {:?}", e);
+                (StatusCode::INTERNAL_SERVER_ERROR, "Failed to delete plan".to_string())
+            }
+        })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /me/calendar - The user's signed calendar feed URL
+#[utoipa::path(
+    get,
+    path = "/me/calendar",
+    responses(
+        (status = 200, description = "Feed URL", body = CalendarFeedResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 501, description = "Calendar feeds are not configured")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn calendar_feed_url_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let secret = feed_secret(&state)?;
+    let signature = URL_SAFE_NO_PAD.encode(feed_mac(secret, user_id).finalize().into_bytes());
+
+    Ok(Json(CalendarFeedResponse {
+        feed_url: format!("/calendar/{}/feed.ics?sig={}", user_id, signature),
+    }))
+}
+
+/// GET /calendar/{user_id}/feed.ics - A user's planned sessions as an iCalendar feed
+#[utoipa::path(
+    get,
+    path = "/calendar/{user_id}/feed.ics",
+    params(
+        ("user_id" = Uuid, Path, description = "User ID"),
+        FeedQuery
+    ),
+    responses(
+        (status = 200, description = "iCalendar feed", content_type = "text/calendar"),
+        (status = 403, description = "Invalid signature"),
+        (status = 500, description = "Internal server error"),
+        (status = 501, description = "Calendar feeds are not configured")
+    )
+)]
+pub async fn calendar_feed_handler(
+    State(state): State<Arc<AppState>>,
+    Path(user_id): Path<Uuid>,
+    Query(query): Query<FeedQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let secret = feed_secret(&state)?;
+    let signature = URL_SAFE_NO_PAD
+        .decode(&query.sig)
+        .map_err(|_| (StatusCode::FORBIDDEN, "Invalid signature".to_string()))?;
+    feed_mac(secret, user_id)
+        .verify_slice(&signature)
+        .map_err(|_| (StatusCode::FORBIDDEN, "Invalid signature".to_string()))?;
+
+    let now = Utc::now();
+    let plans = state
+        .db
+        .get_planned_sessions(user_id, now - Duration::days(FEED_HISTORY_DAYS))
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch planned sessions: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch plans".to_string())
+        })?;
+
+    Ok((
+        [(header::CONTENT_TYPE, "text/calendar; charset=utf-8")],
+        render_ics("Reading Assistant", &plans, now),
+    ))
+}
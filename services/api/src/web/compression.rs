@@ -0,0 +1,49 @@
+//! services/api/src/web/compression.rs
+//!
+//! Gzip/Brotli compression of REST responses. Transcripts, exports and long note
+//! lists can run to hundreds of kilobytes, so they are compressed for clients that
+//! accept it; audio is already compressed and is sent as is.
+
+use axum::{
+    body::HttpBody,
+    http::{header, Response},
+};
+use std::sync::Arc;
+use tower_http::compression::{
+    predicate::{DefaultPredicate, Predicate},
+    CompressionLayer,
+};
+
+use crate::config::Config;
+
+/// Skips responses whose content type starts with one of the excluded prefixes.
+#[derive(Clone)]
+pub struct NotForContentTypes(Arc<[String]>);
+
+impl Predicate for NotForContentTypes {
+    fn should_compress<B>(&self, response: &Response<B>) -> bool
+    where
+        B: HttpBody,
+    {
+        let content_type = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+            .to_lowercase();
+        !self
+            .0
+            .iter()
+            .any(|excluded| content_type.starts_with(excluded.as_str()))
+    }
+}
+
+/// The compression layer for the API. On top of the configured exclusions, the
+/// default predicate leaves tiny bodies, images and event streams alone.
+pub fn compression_layer(config: &Config) -> CompressionLayer<impl Predicate> {
+    let excluded = NotForContentTypes(config.compression_excluded_content_types.clone().into());
+    CompressionLayer::new()
+        .gzip(true)
+        .br(true)
+        .compress_when(DefaultPredicate::new().and(excluded))
+}
@@ -0,0 +1,224 @@
+//! services/api/src/web/gallery.rs
+//!
+//! Endpoints for the public document gallery: admins upload public-domain texts,
+//! and any user can start a session on one instead of finding a text to upload.
+
+use axum::{
+    extract::{Multipart, Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use reading_assistant_core::{domain::GalleryDocument, ports::PortError};
+use serde::Serialize;
+use std::sync::Arc;
+use tracing::error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::web::{rest::check_document_length, retrieval::index_document, state::AppState};
+
+//=========================================================================================
+// Request/Response Types
+//=========================================================================================
+
+#[derive(Serialize, ToSchema)]
+pub struct GalleryItem {
+    pub document_id: Uuid,
+    pub title: String,
+    pub description: String,
+    pub added_at: String, // ISO 8601 timestamp
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ListGalleryResponse {
+    pub documents: Vec<GalleryItem>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct GallerySessionResponse {
+    pub session_id: Uuid,
+    pub document_id: Uuid,
+}
+
+fn gallery_item(document: GalleryDocument) -> GalleryItem {
+    GalleryItem {
+        document_id: document.document_id,
+        title: document.title,
+        description: document.description,
+        added_at: document.added_at.to_rfc3339(),
+    }
+}
+
+//=========================================================================================
+// Handlers
+//=========================================================================================
+
+/// GET /gallery - List the public documents anyone can start a session on
+#[utoipa::path(
+    get,
+    path = "/gallery",
+    responses(
+        (status = 200, description = "Gallery retrieved successfully", body = ListGalleryResponse),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn list_gallery_handler(
+    State(state): State<Arc<AppState>>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let documents = state.db.get_gallery_documents().await.map_err(|e| {
+        error!("Failed to fetch gallery: {:?}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch gallery".to_string())
+    })?;
+
+    let documents = documents.into_iter().map(gallery_item).collect();
+    Ok((StatusCode::OK, Json(ListGalleryResponse { documents })))
+}
+
+/// POST /gallery/{document_id}/sessions - Start your own session on a gallery document
+#[utoipa::path(
+    post,
+    path = "/gallery/{document_id}/sessions",
+    params(
+        ("document_id" = Uuid, Path, description = "Document ID")
+    ),
+    responses(
+        (status = 201, description = "Session created", body = GallerySessionResponse),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 404, description = "Document not found in gallery"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn create_gallery_session_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Path(document_id): Path<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    match state.db.get_gallery_document(document_id).await {
+        Ok(_) => {}
+        Err(PortError::NotFound(_)) => {
+            return Err((StatusCode::NOT_FOUND, "Document not found in gallery".to_string()));
+        }
+        Err(e) => {
+            error!("Failed to get gallery document: {:?}", e);
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, "Failed to create session".to_string()));
+        }
+    }
+
+    let session = state
+        .db
+        .create_session(user_id, document_id, false)
+        .await
+        .map_err(|e| {
+            error!("Failed to create gallery session: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to create session".to_string())
+        })?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(GallerySessionResponse {
+            session_id: session.id,
+            document_id,
+        }),
+    ))
+}
+
+/// POST /admin/gallery - Upload a public-domain document to the gallery (admins only)
+#[utoipa::path(
+    post,
+    path = "/admin/gallery",
+    request_body(content_type = "multipart/form-data", description = "The document to publish, plus optional `title` and `description` fields. The title defaults to the file name."),
+    responses(
+        (status = 201, description = "Document added to the gallery", body = GalleryItem),
+        (status = 400, description = "Bad request (e.g., missing file)"),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 403, description = "Not an admin"),
+        (status = 413, description = "Document longer than the configured maximum"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn add_gallery_document_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let mut upload: Option<(String, String)> = None;
+    let mut title: Option<String> = None;
+    let mut description = String::new();
+
+    while let Some(field) = multipart.next_field().await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to read multipart data: {}", e),
+        )
+    })? {
+        let field_name = field.name().map(str::to_string);
+        if matches!(field_name.as_deref(), Some("title" | "description")) {
+            let value = field.text().await.map_err(|e| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    format!("Failed to read {} field: {}", field_name.as_deref().unwrap_or_default(), e),
+                )
+            })?;
+            let value = value.trim().to_string();
+            if field_name.as_deref() == Some("title") {
+                title = Some(value).filter(|title| !title.is_empty());
+            } else {
+                description = value;
+            }
+        } else if upload.is_none() {
+            let name = field.file_name().unwrap_or("untitled.txt").to_string();
+            let data = field.bytes().await.map_err(|e| {
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    format!("Failed to read file bytes: {}", e),
+                )
+            })?;
+            let text = String::from_utf8(data.to_vec()).map_err(|e| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    format!("Uploaded file is not valid UTF-8 text: {}", e),
+                )
+            })?;
+            upload = Some((name, text));
+        }
+    }
+
+    let (file_name, text) = upload.ok_or((
+        StatusCode::BAD_REQUEST,
+        "Multipart form must include a file".to_string(),
+    ))?;
+    check_document_length(&state, &text)?;
+    let title = title.unwrap_or_else(|| file_name.clone());
+
+    let db = &state.db;
+    let result = async {
+        let doc = db.create_document(user_id, &file_name, &text).await?;
+        db.add_gallery_document(doc.id, &title, &description).await
+    }
+    .await;
+
+    match result {
+        Ok(document) => {
+            tokio::spawn(index_document(state.clone(), document.document_id));
+            Ok((StatusCode::CREATED, Json(gallery_item(document))))
+        }
+        Err(e) => {
+            error!("Failed to add gallery document: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to add document to the gallery".to_string(),
+            ))
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! services/api/src/web/glossary.rs
+//!
+//! Spoken word definitions from the dictionary, and the per-session glossary of the
+//! words the user had defined.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use reading_assistant_core::{
+    domain::{Definition, GlossaryEntry, SpeechSettings},
+    ports::{PortError, PortResult},
+};
+use serde::Serialize;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{error, warn};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::web::{
+    protocol::ServerMessage,
+    qa_task::speak,
+    state::{AppState, SessionState},
+    ws_writer::WsSender,
+};
+
+//=========================================================================================
+// Request/Response Types
+//=========================================================================================
+
+#[derive(Serialize, ToSchema)]
+pub struct GlossaryItem {
+    pub entry_id: Uuid,
+    pub word: String,
+    pub part_of_speech: Option<String>,
+    pub meaning: String,
+    pub created_at: String, // ISO 8601 timestamp
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ListGlossaryResponse {
+    pub entries: Vec<GlossaryItem>,
+}
+
+fn glossary_item(entry: GlossaryEntry) -> GlossaryItem {
+    GlossaryItem {
+        entry_id: entry.id,
+        word: entry.word,
+        part_of_speech: entry.part_of_speech,
+        meaning: entry.meaning,
+        created_at: entry.created_at.to_rfc3339(),
+    }
+}
+
+//=========================================================================================
+// Spoken Definitions
+//=========================================================================================
+
+/// Looks `word` up and speaks its definition, saying the word and spelling it out, then
+/// adds it to the session's glossary. Returns `false` when the dictionary can't define
+/// it, so the caller can answer it as a question instead.
+pub async fn define_word(
+    app_state: &Arc<AppState>,
+    session_state_lock: &Arc<Mutex<SessionState>>,
+    ws_sender: &WsSender,
+    word: &str,
+    question_text: &str,
+    audio_free: bool,
+    speech: &SpeechSettings,
+) -> PortResult<bool> {
+    let definition = match app_state.dictionary_adapter.define(word).await {
+        Ok(definition) => definition,
+        Err(PortError::NotFound(_)) => return Ok(false),
+        Err(e) => {
+            warn!("Failed to look up '{}': {:?}", word, e);
+            return Ok(false);
+        }
+    };
+
+    let spoken = spoken_definition(&definition);
+    let session_id = {
+        let mut session = session_state_lock.lock().await;
+        session.last_question = Some(question_text.to_string());
+        session.last_answer = Some(spoken.clone());
+        session.last_answer_sources.clear();
+        session.session_id
+    };
+    // The definition is still spoken if it can't be kept.
+    if let Err(e) = app_state.db.save_glossary_entry(session_id, &definition).await {
+        error!("Failed to save glossary entry for session {}: {:?}", session_id, e);
+    }
+
+    let defined_msg = ServerMessage::WordDefined {
+        word: definition.word,
+        phonetic: definition.phonetic,
+        part_of_speech: definition.part_of_speech,
+        meaning: definition.meaning,
+    };
+    if ws_sender.send(&defined_msg).await.is_err() {
+        return Err(PortError::Unexpected(
+            "Failed to send WordDefined message.".to_string(),
+        ));
+    }
+    speak(app_state, ws_sender, &spoken, audio_free, speech).await?;
+    Ok(true)
+}
+
+/// What is said for a definition: the word, its spelling letter by letter, then its
+/// meaning, e.g. "Ephemeral. Spelled E, P, H, ... Adjective: lasting a very short time."
+fn spoken_definition(definition: &Definition) -> String {
+    let spelling = definition
+        .word
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .map(|c| c.to_uppercase().to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let meaning = match &definition.part_of_speech {
+        Some(part_of_speech) => format!("{}: {}", capitalize(part_of_speech), definition.meaning),
+        None => definition.meaning.clone(),
+    };
+    format!("{}. Spelled {}. {}", capitalize(&definition.word), spelling, meaning)
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+//=========================================================================================
+// Handlers
+//=========================================================================================
+
+/// GET /sessions/{session_id}/glossary - List the words defined during a session
+#[utoipa::path(
+    get,
+    path = "/sessions/{session_id}/glossary",
+    params(
+        ("session_id" = Uuid, Path, description = "Session ID")
+    ),
+    responses(
+        (status = 200, description = "Glossary retrieved successfully", body = ListGlossaryResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Session belongs to another user"),
+        (status = 404, description = "Session not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn list_glossary_handler(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Path(session_id): Path<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let session = app_state
+        .db
+        .get_session_by_id(session_id)
+        .await
+        .map_err(|e| {
+            error!("Failed to get session: {:?}", e);
+            (StatusCode::NOT_FOUND, "Session not found".to_string())
+        })?;
+
+    if session.user_id != user_id {
+        return Err((StatusCode::FORBIDDEN, "Access denied".to_string()));
+    }
+
+    let entries = app_state
+        .db
+        .get_glossary_for_session(session_id)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch glossary: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch glossary".to_string())
+        })?;
+
+    let entries = entries.into_iter().map(glossary_item).collect();
+    Ok((StatusCode::OK, Json(ListGlossaryResponse { entries })))
+}
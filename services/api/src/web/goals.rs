@@ -0,0 +1,303 @@
+//! services/api/src/web/goals.rs
+//!
+//! Reading goals ("30 minutes, 4 days a week"): endpoints to set and check one,
+//! recording of listening time as sessions close, and the worker that reminds users
+//! when the week's goal is about to slip and end their streak.
+
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Extension, Json};
+use chrono::{NaiveDate, Timelike, Utc};
+use reading_assistant_core::{
+    domain::{Notification, ReadingGoal},
+    goals::{self, GoalProgress},
+    ports::{NotificationService, PortResult},
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{error, info, warn};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::web::state::AppState;
+
+/// How often the worker checks for users to remind.
+const GOAL_REMINDER_INTERVAL_SECS: u64 = 60 * 60;
+
+//=========================================================================================
+// Request/Response Types
+//=========================================================================================
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct GoalBody {
+    /// Minutes to listen on a day for it to count, 1 to 1440.
+    pub minutes_per_day: u32,
+    /// Days a week (Monday to Sunday, UTC) to reach `minutes_per_day` on, 1 to 7.
+    pub days_per_week: u8,
+    /// Send a reminder when the streak is about to end. On by default.
+    #[serde(default = "default_reminders")]
+    pub reminders: bool,
+}
+
+fn default_reminders() -> bool {
+    true
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct GoalProgressBody {
+    /// The Monday the current week started on.
+    pub week_start: NaiveDate,
+    /// Days this week the daily minutes were reached.
+    pub days_met: u8,
+    pub minutes_today: u32,
+    /// Weeks in a row the goal was met, counting this week once it is met.
+    pub streak_weeks: u32,
+    /// The goal can only still be met this week by listening today and every day after.
+    pub at_risk: bool,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct GoalResponse {
+    pub goal: GoalBody,
+    pub progress: GoalProgressBody,
+}
+
+impl From<ReadingGoal> for GoalBody {
+    fn from(goal: ReadingGoal) -> Self {
+        Self {
+            minutes_per_day: goal.minutes_per_day,
+            days_per_week: goal.days_per_week,
+            reminders: goal.reminders,
+        }
+    }
+}
+
+impl From<GoalProgress> for GoalProgressBody {
+    fn from(progress: GoalProgress) -> Self {
+        Self {
+            week_start: progress.week_start,
+            days_met: progress.days_met,
+            minutes_today: progress.minutes_today,
+            streak_weeks: progress.streak_weeks,
+            at_risk: progress.at_risk,
+        }
+    }
+}
+
+/// The user's progress towards `goal` today.
+async fn goal_progress(
+    app_state: &Arc<AppState>,
+    user_id: Uuid,
+    goal: &ReadingGoal,
+    today: NaiveDate,
+) -> PortResult<GoalProgress> {
+    let days = app_state
+        .db
+        .get_listening_days(user_id, goals::history_start(today), today)
+        .await?;
+    Ok(goals::progress(goal, &days, today))
+}
+
+async fn goal_response(
+    app_state: &Arc<AppState>,
+    user_id: Uuid,
+    goal: ReadingGoal,
+) -> Result<GoalResponse, (StatusCode, String)> {
+    let progress = goal_progress(app_state, user_id, &goal, Utc::now().date_naive())
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch listening history: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch goal progress".to_string())
+        })?;
+    Ok(GoalResponse {
+        goal: goal.into(),
+        progress: progress.into(),
+    })
+}
+
+//=========================================================================================
+// Handlers
+//=========================================================================================
+
+/// GET /me/goal - The user's reading goal and this week's progress
+#[utoipa::path(
+    get,
+    path = "/me/goal",
+    responses(
+        (status = 200, description = "Goal and progress", body = GoalResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "No goal set"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn get_goal_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let goal = state
+        .db
+        .get_reading_goal(user_id)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch reading goal: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch goal".to_string())
+        })?
+        .ok_or((StatusCode::NOT_FOUND, "No goal set".to_string()))?;
+
+    Ok(Json(goal_response(&state, user_id, goal).await?))
+}
+
+/// PUT /me/goal - Set the user's reading goal
+#[utoipa::path(
+    put,
+    path = "/me/goal",
+    request_body = GoalBody,
+    responses(
+        (status = 200, description = "Goal saved", body = GoalResponse),
+        (status = 400, description = "Minutes or days out of range"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn update_goal_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Json(req): Json<GoalBody>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    if !(1..=24 * 60).contains(&req.minutes_per_day) {
+        return Err((StatusCode::BAD_REQUEST, "minutes_per_day must be between 1 and 1440".to_string()));
+    }
+    if !(1..=7).contains(&req.days_per_week) {
+        return Err((StatusCode::BAD_REQUEST, "days_per_week must be between 1 and 7".to_string()));
+    }
+
+    let goal = ReadingGoal {
+        minutes_per_day: req.minutes_per_day,
+        days_per_week: req.days_per_week,
+        reminders: req.reminders,
+    };
+    state.db.save_reading_goal(user_id, &goal).await.map_err(|e| {
+        error!("Failed to save reading goal: {:?}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to save goal".to_string())
+    })?;
+
+    Ok(Json(goal_response(&state, user_id, goal).await?))
+}
+
+/// DELETE /me/goal - Remove the user's reading goal
+#[utoipa::path(
+    delete,
+    path = "/me/goal",
+    responses(
+        (status = 204, description = "Goal removed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn delete_goal_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    state.db.delete_reading_goal(user_id).await.map_err(|e| {
+        error!("Failed to delete reading goal: {:?}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to delete goal".to_string())
+    })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+//=========================================================================================
+// Listening Time
+//=========================================================================================
+
+/// Adds a closed session's listening time to the user's total for today, counted
+/// whether or not they have a goal so progress is there once they set one.
+pub async fn record_listening_time(app_state: &Arc<AppState>, user_id: Uuid, seconds: u64) {
+    if seconds == 0 {
+        return;
+    }
+    let today = Utc::now().date_naive();
+    if let Err(e) = app_state.db.add_listening_time(user_id, today, seconds).await {
+        warn!("Failed to record listening time for user {}: {:?}", user_id, e);
+    }
+}
+
+//=========================================================================================
+// Reminder Worker
+//=========================================================================================
+
+/// The main loop of the goal reminder worker. Runs for the lifetime of the server.
+pub async fn goal_reminder_process(
+    app_state: Arc<AppState>,
+    notifier: Arc<dyn NotificationService>,
+) {
+    info!("Goal reminder worker started.");
+    let mut ticker =
+        tokio::time::interval(std::time::Duration::from_secs(GOAL_REMINDER_INTERVAL_SECS));
+
+    loop {
+        ticker.tick().await;
+
+        // Reminders wait until the day is far enough along to be worth one.
+        let now = Utc::now();
+        if now.hour() < app_state.config.goal_reminder_hour_utc {
+            continue;
+        }
+        if let Err(e) = send_due_reminders(&app_state, notifier.as_ref(), now.date_naive()).await {
+            error!("Failed to send goal reminders: {:?}", e);
+        }
+    }
+}
+
+/// Reminds every user whose streak ends unless they listen today.
+async fn send_due_reminders(
+    app_state: &Arc<AppState>,
+    notifier: &dyn NotificationService,
+    today: NaiveDate,
+) -> PortResult<()> {
+    let users = app_state.db.get_users_due_goal_reminder(today).await?;
+
+    for (user_id, goal) in users {
+        let progress = match goal_progress(app_state, user_id, &goal, today).await {
+            Ok(progress) => progress,
+            Err(e) => {
+                warn!("Failed to check goal progress for user {}: {:?}", user_id, e);
+                continue;
+            }
+        };
+        if !progress.at_risk {
+            continue;
+        }
+
+        let notification = reminder(&goal, &progress);
+        if let Err(e) = notifier.notify(user_id, &notification).await {
+            warn!("Failed to send goal reminder to user {}: {:?}", user_id, e);
+            continue;
+        }
+        if let Err(e) = app_state.db.mark_goal_reminded(user_id, today).await {
+            warn!("Failed to mark user {} reminded: {:?}", user_id, e);
+        }
+    }
+
+    Ok(())
+}
+
+fn reminder(goal: &ReadingGoal, progress: &GoalProgress) -> Notification {
+    let weeks = if progress.streak_weeks == 1 { "week" } else { "weeks" };
+    Notification {
+        title: "Keep your reading streak going".to_string(),
+        body: format!(
+            "You've met your reading goal {} {} in a row. Listen for {} minutes today to stay on track this week.",
+            progress.streak_weeks,
+            weeks,
+            goal.minutes_per_day.saturating_sub(progress.minutes_today),
+        ),
+    }
+}
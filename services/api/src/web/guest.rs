@@ -0,0 +1,213 @@
+//! services/api/src/web/guest.rs
+//!
+//! Anonymous trial sessions. A guest gets a short-lived account without signing up,
+//! limited to a single small document. Signing up from a guest session converts the
+//! guest into a regular account that keeps its session; otherwise a background
+//! worker deletes the guest and its data once it expires.
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Extension, Json,
+};
+use chrono::{DateTime, Duration, Utc};
+use serde::Serialize;
+use std::{net::SocketAddr, sync::Arc};
+use tracing::{error, info};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::web::{middleware::TenantId, state::AppState};
+
+/// How often the cleanup worker looks for expired guests.
+const GUEST_CLEANUP_INTERVAL_SECS: u64 = 300;
+
+/// How many reading sessions a guest may create.
+const GUEST_MAX_SESSIONS: usize = 1;
+
+//=========================================================================================
+// Request/Response Types
+//=========================================================================================
+
+#[derive(Serialize, ToSchema)]
+pub struct GuestSessionResponse {
+    pub user_id: Uuid,
+    /// When the guest account and everything in it will be deleted.
+    pub expires_at: DateTime<Utc>,
+    /// The longest document, in characters, the guest may read.
+    pub max_document_chars: usize,
+}
+
+//=========================================================================================
+// Handlers
+//=========================================================================================
+
+/// POST /auth/guest - Start an anonymous trial session
+#[utoipa::path(
+    post,
+    path = "/auth/guest",
+    responses(
+        (status = 201, description = "Guest session created", body = GuestSessionResponse),
+        (status = 404, description = "Guest mode is disabled"),
+        (status = 429, description = "Too many guest sessions from this address"),
+        (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn create_guest_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(TenantId(tenant_id)): Extension<TenantId>,
+    ConnectInfo(addr): ConnectInfo<SocketAddr>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    if !state.config.guest_mode_enabled {
+        return Err((StatusCode::NOT_FOUND, "Guest mode is disabled".to_string()));
+    }
+
+    // 1. Rate-limit by client address
+    let client_ip = addr.ip().to_string();
+    let recent = state
+        .db
+        .count_guests_created_since(&client_ip, Utc::now() - Duration::hours(1))
+        .await
+        .map_err(|e| {
+            error!("Failed to count recent guests: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to create guest".to_string())
+        })?;
+    if recent >= state.config.guest_max_per_ip_per_hour {
+        return Err((
+            StatusCode::TOO_MANY_REQUESTS,
+            "Too many guest sessions, please sign up to continue".to_string(),
+        ));
+    }
+
+    // 2. Create the guest and an auth session that ends with it
+    let lifetime = Duration::minutes(state.config.guest_session_minutes);
+    let expires_at = Utc::now() + lifetime;
+    let user = state
+        .db
+        .create_guest_user(tenant_id, expires_at, &client_ip)
+        .await
+        .map_err(|e| {
+            error!("Failed to create guest user: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to create guest".to_string())
+        })?;
+
+    let auth_session_id = Uuid::new_v4().to_string();
+    state
+        .db
+        .create_auth_session(&auth_session_id, user.user_id, expires_at)
+        .await
+        .map_err(|e| {
+            error!("Failed to create auth session: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to create session".to_string())
+        })?;
+
+    // 3. Set the session cookie
+    let cookie = format!(
+        "session={}; HttpOnly; SameSite=Lax; Path=/; Max-Age={}",
+        auth_session_id,
+        lifetime.num_seconds()
+    );
+
+    let response = GuestSessionResponse {
+        user_id: user.user_id,
+        expires_at,
+        max_document_chars: state.config.guest_max_document_chars,
+    };
+
+    Ok((
+        StatusCode::CREATED,
+        [(header::SET_COOKIE, cookie)],
+        Json(response),
+    ))
+}
+
+//=========================================================================================
+// Guest Limits
+//=========================================================================================
+
+/// Middleware for routes guests may not use, such as importing documents or creating
+/// workspaces. Must run after `require_auth`. Returns 403 Forbidden for guests.
+pub async fn reject_guests(
+    State(state): State<Arc<AppState>>,
+    req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let user_id = req
+        .extensions()
+        .get::<Uuid>()
+        .copied()
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    let user = state.db.get_user(user_id).await.map_err(|e| {
+        error!("Failed to fetch user: {:?}", e);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    if user.is_guest {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    Ok(next.run(req).await)
+}
+
+/// Checks an upload against the guest limits. Does nothing for regular users.
+pub async fn check_guest_upload(
+    state: &AppState,
+    user_id: Uuid,
+    document_text: &str,
+) -> Result<(), (StatusCode, String)> {
+    let user = state.db.get_user(user_id).await.map_err(|e| {
+        error!("Failed to fetch user: {:?}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to create session".to_string())
+    })?;
+    if !user.is_guest {
+        return Ok(());
+    }
+
+    let max_chars = state.config.guest_max_document_chars;
+    if document_text.chars().count() > max_chars {
+        return Err((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!("Guest documents are limited to {} characters, please sign up to read longer ones", max_chars),
+        ));
+    }
+
+    let sessions = state
+        .db
+        .get_sessions_by_user(user_id, None)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch sessions: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to create session".to_string())
+        })?;
+    if sessions.len() >= GUEST_MAX_SESSIONS {
+        return Err((
+            StatusCode::FORBIDDEN,
+            "Guests can read one document, please sign up to read more".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+//=========================================================================================
+// Cleanup Worker
+//=========================================================================================
+
+/// Deletes expired guests and their data. Runs for the lifetime of the server.
+pub async fn guest_cleanup_process(app_state: Arc<AppState>) {
+    info!("Guest cleanup worker started.");
+    let mut ticker =
+        tokio::time::interval(std::time::Duration::from_secs(GUEST_CLEANUP_INTERVAL_SECS));
+
+    loop {
+        ticker.tick().await;
+
+        match app_state.db.delete_expired_guests().await {
+            Ok(0) => {}
+            Ok(deleted) => info!("Deleted {} expired guest accounts.", deleted),
+            Err(e) => error!("Failed to delete expired guests: {:?}", e),
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! services/api/src/web/handoff.rs
+//!
+//! "Continue on phone": moving a live session to another device. The device that is
+//! listening asks for a handoff token (`StartHandoff`) and shows it, e.g. as a QR code;
+//! the new device passes it in its `Init`, which closes the old connection and resumes
+//! at the sentence the old device was playing.
+//!
+//! Connections live in this process, so the registry is kept in memory.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tokio_util::sync::CancellationToken;
+use uuid::Uuid;
+
+/// How long a handoff token can be claimed for.
+pub const HANDOFF_TOKEN_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// The connection currently serving a session.
+struct LiveConnection {
+    connection_id: Uuid,
+    /// Cancelled when another device claims the session.
+    handed_off: CancellationToken,
+    handoff: Option<PendingHandoff>,
+}
+
+/// A handoff offered by the connection, waiting for the new device.
+struct PendingHandoff {
+    token: String,
+    sentence_index: usize,
+    expires_at: Instant,
+}
+
+/// The connections serving each session, and their pending handoffs.
+#[derive(Default)]
+pub struct SessionHandoffs {
+    live: Mutex<HashMap<Uuid, LiveConnection>>,
+}
+
+/// A connection's place in the registry, returned by `SessionHandoffs::register`.
+pub struct Registration {
+    pub connection_id: Uuid,
+    /// Cancelled when the session is handed off to another device.
+    pub handed_off: CancellationToken,
+}
+
+impl SessionHandoffs {
+    /// Records `session_id` as served by a new connection, replacing any other.
+    pub fn register(&self, session_id: Uuid) -> Registration {
+        let registration = Registration {
+            connection_id: Uuid::new_v4(),
+            handed_off: CancellationToken::new(),
+        };
+        self.live.lock().unwrap().insert(
+            session_id,
+            LiveConnection {
+                connection_id: registration.connection_id,
+                handed_off: registration.handed_off.clone(),
+                handoff: None,
+            },
+        );
+        registration
+    }
+
+    /// Forgets the connection once it closes, unless another has taken the session since.
+    pub fn unregister(&self, session_id: Uuid, connection_id: Uuid) {
+        let mut live = self.live.lock().unwrap();
+        if live.get(&session_id).is_some_and(|conn| conn.connection_id == connection_id) {
+            live.remove(&session_id);
+        }
+    }
+
+    /// Offers the session to another device, to resume at `sentence_index`. Returns the
+    /// token the new device claims it with, replacing any earlier one, or `None` if the
+    /// connection no longer serves the session.
+    pub fn offer(&self, session_id: Uuid, connection_id: Uuid, sentence_index: usize) -> Option<String> {
+        let mut live = self.live.lock().unwrap();
+        let conn = live
+            .get_mut(&session_id)
+            .filter(|conn| conn.connection_id == connection_id)?;
+        let token = Uuid::new_v4().simple().to_string();
+        conn.handoff = Some(PendingHandoff {
+            token: token.clone(),
+            sentence_index,
+            expires_at: Instant::now() + HANDOFF_TOKEN_TTL,
+        });
+        Some(token)
+    }
+
+    /// Claims a handoff: the old connection is told to close and forgotten. Returns the
+    /// sentence to resume at, or `None` if the token is wrong or has expired.
+    pub fn claim(&self, session_id: Uuid, token: &str) -> Option<usize> {
+        let mut live = self.live.lock().unwrap();
+        let handoff = live.get(&session_id)?.handoff.as_ref()?;
+        if handoff.token != token || handoff.expires_at < Instant::now() {
+            return None;
+        }
+        let sentence_index = handoff.sentence_index;
+        let conn = live.remove(&session_id)?;
+        conn.handed_off.cancel();
+        Some(sentence_index)
+    }
+}
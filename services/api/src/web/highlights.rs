@@ -0,0 +1,305 @@
+//! services/api/src/web/highlights.rs
+//!
+//! Imports highlights and notes from other reading apps (Kindle's `My Clippings.txt`
+//! or a Readwise CSV export). Each book becomes a document made of its highlights,
+//! or its highlights are attached to a document the user already has. Either way,
+//! every highlight is saved as a note anchored to the passage it came from.
+
+use axum::{
+    extract::{Multipart, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use chrono::Utc;
+use reading_assistant_core::{
+    chunker::chunk_into_sentences,
+    domain::{Note, NoteSource, Session},
+    highlights::{parse_highlights, BookHighlights, Highlight},
+    ports::{PortError, PortResult},
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{error, info};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::web::{retrieval::index_document, state::AppState, tags::suggest_and_save_tags};
+
+//=========================================================================================
+// Request/Response Types
+//=========================================================================================
+
+#[derive(Deserialize, IntoParams)]
+pub struct ImportHighlightsQuery {
+    /// Attach every imported highlight to this existing document instead of creating
+    /// a document per book.
+    pub document_id: Option<Uuid>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ImportedBook {
+    pub title: String,
+    pub document_id: Uuid,
+    /// The session the highlights were saved to, for opening the notes.
+    pub session_id: Uuid,
+    /// How many highlights (and standalone notes) were imported.
+    pub highlights: usize,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ImportHighlightsResponse {
+    pub books: Vec<ImportedBook>,
+}
+
+//=========================================================================================
+// Handlers
+//=========================================================================================
+
+/// POST /imports/highlights - Import Kindle clippings or a Readwise CSV export
+#[utoipa::path(
+    post,
+    path = "/imports/highlights",
+    params(ImportHighlightsQuery),
+    request_body(content_type = "multipart/form-data", description = "A Kindle `My Clippings.txt` file or a Readwise CSV export."),
+    responses(
+        (status = 201, description = "Highlights imported", body = ImportHighlightsResponse),
+        (status = 400, description = "Bad request (e.g., missing file or unreadable export)"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Document not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn import_highlights_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Query(query): Query<ImportHighlightsQuery>,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let field = multipart
+        .next_field()
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to read multipart data: {}", e),
+            )
+        })?
+        .ok_or((
+            StatusCode::BAD_REQUEST,
+            "Multipart form must include a file".to_string(),
+        ))?;
+
+    let file_name = field.file_name().unwrap_or("My Clippings.txt").to_string();
+    let data = field.bytes().await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to read file bytes: {}", e),
+        )
+    })?;
+    let text = String::from_utf8(data.to_vec()).map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            format!("Uploaded file is not valid UTF-8 text: {}", e),
+        )
+    })?;
+
+    let books = parse_highlights(&file_name, &text)
+        .map_err(|e| (StatusCode::BAD_REQUEST, format!("Could not read the export: {}", e)))?;
+    if books.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            "The file contains no highlights".to_string(),
+        ));
+    }
+
+    let result = match query.document_id {
+        Some(document_id) => attach_to_document(&state, user_id, document_id, books).await,
+        None => create_documents(&state, user_id, books).await,
+    };
+
+    match result {
+        Ok(books) => {
+            info!("Imported highlights from {} books for user {}.", books.len(), user_id);
+            Ok((StatusCode::CREATED, Json(ImportHighlightsResponse { books })))
+        }
+        Err(PortError::NotFound(_)) => Err((
+            StatusCode::NOT_FOUND,
+            "Document not found".to_string(),
+        )),
+        Err(e) => {
+            error!("Failed to import highlights: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to import highlights".to_string(),
+            ))
+        }
+    }
+}
+
+//=========================================================================================
+// Import
+//=========================================================================================
+
+/// Creates one document (and session) per book, read as its highlights in order.
+async fn create_documents(
+    state: &Arc<AppState>,
+    user_id: Uuid,
+    books: Vec<BookHighlights>,
+) -> PortResult<Vec<ImportedBook>> {
+    let mut imported = Vec::with_capacity(books.len());
+
+    for book in books {
+        let text = book_text(&book);
+        let doc = state.db.create_document(user_id, &book.title, &text).await?;
+        let session = state.db.create_session(user_id, doc.id, false).await?;
+        let count = save_highlight_notes(state, &session, &book.highlights).await?;
+
+        tokio::spawn(index_document(state.clone(), doc.id));
+        tokio::spawn(suggest_and_save_tags(state.clone(), user_id, doc.id, text));
+        imported.push(ImportedBook {
+            title: book.title,
+            document_id: doc.id,
+            session_id: session.id,
+            highlights: count,
+        });
+    }
+
+    Ok(imported)
+}
+
+/// Saves every book's highlights as notes on the user's latest session of an
+/// existing document, starting one if they've never read it.
+async fn attach_to_document(
+    state: &Arc<AppState>,
+    user_id: Uuid,
+    document_id: Uuid,
+    books: Vec<BookHighlights>,
+) -> PortResult<Vec<ImportedBook>> {
+    let doc = state.db.get_document_by_id(document_id).await?;
+    if doc.user_id != user_id {
+        return Err(PortError::NotFound(format!("Document {} not found", document_id)));
+    }
+
+    let latest = state
+        .db
+        .get_sessions_by_user(user_id, None)
+        .await?
+        .into_iter()
+        .filter(|s| s.document_id == document_id)
+        .max_by_key(|s| s.last_accessed_at);
+    let session = match latest {
+        Some(session) => session,
+        None => state.db.create_session(user_id, document_id, false).await?,
+    };
+
+    let mut imported = Vec::with_capacity(books.len());
+    for book in books {
+        let count = save_highlight_notes(state, &session, &book.highlights).await?;
+        imported.push(ImportedBook {
+            title: book.title,
+            document_id,
+            session_id: session.id,
+            highlights: count,
+        });
+    }
+
+    Ok(imported)
+}
+
+/// The text of a document made from a book's highlights: an introduction, then each
+/// highlight as its own paragraph.
+fn book_text(book: &BookHighlights) -> String {
+    let mut paragraphs = vec![match &book.author {
+        Some(author) => format!("Highlights from {} by {}.", book.title, author),
+        None => format!("Highlights from {}.", book.title),
+    }];
+    paragraphs.extend(
+        book.highlights
+            .iter()
+            .map(|h| h.text.trim())
+            .filter(|text| !text.is_empty())
+            .map(|text| match text.ends_with(['.', '?', '!']) {
+                true => text.to_string(),
+                false => format!("{}.", text),
+            }),
+    );
+    paragraphs.join("\n\n")
+}
+
+/// Saves one imported note per highlight, anchored to the sentences of the session's
+/// document it was found in. Returns how many were saved.
+async fn save_highlight_notes(
+    state: &Arc<AppState>,
+    session: &Session,
+    highlights: &[Highlight],
+) -> PortResult<usize> {
+    let mut chunks = state.db.get_document_chunks(session.document_id).await?;
+    if chunks.is_empty() {
+        let doc = state.db.get_document_by_id(session.document_id).await?;
+        chunks = chunk_into_sentences(&doc.original_text);
+    }
+    let normalized_chunks: Vec<String> = chunks.iter().map(|c| normalize(c)).collect();
+
+    for highlight in highlights {
+        let anchor = find_anchor(&normalized_chunks, &highlight.text);
+        let note = Note {
+            id: Uuid::new_v4(),
+            session_id: session.id,
+            generated_note_text: note_text(highlight),
+            created_at: Utc::now(),
+            source_start_index: anchor.as_ref().map(|range| range.start),
+            source_end_index: anchor.map(|range| range.end),
+            source: NoteSource::Imported,
+        };
+        state.db.save_note(note).await?;
+    }
+
+    Ok(highlights.len())
+}
+
+/// "“highlight” — note", or whichever of the two the user has.
+fn note_text(highlight: &Highlight) -> String {
+    match (highlight.text.trim(), &highlight.note) {
+        ("", Some(note)) => note.clone(),
+        (text, Some(note)) => format!("“{}” — {}", text, note),
+        (text, None) => format!("“{}”", text),
+    }
+}
+
+/// The sentences (as a chunk range) a highlight's first and last sentences were found
+/// in. Kindle highlights often start or end mid-sentence, so they're matched as
+/// substrings, ignoring case and punctuation.
+fn find_anchor(normalized_chunks: &[String], text: &str) -> Option<std::ops::Range<usize>> {
+    let sentences: Vec<String> = chunk_into_sentences(text)
+        .iter()
+        .map(|s| normalize(s))
+        .filter(|s| !s.is_empty())
+        .collect();
+    let first = sentences.first()?;
+    let last = sentences.last()?;
+
+    let find_from = |from: usize, needle: &str| {
+        normalized_chunks[from..]
+            .iter()
+            .position(|chunk| chunk.contains(needle))
+            .map(|offset| from + offset)
+    };
+    let start = find_from(0, first)?;
+    let end = find_from(start, last).unwrap_or(start);
+    Some(start..end + 1)
+}
+
+/// Lowercases and keeps only letters, digits and single spaces.
+fn normalize(text: &str) -> String {
+    text.chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect::<String>()
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,60 @@
+//! services/api/src/web/hotword.rs
+//!
+//! Server-side keyword spotting on the low-bitrate microphone sidechannel, so a
+//! user can interrupt the reader hands-free by saying the hot-word.
+
+use crate::web::{
+    protocol::ServerMessage,
+    state::{AppState, SessionEvent, SessionState},
+    ws_writer::WsSender,
+};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+/// Sidechannel clips larger than this are not "low-bitrate" and are dropped unchecked.
+pub const MAX_SIDECHANNEL_CLIP_BYTES: usize = 64 * 1024;
+
+/// Checks one sidechannel clip for the hot-word and, if heard, interrupts the reader.
+/// The caller sets `hotword_check_in_flight` before spawning this; it is cleared here.
+pub async fn check_hotword_clip(
+    app_state: Arc<AppState>,
+    session_state_lock: Arc<Mutex<SessionState>>,
+    ws_sender: WsSender,
+    clip: Vec<u8>,
+) {
+    let heard = match app_state.sst_adapter.transcribe_audio(&clip).await {
+        Ok(transcript) => {
+            let transcript = transcript.to_lowercase();
+            app_state
+                .config
+                .hotword_phrases
+                .iter()
+                .any(|phrase| transcript.contains(phrase.as_str()))
+        }
+        Err(e) => {
+            warn!("Failed to transcribe hot-word clip: {:?}", e);
+            false
+        }
+    };
+
+    let mut session = session_state_lock.lock().await;
+    session.hotword_check_in_flight = false;
+
+    // The user may have interrupted or paused by hand while the clip was checked.
+    if !heard || !session.current_mode.accepts_hotword_clips() {
+        return;
+    }
+
+    info!("Hot-word detected. Interrupting reading task.");
+    if let Err(e) = session.apply(SessionEvent::Interrupt) {
+        warn!("Failed to interrupt for hot-word: {}", e);
+        return;
+    }
+    drop(session);
+
+    let msg = ServerMessage::HotwordDetected;
+    if ws_sender.send(&msg).await.is_err() {
+        warn!("Failed to send HotwordDetected message. Client may have disconnected.");
+    }
+}
@@ -0,0 +1,482 @@
+//! services/api/src/web/integrations.rs
+//!
+//! Endpoints for connecting third-party integrations and exporting session data to them.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use reading_assistant_core::ports::{DocumentImportService, NoteExportService, PortError};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::web::{
+    rest::check_document_length, retrieval::index_document, state::AppState,
+    tags::suggest_and_save_tags,
+};
+
+/// The provider key under which Notion tokens are stored.
+const NOTION_PROVIDER: &str = "notion";
+/// The provider key under which Google Drive tokens are stored.
+const GOOGLE_PROVIDER: &str = "google";
+
+//=========================================================================================
+// Request/Response Types
+//=========================================================================================
+
+#[derive(Serialize, ToSchema)]
+pub struct AuthorizeUrlResponse {
+    pub url: String,
+}
+
+#[derive(Deserialize)]
+pub struct OAuthCallbackQuery {
+    pub code: String,
+    pub state: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct NotionExportRequest {
+    /// The Notion page under which the export page is created.
+    pub parent_page_id: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ExportResponse {
+    pub url: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ExternalDocumentItem {
+    pub id: String,
+    pub title: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ListExternalDocumentsResponse {
+    pub documents: Vec<ExternalDocumentItem>,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct GoogleImportRequest {
+    /// The Drive file ID of the Google Doc to import.
+    pub file_id: String,
+    /// Run the LLM cleanup pass over the text before storing it.
+    #[serde(default)]
+    pub cleanup: bool,
+    /// Create the session in child/education mode.
+    #[serde(default)]
+    pub education_mode: bool,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ImportResponse {
+    pub document_id: Uuid,
+    pub session_id: Uuid,
+    pub title: String,
+}
+
+//=========================================================================================
+// Helpers
+//=========================================================================================
+
+fn notion_adapter(
+    state: &AppState,
+) -> Result<&Arc<dyn NoteExportService>, (StatusCode, String)> {
+    state.notion_adapter.as_ref().ok_or((
+        StatusCode::NOT_IMPLEMENTED,
+        "Notion integration is not configured".to_string(),
+    ))
+}
+
+fn google_drive_adapter(
+    state: &AppState,
+) -> Result<&Arc<dyn DocumentImportService>, (StatusCode, String)> {
+    state.google_drive_adapter.as_ref().ok_or((
+        StatusCode::NOT_IMPLEMENTED,
+        "Google Drive integration is not configured".to_string(),
+    ))
+}
+
+/// Loads the user's stored Google token, mapping a missing one to a 400.
+async fn google_token(state: &AppState, user_id: Uuid) -> Result<String, (StatusCode, String)> {
+    state
+        .db
+        .get_integration_token(user_id, GOOGLE_PROVIDER)
+        .await
+        .map_err(|e| match e {
+            PortError::NotFound(_) => (
+                StatusCode::BAD_REQUEST,
+                "Google Drive is not connected for this user".to_string(),
+            ),
+            _ => {
+                error!("Failed to load Google token: {:?}", e);
+                (StatusCode::INTERNAL_SERVER_ERROR, "Failed to load integration".to_string())
+            }
+        })
+}
+
+/// Maps errors from the Drive API onto HTTP responses.
+fn google_error(e: PortError) -> (StatusCode, String) {
+    match e {
+        PortError::Unauthorized => (
+            StatusCode::BAD_REQUEST,
+            "Google Drive access expired; please reconnect".to_string(),
+        ),
+        PortError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
+        _ => {
+            error!("Google Drive request failed: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Google Drive request failed".to_string())
+        }
+    }
+}
+
+//=========================================================================================
+// Handlers
+//=========================================================================================
+
+/// GET /integrations/notion/authorize - Get the Notion OAuth consent URL
+#[utoipa::path(
+    get,
+    path = "/integrations/notion/authorize",
+    responses(
+        (status = 200, description = "Authorization URL", body = AuthorizeUrlResponse),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 501, description = "Notion integration is not configured")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn notion_authorize_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let adapter = notion_adapter(&state)?;
+    let url = adapter.authorize_url(&user_id.to_string());
+    Ok(Json(AuthorizeUrlResponse { url }))
+}
+
+/// GET /integrations/notion/callback - OAuth redirect target that stores the user's token
+#[utoipa::path(
+    get,
+    path = "/integrations/notion/callback",
+    params(
+        ("code" = String, Query, description = "OAuth authorization code"),
+        ("state" = String, Query, description = "OAuth state issued by the authorize endpoint")
+    ),
+    responses(
+        (status = 200, description = "Notion connected"),
+        (status = 400, description = "State mismatch"),
+        (status = 401, description = "Unauthorized or code rejected by Notion"),
+        (status = 501, description = "Notion integration is not configured")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn notion_callback_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Query(query): Query<OAuthCallbackQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let adapter = notion_adapter(&state)?;
+
+    // 1. The state must round-trip the user that started the flow
+    if query.state != user_id.to_string() {
+        return Err((StatusCode::BAD_REQUEST, "OAuth state mismatch".to_string()));
+    }
+
+    // 2. Exchange the code for an access token
+    let access_token = adapter.exchange_code(&query.code).await.map_err(|e| {
+        error!("Failed to exchange Notion OAuth code: {:?}", e);
+        (StatusCode::UNAUTHORIZED, "Notion rejected the authorization".to_string())
+    })?;
+
+    // 3. Store the token for later exports
+    state
+        .db
+        .save_integration_token(user_id, NOTION_PROVIDER, &access_token)
+        .await
+        .map_err(|e| {
+            error!("Failed to save Notion token: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to save integration".to_string())
+        })?;
+
+    Ok((StatusCode::OK, "Notion connected"))
+}
+
+/// POST /sessions/{session_id}/export/notion - Push a session's notes and summary to Notion
+#[utoipa::path(
+    post,
+    path = "/sessions/{session_id}/export/notion",
+    params(
+        ("session_id" = Uuid, Path, description = "Session ID")
+    ),
+    request_body = NotionExportRequest,
+    responses(
+        (status = 201, description = "Notes exported", body = ExportResponse),
+        (status = 400, description = "Notion is not connected for this user"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Access denied"),
+        (status = 404, description = "Session or parent page not found"),
+        (status = 501, description = "Notion integration is not configured"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn export_notion_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Path(session_id): Path<Uuid>,
+    Json(req): Json<NotionExportRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let adapter = notion_adapter(&state)?;
+
+    // 1. Verify the session belongs to this user
+    let session = state.db.get_session_by_id(session_id).await.map_err(|e| {
+        error!("Failed to get session: {:?}", e);
+        (StatusCode::NOT_FOUND, "Session not found".to_string())
+    })?;
+    if session.user_id != user_id {
+        return Err((StatusCode::FORBIDDEN, "Access denied".to_string()));
+    }
+
+    // 2. Look up the user's Notion token
+    let access_token = state
+        .db
+        .get_integration_token(user_id, NOTION_PROVIDER)
+        .await
+        .map_err(|e| match e {
+            PortError::NotFound(_) => (
+                StatusCode::BAD_REQUEST,
+                "Notion is not connected for this user".to_string(),
+            ),
+            _ => {
+                error!("Failed to load Notion token: {:?}", e);
+                (StatusCode::INTERNAL_SERVER_ERROR, "Failed to export notes".to_string())
+            }
+        })?;
+
+    // 3. Gather the notes and summarize them
+    let notes = state.db.get_notes_for_session(session_id).await.map_err(|e| {
+        error!("Failed to fetch notes: {:?}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch notes".to_string())
+    })?;
+    let summary = state.notes_adapter.summarize_notes(&notes).await.map_err(|e| {
+        error!("Failed to summarize notes: {:?}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to summarize notes".to_string())
+    })?;
+
+    // 4. Push to Notion
+    let title = format!("Reading notes – {}", session.created_at.format("%Y-%m-%d"));
+    let url = adapter
+        .export_notes(&access_token, &req.parent_page_id, &title, &summary, &notes)
+        .await
+        .map_err(|e| match e {
+            PortError::Unauthorized => (
+                StatusCode::BAD_REQUEST,
+                "Notion access was revoked; please reconnect".to_string(),
+            ),
+            PortError::NotFound(msg) => (StatusCode::NOT_FOUND, msg),
+            _ => {
+                error!("Failed to export notes to Notion: {:?}", e);
+                (StatusCode::INTERNAL_SERVER_ERROR, "Failed to export notes".to_string())
+            }
+        })?;
+
+    Ok((StatusCode::CREATED, Json(ExportResponse { url })))
+}
+
+/// GET /integrations/google/authorize - Get the Google OAuth consent URL
+#[utoipa::path(
+    get,
+    path = "/integrations/google/authorize",
+    responses(
+        (status = 200, description = "Authorization URL", body = AuthorizeUrlResponse),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 501, description = "Google Drive integration is not configured")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn google_authorize_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let adapter = google_drive_adapter(&state)?;
+    let url = adapter.authorize_url(&user_id.to_string());
+    Ok(Json(AuthorizeUrlResponse { url }))
+}
+
+/// GET /integrations/google/callback - OAuth redirect target that stores the user's token
+#[utoipa::path(
+    get,
+    path = "/integrations/google/callback",
+    params(
+        ("code" = String, Query, description = "OAuth authorization code"),
+        ("state" = String, Query, description = "OAuth state issued by the authorize endpoint")
+    ),
+    responses(
+        (status = 200, description = "Google Drive connected"),
+        (status = 400, description = "State mismatch"),
+        (status = 401, description = "Unauthorized or code rejected by Google"),
+        (status = 501, description = "Google Drive integration is not configured")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn google_callback_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Query(query): Query<OAuthCallbackQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let adapter = google_drive_adapter(&state)?;
+
+    if query.state != user_id.to_string() {
+        return Err((StatusCode::BAD_REQUEST, "OAuth state mismatch".to_string()));
+    }
+
+    let access_token = adapter.exchange_code(&query.code).await.map_err(|e| {
+        error!("Failed to exchange Google OAuth code: {:?}", e);
+        (StatusCode::UNAUTHORIZED, "Google rejected the authorization".to_string())
+    })?;
+
+    state
+        .db
+        .save_integration_token(user_id, GOOGLE_PROVIDER, &access_token)
+        .await
+        .map_err(|e| {
+            error!("Failed to save Google token: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to save integration".to_string())
+        })?;
+
+    Ok((StatusCode::OK, "Google Drive connected"))
+}
+
+/// GET /integrations/google/documents - List the user's Google Docs available for import
+#[utoipa::path(
+    get,
+    path = "/integrations/google/documents",
+    responses(
+        (status = 200, description = "Importable documents", body = ListExternalDocumentsResponse),
+        (status = 400, description = "Google Drive is not connected for this user"),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 501, description = "Google Drive integration is not configured"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn list_google_documents_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let adapter = google_drive_adapter(&state)?;
+    let access_token = google_token(&state, user_id).await?;
+
+    let documents = adapter
+        .list_documents(&access_token)
+        .await
+        .map_err(google_error)?
+        .into_iter()
+        .map(|d| ExternalDocumentItem {
+            id: d.id,
+            title: d.title,
+        })
+        .collect();
+
+    Ok((StatusCode::OK, Json(ListExternalDocumentsResponse { documents })))
+}
+
+/// POST /integrations/google/import - Import a Google Doc as a new document and session
+#[utoipa::path(
+    post,
+    path = "/integrations/google/import",
+    request_body = GoogleImportRequest,
+    responses(
+        (status = 201, description = "Document imported", body = ImportResponse),
+        (status = 400, description = "Google Drive is not connected or the document is empty"),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 404, description = "Google document not found"),
+        (status = 501, description = "Google Drive integration is not configured"),
+        (status = 413, description = "Document longer than the configured maximum"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn import_google_document_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Json(req): Json<GoogleImportRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let adapter = google_drive_adapter(&state)?;
+    let access_token = google_token(&state, user_id).await?;
+
+    // 1. Fetch the document text from Drive
+    let (external, text) = adapter
+        .fetch_document(&access_token, &req.file_id)
+        .await
+        .map_err(google_error)?;
+
+    if text.trim().is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "Google document is empty".to_string()));
+    }
+
+    let text = if req.cleanup {
+        state.cleanup_adapter.clean_text(&text).await.map_err(|e| {
+            error!("Failed to clean up imported text: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to clean up imported text".to_string())
+        })?
+    } else {
+        text
+    };
+    check_document_length(&state, &text)?;
+
+    // 2. Store it like an uploaded document and open a session on it
+    let db = &state.db;
+    let result = async {
+        let doc = db.create_document(user_id, &external.title, &text).await?;
+        db.create_session(user_id, doc.id, req.education_mode).await
+    }
+    .await;
+
+    match result {
+        Ok(session) => {
+            tokio::spawn(index_document(state.clone(), session.document_id));
+            tokio::spawn(suggest_and_save_tags(
+                state.clone(),
+                user_id,
+                session.document_id,
+                text,
+            ));
+            Ok((
+                StatusCode::CREATED,
+                Json(ImportResponse {
+                    document_id: session.document_id,
+                    session_id: session.id,
+                    title: external.title,
+                }),
+            ))
+        }
+        Err(e) => {
+            error!("Failed to store imported document: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to import document".to_string(),
+            ))
+        }
+    }
+}
@@ -0,0 +1,299 @@
+//! services/api/src/web/intent.rs
+//!
+//! A lightweight, rule-based classifier that decides what a transcribed utterance
+//! asks for before it is sent to the QA LLM as a question.
+
+/// The action a spoken utterance is asking for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VoiceIntent {
+    /// Continue reading from the current position.
+    ResumeReading,
+    /// Carry on with whatever was stopped: the rest of an interrupted answer if there
+    /// is one, otherwise the reading.
+    Continue,
+    /// Jump to the part of the document about `topic`.
+    SeekTo { topic: String },
+    /// Rewind reading by `sentences` and read on from there.
+    SkipBack { sentences: usize },
+    /// Read the last `sentences` again, slower, then read on at the usual speed.
+    RereadSlower { sentences: usize },
+    /// Skip the rest of the paragraph being read.
+    SkipParagraph,
+    /// Read faster or slower from now on.
+    ChangeSpeed { faster: bool },
+    /// Stop reading until the user resumes.
+    Pause,
+    /// Look `word` up in the dictionary ("what does X mean?", "define X").
+    Define { word: String },
+    /// Re-explain the sentences just read in simpler words ("explain that more simply").
+    Simplify,
+    /// Recap everything read so far in the session.
+    SummarizeSoFar,
+    /// Point out the passage the last answer was based on ("where does it say that?").
+    ShowSources,
+    /// Anything else is treated as a question about the document.
+    Question,
+}
+
+const RESUME_PHRASES: &[&str] = &["continue reading", "resume reading"];
+
+const CONTINUE_PHRASES: &[&str] = &["go on", "keep going", "carry on"];
+
+const SUMMARY_PHRASES: &[&str] = &[
+    "summarize what we",
+    "summarise what we",
+    "summarize so far",
+    "summarise so far",
+    "recap",
+    "what have we read",
+    "what we've read so far",
+];
+
+/// Phrases that ask for what was just read in simpler words.
+const SIMPLIFY_PHRASES: &[&str] = &[
+    "more simply",
+    "simpler terms",
+    "simpler words",
+    "in plain english",
+    "simplify this",
+    "simplify that",
+    "simplify it",
+    "explain like i'm five",
+];
+
+/// Phrases that ask where the document backs up the last answer.
+const SOURCE_PHRASES: &[&str] = &[
+    "where does it say",
+    "where did it say",
+    "where does the book say",
+    "where is that in the",
+    "show me where",
+];
+
+/// Phrases that ask to skip the rest of the paragraph being read.
+const SKIP_PARAGRAPH_PHRASES: &[&str] = &[
+    "skip this paragraph",
+    "skip the paragraph",
+    "skip this part",
+    "skip this bit",
+    "next paragraph",
+];
+
+const SLOW_DOWN_PHRASES: &[&str] = &[
+    "slow down",
+    "read slower",
+    "speak slower",
+    "talk slower",
+    "read more slowly",
+    "speak more slowly",
+];
+
+const SPEED_UP_PHRASES: &[&str] = &[
+    "speed up",
+    "read faster",
+    "speak faster",
+    "talk faster",
+    "go faster",
+    "read more quickly",
+];
+
+/// Utterances that pause reading. Only the whole utterance counts, so that questions
+/// like "why did she stop?" are still answered.
+const PAUSE_COMMANDS: &[&str] = &["pause", "pause reading", "stop", "stop reading", "hold on", "wait"];
+
+/// Politeness stripped from an utterance before it is matched as a whole command.
+const COMMAND_FILLERS: &[&str] = &["please", "okay", "ok", "hey"];
+
+/// Openings of a request to define the word that follows. Only the start of the
+/// utterance counts, so "how does the author define freedom?" is still answered from
+/// the document. "What does X mean" is matched separately.
+const DEFINE_PREFIXES: &[&str] = &[
+    "define ",
+    "can you define ",
+    "what is the definition of ",
+    "what's the definition of ",
+    "what is the meaning of ",
+    "what's the meaning of ",
+];
+
+/// At most this many words are looked up, so longer phrases are answered as questions.
+const MAX_DEFINED_WORDS: usize = 2;
+
+/// Words that point back at the document rather than name a word to define, as in
+/// "what does this mean?".
+const NOT_DEFINABLE: &[&str] = &["this", "that", "it", "he", "she", "they", "the author"];
+
+/// Phrases that ask to hear the last sentence again.
+const REPEAT_PHRASES: &[&str] = &["repeat that", "say that again", "read that again", "repeat the last sentence"];
+
+/// Words that turn a repeat or skip-back request into a slower re-read.
+const SLOWER_WORDS: &[&str] = &["slower", "slowly"];
+
+/// Phrases that, followed by a count and "sentences", ask to rewind that far.
+const SKIP_BACK_PHRASES: &[&str] = &["go back", "back up", "rewind", "skip back"];
+
+const SEEK_PHRASES: &[&str] = &[
+    "skip to",
+    "jump to",
+    "go to the part",
+    "take me to",
+    "fast forward to",
+];
+
+/// Phrases that introduce the topic inside a seek request, e.g. "where it talks about".
+const TOPIC_MARKERS: &[&str] = &[
+    "talks about",
+    "talking about",
+    "discusses",
+    "mentions",
+    "about",
+    "on",
+];
+
+/// Classifies a transcribed utterance.
+pub fn classify(utterance: &str) -> VoiceIntent {
+    let lowercased = utterance.to_lowercase();
+
+    if let Some(topic) = extract_seek_topic(&lowercased) {
+        return VoiceIntent::SeekTo { topic };
+    }
+
+    if let Some(sentences) = extract_skip_back(&lowercased) {
+        if SLOWER_WORDS.iter().any(|w| lowercased.contains(w)) {
+            return VoiceIntent::RereadSlower { sentences };
+        }
+        return VoiceIntent::SkipBack { sentences };
+    }
+
+    if let Some(word) = extract_defined_word(&lowercased) {
+        return VoiceIntent::Define { word };
+    }
+
+    if SIMPLIFY_PHRASES.iter().any(|p| lowercased.contains(p)) {
+        return VoiceIntent::Simplify;
+    }
+
+    if SKIP_PARAGRAPH_PHRASES.iter().any(|p| lowercased.contains(p)) {
+        return VoiceIntent::SkipParagraph;
+    }
+
+    if SLOW_DOWN_PHRASES.iter().any(|p| lowercased.contains(p)) {
+        return VoiceIntent::ChangeSpeed { faster: false };
+    }
+
+    if SPEED_UP_PHRASES.iter().any(|p| lowercased.contains(p)) {
+        return VoiceIntent::ChangeSpeed { faster: true };
+    }
+
+    if PAUSE_COMMANDS.contains(&command_words(&lowercased).as_str()) {
+        return VoiceIntent::Pause;
+    }
+
+    if SUMMARY_PHRASES.iter().any(|p| lowercased.contains(p)) {
+        return VoiceIntent::SummarizeSoFar;
+    }
+
+    if SOURCE_PHRASES.iter().any(|p| lowercased.contains(p)) {
+        return VoiceIntent::ShowSources;
+    }
+
+    if RESUME_PHRASES.iter().any(|p| lowercased.contains(p)) {
+        return VoiceIntent::ResumeReading;
+    }
+
+    if CONTINUE_PHRASES.iter().any(|p| lowercased.contains(p)) {
+        return VoiceIntent::Continue;
+    }
+
+    VoiceIntent::Question
+}
+
+/// The words of an utterance without punctuation or politeness, for matching it as a
+/// whole command: "Okay, pause please." becomes "pause".
+fn command_words(lowercased: &str) -> String {
+    lowercased
+        .split_whitespace()
+        .map(|word| word.trim_matches(|c: char| c.is_ascii_punctuation()))
+        .filter(|word| !word.is_empty() && !COMMAND_FILLERS.contains(word))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Returns the word a definition request asks about, or `None` if the utterance is
+/// not one.
+fn extract_defined_word(lowercased: &str) -> Option<String> {
+    let utterance = command_words(lowercased);
+    let word = match utterance
+        .strip_prefix("what does ")
+        .and_then(|rest| rest.strip_suffix(" mean"))
+    {
+        Some(word) => word,
+        None => DEFINE_PREFIXES.iter().find_map(|p| utterance.strip_prefix(p))?,
+    };
+
+    let word = word.trim_start_matches("the word ");
+    let words = word.split_whitespace().count();
+    if words == 0 || words > MAX_DEFINED_WORDS || NOT_DEFINABLE.contains(&word) {
+        return None;
+    }
+    Some(word.to_string())
+}
+
+/// Returns how many sentences a repeat or skip-back request rewinds, or `None` if the
+/// utterance is not one. "Go back" only counts with "sentence(s)" after it, so that
+/// questions like "why did they go back?" are still answered.
+fn extract_skip_back(lowercased: &str) -> Option<usize> {
+    if REPEAT_PHRASES.iter().any(|p| lowercased.contains(p)) {
+        return Some(1);
+    }
+
+    let (_, after_phrase) = SKIP_BACK_PHRASES
+        .iter()
+        .find_map(|p| lowercased.split_once(p))?;
+    let mut words = after_phrase
+        .split_whitespace()
+        .map(|word| word.trim_matches(|c: char| c.is_ascii_punctuation()));
+    let count = match words.next()? {
+        "a" | "one" => 1,
+        "two" => 2,
+        "three" => 3,
+        "four" => 4,
+        "five" => 5,
+        "six" => 6,
+        "seven" => 7,
+        "eight" => 8,
+        "nine" => 9,
+        "ten" => 10,
+        word => word.parse().ok()?,
+    };
+    words.next()?.starts_with("sentence").then_some(count)
+}
+
+/// Returns the topic of a seek request, or `None` if the utterance is not one.
+fn extract_seek_topic(lowercased: &str) -> Option<String> {
+    let (_, after_phrase) = SEEK_PHRASES
+        .iter()
+        .find_map(|p| lowercased.split_once(p))?;
+
+    let topic = TOPIC_MARKERS
+        .iter()
+        .find_map(|marker| {
+            after_phrase
+                .split_once(&format!(" {} ", marker))
+                .map(|(_, rest)| rest)
+        })
+        .unwrap_or(after_phrase);
+
+    let topic = topic
+        .trim()
+        .trim_start_matches("the part ")
+        .trim_start_matches("the section ")
+        .trim_end_matches(|c: char| c.is_ascii_punctuation())
+        .trim();
+
+    if topic.is_empty() {
+        None
+    } else {
+        Some(topic.to_string())
+    }
+}
@@ -0,0 +1,247 @@
+//! services/api/src/web/listing.rs
+//!
+//! The query parameters shared by every list endpoint: `limit` and `cursor` for
+//! pagination, `sort` for ordering and `fields` for choosing which fields of each item
+//! are returned. A list handler loads its items in their default order and hands them
+//! to `ListParams::page`, so every list pages, sorts and trims the same way.
+//!
+//! Lists are one user's sessions, notes and so on, so they are sorted and paged in
+//! memory after loading rather than in each query. Lists whose items are costly to
+//! load (documents) page in their query instead while in their own order: see
+//! `ListParams::query_page`.
+//!
+//! Lists that clients poll also carry a weak ETag built from a `ListVersion`, so an
+//! unchanged list is answered with 304 Not Modified before it is loaded.
+
+use axum::http::{header, HeaderMap, StatusCode};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine as _};
+use reading_assistant_core::domain::ListVersion;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256};
+use std::cmp::Ordering;
+use utoipa::IntoParams;
+
+/// Items per page when `limit` isn't given.
+const DEFAULT_LIMIT: usize = 50;
+/// The largest page a client can ask for.
+const MAX_LIMIT: usize = 200;
+
+#[derive(Deserialize, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct ListParams {
+    /// How many items to return, from 1 to 200. Defaults to 50.
+    pub limit: Option<usize>,
+    /// The `next_cursor` of the previous page, to get the page after it.
+    pub cursor: Option<String>,
+    /// Comma-separated fields to sort by, each prefixed with '-' for descending order,
+    /// e.g. `-created_at`. Defaults to the list's own order.
+    pub sort: Option<String>,
+    /// Comma-separated fields to return for each item, e.g. `note_id,text`.
+    /// Defaults to all of them.
+    pub fields: Option<String>,
+}
+
+/// One page of a list, with its items serialized so fields can be left out.
+pub struct Page {
+    pub items: Vec<Value>,
+    /// Pass as `cursor` to get the next page; `None` on the last page.
+    pub next_cursor: Option<String>,
+}
+
+impl ListParams {
+    /// Sorts `items`, cuts out the requested page and keeps the requested fields.
+    /// Unknown fields and malformed cursors are rejected with 400 Bad Request.
+    pub fn page<T: Serialize>(&self, items: Vec<T>) -> Result<Page, (StatusCode, String)> {
+        let limit = self.limit()?;
+        let offset = self.offset()?;
+        let mut items = to_objects(items)?;
+
+        let sort = parse_field_list(self.sort.as_deref());
+        let sort: Vec<(&str, bool)> = sort
+            .iter()
+            .map(|key| match key.strip_prefix('-') {
+                Some(field) => (field, true),
+                None => (key.as_str(), false),
+            })
+            .collect();
+        check_fields(&items, sort.iter().map(|(field, _)| *field))?;
+        let fields = parse_field_list(self.fields.as_deref());
+        check_fields(&items, fields.iter().map(String::as_str))?;
+
+        // The sort is stable, so ties keep the list's own order.
+        if !sort.is_empty() {
+            items.sort_by(|a, b| {
+                sort.iter()
+                    .map(|(field, descending)| {
+                        let ordering = compare_values(field_value(a, field), field_value(b, field));
+                        if *descending { ordering.reverse() } else { ordering }
+                    })
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            });
+        }
+
+        let end = offset.saturating_add(limit).min(items.len());
+        let next_cursor = (end < items.len()).then(|| encode_cursor(end));
+        let items = items
+            .into_iter()
+            .skip(offset)
+            .take(limit)
+            .map(|object| Value::Object(select_fields(object, &fields)))
+            .collect();
+
+        Ok(Page { items, next_cursor })
+    }
+
+    /// The offset and length of the requested page, for lists that load only the page
+    /// in their query, or `None` when `sort` is given and the whole list has to be
+    /// loaded (and passed to `page`) to sort it.
+    pub fn query_page(&self) -> Result<Option<(usize, usize)>, (StatusCode, String)> {
+        let (limit, offset) = (self.limit()?, self.offset()?);
+        if !parse_field_list(self.sort.as_deref()).is_empty() {
+            return Ok(None);
+        }
+        Ok(Some((offset, limit)))
+    }
+
+    /// Like `page`, for the items of a page from `query_page`, loaded in the list's own
+    /// order with one item more than the page holds when another page follows.
+    pub fn loaded_page<T: Serialize>(&self, items: Vec<T>) -> Result<Page, (StatusCode, String)> {
+        let (limit, offset) = (self.limit()?, self.offset()?);
+        let items = to_objects(items)?;
+        let fields = parse_field_list(self.fields.as_deref());
+        check_fields(&items, fields.iter().map(String::as_str))?;
+
+        let next_cursor = (items.len() > limit).then(|| encode_cursor(offset + limit));
+        let items = items
+            .into_iter()
+            .take(limit)
+            .map(|object| Value::Object(select_fields(object, &fields)))
+            .collect();
+
+        Ok(Page { items, next_cursor })
+    }
+
+    fn limit(&self) -> Result<usize, (StatusCode, String)> {
+        let limit = self.limit.unwrap_or(DEFAULT_LIMIT);
+        if !(1..=MAX_LIMIT).contains(&limit) {
+            return Err(bad_request(format!("limit must be between 1 and {}", MAX_LIMIT)));
+        }
+        Ok(limit)
+    }
+
+    fn offset(&self) -> Result<usize, (StatusCode, String)> {
+        match &self.cursor {
+            Some(cursor) => decode_cursor(cursor).ok_or_else(|| bad_request("Invalid cursor".to_string())),
+            None => Ok(0),
+        }
+    }
+}
+
+fn to_objects<T: Serialize>(items: Vec<T>) -> Result<Vec<Map<String, Value>>, (StatusCode, String)> {
+    items
+        .into_iter()
+        .map(|item| match serde_json::to_value(item) {
+            Ok(Value::Object(object)) => Ok(object),
+            _ => Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to serialize list item".to_string(),
+            )),
+        })
+        .collect()
+}
+
+fn bad_request(message: String) -> (StatusCode, String) {
+    (StatusCode::BAD_REQUEST, message)
+}
+
+/// Splits a comma-separated parameter, ignoring blanks.
+fn parse_field_list(value: Option<&str>) -> Vec<String> {
+    value
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|field| !field.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Rejects fields the items don't have. Every item of a list has the same fields,
+/// so the first one is checked; an empty list has nothing to reject.
+fn check_fields<'a>(
+    items: &[Map<String, Value>],
+    mut fields: impl Iterator<Item = &'a str>,
+) -> Result<(), (StatusCode, String)> {
+    let Some(first) = items.first() else {
+        return Ok(());
+    };
+    match fields.find(|field| !first.contains_key(*field)) {
+        Some(unknown) => Err(bad_request(format!("Unknown field '{}'", unknown))),
+        None => Ok(()),
+    }
+}
+
+fn field_value<'a>(object: &'a Map<String, Value>, field: &str) -> &'a Value {
+    object.get(field).unwrap_or(&Value::Null)
+}
+
+fn select_fields(object: Map<String, Value>, fields: &[String]) -> Map<String, Value> {
+    if fields.is_empty() {
+        return object;
+    }
+    object.into_iter().filter(|(key, _)| fields.contains(key)).collect()
+}
+
+/// Orders JSON values of the same kind; nulls sort first. Timestamps are RFC 3339
+/// strings in UTC, so they order correctly as strings.
+fn compare_values(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Null, Value::Null) => Ordering::Equal,
+        (Value::Null, _) => Ordering::Less,
+        (_, Value::Null) => Ordering::Greater,
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (Value::Number(a), Value::Number(b)) => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        _ => Ordering::Equal,
+    }
+}
+
+/// Cursors are opaque to clients; they hold the position the next page starts at.
+fn encode_cursor(offset: usize) -> String {
+    URL_SAFE_NO_PAD.encode(format!("offset:{}", offset))
+}
+
+fn decode_cursor(cursor: &str) -> Option<usize> {
+    let decoded = URL_SAFE_NO_PAD.decode(cursor).ok()?;
+    String::from_utf8(decoded).ok()?.strip_prefix("offset:")?.parse().ok()
+}
+
+/// A weak ETag for one page of a list. It changes when the list does, and differs
+/// between pages, sort orders and field selections because `query` is part of it.
+pub fn list_etag(version: ListVersion, query: Option<&str>) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(version.count.to_le_bytes());
+    if let Some(last_changed_at) = version.last_changed_at {
+        hasher.update(last_changed_at.timestamp_micros().to_le_bytes());
+    }
+    hasher.update([0]);
+    hasher.update(query.unwrap_or_default().as_bytes());
+    format!("W/\"{:x}\"", hasher.finalize())
+}
+
+/// Whether the request's `If-None-Match` already names `etag`. ETags are compared
+/// weakly, as RFC 9110 requires for `If-None-Match`.
+pub fn is_not_modified(headers: &HeaderMap, etag: &str) -> bool {
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    let etag = opaque(etag);
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|tag| tag.trim() == "*" || opaque(tag) == etag)
+}
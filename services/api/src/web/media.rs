@@ -0,0 +1,110 @@
+//! services/api/src/web/media.rs
+//!
+//! Serves audio over REST the way media players expect: with an `ETag` and
+//! `Cache-Control` so a clip is downloaded once, and with `Range` support so a
+//! player can seek and resume an interrupted download.
+
+use axum::{
+    body::Body,
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+use std::ops::RangeInclusive;
+
+use crate::web::listing::is_not_modified;
+
+/// Builds the response for a complete audio file, answering conditional and range
+/// requests. Only single ranges are served; a request for several gets the whole
+/// file, which RFC 9110 allows.
+pub fn audio_response(
+    headers: &HeaderMap,
+    audio: Vec<u8>,
+    content_type: &'static str,
+    cache_control: &'static str,
+) -> Response {
+    let etag = format!("\"{:x}\"", Sha256::digest(&audio));
+    let common = [
+        (header::ETAG, etag.clone()),
+        (header::CACHE_CONTROL, cache_control.to_string()),
+        (header::ACCEPT_RANGES, "bytes".to_string()),
+    ];
+
+    if is_not_modified(headers, &etag) {
+        return (StatusCode::NOT_MODIFIED, common).into_response();
+    }
+
+    let total = audio.len();
+    let range = headers
+        .get(header::RANGE)
+        .and_then(|value| value.to_str().ok())
+        .filter(|_| if_range_matches(headers, &etag));
+    let Some(range) = range else {
+        return (common, [(header::CONTENT_TYPE, content_type)], audio).into_response();
+    };
+
+    match parse_range(range, total) {
+        Some(Ok(range)) => {
+            let content_range = format!("bytes {}-{}/{}", range.start(), range.end(), total);
+            let part = audio[range].to_vec();
+            (
+                StatusCode::PARTIAL_CONTENT,
+                common,
+                [
+                    (header::CONTENT_TYPE, content_type.to_string()),
+                    (header::CONTENT_RANGE, content_range),
+                ],
+                Body::from(part),
+            )
+                .into_response()
+        }
+        Some(Err(())) => (
+            StatusCode::RANGE_NOT_SATISFIABLE,
+            common,
+            [(header::CONTENT_RANGE, format!("bytes */{}", total))],
+        )
+            .into_response(),
+        None => (common, [(header::CONTENT_TYPE, content_type)], audio).into_response(),
+    }
+}
+
+/// A range is only honoured when `If-Range`, if sent, names the current file;
+/// otherwise the client's partial copy is stale and gets the whole file instead.
+fn if_range_matches(headers: &HeaderMap, etag: &str) -> bool {
+    match headers.get(header::IF_RANGE).and_then(|value| value.to_str().ok()) {
+        Some(if_range) => if_range.trim() == etag,
+        None => true,
+    }
+}
+
+/// Reads a `Range` header for a file of `total` bytes. `None` means the header is
+/// ignored (malformed, not bytes, or several ranges); `Some(Err(()))` means the range
+/// lies outside the file.
+fn parse_range(value: &str, total: usize) -> Option<Result<RangeInclusive<usize>, ()>> {
+    let spec = value.trim().strip_prefix("bytes=")?;
+    if spec.contains(',') {
+        return None;
+    }
+    let (start, end) = spec.split_once('-')?;
+    let (start, end) = (start.trim(), end.trim());
+
+    let range = if start.is_empty() {
+        // The last `end` bytes
+        let suffix: usize = end.parse().ok()?;
+        if suffix == 0 || total == 0 {
+            return Some(Err(()));
+        }
+        total.saturating_sub(suffix)..=total - 1
+    } else {
+        let start: usize = start.parse().ok()?;
+        let end = match end {
+            "" => total.saturating_sub(1),
+            end => end.parse::<usize>().ok()?.min(total.saturating_sub(1)),
+        };
+        if start >= total || start > end {
+            return Some(Err(()));
+        }
+        start..=end
+    };
+    Some(Ok(range))
+}
@@ -1,6 +1,6 @@
 //! services/api/src/web/middleware.rs
 //!
-//! Authentication middleware for protecting routes.
+//! Tenant resolution and authentication middleware for protecting routes.
 
 use axum::{
     extract::{Request, State},
@@ -8,13 +8,61 @@ use axum::{
     middleware::Next,
     response::Response,
 };
+use reading_assistant_core::{domain::DEFAULT_TENANT_ID, ports::PortError};
 use std::sync::Arc;
 use tracing::error;
 use uuid::Uuid;
 
 use crate::web::state::AppState;
 
+/// The header a client can use to pick its tenant by slug, overriding the hostname.
+pub const TENANT_HEADER: &str = "x-tenant";
+
+/// The tenant a request belongs to, inserted into request extensions by `resolve_tenant`.
+#[derive(Debug, Clone, Copy)]
+pub struct TenantId(pub Uuid);
+
+/// Middleware that resolves the request's tenant and inserts it as a `TenantId`.
+///
+/// With multi-tenancy off every request belongs to the default tenant. Otherwise
+/// the tenant comes from the `X-Tenant` header (a slug) or, failing that, from the
+/// `Host` header. Unknown tenants get 404 Not Found.
+pub async fn resolve_tenant(
+    State(state): State<Arc<AppState>>,
+    mut req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let tenant_id = if !state.config.multi_tenant {
+        DEFAULT_TENANT_ID
+    } else {
+        let headers = req.headers();
+        let slug = headers.get(TENANT_HEADER).and_then(|v| v.to_str().ok());
+        let hostname = headers
+            .get(header::HOST)
+            .and_then(|v| v.to_str().ok())
+            .map(|host| host.split(':').next().unwrap_or(host).to_lowercase());
+
+        let tenant = match (slug, hostname) {
+            (Some(slug), _) => state.db.get_tenant_by_slug(slug.trim()).await,
+            (None, Some(hostname)) => state.db.get_tenant_by_hostname(&hostname).await,
+            (None, None) => return Err(StatusCode::NOT_FOUND),
+        };
+        match tenant {
+            Ok(tenant) => tenant.id,
+            Err(PortError::NotFound(_)) => return Err(StatusCode::NOT_FOUND),
+            Err(e) => {
+                error!("Failed to resolve tenant: {:?}", e);
+                return Err(StatusCode::INTERNAL_SERVER_ERROR);
+            }
+        }
+    };
+
+    req.extensions_mut().insert(TenantId(tenant_id));
+    Ok(next.run(req).await)
+}
+
 /// Middleware that validates the auth session cookie and extracts the user_id.
+/// Must run after `resolve_tenant`: sessions of users from other tenants are rejected.
 /// 
 /// If valid, inserts the user_id into request extensions for handlers to use.
 /// If invalid or missing, returns 401 Unauthorized.
@@ -39,10 +87,15 @@ pub async fn require_auth(
         })
         .ok_or(StatusCode::UNAUTHORIZED)?;
 
-    // 3. Validate auth session in database, get user_id
+    // 3. Validate auth session in database for this tenant, get user_id
+    let TenantId(tenant_id) = req
+        .extensions()
+        .get::<TenantId>()
+        .copied()
+        .ok_or(StatusCode::INTERNAL_SERVER_ERROR)?;
     let user_id = state
         .db
-        .validate_auth_session(auth_session_id)
+        .validate_auth_session(auth_session_id, tenant_id)
         .await
         .map_err(|e| {
             error!("Failed to validate auth session: {:?}", e);
@@ -54,4 +107,24 @@ pub async fn require_auth(
 
     // 5. Continue to the handler
     Ok(next.run(req).await)
-}
\ No newline at end of file
+}
+
+/// Middleware that only lets configured admins through. Must run after `require_auth`,
+/// which provides the user_id. Returns 403 Forbidden for everyone else.
+pub async fn require_admin(
+    State(state): State<Arc<AppState>>,
+    req: Request,
+    next: Next,
+) -> Result<Response, StatusCode> {
+    let user_id = req
+        .extensions()
+        .get::<Uuid>()
+        .copied()
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    if !state.config.admin_user_ids.contains(&user_id) {
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    Ok(next.run(req).await)
+}
@@ -1,14 +1,53 @@
+pub mod admin;
+pub mod analytics;
+pub mod announcements;
+pub mod api_keys;
+pub mod ask;
+pub mod backups;
+pub mod calendar;
+pub mod gallery;
+pub mod glossary;
+pub mod goals;
+pub mod guest;
+pub mod handoff;
+pub mod highlights;
+pub mod hotword;
+pub mod intent;
+pub mod listing;
+pub mod media;
+pub mod note_task;
+pub mod partial_transcript;
+pub mod preferences;
 pub mod protocol;
 pub mod qa_task;
+pub mod quiz_task;
+pub mod ratings;
 pub mod reading_task;
+pub mod recap_task;
+pub mod retrieval;
+pub mod session_controller;
+pub mod sentence_cache;
+pub mod sharing;
 pub mod state;
 pub mod ws_handler;
+pub mod ws_writer;
 pub mod rest;
 pub mod auth;
+pub mod compression;
 pub mod middleware;
+pub mod integrations;
+pub mod queue;
+pub mod queue_task;
+pub mod tags;
+pub mod voices;
+pub mod workspaces;
 
 // Re-export the main WebSocket handler to make it easily accessible
 // to the binary that will build the web server router.
 pub use ws_handler::ws_handler;
-pub use rest::{create_session_handler, list_sessions_handler, list_notes_handler};
-pub use middleware::require_auth;
\ No newline at end of file
+pub use rest::{
+    create_session_handler, delete_document_handler, delete_session_handler, get_document_handler,
+    list_documents_handler, list_notes_handler, list_qa_pairs_handler, list_sessions_handler,
+    update_document_handler, update_session_handler,
+};
+pub use middleware::{require_admin, require_auth, resolve_tenant};
\ No newline at end of file
@@ -0,0 +1,126 @@
+//! services/api/src/web/note_task.rs
+//!
+//! This module writes the notes for answered questions. Questions are saved before
+//! their answer is spoken and their note is written in the background; at startup,
+//! the notes a crash or restart cut short are written too.
+
+use crate::web::{api_keys::app_state_for_user, state::AppState};
+use reading_assistant_core::{
+    domain::{Note, NoteSource, PendingNote},
+    ports::PortResult,
+};
+use std::sync::Arc;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// The maximum number of pending notes fetched at a time at startup.
+const RESUME_BATCH_SIZE: i64 = 20;
+
+/// A "fire-and-forget" background task to generate and save notes without blocking the user.
+/// The question must already be saved; its note is marked done, or failed, at the end.
+pub async fn generate_and_save_notes(app_state: Arc<AppState>, pending: PendingNote) {
+    let qapair = &pending.qa_pair;
+    info!("Spawning background task to generate notes for session {}.", qapair.session_id);
+
+    let note_text = match app_state
+        .notes_adapter
+        .generate_note_from_qapair(qapair, pending.education_mode)
+        .await
+    {
+        Ok(note_text) => note_text,
+        Err(e) => {
+            error!("Failed to generate note from QAPair: {}", e);
+            mark_note_failed(&app_state, qapair.id).await;
+            return;
+        }
+    };
+
+    if note_text.trim() == "SKIP_NOTE" {
+        info!(
+            "Skipping note generation - question was unrelated for session {}",
+            qapair.session_id
+        );
+    } else {
+        let note = Note {
+            id: Uuid::new_v4(),
+            session_id: qapair.session_id,
+            generated_note_text: note_text,
+            created_at: chrono::Utc::now(),
+            source_start_index: Some(pending.source_start),
+            source_end_index: Some(pending.source_end),
+            source: NoteSource::Question,
+        };
+        if app_state.db.save_note(note).await.is_err() {
+            error!(
+                "Failed to save generated note to database for session {}.",
+                qapair.session_id
+            );
+            mark_note_failed(&app_state, qapair.id).await;
+            return;
+        }
+        info!(
+            "Successfully generated and saved note for session {}.",
+            qapair.session_id
+        );
+    }
+
+    if let Err(e) = app_state.db.mark_note_done(qapair.id).await {
+        error!("Failed to mark note done for question {}: {:?}", qapair.id, e);
+    }
+}
+
+async fn mark_note_failed(app_state: &AppState, qa_pair_id: Uuid) {
+    if let Err(e) = app_state.db.mark_note_failed(qa_pair_id).await {
+        error!("Failed to mark note failed for question {}: {:?}", qa_pair_id, e);
+    }
+}
+
+/// Writes the notes still pending from before the server started, e.g. after a crash
+/// between answering a question and writing its note. Runs once at startup; each note
+/// is claimed first, so replicas starting together don't write it twice.
+pub async fn resume_pending_notes(app_state: Arc<AppState>) {
+    // Questions saved from now on belong to this process's own note tasks.
+    let started_at = chrono::Utc::now();
+    let mut resumed = 0;
+    loop {
+        let pending = match app_state.db.claim_pending_notes(started_at, RESUME_BATCH_SIZE).await {
+            Ok(pending) => pending,
+            Err(e) => {
+                error!("Failed to claim pending notes: {:?}", e);
+                return;
+            }
+        };
+        if pending.is_empty() {
+            break;
+        }
+
+        for pending in pending {
+            let qa_pair_id = pending.qa_pair.id;
+            match resume_note(&app_state, pending).await {
+                Ok(()) => resumed += 1,
+                Err(e) => {
+                    error!("Failed to resume pending note: {:?}", e);
+                    mark_note_failed(&app_state, qa_pair_id).await;
+                }
+            }
+        }
+    }
+    if resumed > 0 {
+        info!("Resumed {} pending notes.", resumed);
+    }
+}
+
+/// Writes one pending note with the session owner's API key, if they saved one.
+async fn resume_note(app_state: &Arc<AppState>, pending: PendingNote) -> PortResult<()> {
+    let qa_pair_id = pending.qa_pair.id;
+    // A streamed answer whose text couldn't be saved once it had been spoken.
+    if pending.qa_pair.answer_text.trim().is_empty() {
+        warn!("Question {} has no answer; skipping its note.", qa_pair_id);
+        return app_state.db.mark_note_failed(qa_pair_id).await;
+    }
+
+    let session = app_state.db.get_session_by_id(pending.qa_pair.session_id).await?;
+    let user_state = app_state_for_user(app_state, session.user_id).await?;
+    generate_and_save_notes(user_state, pending).await;
+    Ok(())
+}
@@ -0,0 +1,45 @@
+//! services/api/src/web/partial_transcript.rs
+//!
+//! Interim transcripts of the question being spoken, so the user can see it being
+//! recognized and re-ask before it is answered.
+
+use crate::web::{
+    protocol::ServerMessage,
+    state::{AppState, SessionState},
+    ws_writer::WsSender,
+};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::warn;
+
+/// Transcribes the question audio buffered so far and sends it as a `TranscriptPartial`.
+/// The caller sets `partial_transcript_in_flight` and `partial_transcript_bytes` to
+/// `audio.len()` before spawning this; the flag is cleared here.
+pub async fn send_partial_transcript(
+    app_state: Arc<AppState>,
+    session_state_lock: Arc<Mutex<SessionState>>,
+    ws_sender: WsSender,
+    audio: Vec<u8>,
+) {
+    let transcript = app_state.sst_adapter.transcribe_audio(&audio).await;
+
+    let mut session = session_state_lock.lock().await;
+    session.partial_transcript_in_flight = false;
+    // The question may have been submitted, or a new one started, in the meantime.
+    if !session.current_mode.accepts_question_audio() || session.partial_transcript_bytes != audio.len() {
+        return;
+    }
+    drop(session);
+
+    let text = match transcript {
+        Ok(text) if !text.trim().is_empty() => text,
+        Ok(_) => return,
+        Err(e) => {
+            warn!("Failed to transcribe partial question: {:?}", e);
+            return;
+        }
+    };
+    if ws_sender.send(&ServerMessage::TranscriptPartial { text }).await.is_err() {
+        warn!("Failed to send TranscriptPartial message. Client may have disconnected.");
+    }
+}
@@ -0,0 +1,376 @@
+//! services/api/src/web/preferences.rs
+//!
+//! Endpoints for reading and updating per-user preferences.
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use reading_assistant_core::domain::{AnswerVerbosity, ReadingLevel, UserPreferences};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::{
+    audio::{MAX_CROSSFADE_MS, MAX_SENTENCE_GAP_MS},
+    web::{
+        state::AppState,
+        voices::{MAX_SPEED, MIN_SPEED},
+    },
+};
+
+/// The most paragraphs active reading goes between checkpoint questions.
+pub const MAX_CHECKPOINT_PARAGRAPHS: u32 = 50;
+
+//=========================================================================================
+// Request/Response Types
+//=========================================================================================
+
+/// How long spoken answers should be. Also used by the WebSocket protocol.
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, Copy)]
+#[serde(rename_all = "snake_case")]
+pub enum Verbosity {
+    Brief,
+    Normal,
+    Detailed,
+}
+
+impl From<AnswerVerbosity> for Verbosity {
+    fn from(verbosity: AnswerVerbosity) -> Self {
+        match verbosity {
+            AnswerVerbosity::Brief => Verbosity::Brief,
+            AnswerVerbosity::Normal => Verbosity::Normal,
+            AnswerVerbosity::Detailed => Verbosity::Detailed,
+        }
+    }
+}
+
+impl From<Verbosity> for AnswerVerbosity {
+    fn from(verbosity: Verbosity) -> Self {
+        match verbosity {
+            Verbosity::Brief => AnswerVerbosity::Brief,
+            Verbosity::Normal => AnswerVerbosity::Normal,
+            Verbosity::Detailed => AnswerVerbosity::Detailed,
+        }
+    }
+}
+
+/// The level "explain this more simply" re-explains passages at.
+#[derive(Serialize, Deserialize, ToSchema, Debug, Clone, Copy, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadingLevelBody {
+    /// For young readers, around 8 years old.
+    Elementary,
+    /// For readers around 12 years old.
+    MiddleSchool,
+    /// Plain everyday language for adults.
+    #[default]
+    General,
+}
+
+impl From<ReadingLevel> for ReadingLevelBody {
+    fn from(level: ReadingLevel) -> Self {
+        match level {
+            ReadingLevel::Elementary => ReadingLevelBody::Elementary,
+            ReadingLevel::MiddleSchool => ReadingLevelBody::MiddleSchool,
+            ReadingLevel::General => ReadingLevelBody::General,
+        }
+    }
+}
+
+impl From<ReadingLevelBody> for ReadingLevel {
+    fn from(level: ReadingLevelBody) -> Self {
+        match level {
+            ReadingLevelBody::Elementary => ReadingLevel::Elementary,
+            ReadingLevelBody::MiddleSchool => ReadingLevel::MiddleSchool,
+            ReadingLevelBody::General => ReadingLevel::General,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, ToSchema)]
+pub struct PreferencesBody {
+    pub answer_verbosity: Verbosity,
+    /// Speak a one-sentence "last time we covered" recap when reopening a session
+    /// after a long break.
+    #[serde(default)]
+    pub resume_recap: bool,
+    /// Silence after each narrated sentence, from 0 to 2000 milliseconds.
+    #[serde(default)]
+    pub sentence_gap_ms: u32,
+    /// Fade on both ends of each narrated sentence, from 0 to 50 milliseconds.
+    #[serde(default)]
+    pub crossfade_ms: u32,
+    /// Take key-point notes on each paragraph as it is read. These are listed with
+    /// `source: "auto"`, apart from notes on the user's questions.
+    #[serde(default)]
+    pub auto_notes: bool,
+    /// Active reading: stop every this many paragraphs (1 to 50) for one quick question
+    /// about them, and read on once it is answered. Absent to read straight through.
+    #[serde(default)]
+    pub checkpoint_paragraphs: Option<u32>,
+    /// Write a "this week you read about ..." recap every Monday, spoken at the start
+    /// of the next session and listed with `source: "weekly_recap"`.
+    #[serde(default)]
+    pub weekly_recap: bool,
+    /// Leave your sessions out of the anonymized feature usage analytics.
+    #[serde(default)]
+    pub analytics_opt_out: bool,
+    /// The voice to read in, one of those listed by `GET /voices`. Absent for the
+    /// server's default voice.
+    #[serde(default)]
+    pub voice: Option<String>,
+    /// Speaking speed from 0.25 to 4.0, where 1.0 is normal. Absent for normal speed.
+    #[serde(default)]
+    pub speaking_speed: Option<f32>,
+    /// The level passages are re-explained at when you ask to hear them more simply.
+    #[serde(default)]
+    pub reading_level: ReadingLevelBody,
+}
+
+/// A partial update: only the fields present are changed.
+#[derive(Deserialize, ToSchema)]
+pub struct PreferencesPatch {
+    pub answer_verbosity: Option<Verbosity>,
+    pub resume_recap: Option<bool>,
+    pub sentence_gap_ms: Option<u32>,
+    pub crossfade_ms: Option<u32>,
+    pub auto_notes: Option<bool>,
+    /// Paragraphs between checkpoint questions, or 0 to turn checkpoints off.
+    pub checkpoint_paragraphs: Option<u32>,
+    pub weekly_recap: Option<bool>,
+    pub analytics_opt_out: Option<bool>,
+    /// A voice listed by `GET /voices`, or an empty string for the server's default.
+    pub voice: Option<String>,
+    /// Speaking speed from 0.25 to 4.0, or 1.0 for normal speed.
+    pub speaking_speed: Option<f32>,
+    pub reading_level: Option<ReadingLevelBody>,
+}
+
+impl From<UserPreferences> for PreferencesBody {
+    fn from(preferences: UserPreferences) -> Self {
+        Self {
+            answer_verbosity: preferences.answer_verbosity.into(),
+            resume_recap: preferences.resume_recap,
+            sentence_gap_ms: preferences.sentence_gap_ms,
+            crossfade_ms: preferences.crossfade_ms,
+            auto_notes: preferences.auto_notes,
+            checkpoint_paragraphs: preferences.checkpoint_paragraphs,
+            weekly_recap: preferences.weekly_recap,
+            analytics_opt_out: preferences.analytics_opt_out,
+            voice: preferences.voice,
+            speaking_speed: preferences.speaking_speed,
+            reading_level: preferences.reading_level.into(),
+        }
+    }
+}
+
+//=========================================================================================
+// Handlers
+//=========================================================================================
+
+/// GET /preferences - Get the current user's preferences
+#[utoipa::path(
+    get,
+    path = "/preferences",
+    responses(
+        (status = 200, description = "Preferences retrieved successfully", body = PreferencesBody),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn get_preferences_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let preferences = state.db.get_user_preferences(user_id).await.map_err(|e| {
+        error!("Failed to fetch preferences: {:?}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch preferences".to_string())
+    })?;
+
+    Ok((StatusCode::OK, Json(PreferencesBody::from(preferences))))
+}
+
+/// PUT /preferences - Replace the current user's preferences
+#[utoipa::path(
+    put,
+    path = "/preferences",
+    request_body = PreferencesBody,
+    responses(
+        (status = 200, description = "Preferences saved", body = PreferencesBody),
+        (status = 400, description = "Unknown voice, or speed, narration pacing or checkpoint spacing out of range"),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn update_preferences_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Json(req): Json<PreferencesBody>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let preferences = UserPreferences {
+        answer_verbosity: req.answer_verbosity.into(),
+        resume_recap: req.resume_recap,
+        sentence_gap_ms: req.sentence_gap_ms,
+        crossfade_ms: req.crossfade_ms,
+        auto_notes: req.auto_notes,
+        checkpoint_paragraphs: req.checkpoint_paragraphs,
+        weekly_recap: req.weekly_recap,
+        analytics_opt_out: req.analytics_opt_out,
+        voice: req.voice,
+        speaking_speed: req.speaking_speed,
+        reading_level: req.reading_level.into(),
+    };
+    check_voice(&state, preferences.voice.as_deref())?;
+    save_preferences(&state, user_id, preferences).await
+}
+
+/// GET /me/preferences - Get the current user's preferences
+#[utoipa::path(
+    get,
+    path = "/me/preferences",
+    responses(
+        (status = 200, description = "Preferences retrieved successfully", body = PreferencesBody),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn get_my_preferences_handler(
+    state: State<Arc<AppState>>,
+    user_id: Extension<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    get_preferences_handler(state, user_id).await
+}
+
+/// PATCH /me/preferences - Change some of the current user's preferences
+#[utoipa::path(
+    patch,
+    path = "/me/preferences",
+    request_body = PreferencesPatch,
+    responses(
+        (status = 200, description = "Preferences saved", body = PreferencesBody),
+        (status = 400, description = "Unknown voice, or speed, narration pacing or checkpoint spacing out of range"),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn patch_preferences_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Json(req): Json<PreferencesPatch>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let mut preferences = state.db.get_user_preferences(user_id).await.map_err(|e| {
+        error!("Failed to fetch preferences: {:?}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch preferences".to_string())
+    })?;
+
+    if let Some(verbosity) = req.answer_verbosity {
+        preferences.answer_verbosity = verbosity.into();
+    }
+    if let Some(resume_recap) = req.resume_recap {
+        preferences.resume_recap = resume_recap;
+    }
+    if let Some(sentence_gap_ms) = req.sentence_gap_ms {
+        preferences.sentence_gap_ms = sentence_gap_ms;
+    }
+    if let Some(crossfade_ms) = req.crossfade_ms {
+        preferences.crossfade_ms = crossfade_ms;
+    }
+    if let Some(auto_notes) = req.auto_notes {
+        preferences.auto_notes = auto_notes;
+    }
+    if let Some(paragraphs) = req.checkpoint_paragraphs {
+        preferences.checkpoint_paragraphs = (paragraphs != 0).then_some(paragraphs);
+    }
+    if let Some(weekly_recap) = req.weekly_recap {
+        preferences.weekly_recap = weekly_recap;
+    }
+    if let Some(analytics_opt_out) = req.analytics_opt_out {
+        preferences.analytics_opt_out = analytics_opt_out;
+    }
+    // Defaults are stored as NULL, so the server's own defaults can change later.
+    if let Some(voice) = req.voice {
+        preferences.voice = (!voice.is_empty()).then_some(voice);
+        check_voice(&state, preferences.voice.as_deref())?;
+    }
+    if let Some(speed) = req.speaking_speed {
+        preferences.speaking_speed = (speed != 1.0).then_some(speed);
+    }
+    if let Some(level) = req.reading_level {
+        preferences.reading_level = level.into();
+    }
+
+    save_preferences(&state, user_id, preferences).await
+}
+
+/// Rejects a voice the server's TTS provider doesn't offer. A voice saved under an
+/// earlier provider is left alone by a PATCH that doesn't touch it, and reads in the
+/// default voice until a new one is chosen.
+fn check_voice(state: &AppState, voice: Option<&str>) -> Result<(), (StatusCode, String)> {
+    match voice {
+        Some(voice) if !state.tts_adapter.available_voices().iter().any(|v| v == voice) => {
+            Err((StatusCode::BAD_REQUEST, format!("Unknown voice '{}'", voice)))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Validates and stores a complete set of preferences, returning them as saved.
+async fn save_preferences(
+    state: &AppState,
+    user_id: Uuid,
+    preferences: UserPreferences,
+) -> Result<(StatusCode, Json<PreferencesBody>), (StatusCode, String)> {
+    if preferences.sentence_gap_ms > MAX_SENTENCE_GAP_MS || preferences.crossfade_ms > MAX_CROSSFADE_MS {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!(
+                "sentence_gap_ms must be at most {} and crossfade_ms at most {}",
+                MAX_SENTENCE_GAP_MS, MAX_CROSSFADE_MS
+            ),
+        ));
+    }
+    if let Some(paragraphs) = preferences.checkpoint_paragraphs {
+        if !(1..=MAX_CHECKPOINT_PARAGRAPHS).contains(&paragraphs) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("checkpoint_paragraphs must be between 1 and {}", MAX_CHECKPOINT_PARAGRAPHS),
+            ));
+        }
+    }
+    if let Some(speed) = preferences.speaking_speed {
+        if !(MIN_SPEED..=MAX_SPEED).contains(&speed) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("speaking_speed must be between {} and {}", MIN_SPEED, MAX_SPEED),
+            ));
+        }
+    }
+
+    state
+        .db
+        .save_user_preferences(user_id, &preferences)
+        .await
+        .map_err(|e| {
+            error!("Failed to save preferences: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to save preferences".to_string())
+        })?;
+
+    Ok((StatusCode::OK, Json(PreferencesBody::from(preferences))))
+}
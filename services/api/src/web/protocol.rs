@@ -3,6 +3,9 @@
 //! Defines the WebSocket message protocol between the browser client and the API server
 //! for the interactive audio reader application.
 
+use crate::web::preferences::Verbosity;
+use reading_assistant_core::domain::{AudioEncoding, AudioFormat};
+use reading_assistant_core::session_machine::SessionMode;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
@@ -12,26 +15,216 @@ use uuid::Uuid;
 // NOTE: User's question audio is sent as raw Binary frames, not as part of this enum.
 //=========================================================================================
 
+/// Optional features the client opts into at `Init`.
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct ClientCapabilities {
+    /// The client streams a low-bitrate microphone sidechannel while reading so the
+    /// server can listen for the hot-word. Each Binary frame sent during reading must
+    /// be a short, self-contained audio clip (e.g. one ~2s WebM/Opus recording).
+    pub hotword: bool,
+    /// Audio-free mode, for deaf or hard-of-hearing users and silent reading: nothing is
+    /// synthesized. Sentences arrive as `SentenceText` at a reading pace, and anything the
+    /// assistant would say arrives as `AssistantText`.
+    pub audio_free: bool,
+}
+
+/// The encoding of the reader's voice in Binary frames.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AudioCodec {
+    /// One 16-bit mono WAV file per frame. The default.
+    Wav,
+    /// Raw 16-bit little-endian mono PCM at the announced sample rate.
+    Pcm16,
+    Mp3,
+    /// Opus in an Ogg container; the smallest, for clients on low bandwidth.
+    Opus,
+}
+
+impl From<AudioCodec> for AudioEncoding {
+    fn from(codec: AudioCodec) -> Self {
+        match codec {
+            AudioCodec::Wav => AudioEncoding::Wav,
+            AudioCodec::Pcm16 => AudioEncoding::Pcm16,
+            AudioCodec::Mp3 => AudioEncoding::Mp3,
+            AudioCodec::Opus => AudioEncoding::Opus,
+        }
+    }
+}
+
+impl From<AudioEncoding> for AudioCodec {
+    fn from(encoding: AudioEncoding) -> Self {
+        match encoding {
+            AudioEncoding::Wav => AudioCodec::Wav,
+            AudioEncoding::Pcm16 => AudioCodec::Pcm16,
+            AudioEncoding::Mp3 => AudioCodec::Mp3,
+            AudioEncoding::Opus => AudioCodec::Opus,
+        }
+    }
+}
+
+/// The audio format a client asks for at `Init`, and the one the server settles on in
+/// `SessionInitialized`. A codec the TTS provider can't produce falls back to `wav`.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+pub struct AudioFormatSpec {
+    pub codec: AudioCodec,
+    /// Samples per second (8000-48000) for `wav` and `pcm16`. Left out to use the
+    /// voice's own rate when asking; always set for them in the server's reply.
+    /// Compressed codecs use the provider's rate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sample_rate: Option<u32>,
+}
+
+impl From<AudioFormatSpec> for AudioFormat {
+    fn from(spec: AudioFormatSpec) -> Self {
+        Self {
+            encoding: spec.codec.into(),
+            sample_rate: spec.sample_rate,
+        }
+    }
+}
+
+impl From<AudioFormat> for AudioFormatSpec {
+    fn from(format: AudioFormat) -> Self {
+        Self {
+            codec: format.encoding.into(),
+            sample_rate: format.sample_rate,
+        }
+    }
+}
+
 /// Represents the structured text messages a client can send to the server.
 #[derive(Deserialize, Debug)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ClientMessage {
     /// Initializes a session. This must be the first message sent on the connection.
-    Init { session_id: Uuid },
+    /// `device_id` is a stable, client-generated identifier used to track per-device positions.
+    Init {
+        session_id: Uuid,
+        #[serde(default)]
+        device_id: Option<String>,
+        #[serde(default)]
+        capabilities: ClientCapabilities,
+        /// The audio format to send the reader's voice in. Defaults to WAV at the
+        /// voice's own sample rate.
+        #[serde(default)]
+        audio_format: Option<AudioFormatSpec>,
+        /// A token from another device's `HandoffReady`. That device is disconnected and
+        /// reading resumes where it stopped, reported as `device_index`.
+        #[serde(default)]
+        handoff_token: Option<String>,
+        /// Picks up where this session's last connection dropped, e.g. after a page
+        /// refresh: its mode, position and last question and answer come back, reported
+        /// by `SessionResumed`, and the greeting is skipped. Ignored with a handoff token,
+        /// or when there is nothing recent to resume.
+        #[serde(default)]
+        resume: bool,
+    },
 
     /// Signals that the user has started speaking, interrupting the reader.
     /// The server should cancel the reading process and prepare to receive audio.
+    /// Sent while an answer is playing, it stops the rest of the answer; the part
+    /// already sent is kept as the answer and marked as interrupted, until the user
+    /// says "go on" to hear the rest.
     InterruptStarted,
 
     /// Signals that the user has finished speaking their question.
     /// The server should now process the buffered audio.
     InterruptEnded,
 
+    /// A typed question, for users who can't speak. Handled like a spoken question
+    /// (or a quiz answer during a quiz) without transcription; the answer is still spoken.
+    TextQuestion { text: String },
+
+    /// Cancels the question being processed, e.g. because `QuestionTranscribed` showed it
+    /// was misheard. The server stops before answering, sends `AnsweringEnded`, and
+    /// listens for the next question. Ignored if no question is being processed.
+    CancelQuestion,
+
+    /// Stops the answer being generated or spoken. The server stops sending its audio,
+    /// sends `AnsweringEnded`, and listens for the next question; the client should drop
+    /// answer audio it has already queued. Ignored if no question is being processed.
+    CancelAnswer,
+
+    /// Jumps reading to `sentence_index`, e.g. the start of a note's passage, or to rewind
+    /// or skip ahead. Reading restarts there and the position is saved. Allowed while
+    /// reading (also after `ReadingEnded`), paused or listening for a question. Also
+    /// accepted as `seek`.
+    #[serde(alias = "seek")]
+    SeekTo { sentence_index: usize },
+
+    /// Rewinds reading by `sentences` from the current position, to hear the last
+    /// passage again, and reads on from there. Allowed whenever `SeekTo` is.
+    SkipBack { sentences: usize },
+
+    /// Like `SkipBack`, but the sentences are read again slower, as a one-off: reading
+    /// goes back to the session's speed once past them.
+    RereadSlower { sentences: usize },
+
+    /// Asks to continue the session on another device. The server replies with
+    /// `HandoffReady`; `sentence_index` is the sentence playing now, to resume at, and
+    /// defaults to the server's reading position.
+    StartHandoff {
+        #[serde(default)]
+        sentence_index: Option<usize>,
+    },
+
+    /// Jumps reading to the start of chapter `index` from `TableOfContents`, like
+    /// `SeekTo` its first sentence.
+    GoToChapter { index: usize },
+
     /// A user-initiated command to continue reading from the last position.
     ResumeReading,
 
     /// A user-initiated command to pause the reading.
     PauseReading,
+
+    /// Starts a spoken quiz on the passage just read. Reading stops; each answer is
+    /// sent like a question (`InterruptStarted`, audio frames, `InterruptEnded`).
+    StartQuiz {
+        #[serde(default)]
+        question_count: Option<usize>,
+    },
+
+    /// Changes how long spoken answers are, for this session and as the user's saved preference.
+    SetAnswerVerbosity { verbosity: Verbosity },
+
+    /// Changes narration pacing from the next sentence on, for this session and as the
+    /// user's saved preference. Values above the server maximums are clamped.
+    SetNarrationPacing { sentence_gap_ms: u32, crossfade_ms: u32 },
+
+    /// Changes the reader's speaking speed from the next sentence or answer on, for this
+    /// session and as the user's saved preference. 1.0 is normal; values outside
+    /// 0.25–4.0 are clamped.
+    SetSpeed { rate: f32 },
+
+    /// Switches the reader's voice from the next sentence or answer on, for this session
+    /// only. `voice` is one of `GET /voices`; an empty string goes back to the user's
+    /// saved voice. An unknown voice is answered with an `InvalidMessage` error.
+    SetVoice { voice: String },
+
+    /// Rates the session 1–5 with optional feedback, typically after `ReadingEnded`. Rating
+    /// again replaces the earlier rating. Answered with `SessionRated`, or an
+    /// `InvalidMessage` error when out of range. After `EndSession` has closed the
+    /// connection, use `POST /sessions/{session_id}/rating` instead.
+    RateSession {
+        rating: u8,
+        #[serde(default)]
+        feedback: Option<String>,
+    },
+
+    /// Ends the session: progress is saved, a summary note is generated, a
+    /// `SessionSummary` is sent, and the server closes the connection.
+    EndSession,
+
+    /// Switches the connection to another of the user's sessions, e.g. another open
+    /// document. The current session is saved as if its connection had dropped, so
+    /// switching back within the resume window picks up where it stood. The new session
+    /// starts as after `Init` with `resume`, with `SessionInitialized` and the rest; audio
+    /// still queued from the old session should be dropped when it arrives. An unknown or
+    /// foreign session is answered with an error and the current session carries on.
+    SwitchSession { session_id: Uuid },
 }
 
 //=========================================================================================
@@ -41,20 +234,242 @@ pub enum ClientMessage {
 // not as part of this enum. These messages provide context for that audio.
 //=========================================================================================
 
+/// Statistics for one listening session, reported when the session ends.
+#[derive(Serialize, Debug, Clone)]
+pub struct SessionStats {
+    /// Wall-clock seconds since this connection opened the session.
+    pub time_listened_secs: u64,
+    pub questions_asked: usize,
+    /// Sentences advanced past on this connection.
+    pub sentences_read: usize,
+    pub reading_progress_index: usize,
+    pub total_sentences: usize,
+}
+
+/// One entry of `ServerMessage::TableOfContents`.
+#[derive(Serialize, Debug, Clone)]
+pub struct ChapterEntry {
+    pub title: String,
+    /// The sentence the chapter starts at.
+    pub sentence_index: usize,
+}
+
+/// Machine-readable reasons carried by `ServerMessage::Error`.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// The first message was not a valid `Init`.
+    InitRequired,
+    /// The session does not exist.
+    SessionNotFound,
+    /// The session belongs to another user.
+    Unauthorized,
+    /// The session exists but its data could not be loaded.
+    SessionLoadFailed,
+    /// The user's saved OpenAI API key could not be used.
+    ApiKeyUnusable,
+    /// A client message could not be parsed. The message is ignored.
+    InvalidMessage,
+    /// A client message isn't allowed in the session's current mode, e.g. resuming
+    /// a session that isn't paused. The message is ignored.
+    InvalidTransition,
+    /// Speech could not be synthesized for several sentences in a row. Reading pauses
+    /// and can be resumed.
+    SpeechSynthesisFailed,
+    /// The question could not be answered. The user can ask again.
+    AnswerFailed,
+    /// The quiz could not be started or an answer could not be graded.
+    QuizFailed,
+    /// The session rating could not be saved. The client can submit it again.
+    RatingFailed,
+    /// The question's audio went over the server's size limit and was dropped. The
+    /// user should ask again, more briefly.
+    QuestionTooLong,
+    /// A handoff token was wrong or had expired, so the session opened at its saved
+    /// position; or a handoff could not be offered.
+    HandoffFailed,
+}
+
+/// What a resumed session is doing, reported by `SessionResumed`.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ResumedMode {
+    /// Reading carries on from `device_index`.
+    Reading,
+    /// Reading stays paused until `ResumeReading`.
+    Paused,
+    /// The server is listening for the user's next question. A question that was being
+    /// answered when the connection dropped has to be asked again.
+    Listening,
+    /// The server is waiting for the user's reply to its clarifying question.
+    Clarifying,
+}
+
+impl From<SessionMode> for ResumedMode {
+    fn from(mode: SessionMode) -> Self {
+        match mode.resumed() {
+            SessionMode::Paused => ResumedMode::Paused,
+            SessionMode::InterruptedListening | SessionMode::ProcessingQuestion => ResumedMode::Listening,
+            SessionMode::Clarifying => ResumedMode::Clarifying,
+            SessionMode::Reading | SessionMode::Checkpoint | SessionMode::Ended => ResumedMode::Reading,
+        }
+    }
+}
+
+/// What the Binary frame after an `AudioHeader` contains.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AudioKind {
+    /// The welcome message. The same for every session with the same voice, so
+    /// clients may cache it.
+    Welcome,
+    /// A document sentence; the header's `index` is its sentence index.
+    Reading,
+    /// A sentence of an answer or recap; the header's `index` counts from 0 within it.
+    Answer,
+    /// A short announcement, such as a quiz question or a mode change.
+    Announcement,
+    /// An empty frame that only tells the client to resume playing the audio it
+    /// already has.
+    Resume,
+}
+
+/// What the server is doing while it handles a question, reported by `ProcessingStatus`.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ProcessingStage {
+    /// The question audio is being transcribed.
+    Transcribing,
+    /// The document is being searched, for passages relevant to the question or for
+    /// the topic the user asked to skip to.
+    SearchingDocument,
+    /// The answer (or recap) is being generated.
+    Thinking,
+    /// The answer's speech is being generated.
+    Synthesizing,
+}
+
 /// Represents the structured text messages the server can send to the client.
 #[derive(Serialize, Debug, Clone)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ServerMessage {
-    /// Confirms successful session initialization.
-    SessionInitialized { session_id: Uuid },
+    /// Confirms successful session initialization. `audio_format` is the format every
+    /// Binary audio frame of the session is in; it is absent in audio-free mode.
+    SessionInitialized {
+        session_id: Uuid,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        audio_format: Option<AudioFormatSpec>,
+    },
+
+    /// Reports the reading positions known for this session, sent right after initialization.
+    /// `furthest_read_index` is the furthest sentence reached on any device;
+    /// `device_index` is where this device last listened, if it has listened before.
+    /// Reading resumes from `device_index` when present, otherwise from `furthest_read_index`.
+    ReadingPositions {
+        furthest_read_index: usize,
+        device_index: Option<usize>,
+    },
+
+    /// The document's chapters, in order, sent after `ReadingPositions`. Chapters are
+    /// found from headings in the text, so the list is empty for documents without them.
+    TableOfContents { chapters: Vec<ChapterEntry> },
+
+    /// Sent after `TableOfContents` when `Init` asked to resume and the session's last
+    /// connection was recent enough: the mode it is back in, and the last question and
+    /// answer, which follow-up questions refer to.
+    SessionResumed {
+        mode: ResumedMode,
+        last_question: Option<String>,
+        last_answer: Option<String>,
+    },
 
-    /// Reports a fatal error to the client, which should display an error message.
-    Error { message: String },
+    /// Reports an error to the client, which should display `message`. When `fatal`
+    /// is set the server closes the connection right after; otherwise the session
+    /// carries on and the user can retry.
+    Error {
+        code: ErrorCode,
+        message: String,
+        fatal: bool,
+    },
 
     /// Signals that the server is now streaming audio for the document reading.
     /// The UI can update to a "playing" state.
     ReadingStarted,
 
+    /// The text of the audio clip that follows (narration, an answer, an announcement),
+    /// for rendering captions.
+    Caption { text: String },
+
+    /// Describes the Binary frame that immediately follows it. Every Binary frame the
+    /// server sends is preceded by one, so clients can label, order and cache audio.
+    /// `byte_length` is the length of the frame's payload.
+    AudioHeader {
+        kind: AudioKind,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        index: Option<usize>,
+        byte_length: usize,
+    },
+
+    /// The sentence at `index` is about to be read; sent just before its audio so the
+    /// client can highlight it when playback reaches it.
+    SentenceStarted { index: usize, text: String },
+
+    /// In audio-free mode, the sentence at `index`, sent in place of its audio.
+    SentenceText { index: usize, text: String },
+
+    /// In audio-free mode, something the assistant would otherwise have said aloud,
+    /// such as an answer or an announcement.
+    AssistantText { text: String },
+
+    /// One sentence of an answer that is streamed while the model is still writing it.
+    /// It comes just before the sentence's audio, in place of `Caption`, or alone in
+    /// audio-free mode.
+    AnswerSentence { text: String },
+
+    /// The document sentences the answer that follows is based on, sorted, so the client
+    /// can highlight the passage that backs it up. Sent again, with the sentences read
+    /// out, when the user asks where the document says so.
+    AnswerSources { sentence_indices: Vec<usize> },
+
+    /// The verdict of the grounding check, when the deployment has it on: whether the
+    /// answer just given is backed up by the document, and if not, what isn't. An
+    /// unsupported answer is followed by a spoken hedge.
+    AnswerGrounding {
+        supported: bool,
+        reason: Option<String>,
+    },
+
+    /// The sentences just read, re-explained more simply at the user's reading level
+    /// because they asked for it. Sent before the explanation is spoken; it is an
+    /// explanation of `sentence_indices` rather than an answer to a question.
+    Explanation {
+        text: String,
+        sentence_indices: Vec<usize>,
+    },
+
+    /// A word the user asked to have defined, looked up in the dictionary. Sent before
+    /// the definition is spoken; the word is also added to the session's glossary.
+    /// `phonetic` is its pronunciation in IPA.
+    WordDefined {
+        word: String,
+        phonetic: Option<String>,
+        part_of_speech: Option<String>,
+        meaning: String,
+    },
+
+    /// Signals that the sentence at `index` could not be synthesized and was skipped.
+    /// Reading carries on with the next sentence.
+    SentenceSkipped { index: usize },
+
+    /// Signals that reading jumped to a new position at the user's request.
+    /// The client should drop any queued document audio before playing what follows.
+    ReadingSeeked { sentence_index: usize },
+
+    /// Signals that the hot-word was heard on the sidechannel. Reading has stopped and
+    /// the server is listening, exactly as after `InterruptStarted`; the client should
+    /// record the question and finish with `InterruptEnded`.
+    HotwordDetected,
+
     /// Signals that the reading has been paused.
     ReadingPaused,
 
@@ -65,7 +480,94 @@ pub enum ServerMessage {
     /// The UI can update to a "thinking..." or "listening..." state.
     AnsweringStarted,
 
+    /// The question was ambiguous; `question` asks what the user meant and is sent just
+    /// before its audio. The server then listens for the reply, as after `InterruptStarted`.
+    ClarificationRequested { question: String },
+
+    /// Progress between `AnsweringStarted` and the answer, so the client can show what
+    /// the server is doing during the silence. Stages arrive in order, some may be skipped.
+    ProcessingStatus { stage: ProcessingStage },
+
+    /// What the server heard the user ask, sent right after transcription so the client
+    /// can show it and offer `CancelQuestion` before the answer is generated.
+    QuestionTranscribed { text: String },
+
+    /// What the server has heard of the question so far, sent now and then while the
+    /// user is still speaking so they can re-ask if it is being misheard. Each replaces
+    /// the last; `QuestionTranscribed` has the final text.
+    TranscriptPartial { text: String },
+
+    /// Another of the user's documents covers the topic of the question just answered.
+    /// Sent after the answer, only when one is found. `session_id` is the user's latest
+    /// session on that document and `sentence_index` the matching passage, for a link.
+    RelatedDocument {
+        document_id: Uuid,
+        session_id: Option<Uuid>,
+        sentence_index: usize,
+        excerpt: String,
+    },
+
     /// Signals that the AI has finished speaking its answer.
     /// The UI can transition back to an idle/listening state.
     AnsweringEnded,
-}
\ No newline at end of file
+
+    /// Active reading stopped at a comprehension checkpoint. The `QuizQuestion` that
+    /// follows is answered like a quiz answer, and reading carries on by itself once it
+    /// is; "skip" skips it, as does `ResumeReading`.
+    CheckpointReached,
+
+    /// A quiz question, sent just before its audio. `question_number` is 1-based.
+    QuizQuestion {
+        question_number: usize,
+        total_questions: usize,
+        question: String,
+    },
+
+    /// The grade for the user's answer to a quiz question, sent before the spoken feedback.
+    QuizResult {
+        question_number: usize,
+        correct: bool,
+        expected_answer: String,
+        feedback: String,
+    },
+
+    /// Signals that the quiz is over. Reading can be resumed with `ResumeReading`.
+    QuizEnded {
+        correct_answers: usize,
+        total_questions: usize,
+    },
+
+    /// Confirms that the rating from `RateSession` was saved.
+    SessionRated,
+
+    /// Sent in response to `StartHandoff`. The new device passes `token` in its `Init`
+    /// within `expires_in_secs`; a later `StartHandoff` replaces it.
+    HandoffReady { token: String, expires_in_secs: u64 },
+
+    /// The session was handed off to another device. Reading has stopped and the server
+    /// closes the connection next.
+    SessionHandedOff,
+
+    /// Sent in response to `EndSession`, just before the server closes the connection.
+    SessionSummary { stats: SessionStats },
+}
+impl ServerMessage {
+    /// Whether the message is out-of-band and may overtake queued audio. The rest
+    /// describe the audio around them and stay in sequence with it.
+    pub fn is_control(&self) -> bool {
+        matches!(
+            self,
+            ServerMessage::SessionInitialized { .. }
+                | ServerMessage::ReadingPositions { .. }
+                | ServerMessage::TableOfContents { .. }
+                | ServerMessage::SessionResumed { .. }
+                | ServerMessage::Error { .. }
+                | ServerMessage::HotwordDetected
+                | ServerMessage::ReadingPaused
+                | ServerMessage::SessionRated
+                | ServerMessage::HandoffReady { .. }
+                | ServerMessage::SessionHandedOff
+                | ServerMessage::SessionSummary { .. }
+        )
+    }
+}
@@ -4,22 +4,33 @@
 //! handling a single question-and-answer cycle.
 
 use crate::web::{
-    protocol::ServerMessage,
-    state::{AppState, SessionState},
+    analytics::track,
+    glossary::define_word,
+    intent::{classify, VoiceIntent},
+    note_task::generate_and_save_notes,
+    protocol::{AudioKind, ProcessingStage, ServerMessage},
+    retrieval::{build_context, retrieve, Retrieved},
+    ws_writer::WsSender,
+    state::{lock_with_sentences, AppState, SessionState},
+    voices::set_speaking_speed,
 };
-use axum::extract::ws::{Message, WebSocket};
-use futures::{stream::SplitSink, SinkExt};
 use reading_assistant_core::{
-    domain::QAPair,
-    ports::{PortError, PortResult},
+    domain::{InterruptedAnswer, LatencyStage, NoteSource, PendingNote, QAPair, QaReply, SpeechSettings, StageLatency, TokenUsage, UsageEventKind},
+    ports::{AnswerStream, PortError, PortResult, QaReplyStream, UsageReceiver},
+    sentence_stream::SentenceBuffer,
 };
 
 
-use std::sync::Arc;
-use tokio::sync::Mutex;
+use futures::StreamExt;
+use std::{ops::Range, sync::Arc};
+use tokio::{
+    sync::{mpsc, Mutex},
+    task::JoinHandle,
+};
+use tokio_util::sync::CancellationToken;
 use tracing::{error, info, warn};
 use uuid::Uuid;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 
 /// Represents the outcome of the `qa_process` task.
@@ -28,144 +39,1290 @@ use std::time::Instant;
 pub enum QaOutcome {
     /// The user's speech was a command to resume reading.
     ResumeReading,
+    /// The user asked to jump to a topic; reading should resume at `sentence_index`.
+    Seek { sentence_index: usize },
+    /// The user asked to pause reading.
+    Pause,
     /// The user's question was successfully answered.
     QuestionAnswered,
+    /// The question was ambiguous and a clarifying question was asked back; the
+    /// user's next question is their reply.
+    ClarificationAsked,
+    /// The user cancelled the question, or stopped its answer part-way.
+    Cancelled,
+}
+
+/// The model's reply to a question, before any of it is spoken.
+enum Reply {
+    /// The answer, and the document sentences it cites.
+    Answer(Answer, Vec<usize>),
+    Clarification(String),
 }
 
-/// The main asynchronous task for handling a single user question.
+/// An answer, either complete or still being written.
+enum Answer {
+    Complete(String),
+    Streaming(AnswerStream),
+}
+
+/// The main asynchronous task for handling a single user question. The question is
+/// transcribed from the buffered audio unless it was typed.
 pub async fn qa_process(
     app_state: Arc<AppState>,
     session_state_lock: Arc<Mutex<SessionState>>,
-    ws_sender: Arc<Mutex<SplitSink<WebSocket, Message>>>,
+    ws_sender: WsSender,
+    typed_question: Option<String>,
 ) -> PortResult<QaOutcome> {
     let start_time = Instant::now();
+    // Kept for the latency report if the question is answered.
+    let mut latencies = Vec::new();
     info!("QA process started.");
 
     let start_msg = ServerMessage::AnsweringStarted;
-    let start_json = serde_json::to_string(&start_msg).unwrap();
-    if ws_sender.lock().await.send(Message::Text(start_json.into())).await.is_err() {
+    if ws_sender.send(&start_msg).await.is_err() {
         return Err(PortError::Unexpected(
             "Failed to send AnsweringStarted message.".to_string(),
         ));
     }
 
-    let (audio_buffer, context, session_id) = {
+    let (audio_buffer, source, session_id, user_id, document_id, verbosity, education_mode, pending_clarification, interrupted_answer, audio_free, speech, question_token, visit_id) = {
     let mut session = session_state_lock.lock().await;
     let audio_buffer = std::mem::take(&mut session.audio_buffer);
-    
-    // Build context using helper function
-    let doc_context = get_context_from_document(&session);
-    let context = if let (Some(prev_q), Some(prev_a)) = (&session.last_question, &session.last_answer) {
-        format!(
-            "DOCUMENT CONTEXT:\n{}\n\nPREVIOUS Q&A:\nQ: {}\nA: {}",
-            doc_context, prev_q, prev_a
-        )
-    } else {
-        doc_context
-    };
+    let pending_clarification = session.pending_clarification.take();
+    // Asking anything else drops an interrupted answer; only "go on" picks it up.
+    let interrupted_answer = session.interrupted_answer.take();
+    let source = context_window(&session);
     
     let session_id = session.session_id;
-    (audio_buffer, context, session_id)
+    (audio_buffer, source, session_id, session.user_id, session.document_id, session.answer_verbosity, session.education_mode, pending_clarification, interrupted_answer, session.audio_free, session.speech.clone(), session.question_token.clone(), session.analytics_visit_id)
     };
 
-    let stt_start = Instant::now();
-    let question_text = app_state
-        .sst_adapter
-        .transcribe_audio(&audio_buffer)
-        .await?;
-    let stt_duration = stt_start.elapsed();
-    info!("⏱️ STT took: {:?}", stt_duration);
-    info!("Transcribed question: '{}'", question_text);
-
-    let lowercased_question = question_text.to_lowercase();
-    if lowercased_question.contains("continue reading")
-        || lowercased_question.contains("resume reading")
-        || lowercased_question.contains("go on")
-    {
-        info!("'Resume reading' command detected.");
-        return Ok(QaOutcome::ResumeReading);
+    let question_text = match typed_question {
+        Some(text) => {
+            info!("Typed question: '{}'", text);
+            text
+        }
+        None => {
+            send_status(&ws_sender, ProcessingStage::Transcribing).await;
+            let stt_start = Instant::now();
+            let question_text = app_state
+                .sst_adapter
+                .transcribe_audio(&audio_buffer)
+                .await?;
+            let stt_duration = stt_start.elapsed();
+            info!("⏱️ STT took: {:?}", stt_duration);
+            latencies.push(stage_latency(LatencyStage::Stt, stt_duration));
+            info!("Transcribed question: '{}'", question_text);
+            let transcribed_msg = ServerMessage::QuestionTranscribed { text: question_text.clone() };
+            if ws_sender.send(&transcribed_msg).await.is_err() {
+                return Err(PortError::Unexpected(
+                    "Failed to send QuestionTranscribed message.".to_string(),
+                ));
+            }
+            question_text
+        }
+    };
+
+    let intent = classify(&question_text);
+    if intent == VoiceIntent::Continue {
+        if let Some(answer) = interrupted_answer {
+            track(&app_state, visit_id, UsageEventKind::ResumeCommand);
+            return resume_answer(&app_state, &session_state_lock, &ws_sender, answer, audio_free, &speech, &question_token).await;
+        }
     }
+    match intent {
+        VoiceIntent::ResumeReading | VoiceIntent::Continue => {
+            info!("'Resume reading' command detected.");
+            track(&app_state, visit_id, UsageEventKind::ResumeCommand);
+            return Ok(QaOutcome::ResumeReading);
+        }
+        VoiceIntent::SeekTo { topic } => {
+            info!("'Seek' command detected for topic '{}'.", topic);
+            track(&app_state, visit_id, UsageEventKind::SeekCommand);
+            send_status(&ws_sender, ProcessingStage::SearchingDocument).await;
+            if let Some(sentence_index) =
+                find_sentence_for_topic(&app_state, &session_state_lock, &topic).await?
+            {
+                // Tell the client first so it drops stale audio before the announcement plays.
+                let seek_msg = ServerMessage::ReadingSeeked { sentence_index };
+                if ws_sender.send(&seek_msg).await.is_err() {
+                    return Err(PortError::Unexpected(
+                        "Failed to send ReadingSeeked message.".to_string(),
+                    ));
+                }
+                speak(&app_state, &ws_sender, &format!("Okay, skipping to the part about {}.", topic), audio_free, &speech)
+                    .await?;
+                return Ok(QaOutcome::Seek { sentence_index });
+            }
+            // Nothing matched; fall through and let the LLM answer it as a question.
+            warn!("No sentence matched topic '{}'.", topic);
+        }
+        VoiceIntent::SkipBack { sentences } => {
+            info!("'Skip back' command detected for {} sentences.", sentences);
+            track(&app_state, visit_id, UsageEventKind::SeekCommand);
+            let sentence_index = session_state_lock.lock().await.skip_back_index(sentences);
+            let seek_msg = ServerMessage::ReadingSeeked { sentence_index };
+            if ws_sender.send(&seek_msg).await.is_err() {
+                return Err(PortError::Unexpected(
+                    "Failed to send ReadingSeeked message.".to_string(),
+                ));
+            }
+            return Ok(QaOutcome::Seek { sentence_index });
+        }
+        VoiceIntent::RereadSlower { sentences } => {
+            info!("'Read that again slower' command detected for {} sentences.", sentences);
+            track(&app_state, visit_id, UsageEventKind::SeekCommand);
+            let sentence_index = session_state_lock.lock().await.reread_slower(sentences);
+            let seek_msg = ServerMessage::ReadingSeeked { sentence_index };
+            if ws_sender.send(&seek_msg).await.is_err() {
+                return Err(PortError::Unexpected(
+                    "Failed to send ReadingSeeked message.".to_string(),
+                ));
+            }
+            return Ok(QaOutcome::Seek { sentence_index });
+        }
+        VoiceIntent::SkipParagraph => {
+            info!("'Skip this paragraph' command detected.");
+            track(&app_state, visit_id, UsageEventKind::SeekCommand);
+            let next_paragraph = session_state_lock.lock().await.next_paragraph_index();
+            let Some(sentence_index) = next_paragraph else {
+                speak(&app_state, &ws_sender, NO_NEXT_PARAGRAPH_MESSAGE, audio_free, &speech).await?;
+                return Ok(QaOutcome::ResumeReading);
+            };
+            let seek_msg = ServerMessage::ReadingSeeked { sentence_index };
+            if ws_sender.send(&seek_msg).await.is_err() {
+                return Err(PortError::Unexpected(
+                    "Failed to send ReadingSeeked message.".to_string(),
+                ));
+            }
+            return Ok(QaOutcome::Seek { sentence_index });
+        }
+        VoiceIntent::ChangeSpeed { faster } => {
+            info!("'Read {}' command detected.", if faster { "faster" } else { "slower" });
+            track(&app_state, visit_id, UsageEventKind::SpeedCommand);
+            let current = speech.speed.unwrap_or(1.0);
+            let step = if faster { SPOKEN_SPEED_STEP } else { -SPOKEN_SPEED_STEP };
+            set_speaking_speed(&app_state, &session_state_lock, current + step).await;
+            // Confirmed at the new speed, so the user hears the difference straight away.
+            let speech = session_state_lock.lock().await.speech.clone();
+            let confirmation = if faster { "Okay, reading faster." } else { "Okay, reading slower." };
+            speak(&app_state, &ws_sender, confirmation, audio_free, &speech).await?;
+            return Ok(QaOutcome::ResumeReading);
+        }
+        VoiceIntent::Pause => {
+            info!("'Pause' command detected.");
+            track(&app_state, visit_id, UsageEventKind::Paused);
+            send_answering_ended(&ws_sender).await;
+            return Ok(QaOutcome::Pause);
+        }
+        VoiceIntent::Define { word } => {
+            info!("'Define' command detected for '{}'.", word);
+            if define_word(&app_state, &session_state_lock, &ws_sender, &word, &question_text, audio_free, &speech).await? {
+                send_answering_ended(&ws_sender).await;
+                return Ok(QaOutcome::QuestionAnswered);
+            }
+            // The dictionary doesn't know it; fall through and let the LLM answer it.
+            warn!("No dictionary definition for '{}'.", word);
+        }
+        VoiceIntent::SummarizeSoFar => {
+            info!("'Summarize so far' command detected.");
+            track(&app_state, visit_id, UsageEventKind::SummaryCommand);
+            send_status(&ws_sender, ProcessingStage::Thinking).await;
+            let recap = summarize_read_so_far(&app_state, &session_state_lock).await?;
+            {
+                let mut session = session_state_lock.lock().await;
+                session.last_question = Some(question_text);
+                session.last_answer = Some(recap.clone());
+                session.last_answer_sources.clear();
+            }
+            speak_sentences(&app_state, &ws_sender, &recap, audio_free, &speech).await?;
+            send_answering_ended(&ws_sender).await;
+            return Ok(QaOutcome::QuestionAnswered);
+        }
+        VoiceIntent::Simplify => {
+            info!("'Explain that more simply' command detected.");
+            send_status(&ws_sender, ProcessingStage::Thinking).await;
+            explain_more_simply(&app_state, &session_state_lock, &ws_sender, &question_token, question_text, audio_free, &speech).await?;
+            send_answering_ended(&ws_sender).await;
+            return Ok(QaOutcome::QuestionAnswered);
+        }
+        VoiceIntent::ShowSources => {
+            info!("'Where does it say that' command detected.");
+            show_answer_sources(&app_state, &session_state_lock, &ws_sender, audio_free, &speech).await?;
+            send_answering_ended(&ws_sender).await;
+            return Ok(QaOutcome::QuestionAnswered);
+        }
+        VoiceIntent::Question => {}
+    }
+    track(&app_state, visit_id, UsageEventKind::QuestionAsked);
+
+    // A reply to a clarifying question is answered together with the original question,
+    // and at most one clarifying question is asked per question.
+    let allow_clarification = pending_clarification.is_none();
+    let question_text = match pending_clarification {
+        Some(original) => format!(
+            "{} (When asked what they meant, the listener said: {})",
+            original, question_text
+        ),
+        None => question_text,
+    };
+
+    if education_mode && !passes_education_moderation(&app_state, &question_text).await? {
+        info!("Question blocked by education-mode moderation.");
+        speak_sentences(&app_state, &ws_sender, EDUCATION_MODE_REFUSAL, audio_free, &speech).await?;
+        send_answering_ended(&ws_sender).await;
+        return Ok(QaOutcome::QuestionAnswered);
+    }
+
+    send_status(&ws_sender, ProcessingStage::SearchingDocument).await;
+    let retrieved = tokio::select! {
+        biased;
+        _ = question_token.cancelled() => return cancel_question(&ws_sender).await,
+        retrieved = retrieve_or_default(&app_state, user_id, document_id, &question_text) => retrieved,
+    };
+    let context = {
+        let Some(session) =
+            lock_with_sentences(&session_state_lock, &app_state, source.clone(), &question_token).await?
+        else {
+            return cancel_question(&ws_sender).await;
+        };
+        let doc_context = build_context(&session, source.clone(), &retrieved);
+        if let (Some(prev_q), Some(prev_a)) = (&session.last_question, &session.last_answer) {
+            format!(
+                "DOCUMENT CONTEXT:\n{}\n\nPREVIOUS Q&A:\nQ: {}\nA: {}",
+                doc_context, prev_q, prev_a
+            )
+        } else {
+            doc_context
+        }
+    };
 
+    send_status(&ws_sender, ProcessingStage::Thinking).await;
     let llm_start = Instant::now();
-    let answer_text = app_state
-        .qa_adapter
-        .answer_question(&question_text, &context)
-        .await?;
-    let llm_duration = llm_start.elapsed();
-    info!("⏱️ LLM took: {:?}", llm_duration);
-    info!("Generated answer: '{}'", answer_text);
+    // Education-mode answers are moderated as a whole before any of it is spoken, so
+    // only other sessions speak the answer while it is still being written.
+    let reply = if education_mode {
+        let reply = tokio::select! {
+            biased;
+            _ = question_token.cancelled() => return cancel_question(&ws_sender).await,
+            reply = app_state
+                .qa_adapter
+                .answer_question(&question_text, &context, verbosity, education_mode, allow_clarification) => reply?,
+        };
+        record_llm_usage(&app_state, reply.usage);
+        match reply.value {
+            QaReply::Answer { text, sources } => Reply::Answer(Answer::Complete(text), sources),
+            QaReply::Clarification(clarifying) => Reply::Clarification(clarifying),
+        }
+    } else {
+        let reply = tokio::select! {
+            biased;
+            _ = question_token.cancelled() => return cancel_question(&ws_sender).await,
+            reply = app_state
+                .qa_adapter
+                .answer_question_streaming(&question_text, &context, verbosity, education_mode, allow_clarification) => reply?,
+        };
+        match reply {
+            QaReplyStream::Answer { stream, sources, usage } => {
+                record_streamed_llm_usage(&app_state, usage);
+                Reply::Answer(Answer::Streaming(stream), sources)
+            }
+            QaReplyStream::Clarification(clarifying) => Reply::Clarification(clarifying),
+        }
+    };
+    info!("⏱️ LLM (first reply) took: {:?}", llm_start.elapsed());
+    latencies.push(stage_latency(LatencyStage::Llm, llm_start.elapsed()));
+
+    let (answer, sources) = match reply {
+        Reply::Clarification(_) if question_token.is_cancelled() => {
+            return cancel_question(&ws_sender).await;
+        }
+        Reply::Clarification(clarifying) => {
+            info!("Asking clarifying question: '{}'", clarifying);
+            if education_mode && !passes_education_moderation(&app_state, &clarifying).await? {
+                warn!("Clarifying question replaced by education-mode moderation.");
+                speak_sentences(&app_state, &ws_sender, EDUCATION_MODE_REFUSAL, audio_free, &speech).await?;
+                send_answering_ended(&ws_sender).await;
+                return Ok(QaOutcome::QuestionAnswered);
+            }
+            return ask_clarification(&app_state, &session_state_lock, &ws_sender, question_text, clarifying, audio_free, &speech).await;
+        }
+        Reply::Answer(answer, sources) => (answer, sources),
+    };
+
+    if !audio_free {
+        send_status(&ws_sender, ProcessingStage::Synthesizing).await;
+    }
+    // Saved before any of the answer is spoken, so it isn't lost if the server stops
+    // before its note is written. A streamed answer is filled in once it has ended.
+    let mut pending = PendingNote {
+        qa_pair: QAPair {
+            id: Uuid::new_v4(),
+            session_id,
+            question_text: question_text.clone(),
+            answer_text: match &answer {
+                Answer::Complete(answer_text) => answer_text.clone(),
+                Answer::Streaming(_) => String::new(),
+            },
+            answer_interrupted: false,
+            created_at: chrono::Utc::now(),
+        },
+        source_start: source.start,
+        source_end: source.end,
+        education_mode,
+    };
+    let saved = match app_state.db.save_pending_qa_pair(&pending).await {
+        Ok(()) => true,
+        Err(e) => {
+            error!("Failed to save QAPair for session {}: {:?}", session_id, e);
+            false
+        }
+    };
+    let question_id = pending.qa_pair.id;
+
+    let tts_start = Instant::now();
+    // Kept in case the user stops the answer partway.
+    let mut progress = SpokenProgress::default();
+    // `None` if the user stopped the answer.
+    let spoken: PortResult<Option<String>> = async {
+        match answer {
+            Answer::Complete(mut answer_text) => {
+                info!("Generated answer: '{}'", answer_text);
+                if education_mode && !passes_education_moderation(&app_state, &answer_text).await? {
+                    warn!("Answer replaced by education-mode moderation.");
+                    answer_text = EDUCATION_MODE_REFUSAL.to_string();
+                } else {
+                    send_answer_sources(&session_state_lock, &ws_sender, sources).await;
+                }
+                let finished = speak_sentences_until_cancelled(&app_state, &ws_sender, &answer_text, audio_free, &speech, &question_token, &mut progress).await?;
+                Ok(finished.then_some(answer_text))
+            }
+            Answer::Streaming(stream) => {
+                send_answer_sources(&session_state_lock, &ws_sender, sources).await;
+                let answer_text = speak_answer_stream(&app_state, &ws_sender, stream, audio_free, &speech, &question_token, &mut progress).await?;
+                if let Some(answer_text) = &answer_text {
+                    info!("Generated answer: '{}'", answer_text);
+                }
+                Ok(answer_text)
+            }
+        }
+    }
+    .await;
+    let answer_text = match spoken {
+        Ok(Some(answer_text)) => answer_text,
+        Ok(None) => {
+            save_interrupted_answer(&app_state, &session_state_lock, question_id, question_text, progress).await;
+            return cancel_question(&ws_sender).await;
+        }
+        Err(e) => {
+            if saved {
+                abandon_answer(&app_state, question_id, &progress).await;
+            }
+            return Err(e);
+        }
+    };
+    info!("⏱️ Answer (LLM and TTS) took: {:?}", tts_start.elapsed());
+    latencies.push(stage_latency(LatencyStage::Tts, tts_start.elapsed()));
+
+    if app_state.config.verify_answer_grounding && answer_text != EDUCATION_MODE_REFUSAL {
+        check_grounding(&app_state, &ws_sender, &answer_text, &context, audio_free, &speech, &question_token).await;
+    }
+
     {
     let mut session = session_state_lock.lock().await;
     session.last_question = Some(question_text.clone());
     session.last_answer = Some(answer_text.clone());
+    session.questions_asked += 1;
     }
 
-    let notes_app_state = app_state.clone();
-    let qapair = QAPair {
-        id: Uuid::new_v4(),
-        session_id,
-        question_text,
-        answer_text: answer_text.clone(),
+    if saved {
+        if pending.qa_pair.answer_text != answer_text {
+            if let Err(e) = app_state.db.update_qa_pair_answer(question_id, &answer_text, false).await {
+                error!("Failed to update saved answer: {:?}", e);
+            }
+            pending.qa_pair.answer_text = answer_text;
+        }
+        if let Err(e) = app_state.db.mark_answer_delivered(question_id).await {
+            error!("Failed to mark answer delivered: {:?}", e);
+        }
+        tokio::spawn(generate_and_save_notes(app_state.clone(), pending));
+    } else {
+        error!("Note generation will be skipped for session {}.", session_id);
+    }
+
+    if let Some(related) = retrieved.related {
+        info!(
+            "Question also matches document {} (score {:.3}).",
+            related.document_id, related.similarity
+        );
+        let related_msg = ServerMessage::RelatedDocument {
+            document_id: related.document_id,
+            session_id: related.session_id,
+            sentence_index: related.chunk_index,
+            excerpt: related.text,
+        };
+        if ws_sender.send(&related_msg).await.is_err() {
+            warn!("Failed to send RelatedDocument message. Client may have disconnected.");
+        }
+    }
+
+    let total_duration = start_time.elapsed();
+    info!("⏱️ Total QA process took: {:?}", total_duration);
+    latencies.push(stage_latency(LatencyStage::Total, total_duration));
+    record_latencies(&app_state, question_id, latencies);
+    info!("Finished sending answer audio.");
+
+    send_answering_ended(&ws_sender).await;
+
+    Ok(QaOutcome::QuestionAnswered)
+}
+
+/// Answers a typed question about a session's document, without audio. The QAPair is
+/// saved before the answer is returned and turned into a note in the background, as
+/// for spoken questions.
+pub async fn answer_text_question(
+    app_state: Arc<AppState>,
+    session: &mut SessionState,
+    question_text: String,
+) -> PortResult<String> {
+    let education_mode = session.education_mode;
+    if education_mode && !passes_education_moderation(&app_state, &question_text).await? {
+        info!("Typed question blocked by education-mode moderation.");
+        return Ok(EDUCATION_MODE_REFUSAL.to_string());
+    }
+
+    let retrieved =
+        retrieve_or_default(&app_state, session.user_id, session.document_id, &question_text).await;
+    let window = context_window(session);
+    session.load_sentences(&app_state, window.clone()).await?;
+    let context = build_context(session, window, &retrieved);
+    // There is no conversation to carry a clarifying question, so the model must answer.
+    let reply = app_state
+        .qa_adapter
+        .answer_question(&question_text, &context, session.answer_verbosity, education_mode, false)
+        .await?;
+    record_llm_usage(&app_state, reply.usage);
+    let answer_text = match reply.value {
+        QaReply::Answer { text, .. } | QaReply::Clarification(text) => text,
     };
-    tokio::spawn(generate_and_save_notes(notes_app_state, qapair));
+    if education_mode && !passes_education_moderation(&app_state, &answer_text).await? {
+        warn!("Typed answer replaced by education-mode moderation.");
+        return Ok(EDUCATION_MODE_REFUSAL.to_string());
+    }
+
+    let source = context_window(session);
+    let pending = PendingNote {
+        qa_pair: QAPair {
+            id: Uuid::new_v4(),
+            session_id: session.session_id,
+            question_text,
+            answer_text: answer_text.clone(),
+            answer_interrupted: false,
+            created_at: chrono::Utc::now(),
+        },
+        source_start: source.start,
+        source_end: source.end,
+        education_mode,
+    };
+    // A typed answer is delivered as soon as it is returned.
+    let saved = async {
+        app_state.db.save_pending_qa_pair(&pending).await?;
+        app_state.db.mark_answer_delivered(pending.qa_pair.id).await
+    }
+    .await;
+    match saved {
+        Ok(()) => {
+            tokio::spawn(generate_and_save_notes(app_state, pending));
+        }
+        Err(e) => error!("Failed to save typed QAPair for session {}: {:?}", session.session_id, e),
+    }
+
+    Ok(answer_text)
+}
+
+fn stage_latency(stage: LatencyStage, duration: Duration) -> StageLatency {
+    StageLatency {
+        stage,
+        duration_ms: duration.as_millis().min(u128::from(u32::MAX)) as u32,
+    }
+}
+
+/// Adds the tokens an answer used to the `GET /admin/llm-usage` report, in the
+/// background so the listener never waits on it.
+fn record_llm_usage(app_state: &Arc<AppState>, usage: Option<TokenUsage>) {
+    let Some(usage) = usage else {
+        return;
+    };
+    let db = app_state.db.clone();
+    let qa_adapter = app_state.qa_adapter.clone();
+    tokio::spawn(async move {
+        if let Err(e) = db.record_llm_usage(qa_adapter.provider(), &qa_adapter.model(), usage).await {
+            warn!("Failed to record LLM usage: {:?}", e);
+        }
+    });
+}
+
+/// Records a streamed answer's tokens once the stream has ended. Nothing is recorded
+/// if the answer is stopped early or the provider doesn't report usage.
+fn record_streamed_llm_usage(app_state: &Arc<AppState>, usage: UsageReceiver) {
+    let app_state = app_state.clone();
+    tokio::spawn(async move {
+        if let Ok(usage) = usage.await {
+            record_llm_usage(&app_state, Some(usage));
+        }
+    });
+}
+
+/// Stores an answered question's stage latencies for `GET /admin/latency`, in the
+/// background so the listener never waits on it.
+fn record_latencies(app_state: &Arc<AppState>, question_id: Uuid, latencies: Vec<StageLatency>) {
+    let db = app_state.db.clone();
+    tokio::spawn(async move {
+        if let Err(e) = db.save_question_latencies(question_id, &latencies).await {
+            warn!("Failed to record question latencies: {:?}", e);
+        }
+    });
+}
+
+/// Records how much of an answer the user heard before stopping it, as the saved
+/// question's answer and as context for a follow-up, and keeps the rest in case they
+/// go on with it. The saved question is removed if none of it was sent, and a stopped
+/// answer is left out of the user's notes.
+async fn save_interrupted_answer(
+    app_state: &Arc<AppState>,
+    session_state_lock: &Arc<Mutex<SessionState>>,
+    qa_pair_id: Uuid,
+    question_text: String,
+    progress: SpokenProgress,
+) {
+    if progress.sent.is_empty() {
+        if let Err(e) = app_state.db.delete_qa_pair(qa_pair_id).await {
+            error!("Failed to remove unheard answer: {:?}", e);
+        }
+        return;
+    }
+    let heard = progress.sent.join(" ");
+    info!(
+        "Answer interrupted after {} sentences, {} left.",
+        progress.sent.len(),
+        progress.unsent.len()
+    );
+
+    let db = &app_state.db;
+    let saved = async {
+        db.update_qa_pair_answer(qa_pair_id, &heard, true).await?;
+        db.mark_note_done(qa_pair_id).await
+    }
+    .await;
+    if let Err(e) = saved {
+        error!("Failed to save interrupted answer: {:?}", e);
+    }
+
+    let mut session = session_state_lock.lock().await;
+    session.last_question = Some(question_text);
+    session.last_answer = Some(heard);
+    session.questions_asked += 1;
+    if !progress.unsent.is_empty() {
+        session.interrupted_answer = Some(InterruptedAnswer {
+            qa_pair_id,
+            heard: progress.sent,
+            remaining: progress.unsent,
+        });
+    }
+}
+
+/// Settles a saved question whose answer failed partway, so no note is written for
+/// it: it is removed if none of the answer was heard, and otherwise keeps what was.
+async fn abandon_answer(app_state: &Arc<AppState>, qa_pair_id: Uuid, progress: &SpokenProgress) {
+    let db = &app_state.db;
+    let settled = if progress.sent.is_empty() {
+        db.delete_qa_pair(qa_pair_id).await
+    } else {
+        async {
+            db.update_qa_pair_answer(qa_pair_id, &progress.sent.join(" "), true).await?;
+            db.mark_note_failed(qa_pair_id).await
+        }
+        .await
+    };
+    if let Err(e) = settled {
+        error!("Failed to settle abandoned answer: {:?}", e);
+    }
+}
+
+/// Speaks the rest of an answer the user stopped, and updates the saved answer with
+/// what they have now heard. If they stop it again, the rest is kept again.
+async fn resume_answer(
+    app_state: &Arc<AppState>,
+    session_state_lock: &Arc<Mutex<SessionState>>,
+    ws_sender: &WsSender,
+    answer: InterruptedAnswer,
+    audio_free: bool,
+    speech: &SpeechSettings,
+    question_token: &CancellationToken,
+) -> PortResult<QaOutcome> {
+    info!("Going on with an interrupted answer, {} sentences left.", answer.remaining.len());
+    let mut progress = SpokenProgress::default();
+    let finished = speak_sentences_until_cancelled(
+        app_state,
+        ws_sender,
+        &answer.remaining.join(" "),
+        audio_free,
+        speech,
+        question_token,
+        &mut progress,
+    )
+    .await?;
+
+    let mut heard = answer.heard;
+    heard.extend(progress.sent);
+    let answer_text = heard.join(" ");
+    if let Err(e) = app_state
+        .db
+        .update_qa_pair_answer(answer.qa_pair_id, &answer_text, !finished)
+        .await
+    {
+        error!("Failed to update resumed answer: {:?}", e);
+    }
+
+    {
+        let mut session = session_state_lock.lock().await;
+        session.last_answer = Some(answer_text);
+        if !finished && !progress.unsent.is_empty() {
+            session.interrupted_answer = Some(InterruptedAnswer {
+                qa_pair_id: answer.qa_pair_id,
+                heard,
+                remaining: progress.unsent,
+            });
+        }
+    }
+    if !finished {
+        return cancel_question(ws_sender).await;
+    }
+    send_answering_ended(ws_sender).await;
+    Ok(QaOutcome::QuestionAnswered)
+}
+
+/// Abandons a question the user cancelled, or whose answer they stopped.
+async fn cancel_question(ws_sender: &WsSender) -> PortResult<QaOutcome> {
+    info!("Question cancelled by the user.");
+    send_answering_ended(ws_sender).await;
+    Ok(QaOutcome::Cancelled)
+}
+
+/// Remembers the ambiguous question and asks the user what they meant.
+async fn ask_clarification(
+    app_state: &Arc<AppState>,
+    session_state_lock: &Arc<Mutex<SessionState>>,
+    ws_sender: &WsSender,
+    question_text: String,
+    clarifying: String,
+    audio_free: bool,
+    speech: &SpeechSettings,
+) -> PortResult<QaOutcome> {
+    session_state_lock.lock().await.pending_clarification = Some(question_text);
+
+    let clarify_msg = ServerMessage::ClarificationRequested { question: clarifying.clone() };
+    if ws_sender.send(&clarify_msg).await.is_err() {
+        return Err(PortError::Unexpected(
+            "Failed to send ClarificationRequested message.".to_string(),
+        ));
+    }
+    speak_sentences(app_state, ws_sender, &clarifying, audio_free, speech).await?;
+    send_answering_ended(ws_sender).await;
+
+    Ok(QaOutcome::ClarificationAsked)
+}
+
+/// Spoken instead of a question or answer that fails education-mode moderation.
+const EDUCATION_MODE_REFUSAL: &str =
+    "That's not something we can talk about here. Let's get back to our reading.";
+
+/// In education mode, anything scoring above this in any moderation category is blocked,
+/// well below the provider's own flagging thresholds.
+const EDUCATION_MODE_MAX_CATEGORY_SCORE: f32 = 0.1;
+
+async fn passes_education_moderation(app_state: &Arc<AppState>, text: &str) -> PortResult<bool> {
+    let result = app_state.moderation_adapter.moderate(text).await?;
+    Ok(!result.flagged && result.max_category_score < EDUCATION_MODE_MAX_CATEGORY_SCORE)
+}
+
+/// Reports progress on the question. Purely informational, so a failed send is only logged.
+async fn send_status(ws_sender: &WsSender, stage: ProcessingStage) {
+    if ws_sender.send(&ServerMessage::ProcessingStatus { stage }).await.is_err() {
+        warn!("Failed to send ProcessingStatus message. Client may have disconnected.");
+    }
+}
+
+/// Spoken after an answer the grounding check found unsupported by the document.
+const UNGROUNDED_ANSWER_HEDGE: &str =
+    "I'm not certain the text says that exactly, so you may want to check that part yourself.";
+
+/// Checks a delivered answer against the context it was written from, sends the
+/// verdict, and hedges aloud if the context doesn't back it up. A failed check, or one
+/// the user talks over, leaves the answer as it was.
+async fn check_grounding(
+    app_state: &Arc<AppState>,
+    ws_sender: &WsSender,
+    answer_text: &str,
+    context: &str,
+    audio_free: bool,
+    speech: &SpeechSettings,
+    question_token: &CancellationToken,
+) {
+    let verdict = tokio::select! {
+        biased;
+        _ = question_token.cancelled() => return,
+        verdict = app_state.qa_adapter.verify_grounding(answer_text, context) => verdict,
+    };
+    let verdict = match verdict {
+        Ok(verdict) => verdict,
+        Err(e) => {
+            warn!("Failed to verify the answer's grounding: {:?}", e);
+            return;
+        }
+    };
+
+    let grounding_msg = ServerMessage::AnswerGrounding {
+        supported: verdict.supported,
+        reason: (!verdict.supported).then_some(verdict.reason.clone()),
+    };
+    if ws_sender.send(&grounding_msg).await.is_err() {
+        warn!("Failed to send AnswerGrounding message. Client may have disconnected.");
+        return;
+    }
+    if !verdict.supported {
+        warn!("Answer not supported by its context: {}", verdict.reason);
+        if let Err(e) = speak_sentences(app_state, ws_sender, UNGROUNDED_ANSWER_HEDGE, audio_free, speech).await {
+            warn!("Failed to speak the grounding hedge: {:?}", e);
+        }
+    }
+}
+
+/// Spoken for "where does it say that?" when the last answer cited nothing.
+const NO_SOURCES_MESSAGE: &str =
+    "I can't point to a particular passage for that one. Try asking the question again.";
+
+/// Records the sentences an answer cites, keeping those that exist, and sends them to
+/// the client ahead of the answer.
+async fn send_answer_sources(
+    session_state_lock: &Arc<Mutex<SessionState>>,
+    ws_sender: &WsSender,
+    mut sources: Vec<usize>,
+) {
+    {
+        let mut session = session_state_lock.lock().await;
+        let sentence_count = session.chunked_document.len();
+        sources.retain(|&index| index < sentence_count);
+        sources.sort_unstable();
+        sources.dedup();
+        session.last_answer_sources = sources.clone();
+    }
+    if sources.is_empty() {
+        return;
+    }
+    let sources_msg = ServerMessage::AnswerSources { sentence_indices: sources };
+    if ws_sender.send(&sources_msg).await.is_err() {
+        warn!("Failed to send AnswerSources message. Client may have disconnected.");
+    }
+}
+
+/// Answers "where does it say that?": highlights the sentences the last answer cited
+/// and reads them out.
+async fn show_answer_sources(
+    app_state: &Arc<AppState>,
+    session_state_lock: &Arc<Mutex<SessionState>>,
+    ws_sender: &WsSender,
+    audio_free: bool,
+    speech: &SpeechSettings,
+) -> PortResult<()> {
+    let (sources, document_id) = {
+        let session = session_state_lock.lock().await;
+        (session.last_answer_sources.clone(), session.document_id)
+    };
+    // Cited sentences can be anywhere in the document, not just around the reading
+    // position, so they are read from the database.
+    let passage = app_state
+        .db
+        .get_document_chunks_at(document_id, &sources)
+        .await?
+        .into_iter()
+        .map(|(_, text)| text)
+        .collect::<Vec<_>>()
+        .join(" ");
+    if sources.is_empty() {
+        return speak_sentences(app_state, ws_sender, NO_SOURCES_MESSAGE, audio_free, speech).await;
+    }
+
+    let sources_msg = ServerMessage::AnswerSources { sentence_indices: sources };
+    if ws_sender.send(&sources_msg).await.is_err() {
+        return Err(PortError::Unexpected(
+            "Failed to send AnswerSources message.".to_string(),
+        ));
+    }
+    speak_sentences(app_state, ws_sender, &passage, audio_free, speech).await
+}
+
+/// Re-explains the sentences just read in simpler words, at the user's reading level.
+/// The explanation is sent as an `Explanation` before it is spoken, and the sentences
+/// it explains become the sources "where does it say that?" points to.
+async fn explain_more_simply(
+    app_state: &Arc<AppState>,
+    session_state_lock: &Arc<Mutex<SessionState>>,
+    ws_sender: &WsSender,
+    question_token: &CancellationToken,
+    question_text: String,
+    audio_free: bool,
+    speech: &SpeechSettings,
+) -> PortResult<()> {
+    let (sentence_indices, passage, level) = {
+        let range = session_state_lock.lock().await.recent_passage();
+        let Some(session) =
+            lock_with_sentences(session_state_lock, app_state, range.clone(), question_token).await?
+        else {
+            return Ok(());
+        };
+        let passage = session.chunked_document.passage(range.clone());
+        (range.collect::<Vec<_>>(), passage, session.reading_level)
+    };
+    if sentence_indices.is_empty() {
+        return speak_sentences(app_state, ws_sender, NOTHING_TO_SIMPLIFY_MESSAGE, audio_free, speech).await;
+    }
+
+    let explanation = app_state.qa_adapter.simplify_passage(&passage, level).await?;
+    {
+        let mut session = session_state_lock.lock().await;
+        session.last_question = Some(question_text);
+        session.last_answer = Some(explanation.clone());
+        session.last_answer_sources = sentence_indices.clone();
+    }
+
+    let explanation_msg = ServerMessage::Explanation {
+        text: explanation.clone(),
+        sentence_indices,
+    };
+    if ws_sender.send(&explanation_msg).await.is_err() {
+        return Err(PortError::Unexpected(
+            "Failed to send Explanation message.".to_string(),
+        ));
+    }
+    speak_sentences(app_state, ws_sender, &explanation, audio_free, speech).await
+}
+
+/// Spoken when "explain that more simply" comes before anything has been read.
+const NOTHING_TO_SIMPLIFY_MESSAGE: &str = "We haven't read anything yet, so there's nothing to explain.";
+
+/// How much a spoken "slow down" or "speed up" changes the speaking speed.
+const SPOKEN_SPEED_STEP: f32 = 0.25;
+
+/// Spoken when "skip this paragraph" comes in the last paragraph, or in a document
+/// whose paragraphs aren't known.
+const NO_NEXT_PARAGRAPH_MESSAGE: &str = "There's no next paragraph to skip to, so I'll read on from here.";
+
+async fn send_answering_ended(ws_sender: &WsSender) {
+    let end_msg = ServerMessage::AnsweringEnded;
+    if ws_sender.send(&end_msg).await.is_err() {
+        warn!("Failed to send AnsweringEnded message. Client may have disconnected.");
+    }
+}
+
+/// The minimum cosine similarity for a sentence to count as being "about" a topic.
+const SEEK_MIN_SIMILARITY: f32 = 0.2;
+
+/// Finds the sentence that best matches a spoken topic in the document's embedding
+/// index (see `retrieval::index_document`). Nothing matches until the document has
+/// been embedded.
+async fn find_sentence_for_topic(
+    app_state: &Arc<AppState>,
+    session_state_lock: &Arc<Mutex<SessionState>>,
+    topic: &str,
+) -> PortResult<Option<usize>> {
+    let document_id = session_state_lock.lock().await.document_id;
+
+    let query = app_state
+        .embedding_adapter
+        .embed(&[topic.to_string()])
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| PortError::Unexpected("No embedding returned for topic.".to_string()))?;
+
+    let best = app_state
+        .db
+        .search_document_chunks(document_id, &query, 1)
+        .await?
+        .into_iter()
+        .next();
+
+    Ok(match best {
+        Some((index, score)) if score >= SEEK_MIN_SIMILARITY => {
+            info!("Topic '{}' matched sentence {} (score {:.3}).", topic, index, score);
+            Some(index)
+        }
+        _ => None,
+    })
+}
+
+/// Splits text into sentences, generates their audio in parallel, and sends it in order.
+/// In audio-free mode the text is sent as is instead.
+pub async fn speak_sentences(
+    app_state: &Arc<AppState>,
+    ws_sender: &WsSender,
+    text: &str,
+    audio_free: bool,
+    speech: &SpeechSettings,
+) -> PortResult<()> {
+    speak_sentences_until_cancelled(app_state, ws_sender, text, audio_free, speech, &CancellationToken::new(), &mut SpokenProgress::default())
+        .await
+        .map(|_| ())
+}
+
+/// How far speaking a text got before it finished or was stopped.
+#[derive(Default)]
+pub struct SpokenProgress {
+    /// The sentences sent to the client, in order.
+    pub sent: Vec<String>,
+    /// The sentences left unsent because speaking was stopped.
+    pub unsent: Vec<String>,
+}
+
+/// Like `speak_sentences`, but stops generating and sending audio as soon as
+/// `cancellation_token` is cancelled. Each sentence is sent as soon as its audio and
+/// that of the sentences before it are ready, and recorded in `progress`. Returns
+/// whether all of the text was sent.
+pub async fn speak_sentences_until_cancelled(
+    app_state: &Arc<AppState>,
+    ws_sender: &WsSender,
+    text: &str,
+    audio_free: bool,
+    speech: &SpeechSettings,
+    cancellation_token: &CancellationToken,
+    progress: &mut SpokenProgress,
+) -> PortResult<bool> {
+    let sentences = split_into_sentences(text);
+    if cancellation_token.is_cancelled() {
+        progress.unsent = sentences;
+        return Ok(false);
+    }
+    if audio_free {
+        send_assistant_text(ws_sender, text).await?;
+        progress.sent = sentences;
+        return Ok(true);
+    }
 
-    // ✅ Split into sentences and generate TTS in PARALLEL
-    let tts_start = Instant::now();
-    let sentences = split_into_sentences(&answer_text);
-    
     info!("🔊 Generating audio for {} sentences in parallel", sentences.len());
-    
+
     // Generate all TTS in parallel
     let mut tts_tasks = Vec::new();
     for sentence in sentences.iter() {
         let tts_adapter = app_state.tts_adapter.clone();
         let sentence = sentence.clone();
+        let speech = speech.clone();
         tts_tasks.push(tokio::spawn(async move {
-            tts_adapter.generate_audio(&sentence).await
+            tts_adapter.generate_audio(&sentence, &speech).await
         }));
     }
 
-    // Wait for all TTS to complete
-    let mut audio_chunks = Vec::new();
-    for (i, task) in tts_tasks.into_iter().enumerate() {
-        match task.await {
-            Ok(Ok(audio_data)) => {
-                audio_chunks.push(audio_data);
-            }
+    // Send each sentence in order as soon as its audio is ready, so the user hears the
+    // start of the answer while the rest is generated and can stop it at any point.
+    for i in 0..tts_tasks.len() {
+        let joined = tokio::select! {
+            biased;
+            _ = cancellation_token.cancelled() => None,
+            joined = &mut tts_tasks[i] => Some(joined),
+        };
+        let Some(joined) = joined else {
+            tts_tasks.iter().for_each(|task| task.abort());
+            progress.unsent = sentences[i..].to_vec();
+            return Ok(false);
+        };
+        let audio_data = match joined {
+            Ok(Ok(audio_data)) => audio_data,
             Ok(Err(e)) => {
                 error!("TTS generation failed for sentence {}: {:?}", i + 1, e);
+                tts_tasks.iter().for_each(|task| task.abort());
                 return Err(e);
             }
             Err(e) => {
                 error!("Task join error for sentence {}: {:?}", i + 1, e);
                 return Err(PortError::Unexpected(e.to_string()));
             }
+        };
+
+        let delivered = tokio::select! {
+            biased;
+            _ = cancellation_token.cancelled() => None,
+            delivered = ws_sender.send_captioned_audio(&sentences[i], AudioKind::Answer, Some(i), audio_data) => Some(delivered),
+        };
+        match delivered {
+            None => {
+                tts_tasks.iter().for_each(|task| task.abort());
+                progress.unsent = sentences[i..].to_vec();
+                return Ok(false);
+            }
+            Some(Err(_)) => {
+                return Err(PortError::Unexpected(
+                    "Failed to send answer audio chunk to client.".to_string(),
+                ));
+            }
+            Some(Ok(())) => progress.sent.push(sentences[i].clone()),
         }
     }
 
-    // Send all chunks in order
-    for audio_data in audio_chunks {
-        if ws_sender.lock().await.send(Message::Binary(audio_data.into())).await.is_err() {
-            return Err(PortError::Unexpected(
-                "Failed to send answer audio chunk to client.".to_string(),
-            ));
+    Ok(true)
+}
+
+/// Speaks an answer while the model is still writing it. Each sentence is synthesized
+/// as soon as it is complete, and sent (as `AnswerSentence`, then its audio) in order
+/// once its audio is ready. What was sent, and what was written but not sent, is
+/// recorded in `progress`. Returns the whole answer, or `None` if the user stopped it.
+async fn speak_answer_stream(
+    app_state: &Arc<AppState>,
+    ws_sender: &WsSender,
+    mut stream: AnswerStream,
+    audio_free: bool,
+    speech: &SpeechSettings,
+    cancellation_token: &CancellationToken,
+    progress: &mut SpokenProgress,
+) -> PortResult<Option<String>> {
+    let (tts_tx, mut tts_rx) = mpsc::unbounded_channel::<QueuedSentence>();
+
+    // Reads the answer, starting the synthesis of each sentence as it completes.
+    let write = async move {
+        let mut answer = String::new();
+        let mut sentences = SentenceBuffer::default();
+        while let Some(delta) = stream.next().await {
+            let delta = delta?;
+            answer.push_str(&delta);
+            for sentence in sentences.push(&delta) {
+                queue_answer_sentence(app_state, &tts_tx, sentence, audio_free, speech);
+            }
         }
+        if let Some(sentence) = sentences.finish() {
+            queue_answer_sentence(app_state, &tts_tx, sentence, audio_free, speech);
+        }
+        Ok::<_, PortError>(answer.trim().to_string())
+    };
+
+    // Sends each sentence and its audio, in order, as soon as the audio is ready. The
+    // sentence being sent is held outside, so one the user stops is not lost.
+    let mut in_flight = None;
+    let speak = async {
+        let mut index = 0;
+        while let Some((sentence, task)) = tts_rx.recv().await {
+            index += 1;
+            let caption = ServerMessage::AnswerSentence { text: sentence.clone() };
+            in_flight = Some(sentence);
+            let audio_data = match task {
+                None => None,
+                Some(task) => match task.await {
+                    Ok(Ok(audio_data)) => Some(audio_data),
+                    Ok(Err(e)) => {
+                        error!("TTS generation failed for answer sentence {}: {:?}", index, e);
+                        return Err(e);
+                    }
+                    Err(e) => {
+                        error!("Task join error for answer sentence {}: {:?}", index, e);
+                        return Err(PortError::Unexpected(e.to_string()));
+                    }
+                },
+            };
+            if ws_sender.send(&caption).await.is_err() {
+                return Err(PortError::Unexpected(
+                    "Failed to send answer sentence to client.".to_string(),
+                ));
+            }
+            if let Some(audio_data) = audio_data {
+                if ws_sender.send_audio(AudioKind::Answer, Some(index - 1), audio_data).await.is_err() {
+                    return Err(PortError::Unexpected(
+                        "Failed to send answer audio chunk to client.".to_string(),
+                    ));
+                }
+            }
+            progress.sent.extend(in_flight.take());
+        }
+        Ok(())
+    };
+
+    let spoken = tokio::select! {
+        biased;
+        _ = cancellation_token.cancelled() => None,
+        spoken = async { tokio::try_join!(write, speak) } => Some(spoken),
+    };
+    progress.unsent.extend(in_flight);
+    // Audio that will never be sent is not worth finishing.
+    while let Ok((sentence, task)) = tts_rx.try_recv() {
+        if let Some(task) = task {
+            task.abort();
+        }
+        progress.unsent.push(sentence);
     }
-    
-    let tts_duration = tts_start.elapsed();
-    info!("⏱️ TTS (parallel) took: {:?}", tts_duration);
 
-    let total_duration = start_time.elapsed();
-    info!("⏱️ Total QA process took: {:?}", total_duration);
-    info!("Finished sending answer audio.");
-    
-    let end_msg = ServerMessage::AnsweringEnded;
-    let end_json = serde_json::to_string(&end_msg).unwrap();
-    if ws_sender.lock().await.send(Message::Text(end_json.into())).await.is_err() {
-        warn!("Failed to send AnsweringEnded message. Client may have disconnected.");
+    match spoken {
+        Some(Ok((answer, ()))) => Ok(Some(answer)),
+        Some(Err(e)) => Err(e),
+        None => Ok(None),
     }
+}
 
-    Ok(QaOutcome::QuestionAnswered)
+/// A sentence of a streamed answer waiting to be sent, with the task synthesizing its
+/// audio (`None` in audio-free mode).
+type QueuedSentence = (String, Option<JoinHandle<PortResult<Vec<u8>>>>);
+
+/// Starts synthesizing one sentence of a streamed answer and queues it to be spoken.
+/// In audio-free mode the sentence is queued without audio.
+fn queue_answer_sentence(
+    app_state: &Arc<AppState>,
+    tts_tx: &mpsc::UnboundedSender<QueuedSentence>,
+    sentence: String,
+    audio_free: bool,
+    speech: &SpeechSettings,
+) {
+    let task = (!audio_free).then(|| {
+        let tts_adapter = app_state.tts_adapter.clone();
+        let text = sentence.clone();
+        let speech = speech.clone();
+        tokio::spawn(async move { tts_adapter.generate_audio(&text, &speech).await })
+    });
+    // The receiver is only gone once speaking has stopped.
+    let _ = tts_tx.send((sentence, task));
+}
+
+/// The approximate number of characters summarized per LLM call when building a recap.
+const RECAP_CHUNK_CHARS: usize = 8000;
+
+/// Summarizes sentences `0..current_index`. Long texts are summarized hierarchically:
+/// each chunk is summarized, then the summaries are summarized until one remains.
+async fn summarize_read_so_far(
+    app_state: &Arc<AppState>,
+    session_state_lock: &Arc<Mutex<SessionState>>,
+) -> PortResult<String> {
+    let (document_id, end) = {
+        let session = session_state_lock.lock().await;
+        let end = session.reading_progress_index.min(session.chunked_document.len());
+        (session.document_id, end)
+    };
+
+    // Everything read so far can be more than the session holds, so it is read from
+    // the database a window at a time, and each window dropped once summarized.
+    let window = app_state.config.session_sentence_window;
+    let mut summaries = Vec::new();
+    for start in (0..end).step_by(window) {
+        let sentences = app_state
+            .db
+            .get_document_chunk_range(document_id, start..(start + window).min(end))
+            .await?;
+        for chunk in group_into_chunks(&sentences, RECAP_CHUNK_CHARS) {
+            summaries.push(app_state.qa_adapter.summarize_passage(&chunk).await?);
+        }
+    }
+
+    if summaries.is_empty() {
+        return Ok("We haven't read anything yet.".to_string());
+    }
+
+    while summaries.len() > 1 {
+        let chunks = group_into_chunks(&summaries, RECAP_CHUNK_CHARS);
+        info!("Summarizing {} recap chunks.", chunks.len());
+        summaries = Vec::with_capacity(chunks.len());
+        for chunk in &chunks {
+            summaries.push(app_state.qa_adapter.summarize_passage(chunk).await?);
+        }
+    }
+    Ok(format!("Here's a recap of what we've read so far. {}", summaries[0]))
+}
+
+/// Speaks a one-sentence "last time we covered" recap of the text just before the
+/// current position. Only the most recent `RECAP_CHUNK_CHARS` characters are used.
+pub async fn speak_resume_recap(
+    app_state: &Arc<AppState>,
+    session_state_lock: &Arc<Mutex<SessionState>>,
+    ws_sender: &WsSender,
+) -> PortResult<()> {
+    let (recent_text, audio_free, speech) = {
+        let session = session_state_lock.lock().await;
+        let end = session.reading_progress_index.min(session.chunked_document.len());
+        let mut start = end;
+        let mut chars = 0;
+        // Only loaded sentences are used; the window reaches back well past the position.
+        while start > 0 && chars < RECAP_CHUNK_CHARS {
+            let Some(sentence) = session.chunked_document.get(start - 1) else {
+                break;
+            };
+            start -= 1;
+            chars += sentence.len();
+        }
+        (session.chunked_document.passage(start..end), session.audio_free, session.speech.clone())
+    };
+
+    if recent_text.is_empty() {
+        return Ok(());
+    }
+
+    let recap = app_state
+        .qa_adapter
+        .summarize_passage_in_one_sentence(&recent_text)
+        .await?;
+    speak(
+        app_state,
+        ws_sender,
+        &format!("Last time we covered {} Let's pick up where we left off.", recap.trim()),
+        audio_free,
+        &speech,
+    )
+    .await
+}
+
+/// Joins consecutive pieces into chunks of roughly `max_chars` characters.
+fn group_into_chunks(pieces: &[String], max_chars: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    for piece in pieces {
+        if !current.is_empty() && current.len() + piece.len() > max_chars {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push(' ');
+        }
+        current.push_str(piece);
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+
+    chunks
+}
+
+/// Speaks a short system announcement to the client, or sends its text in audio-free mode.
+pub async fn speak(
+    app_state: &Arc<AppState>,
+    ws_sender: &WsSender,
+    text: &str,
+    audio_free: bool,
+    speech: &SpeechSettings,
+) -> PortResult<()> {
+    if audio_free {
+        return send_assistant_text(ws_sender, text).await;
+    }
+
+    let audio_data = app_state.tts_adapter.generate_audio(text, speech).await?;
+    if ws_sender.send_captioned_audio(text, AudioKind::Announcement, None, audio_data).await.is_err() {
+        return Err(PortError::Unexpected(
+            "Failed to send announcement audio to client.".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Sends what the assistant would have said aloud, for audio-free sessions.
+async fn send_assistant_text(ws_sender: &WsSender, text: &str) -> PortResult<()> {
+    let text_msg = ServerMessage::AssistantText { text: text.to_string() };
+    if ws_sender.send(&text_msg).await.is_err() {
+        return Err(PortError::Unexpected(
+            "Failed to send assistant text to client.".to_string(),
+        ));
+    }
+    Ok(())
 }
 
 // Helper function
@@ -183,8 +1340,25 @@ fn split_into_sentences(text: &str) -> Vec<String> {
         .collect()
 }
 
-/// A helper function to extract the last few sentences of context from the document.
-fn get_context_from_document(session: &SessionState) -> String {
+/// Searches the index for a question, or finds nothing if retrieval fails; the question
+/// is then answered from the passage being read alone.
+async fn retrieve_or_default(
+    app_state: &Arc<AppState>,
+    user_id: Uuid,
+    document_id: Uuid,
+    question: &str,
+) -> Retrieved {
+    retrieve(app_state, user_id, document_id, question)
+        .await
+        .unwrap_or_else(|e| {
+            warn!("Failed to retrieve relevant chunks, using the current passage only: {:?}", e);
+            Retrieved::default()
+        })
+}
+
+/// The sentences around the reading position that questions are answered from,
+/// together with any retrieved chunks.
+fn context_window(session: &SessionState) -> Range<usize> {
     let current_index = session.reading_progress_index;
     let total_sentences = session.chunked_document.len();
     
@@ -202,57 +1376,84 @@ fn get_context_from_document(session: &SessionState) -> String {
     
     let end_index = (start_index + 10).min(total_sentences);
     
-    session.chunked_document[start_index..end_index].join(" ")
+    start_index..end_index
 }
 
-/// A "fire-and-forget" background task to generate and save notes without blocking the user.
-async fn generate_and_save_notes(app_state: Arc<AppState>, qapair: QAPair) {
-    info!(
-        "Spawning background task to save QAPair and generate notes for session {}.",
-        qapair.session_id
-    );
-
-    if app_state.db.save_qa_pair(qapair.clone()).await.is_err() {
-        error!(
-            "Failed to save QAPair to database for session {}. Note generation will be skipped.",
-            qapair.session_id
-        );
+/// A "fire-and-forget" background task that condenses a session's notes into one summary note.
+pub async fn generate_and_save_summary_note(app_state: Arc<AppState>, session_id: Uuid) {
+    let mut notes = match app_state.db.get_notes_for_session(session_id).await {
+        Ok(notes) => notes,
+        Err(e) => {
+            error!("Failed to fetch notes for session {}: {:?}", session_id, e);
+            return;
+        }
+    };
+    // A weekly recap shown on this session covers other sessions as well.
+    notes.retain(|note| note.source != NoteSource::WeeklyRecap);
+    if notes.is_empty() {
+        info!("No notes to summarize for session {}.", session_id);
         return;
     }
 
-    match app_state
-        .notes_adapter
-        .generate_note_from_qapair(&qapair)
-        .await
-    {
-        Ok(note_text) => {
-            if note_text.trim() == "SKIP_NOTE" {
-            info!(
-                "Skipping note generation - question was unrelated for session {}",
-                qapair.session_id
-            );
-            return;
-            }
+    match app_state.notes_adapter.summarize_notes(&notes).await {
+        Ok(summary) => {
             let note = reading_assistant_core::domain::Note {
                 id: Uuid::new_v4(),
-                session_id: qapair.session_id,
-                generated_note_text: note_text,
-                created_at: chrono::Utc::now(), 
+                session_id,
+                generated_note_text: format!("Session summary: {}", summary),
+                created_at: chrono::Utc::now(),
+                source_start_index: None,
+                source_end_index: None,
+                source: NoteSource::Summary,
             };
             if app_state.db.save_note(note).await.is_err() {
-                error!(
-                    "Failed to save generated note to database for session {}.",
-                    qapair.session_id
-                );
+                error!("Failed to save summary note for session {}.", session_id);
             } else {
-                info!(
-                    "Successfully generated and saved note for session {}.",
-                    qapair.session_id
-                );
+                info!("Saved summary note for session {}.", session_id);
             }
         }
+        Err(e) => error!("Failed to summarize notes for session {}: {:?}", session_id, e),
+    }
+}
+
+/// A "fire-and-forget" background task that saves the key points of a passage just read
+/// as auto notes, each linked back to the passage.
+pub async fn generate_and_save_auto_notes(
+    app_state: Arc<AppState>,
+    session_id: Uuid,
+    passage: String,
+    source: Range<usize>,
+    education_mode: bool,
+) {
+    let key_points = match app_state
+        .notes_adapter
+        .extract_key_points(&passage, education_mode)
+        .await
+    {
+        Ok(key_points) => key_points,
         Err(e) => {
-            error!("Failed to generate note from QAPair: {}", e);
+            error!("Failed to extract key points for session {}: {:?}", session_id, e);
+            return;
+        }
+    };
+
+    for key_point in key_points {
+        let note = reading_assistant_core::domain::Note {
+            id: Uuid::new_v4(),
+            session_id,
+            generated_note_text: key_point,
+            created_at: chrono::Utc::now(),
+            source_start_index: Some(source.start),
+            source_end_index: Some(source.end),
+            source: NoteSource::Auto,
+        };
+        if app_state.db.save_note(note).await.is_err() {
+            error!("Failed to save auto note for session {}.", session_id);
+            return;
         }
     }
+    info!(
+        "Saved auto notes on sentences {}..{} for session {}.",
+        source.start, source.end, session_id
+    );
 }
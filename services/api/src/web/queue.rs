@@ -0,0 +1,224 @@
+//! services/api/src/web/queue.rs
+//!
+//! Endpoints for the per-user read-later queue: subscribing to feeds, saving
+//! links, and listing what is ready to listen to next.
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use reading_assistant_core::domain::QueueItemStatus;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::web::state::AppState;
+
+//=========================================================================================
+// Request/Response Types
+//=========================================================================================
+
+#[derive(Deserialize, ToSchema)]
+pub struct AddFeedRequest {
+    pub url: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct SaveLinkRequest {
+    pub url: String,
+    pub title: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct FeedItem {
+    pub feed_id: Uuid,
+    pub url: String,
+    pub last_fetched_at: Option<String>, // ISO 8601 timestamp
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ListFeedsResponse {
+    pub feeds: Vec<FeedItem>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct QueueEntry {
+    pub item_id: Uuid,
+    pub url: String,
+    pub title: Option<String>,
+    /// One of `pending`, `ready`, or `failed`.
+    pub status: String,
+    /// The session to open once the item is ready.
+    pub session_id: Option<Uuid>,
+    pub from_feed: bool,
+    pub created_at: String, // ISO 8601 timestamp
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ListQueueResponse {
+    pub items: Vec<QueueEntry>,
+}
+
+fn validate_url(url: &str) -> Result<(), (StatusCode, String)> {
+    if url.starts_with("http://") || url.starts_with("https://") {
+        Ok(())
+    } else {
+        Err((StatusCode::BAD_REQUEST, "URL must start with http:// or https://".to_string()))
+    }
+}
+
+//=========================================================================================
+// Handlers
+//=========================================================================================
+
+/// POST /queue/feeds - Subscribe to an RSS/Atom feed
+#[utoipa::path(
+    post,
+    path = "/queue/feeds",
+    request_body = AddFeedRequest,
+    responses(
+        (status = 201, description = "Feed subscribed", body = FeedItem),
+        (status = 400, description = "Invalid URL"),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn add_feed_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Json(req): Json<AddFeedRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    validate_url(&req.url)?;
+
+    let feed = state.db.create_feed(user_id, &req.url).await.map_err(|e| {
+        error!("Failed to create feed: {:?}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to add feed".to_string())
+    })?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(FeedItem {
+            feed_id: feed.id,
+            url: feed.url,
+            last_fetched_at: feed.last_fetched_at.map(|t| t.to_rfc3339()),
+        }),
+    ))
+}
+
+/// GET /queue/feeds - List the user's feed subscriptions
+#[utoipa::path(
+    get,
+    path = "/queue/feeds",
+    responses(
+        (status = 200, description = "Feeds retrieved successfully", body = ListFeedsResponse),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn list_feeds_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let feeds = state.db.get_feeds_by_user(user_id).await.map_err(|e| {
+        error!("Failed to fetch feeds: {:?}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch feeds".to_string())
+    })?;
+
+    let feeds = feeds
+        .into_iter()
+        .map(|f| FeedItem {
+            feed_id: f.id,
+            url: f.url,
+            last_fetched_at: f.last_fetched_at.map(|t| t.to_rfc3339()),
+        })
+        .collect();
+
+    Ok((StatusCode::OK, Json(ListFeedsResponse { feeds })))
+}
+
+/// POST /queue/links - Save a single link to read later
+#[utoipa::path(
+    post,
+    path = "/queue/links",
+    request_body = SaveLinkRequest,
+    responses(
+        (status = 202, description = "Link queued for ingestion"),
+        (status = 400, description = "Invalid URL"),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn save_link_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Json(req): Json<SaveLinkRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    validate_url(&req.url)?;
+
+    state
+        .db
+        .enqueue_link(user_id, None, &req.url, req.title.as_deref())
+        .await
+        .map_err(|e| {
+            error!("Failed to queue link: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to save link".to_string())
+        })?;
+
+    Ok(StatusCode::ACCEPTED)
+}
+
+/// GET /queue - List the user's reading queue, oldest first
+#[utoipa::path(
+    get,
+    path = "/queue",
+    responses(
+        (status = 200, description = "Queue retrieved successfully", body = ListQueueResponse),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn list_queue_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let items = state.db.get_queue_for_user(user_id).await.map_err(|e| {
+        error!("Failed to fetch queue: {:?}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch queue".to_string())
+    })?;
+
+    let items = items
+        .into_iter()
+        .map(|i| QueueEntry {
+            item_id: i.id,
+            url: i.url,
+            title: i.title,
+            status: match i.status {
+                QueueItemStatus::Pending => "pending",
+                QueueItemStatus::Ready => "ready",
+                QueueItemStatus::Failed => "failed",
+            }
+            .to_string(),
+            session_id: i.session_id,
+            from_feed: i.feed_id.is_some(),
+            created_at: i.created_at.to_rfc3339(),
+        })
+        .collect();
+
+    Ok((StatusCode::OK, Json(ListQueueResponse { items })))
+}
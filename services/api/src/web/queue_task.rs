@@ -0,0 +1,98 @@
+//! services/api/src/web/queue_task.rs
+//!
+//! This module contains the long-running background "worker" that keeps users'
+//! reading queues filled: it polls subscribed feeds for new links and ingests
+//! pending links as documents with a ready-to-play session.
+
+use crate::web::{retrieval::index_document, state::AppState, tags::suggest_and_save_tags};
+use reading_assistant_core::{
+    domain::QueueItem,
+    ports::{PortError, PortResult},
+};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+
+/// The maximum number of pending links ingested per tick.
+const INGEST_BATCH_SIZE: i64 = 20;
+
+/// The main loop of the queue worker. Runs for the lifetime of the server.
+pub async fn queue_ingest_process(app_state: Arc<AppState>) {
+    info!("Reading queue worker started.");
+    let mut ticker = tokio::time::interval(Duration::from_secs(
+        app_state.config.queue_poll_interval_secs,
+    ));
+
+    loop {
+        ticker.tick().await;
+
+        if let Err(e) = poll_feeds(&app_state).await {
+            error!("Failed to poll feeds: {:?}", e);
+        }
+        if let Err(e) = ingest_pending(&app_state).await {
+            error!("Failed to ingest queued links: {:?}", e);
+        }
+    }
+}
+
+/// Fetches every feed that is due for a refresh and enqueues its entries.
+async fn poll_feeds(app_state: &Arc<AppState>) -> PortResult<()> {
+    let refresh = chrono::Duration::seconds(app_state.config.feed_refresh_interval_secs as i64);
+    let feeds = app_state.db.get_feeds_due(chrono::Utc::now() - refresh).await?;
+
+    for feed in feeds {
+        match app_state.content_fetcher.fetch_feed(&feed.url).await {
+            Ok(entries) => {
+                info!("Feed {} returned {} entries.", feed.url, entries.len());
+                for entry in entries {
+                    app_state
+                        .db
+                        .enqueue_link(feed.user_id, Some(feed.id), &entry.url, entry.title.as_deref())
+                        .await?;
+                }
+            }
+            Err(e) => warn!("Failed to fetch feed {}: {:?}", feed.url, e),
+        }
+        // Mark as fetched even on failure so a broken feed doesn't get hammered every tick.
+        app_state.db.mark_feed_fetched(feed.id).await?;
+    }
+
+    Ok(())
+}
+
+/// Turns pending queue items into documents and sessions.
+async fn ingest_pending(app_state: &Arc<AppState>) -> PortResult<()> {
+    let items = app_state.db.get_pending_queue_items(INGEST_BATCH_SIZE).await?;
+
+    for item in items {
+        if let Err(e) = ingest_item(app_state, &item).await {
+            warn!("Failed to ingest {}: {:?}", item.url, e);
+            app_state.db.mark_queue_item_failed(item.id).await?;
+        }
+    }
+
+    Ok(())
+}
+
+async fn ingest_item(app_state: &Arc<AppState>, item: &QueueItem) -> PortResult<()> {
+    let (page_title, text) = app_state.content_fetcher.fetch_article(&item.url).await?;
+    let title = item.title.clone().unwrap_or(page_title);
+    if text.chars().count() > app_state.config.max_document_chars {
+        return Err(PortError::Unexpected(format!(
+            "Article is longer than {} characters",
+            app_state.config.max_document_chars
+        )));
+    }
+
+    let doc = app_state.db.create_document(item.user_id, &title, &text).await?;
+    let session = app_state.db.create_session(item.user_id, doc.id, false).await?;
+    tokio::spawn(index_document(app_state.clone(), doc.id));
+    tokio::spawn(suggest_and_save_tags(app_state.clone(), item.user_id, doc.id, text));
+    app_state
+        .db
+        .mark_queue_item_ready(item.id, &title, session.id)
+        .await?;
+
+    info!("Ingested '{}' into the reading queue of user {}.", title, item.user_id);
+    Ok(())
+}
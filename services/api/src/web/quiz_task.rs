@@ -0,0 +1,332 @@
+//! services/api/src/web/quiz_task.rs
+//!
+//! This module contains the "quiz me" flow: generating questions about the passage
+//! just read, grading the user's spoken answers, and storing the results. Active
+//! reading's comprehension checkpoints are one-question quizzes that read on after.
+
+use crate::web::{
+    protocol::ServerMessage,
+    qa_task::{speak, speak_sentences},
+    state::{lock_with_sentences, AppState, QuizState, SessionEvent, SessionState},
+    ws_writer::WsSender,
+};
+use reading_assistant_core::{
+    domain::QuizAttempt,
+    ports::{PortError, PortResult},
+};
+use std::{ops::Range, sync::Arc};
+use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// How many questions to ask when the client doesn't say.
+const DEFAULT_QUESTION_COUNT: usize = 3;
+/// Upper bound on a client-requested question count.
+const MAX_QUESTION_COUNT: usize = 10;
+/// How many sentences before the current position the quiz covers.
+const QUIZ_CONTEXT_SENTENCES: usize = 40;
+
+/// What happens once a quiz answer has been handled.
+pub enum QuizOutcome {
+    /// The quiz goes on, or has ended and waits for the user to say what's next.
+    AwaitingUser,
+    /// A checkpoint question was answered or skipped, so reading carries on.
+    ReadOn,
+}
+
+/// Phrases that end a quiz early.
+const STOP_PHRASES: &[&str] = &["stop the quiz", "end the quiz", "quit the quiz", "stop quiz"];
+/// Phrases that skip a checkpoint question and read on.
+const CHECKPOINT_SKIP_PHRASES: &[&str] = &["skip", "keep reading", "read on"];
+
+async fn send_message(
+    ws_sender: &WsSender,
+    msg: &ServerMessage,
+) -> PortResult<()> {
+    if ws_sender.send(msg).await.is_err() {
+        return Err(PortError::Unexpected(format!(
+            "Failed to send {:?} message.",
+            msg
+        )));
+    }
+    Ok(())
+}
+
+/// Generates questions about the recently read passage and asks the first one.
+pub async fn start_quiz(
+    app_state: Arc<AppState>,
+    session_state_lock: Arc<Mutex<SessionState>>,
+    ws_sender: WsSender,
+    question_count: Option<usize>,
+) -> PortResult<()> {
+    let count = question_count
+        .unwrap_or(DEFAULT_QUESTION_COUNT)
+        .clamp(1, MAX_QUESTION_COUNT);
+
+    let (context, audio_free, speech) = {
+        let mut session = session_state_lock.lock().await;
+        let end = session.reading_progress_index.min(session.chunked_document.len());
+        let start = end.saturating_sub(QUIZ_CONTEXT_SENTENCES);
+        session.load_sentences(&app_state, start..end).await?;
+        (session.chunked_document.passage(start..end), session.audio_free, session.speech.clone())
+    };
+
+    if context.trim().is_empty() {
+        speak(&app_state, &ws_sender, "We haven't read anything yet, so there's nothing to quiz you on.", audio_free, &speech).await?;
+        return Ok(());
+    }
+
+    info!("Generating {} quiz questions.", count);
+    let questions = app_state
+        .qa_adapter
+        .generate_quiz_questions(&context, count)
+        .await?;
+
+    session_state_lock.lock().await.quiz = Some(QuizState {
+        questions,
+        current: 0,
+        correct_answers: 0,
+        checkpoint: false,
+    });
+
+    speak(&app_state, &ws_sender, "Let's see what you remember.", audio_free, &speech).await?;
+    ask_current_question(&app_state, &session_state_lock, &ws_sender).await
+}
+
+/// Stops active reading at a comprehension checkpoint and asks one question about the
+/// sentences in `section`, just read. Returns whether reading stopped: if no question
+/// could be written, reading carries on past the checkpoint, and if the user stopped
+/// reading meanwhile, their request stands.
+pub async fn ask_checkpoint_question(
+    app_state: &Arc<AppState>,
+    session_state_lock: &Arc<Mutex<SessionState>>,
+    ws_sender: &WsSender,
+    cancellation_token: &CancellationToken,
+    section: Range<usize>,
+) -> bool {
+    let passage = match lock_with_sentences(session_state_lock, app_state, section.clone(), cancellation_token).await {
+        Ok(Some(session)) => session.chunked_document.passage(section),
+        Ok(None) => return false,
+        Err(e) => {
+            warn!("Failed to load the checkpoint passage: {:?}", e);
+            session_state_lock.lock().await.chunked_document.passage(section)
+        }
+    };
+
+    // The client is still playing the end of the passage while the question is written.
+    let questions = tokio::select! {
+        biased;
+        _ = cancellation_token.cancelled() => return false,
+        questions = app_state.qa_adapter.generate_quiz_questions(&passage, 1) => questions,
+    };
+    let questions = match questions {
+        Ok(questions) if !questions.is_empty() => questions,
+        Ok(_) => {
+            warn!("No checkpoint question was generated, reading on.");
+            return false;
+        }
+        Err(e) => {
+            warn!("Failed to generate a checkpoint question, reading on: {:?}", e);
+            return false;
+        }
+    };
+
+    {
+        let mut session = session_state_lock.lock().await;
+        if cancellation_token.is_cancelled() || session.apply(SessionEvent::ReachCheckpoint).is_err() {
+            return false;
+        }
+        session.quiz = Some(QuizState {
+            questions,
+            current: 0,
+            correct_answers: 0,
+            checkpoint: true,
+        });
+    }
+
+    if let Err(e) = send_message(ws_sender, &ServerMessage::CheckpointReached).await {
+        warn!("{:?}", e);
+    }
+    if let Err(e) = ask_current_question(app_state, session_state_lock, ws_sender).await {
+        warn!("Failed to ask the checkpoint question: {:?}", e);
+    }
+    true
+}
+
+/// Sends and speaks the question the quiz is currently on.
+async fn ask_current_question(
+    app_state: &Arc<AppState>,
+    session_state_lock: &Arc<Mutex<SessionState>>,
+    ws_sender: &WsSender,
+) -> PortResult<()> {
+    let (question_number, total_questions, question, audio_free, speech) = {
+        let session = session_state_lock.lock().await;
+        let quiz = session
+            .quiz
+            .as_ref()
+            .ok_or_else(|| PortError::Unexpected("No active quiz.".to_string()))?;
+        (
+            quiz.current + 1,
+            quiz.questions.len(),
+            quiz.questions[quiz.current].question.clone(),
+            session.audio_free,
+            session.speech.clone(),
+        )
+    };
+
+    send_message(
+        ws_sender,
+        &ServerMessage::QuizQuestion {
+            question_number,
+            total_questions,
+            question: question.clone(),
+        },
+    )
+    .await?;
+    speak(app_state, ws_sender, &question, audio_free, &speech).await
+}
+
+/// Grades the user's answer, transcribed from the buffered audio unless it was typed,
+/// then asks the next question or ends the quiz. A checkpoint ends after its one
+/// question, without a score.
+pub async fn quiz_answer_process(
+    app_state: Arc<AppState>,
+    session_state_lock: Arc<Mutex<SessionState>>,
+    ws_sender: WsSender,
+    typed_answer: Option<String>,
+) -> PortResult<QuizOutcome> {
+    let (audio_buffer, session_id, question_number, question, checkpoint, audio_free, speech) = {
+        let mut session = session_state_lock.lock().await;
+        let audio_buffer = std::mem::take(&mut session.audio_buffer);
+        let session_id = session.session_id;
+        let quiz = session
+            .quiz
+            .as_ref()
+            .ok_or_else(|| PortError::Unexpected("No active quiz.".to_string()))?;
+        (
+            audio_buffer,
+            session_id,
+            quiz.current + 1,
+            quiz.questions[quiz.current].clone(),
+            quiz.checkpoint,
+            session.audio_free,
+            session.speech.clone(),
+        )
+    };
+
+    let user_answer = match typed_answer {
+        Some(text) => text,
+        None => {
+            app_state
+                .sst_adapter
+                .transcribe_audio(&audio_buffer)
+                .await?
+        }
+    };
+    info!("Quiz answer: '{}'", user_answer);
+
+    let lowercased = user_answer.to_lowercase();
+    let skip_phrases: &[&str] = if checkpoint { CHECKPOINT_SKIP_PHRASES } else { &[] };
+    if STOP_PHRASES.iter().chain(skip_phrases).any(|p| lowercased.contains(p)) {
+        if checkpoint {
+            info!("Checkpoint question skipped by the user.");
+            session_state_lock.lock().await.quiz = None;
+            return Ok(QuizOutcome::ReadOn);
+        }
+        info!("Quiz stopped early by the user.");
+        end_quiz(&app_state, &session_state_lock, &ws_sender).await?;
+        return Ok(QuizOutcome::AwaitingUser);
+    }
+
+    let grade = app_state
+        .qa_adapter
+        .grade_quiz_answer(&question, &user_answer)
+        .await?;
+
+    let attempt = QuizAttempt {
+        id: Uuid::new_v4(),
+        session_id,
+        question_text: question.question.clone(),
+        expected_answer: question.expected_answer.clone(),
+        user_answer,
+        correct: grade.correct,
+        feedback: grade.feedback.clone(),
+    };
+    if let Err(e) = app_state.db.save_quiz_attempt(attempt).await {
+        error!("Failed to save quiz attempt for session {}: {:?}", session_id, e);
+    }
+
+    send_message(
+        &ws_sender,
+        &ServerMessage::QuizResult {
+            question_number,
+            correct: grade.correct,
+            expected_answer: question.expected_answer,
+            feedback: grade.feedback.clone(),
+        },
+    )
+    .await?;
+    speak_sentences(&app_state, &ws_sender, &grade.feedback, audio_free, &speech).await?;
+
+    let finished = {
+        let mut session = session_state_lock.lock().await;
+        let quiz = session
+            .quiz
+            .as_mut()
+            .ok_or_else(|| PortError::Unexpected("No active quiz.".to_string()))?;
+        if grade.correct {
+            quiz.correct_answers += 1;
+        }
+        quiz.current += 1;
+        quiz.current >= quiz.questions.len()
+    };
+
+    if finished && checkpoint {
+        session_state_lock.lock().await.quiz = None;
+        return Ok(QuizOutcome::ReadOn);
+    }
+    if finished {
+        end_quiz(&app_state, &session_state_lock, &ws_sender).await?;
+    } else {
+        ask_current_question(&app_state, &session_state_lock, &ws_sender).await?;
+    }
+    Ok(QuizOutcome::AwaitingUser)
+}
+
+/// Clears the quiz, reports the score, and speaks it.
+async fn end_quiz(
+    app_state: &Arc<AppState>,
+    session_state_lock: &Arc<Mutex<SessionState>>,
+    ws_sender: &WsSender,
+) -> PortResult<()> {
+    let (quiz, audio_free, speech) = {
+        let mut session = session_state_lock.lock().await;
+        (session.quiz.take(), session.audio_free, session.speech.clone())
+    };
+    let Some(quiz) = quiz else {
+        return Ok(());
+    };
+    let correct_answers = quiz.correct_answers;
+    let total_questions = quiz.current;
+
+    send_message(
+        ws_sender,
+        &ServerMessage::QuizEnded {
+            correct_answers,
+            total_questions,
+        },
+    )
+    .await?;
+    speak(
+        app_state,
+        ws_sender,
+        &format!(
+            "Quiz over. You got {} out of {} right. Say continue reading when you're ready.",
+            correct_answers, total_questions
+        ),
+        audio_free,
+        &speech,
+    )
+    .await
+}
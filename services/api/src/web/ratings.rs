@@ -0,0 +1,111 @@
+//! services/api/src/web/ratings.rs
+//!
+//! Session ratings: a 1–5 score and optional feedback a listener leaves once reading
+//! ends, kept for quality tracking. Submitted over the WebSocket (`RateSession`) while
+//! the connection is open, or here once `EndSession` has closed it.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use chrono::Utc;
+use reading_assistant_core::domain::SessionRating;
+use serde::Deserialize;
+use std::sync::Arc;
+use tracing::error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::web::state::AppState;
+
+/// Longest feedback accepted, in characters.
+pub const MAX_FEEDBACK_CHARS: usize = 2000;
+
+/// Checks a submitted rating and builds it for storage. Blank feedback is dropped.
+/// The error describes what was wrong, for the client.
+pub fn build_rating(
+    session_id: Uuid,
+    rating: u8,
+    feedback: Option<String>,
+) -> Result<SessionRating, String> {
+    if !(1..=5).contains(&rating) {
+        return Err("Ratings must be between 1 and 5.".to_string());
+    }
+    let feedback = feedback
+        .map(|text| text.trim().to_string())
+        .filter(|text| !text.is_empty());
+    if feedback
+        .as_ref()
+        .is_some_and(|text| text.chars().count() > MAX_FEEDBACK_CHARS)
+    {
+        return Err(format!("Feedback must be at most {} characters.", MAX_FEEDBACK_CHARS));
+    }
+    Ok(SessionRating {
+        session_id,
+        rating,
+        feedback,
+        rated_at: Utc::now(),
+    })
+}
+
+//=========================================================================================
+// Request/Response Types
+//=========================================================================================
+
+#[derive(Deserialize, ToSchema)]
+pub struct RateSessionRequest {
+    /// 1 (poor) to 5 (excellent).
+    pub rating: u8,
+    #[serde(default)]
+    pub feedback: Option<String>,
+}
+
+//=========================================================================================
+// Handlers
+//=========================================================================================
+
+/// POST /sessions/{session_id}/rating - Rate a session, replacing any earlier rating
+#[utoipa::path(
+    post,
+    path = "/sessions/{session_id}/rating",
+    params(
+        ("session_id" = Uuid, Path, description = "Session ID")
+    ),
+    request_body = RateSessionRequest,
+    responses(
+        (status = 204, description = "Rating saved"),
+        (status = 400, description = "Rating out of range or feedback too long"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Access denied"),
+        (status = 404, description = "Session not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn rate_session_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Path(session_id): Path<Uuid>,
+    Json(req): Json<RateSessionRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let session = state.db.get_session_by_id(session_id).await.map_err(|e| {
+        error!("Failed to get session: {:?}", e);
+        (StatusCode::NOT_FOUND, "Session not found".to_string())
+    })?;
+    if session.user_id != user_id {
+        return Err((StatusCode::FORBIDDEN, "Access denied".to_string()));
+    }
+
+    let rating = build_rating(session_id, req.rating, req.feedback)
+        .map_err(|message| (StatusCode::BAD_REQUEST, message))?;
+    state.db.save_session_rating(&rating).await.map_err(|e| {
+        error!("Failed to save rating for session {}: {:?}", session_id, e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to save rating".to_string())
+    })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -3,83 +3,378 @@
 //! This module contains the asynchronous "worker" function responsible for
 //! the document reading process.
 
-use crate::web::{
-    protocol::ServerMessage,
-    state::{AppState, SessionState},
+use crate::{
+    audio::apply_pacing,
+    web::{
+        protocol::{AudioKind, ErrorCode, ServerMessage},
+        qa_task::generate_and_save_auto_notes,
+        quiz_task::ask_checkpoint_question,
+        state::{lock_with_sentences, AppState, SessionEvent, SessionState},
+        ws_handler::send_error,
+        ws_writer::{ConnectionClosed, WsSender},
+    },
 };
-use axum::extract::ws::{Message, WebSocket};
-use futures::{stream::SplitSink, SinkExt};
-use reading_assistant_core::ports::{PortError, PortResult};
-use std::sync::Arc;
-use tokio::sync::Mutex;
+use reading_assistant_core::{
+    domain::SpeechSettings,
+    ports::{PortError, PortResult},
+    sentence_window::SentenceWindow,
+};
+use std::{collections::VecDeque, ops::Range, sync::Arc, time::Duration};
+use tokio::{sync::Mutex, task::JoinHandle};
 use tokio_util::sync::CancellationToken;
-use tracing::{error, info};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// How many times a sentence's audio is requested before the sentence is skipped.
+const TTS_ATTEMPTS: u32 = 3;
+/// The delay before the first retry; it doubles for each later retry.
+const TTS_RETRY_BASE_DELAY_MS: u64 = 500;
+/// After this many sentences in a row are skipped, the TTS service is presumably
+/// down, so reading pauses instead of skipping through the rest of the document.
+const MAX_CONSECUTIVE_SKIPS: usize = 3;
+/// In audio-free mode, sentences are sent at this silent reading speed, so the reading
+/// position (and with it quizzes, recaps and answer context) keeps up with the reader.
+const SILENT_READING_WORDS_PER_MINUTE: u64 = 230;
+/// How many sentences' audio is generated ahead of the one being sent, so the next
+/// clip is ready before the client finishes playing the previous one.
+const PREFETCH_SENTENCES: usize = 3;
 
 /// The main asynchronous task for reading the document aloud.
 ///
 /// This is a long-running task that loops through the document's sentences,
-/// generates audio for each one, and streams it to the client.
+/// generates audio for each one, and streams it to the client. Audio for the next
+/// few sentences is generated in the background while the current one is sent.
 /// It is designed to be gracefully cancelled via a `CancellationToken`.
 pub async fn reading_process(
     app_state: Arc<AppState>,
     session_state_lock: Arc<Mutex<SessionState>>,
-    ws_sender: Arc<Mutex<SplitSink<WebSocket, Message>>>, // Now accepts the shared sender
+    ws_sender: WsSender, // Now accepts the shared sender
     cancellation_token: CancellationToken,
 ) -> PortResult<()> {
     info!("Reading process started.");
 
     let start_msg = ServerMessage::ReadingStarted;
-    let start_json = serde_json::to_string(&start_msg).unwrap();
-    if ws_sender.lock().await.send(Message::Text(start_json.into())).await.is_err() {
+    if ws_sender.send(&start_msg).await.is_err() {
         return Err(PortError::Unexpected(
             "Failed to send ReadingStarted message.".to_string(),
         ));
     }
 
+    // The format is settled when the session opens; the voice and speed can change
+    // between sentences (`SetVoice`, `SetSpeed`, a slower re-read).
+    let speech = session_state_lock.lock().await.speech.clone();
+    let format = speech.format;
+    let mut prefetch = Prefetch::new(app_state.clone(), speech, cancellation_token.clone());
+    let mut consecutive_skips = 0;
+    // The passage of a comprehension checkpoint reading just reached, if any.
+    let mut checkpoint: Option<Range<usize>> = None;
     loop {
         if cancellation_token.is_cancelled() {
             info!("Reading process cancelled.");
             return Ok(());
         }
 
-        let (current_index, sentence_to_read, session_id) = {
-            let session = session_state_lock.lock().await;
-            let current_index = session.reading_progress_index;
+        if let Some(section) = checkpoint.take() {
+            if ask_checkpoint_question(&app_state, &session_state_lock, &ws_sender, &cancellation_token, section).await {
+                info!("Reading stopped at a checkpoint.");
+                return Ok(());
+            }
+            continue;
+        }
+
+        let (current_index, sentence_to_read, session_id, device_id, pacing, audio_free) = {
+            // Only a seek moves the position, and it cancels this task.
+            let current_index = session_state_lock.lock().await.reading_progress_index;
+            let upcoming = current_index..current_index + 1 + PREFETCH_SENTENCES;
+            let Some(mut session) =
+                lock_with_sentences(&session_state_lock, &app_state, upcoming, &cancellation_token).await?
+            else {
+                info!("Reading process cancelled.");
+                return Ok(());
+            };
             if current_index >= session.chunked_document.len() {
                 break;
             }
-            let sentence_to_read = session.chunked_document[current_index].clone();
+            let sentence_to_read = session
+                .chunked_document
+                .get(current_index)
+                .ok_or_else(|| PortError::Unexpected(format!("Sentence {} is not loaded.", current_index)))?
+                .to_string();
             let session_id = session.session_id;
-            (current_index, sentence_to_read, session_id)
+            let pacing = (session.sentence_gap_ms, session.crossfade_ms);
+            if !session.audio_free {
+                prefetch.set_speech(&session.reading_speech());
+                prefetch.fill(current_index, &session.chunked_document);
+            }
+            (current_index, sentence_to_read, session_id, session.device_id.clone(), pacing, session.audio_free)
         };
 
-        let audio_data = app_state
-            .tts_adapter
-            .generate_audio(&sentence_to_read)
-            .await?;
+        if audio_free {
+            let text_msg = ServerMessage::SentenceText { index: current_index, text: sentence_to_read.clone() };
+            if ws_sender.send(&text_msg).await.is_err() {
+                error!("Failed to send sentence text to client. Ending reading task.");
+                break;
+            }
+            let read_time = silent_reading_time(&sentence_to_read) + Duration::from_millis(pacing.0 as u64);
+            tokio::select! {
+                biased;
+                _ = cancellation_token.cancelled() => {
+                    info!("Reading process cancelled while sentence {} was being read.", current_index);
+                    return Ok(());
+                }
+                _ = tokio::time::sleep(read_time) => {}
+            }
+            checkpoint = advance_progress(&app_state, &session_state_lock, &cancellation_token, session_id, device_id.as_deref(), current_index).await;
+            continue;
+        }
+
+        let audio_data = match prefetch.next(current_index).await {
+            Some(Ok(audio_data)) => {
+                consecutive_skips = 0;
+                audio_data
+            }
+            None => {
+                info!("Reading process cancelled.");
+                return Ok(());
+            }
+            Some(Err(e)) if consecutive_skips + 1 >= MAX_CONSECUTIVE_SKIPS => {
+                // Pause so the user can resume, which retries this sentence.
+                error!("Failed to generate audio for sentence {}, pausing: {:?}", current_index, e);
+                if let Err(e) = session_state_lock.lock().await.apply(SessionEvent::ReadingFailed) {
+                    info!("Reading already stopped, not pausing: {}", e);
+                    return Ok(());
+                }
+                send_error(
+                    &ws_sender,
+                    ErrorCode::SpeechSynthesisFailed,
+                    "Reading paused because speech could not be generated. Resume to try again.",
+                )
+                .await;
+                if ws_sender.send(&ServerMessage::ReadingPaused).await.is_err() {
+                    error!("Failed to send ReadingPaused message.");
+                }
+                return Ok(());
+            }
+            Some(Err(e)) => {
+                warn!("Failed to generate audio for sentence {}, skipping it: {:?}", current_index, e);
+                consecutive_skips += 1;
+                let skipped_msg = ServerMessage::SentenceSkipped { index: current_index };
+                if ws_sender.send(&skipped_msg).await.is_err() {
+                    error!("Failed to send SentenceSkipped message. Ending reading task.");
+                    break;
+                }
+                checkpoint = advance_progress(&app_state, &session_state_lock, &cancellation_token, session_id, device_id.as_deref(), current_index).await;
+                continue;
+            }
+        };
 
-        if ws_sender.lock().await.send(Message::Binary(audio_data.into())).await.is_err() {
+        let (sentence_gap_ms, crossfade_ms) = pacing;
+        let audio_data = match apply_pacing(&audio_data, format, sentence_gap_ms, crossfade_ms) {
+            Ok(paced) => paced,
+            Err(e) => {
+                warn!("Failed to apply narration pacing, sending clip as-is: {:?}", e);
+                audio_data
+            }
+        };
+
+        // An interrupt while waiting for the socket means this audio is already stale.
+        let sent = tokio::select! {
+            biased;
+            _ = cancellation_token.cancelled() => {
+                info!("Reading process cancelled before sending sentence {}.", current_index);
+                return Ok(());
+            }
+            sent = send_sentence(&ws_sender, current_index, &sentence_to_read, audio_data) => sent,
+        };
+        if sent.is_err() {
             error!("Failed to send audio chunk to client. Ending reading task.");
             break;
         }
 
-        {
-            let mut session = session_state_lock.lock().await;
-            session.reading_progress_index += 1;
-        }
-
-        app_state
-            .db
-            .update_session_progress(session_id, current_index + 1)
-            .await?;
+        checkpoint = advance_progress(&app_state, &session_state_lock, &cancellation_token, session_id, device_id.as_deref(), current_index).await;
     }
 
     info!("Document reading finished.");
     let end_msg = ServerMessage::ReadingEnded;
-    let end_json = serde_json::to_string(&end_msg).unwrap();
-    if ws_sender.lock().await.send(Message::Text(end_json.into())).await.is_err() {
+    if ws_sender.send(&end_msg).await.is_err() {
         error!("Failed to send ReadingEnded message.");
     }
 
     Ok(())
 }
+
+/// The audio requests running ahead of the reading position, in sentence order.
+/// Requests still running when reading stops are aborted.
+struct Prefetch {
+    app_state: Arc<AppState>,
+    speech: SpeechSettings,
+    cancellation_token: CancellationToken,
+    pending: VecDeque<(usize, JoinHandle<Option<PortResult<Vec<u8>>>>)>,
+}
+
+impl Prefetch {
+    fn new(app_state: Arc<AppState>, speech: SpeechSettings, cancellation_token: CancellationToken) -> Self {
+        Self { app_state, speech, cancellation_token, pending: VecDeque::new() }
+    }
+
+    /// Uses `speech` for the sentences requested from now on. Audio already requested
+    /// with other settings is dropped, so the change is heard from the next sentence.
+    fn set_speech(&mut self, speech: &SpeechSettings) {
+        if *speech != self.speech {
+            self.abort_all();
+            self.speech = speech.clone();
+        }
+    }
+
+    /// Starts requests for the sentences from `current_index` up to `PREFETCH_SENTENCES`
+    /// ahead that aren't already requested.
+    fn fill(&mut self, current_index: usize, sentences: &SentenceWindow) {
+        if self.pending.front().is_some_and(|(index, _)| *index != current_index) {
+            // Not expected, since reading only moves forward one sentence at a time.
+            self.abort_all();
+        }
+        let start = self.pending.back().map_or(current_index, |(index, _)| index + 1);
+        let end = (current_index + 1 + PREFETCH_SENTENCES).min(sentences.len());
+        for index in start..end {
+            let Some(sentence) = sentences.get(index).map(str::to_string) else {
+                break;
+            };
+            let app_state = self.app_state.clone();
+            let cancellation_token = self.cancellation_token.clone();
+            let speech = self.speech.clone();
+            let handle = tokio::spawn(async move {
+                generate_with_retry(&app_state, &sentence, &speech, &cancellation_token).await
+            });
+            self.pending.push_back((index, handle));
+        }
+    }
+
+    /// Waits for the audio of the sentence at `index`, which must have been requested by
+    /// `fill`. Returns `None` if reading was cancelled.
+    async fn next(&mut self, index: usize) -> Option<PortResult<Vec<u8>>> {
+        let (pending_index, handle) = self.pending.pop_front()?;
+        debug_assert_eq!(pending_index, index);
+        match handle.await {
+            Ok(result) => result,
+            Err(e) => Some(Err(PortError::Unexpected(e.to_string()))),
+        }
+    }
+
+    fn abort_all(&mut self) {
+        for (_, handle) in self.pending.drain(..) {
+            handle.abort();
+        }
+    }
+}
+
+impl Drop for Prefetch {
+    fn drop(&mut self) {
+        self.abort_all();
+    }
+}
+
+/// Requests a sentence's audio, retrying with exponential backoff. Returns `None` as
+/// soon as reading is cancelled, abandoning any request in flight.
+async fn generate_with_retry(
+    app_state: &Arc<AppState>,
+    sentence: &str,
+    speech: &SpeechSettings,
+    cancellation_token: &CancellationToken,
+) -> Option<PortResult<Vec<u8>>> {
+    let mut attempt = 1;
+    loop {
+        let result = tokio::select! {
+            biased;
+            _ = cancellation_token.cancelled() => return None,
+            result = app_state.tts_adapter.generate_audio(sentence, speech) => result,
+        };
+        match result {
+            Ok(audio_data) => return Some(Ok(audio_data)),
+            Err(e) if attempt >= TTS_ATTEMPTS => return Some(Err(e)),
+            Err(e) => {
+                warn!("TTS attempt {} of {} failed: {:?}", attempt, TTS_ATTEMPTS, e);
+                let delay = Duration::from_millis(TTS_RETRY_BASE_DELAY_MS << (attempt - 1));
+                tokio::select! {
+                    biased;
+                    _ = cancellation_token.cancelled() => return None,
+                    _ = tokio::time::sleep(delay) => {}
+                }
+                attempt += 1;
+            }
+        }
+    }
+}
+
+/// Sends a `SentenceStarted` for highlighting, then the sentence's captioned audio.
+async fn send_sentence(
+    ws_sender: &WsSender,
+    index: usize,
+    sentence: &str,
+    audio_data: Vec<u8>,
+) -> Result<(), ConnectionClosed> {
+    let started_msg = ServerMessage::SentenceStarted { index, text: sentence.to_string() };
+    ws_sender
+        .send_captioned_audio_after(&started_msg, sentence, AudioKind::Reading, Some(index), audio_data)
+        .await
+}
+
+/// How long a sentence takes to read silently.
+fn silent_reading_time(sentence: &str) -> Duration {
+    let words = sentence.split_whitespace().count() as u64;
+    Duration::from_millis(words * 60_000 / SILENT_READING_WORDS_PER_MINUTE)
+}
+
+/// Moves reading past the sentence at `current_index` and persists the new position.
+/// If that ends a paragraph and auto notes are on, its notes are taken in the background.
+/// Returns the passage to ask about if that reaches an active reading checkpoint.
+///
+/// Nothing changes if reading was cancelled or moved elsewhere (a seek) since the
+/// sentence was sent, so a stale task can't move or overwrite the new position.
+async fn advance_progress(
+    app_state: &Arc<AppState>,
+    session_state_lock: &Arc<Mutex<SessionState>>,
+    cancellation_token: &CancellationToken,
+    session_id: Uuid,
+    device_id: Option<&str>,
+    current_index: usize,
+) -> Option<Range<usize>> {
+    let mut session = session_state_lock.lock().await;
+    if cancellation_token.is_cancelled() || session.reading_progress_index != current_index {
+        info!("Reading moved on before sentence {} was done, not advancing.", current_index);
+        return None;
+    }
+    session.reading_progress_index += 1;
+    // Persisted under the lock, so the write can't land after a seek's own.
+    // Progress is also flushed when the session ends, so a failed write isn't fatal.
+    if let Err(e) = app_state
+        .db
+        .update_session_progress(session_id, device_id, current_index + 1)
+        .await
+    {
+        warn!("Failed to persist reading progress: {:?}", e);
+    }
+
+    if let Some(section) = session.take_auto_note_section() {
+        // The section was just read, so it is normally still loaded; if not, it is read
+        // from the database in the background rather than under the lock.
+        let loaded = session.chunked_document.loaded();
+        let passage = (loaded.start <= section.start && section.end <= loaded.end)
+            .then(|| session.chunked_document.passage(section.clone()));
+        let (app_state, document_id, education_mode) =
+            (app_state.clone(), session.document_id, session.education_mode);
+        tokio::spawn(async move {
+            let passage = match passage {
+                Some(passage) => passage,
+                None => match app_state.db.get_document_chunk_range(document_id, section.clone()).await {
+                    Ok(sentences) => sentences.join(" "),
+                    Err(e) => {
+                        warn!("Failed to load the sentences to note: {:?}", e);
+                        return;
+                    }
+                },
+            };
+            generate_and_save_auto_notes(app_state, session_id, passage, section, education_mode).await;
+        });
+    }
+    session.take_checkpoint_section()
+}
@@ -0,0 +1,122 @@
+//! services/api/src/web/recap_task.rs
+//!
+//! This module contains the background worker that writes weekly recaps. Once a week
+//! (Monday to Sunday, UTC) is over, every user who asked for recaps and took notes
+//! during it gets a short recap of those notes, stored as a note and spoken at the
+//! start of their next session.
+
+use crate::web::{
+    api_keys::app_state_for_user,
+    qa_task::speak_sentences,
+    state::{AppState, SessionState},
+    ws_writer::WsSender,
+};
+use chrono::{Datelike, Duration, NaiveDate, NaiveTime, Utc};
+use reading_assistant_core::{
+    domain::{Note, NoteSource, WeeklyRecap},
+    ports::PortResult,
+};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// How often the worker checks for users due a recap.
+const WEEKLY_RECAP_INTERVAL_SECS: u64 = 60 * 60;
+
+/// The main loop of the recap worker. Runs for the lifetime of the server.
+pub async fn weekly_recap_process(app_state: Arc<AppState>) {
+    info!("Weekly recap worker started.");
+    let mut ticker =
+        tokio::time::interval(std::time::Duration::from_secs(WEEKLY_RECAP_INTERVAL_SECS));
+
+    loop {
+        ticker.tick().await;
+
+        if let Err(e) = write_due_recaps(&app_state).await {
+            error!("Failed to write weekly recaps: {:?}", e);
+        }
+    }
+}
+
+/// The Monday that started the most recent full week.
+fn last_week_start(today: NaiveDate) -> NaiveDate {
+    let this_week_start = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+    this_week_start - Duration::days(7)
+}
+
+/// Writes last week's recap for every user who is due one.
+async fn write_due_recaps(app_state: &Arc<AppState>) -> PortResult<()> {
+    let week_start = last_week_start(Utc::now().date_naive());
+    let users = app_state.db.get_users_due_weekly_recap(week_start).await?;
+
+    for user_id in users {
+        if let Err(e) = write_recap(app_state, user_id, week_start).await {
+            warn!("Failed to write weekly recap for user {}: {:?}", user_id, e);
+        }
+    }
+
+    Ok(())
+}
+
+async fn write_recap(app_state: &Arc<AppState>, user_id: Uuid, week_start: NaiveDate) -> PortResult<()> {
+    let from = week_start.and_time(NaiveTime::MIN).and_utc();
+    let mut notes = app_state
+        .db
+        .get_user_notes_between(user_id, from, from + Duration::days(7))
+        .await?;
+    // Session summaries only repeat the notes they were made from.
+    notes.retain(|note| note.source != NoteSource::Summary);
+    // The recap is shown with the session the user read last that week.
+    let Some(session_id) = notes.last().map(|note| note.session_id) else {
+        return Ok(());
+    };
+
+    // Billed to the user's own key if they stored one, like their sessions.
+    let user_state = app_state_for_user(app_state, user_id).await?;
+    let text = user_state.notes_adapter.generate_weekly_recap(&notes).await?;
+
+    let recap = WeeklyRecap { user_id, week_start, text: text.clone(), spoken_at: None };
+    let note = Note {
+        id: Uuid::new_v4(),
+        session_id,
+        generated_note_text: text,
+        created_at: Utc::now(),
+        source_start_index: None,
+        source_end_index: None,
+        source: NoteSource::WeeklyRecap,
+    };
+    app_state.db.save_weekly_recap(&recap, note).await?;
+
+    info!("Wrote weekly recap for user {} (week of {}).", user_id, week_start);
+    Ok(())
+}
+
+/// Speaks the user's newest weekly recap if it hasn't been spoken yet, then marks it
+/// spoken. Called once when a session opens.
+pub async fn speak_weekly_recap(
+    app_state: &Arc<AppState>,
+    session_state_lock: &Arc<Mutex<SessionState>>,
+    ws_sender: &WsSender,
+) -> PortResult<()> {
+    let (user_id, audio_free, speech) = {
+        let session = session_state_lock.lock().await;
+        if !session.weekly_recap {
+            return Ok(());
+        }
+        (session.user_id, session.audio_free, session.speech.clone())
+    };
+    let Some(recap) = app_state.db.get_unspoken_weekly_recap(user_id).await? else {
+        return Ok(());
+    };
+
+    speak_sentences(
+        app_state,
+        ws_sender,
+        &format!("Here's your recap of last week. {}", recap.text.trim()),
+        audio_free,
+        &speech,
+    )
+    .await?;
+    app_state.db.mark_weekly_recap_spoken(user_id, recap.week_start).await
+}
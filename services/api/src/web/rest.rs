@@ -4,17 +4,62 @@
 //! definition for the OpenAPI specification.
 
 use crate::web::state::AppState;
+use crate::web::admin::{IntegrityReportBody, LatencyDay, LatencyReport, LatencyStageBody, LlmUsageDay, LlmUsageReport, LlmUsageTotal, TtsUsageDay, TtsUsageReport, TtsUsageTotal};
+use crate::web::analytics::{UsageDay, UsageFeature, UsageReport};
+use crate::web::api_keys::{ApiKeyStatusResponse, SaveApiKeyRequest};
+use crate::web::ask::{AskRequest, AskResponse};
+use crate::web::backups::{BackupItem, ListBackupsResponse};
 use crate::web::auth::{SignupRequest, LoginRequest, AuthResponse};
+use crate::web::guest::{check_guest_upload, GuestSessionResponse};
+use crate::web::highlights::{ImportHighlightsResponse, ImportedBook};
+use crate::web::integrations::{
+    AuthorizeUrlResponse, ExportResponse, ExternalDocumentItem, GoogleImportRequest,
+    ImportResponse, ListExternalDocumentsResponse, NotionExportRequest,
+};
+use crate::web::listing::{is_not_modified, list_etag, ListParams};
+use crate::web::preferences::{PreferencesBody, PreferencesPatch, ReadingLevelBody, Verbosity};
+use crate::web::calendar::{CalendarFeedResponse, CreatePlanRequest, ListPlansResponse, PlanItem, PlanKindBody};
+use crate::web::gallery::{GalleryItem, GallerySessionResponse, ListGalleryResponse};
+use crate::web::glossary::{GlossaryItem, ListGlossaryResponse};
+use crate::web::goals::{GoalBody, GoalProgressBody, GoalResponse};
+use crate::web::ratings::RateSessionRequest;
+use crate::web::sharing::{
+    DocumentShareItem, ListDocumentNotesResponse, ListDocumentSharesResponse,
+    ListSharedDocumentsResponse, ShareDocumentRequest, SharedDocumentItem, SharedSessionResponse,
+};
+use crate::web::retrieval::index_document;
+use crate::web::tags::{
+    suggest_and_save_tags, AddTagsRequest, DocumentTagsResponse, ListTagsResponse, TagItem,
+};
+use crate::web::voices::{ListVoicesResponse, ProviderVoices, VoicePreviewRequest};
+use crate::web::workspaces::{
+    ActivityQuestion, CreateWorkspaceRequest, DocumentActivityResponse, JoinWorkspaceRequest,
+    ListWorkspaceDocumentsResponse, ListWorkspacesResponse, WorkspaceDocumentItem, WorkspaceItem,
+    WorkspaceSessionResponse,
+};
+use crate::web::queue::{
+    AddFeedRequest, FeedItem, ListFeedsResponse, ListQueueResponse, QueueEntry, SaveLinkRequest,
+};
 use axum::{
-    extract::{Multipart, State},
-    http::{StatusCode},
-    response::{IntoResponse, Json},
+    extract::{Multipart, Query, State},
+    http::{header, HeaderMap, StatusCode, Uri},
+    response::{IntoResponse, Json, Response},
     Extension,
 };
-use serde::Serialize;
+use reading_assistant_core::{
+    domain::{Document, Note, NoteSource},
+    ports::PortError,
+};
+use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use tracing::error;
-use utoipa::{OpenApi, ToSchema};
+use utoipa::{
+    openapi::{
+        security::{ApiKey, ApiKeyValue, SecurityScheme},
+        Content, Ref, RefOr,
+    },
+    IntoParams, Modify, OpenApi, ToSchema,
+};
 use uuid::Uuid;
 
 //=========================================================================================
@@ -27,32 +72,248 @@ use uuid::Uuid;
         create_session_handler,
         list_notes_handler,
         list_sessions_handler, 
+        list_documents_handler,
+        get_document_handler,
+        delete_document_handler,
+        list_qa_pairs_handler,
+        delete_session_handler,
+        update_session_handler,
+        update_document_handler,
+        crate::web::ask::ask_question_handler,
         crate::web::auth::signup_handler,    // Add
         crate::web::auth::login_handler,     // Add
         crate::web::auth::logout_handler,    // Add
+        crate::web::guest::create_guest_handler,
+        crate::web::integrations::notion_authorize_handler,
+        crate::web::integrations::notion_callback_handler,
+        crate::web::integrations::export_notion_handler,
+        crate::web::integrations::google_authorize_handler,
+        crate::web::integrations::google_callback_handler,
+        crate::web::integrations::list_google_documents_handler,
+        crate::web::integrations::import_google_document_handler,
+        crate::web::highlights::import_highlights_handler,
+        crate::web::queue::add_feed_handler,
+        crate::web::queue::list_feeds_handler,
+        crate::web::queue::save_link_handler,
+        crate::web::queue::list_queue_handler,
+        crate::web::tags::list_tags_handler,
+        crate::web::tags::get_document_tags_handler,
+        crate::web::tags::add_document_tags_handler,
+        crate::web::tags::remove_document_tag_handler,
+        crate::web::ratings::rate_session_handler,
+        crate::web::calendar::create_plan_handler,
+        crate::web::calendar::list_plans_handler,
+        crate::web::calendar::delete_plan_handler,
+        crate::web::calendar::calendar_feed_url_handler,
+        crate::web::calendar::calendar_feed_handler,
+        crate::web::goals::get_goal_handler,
+        crate::web::goals::update_goal_handler,
+        crate::web::goals::delete_goal_handler,
+        crate::web::preferences::get_preferences_handler,
+        crate::web::preferences::update_preferences_handler,
+        crate::web::preferences::get_my_preferences_handler,
+        crate::web::preferences::patch_preferences_handler,
+        crate::web::voices::list_voices_handler,
+        crate::web::voices::preview_voice_handler,
+        crate::web::voices::get_voice_preview_handler,
+        crate::web::workspaces::create_workspace_handler,
+        crate::web::workspaces::list_workspaces_handler,
+        crate::web::workspaces::join_workspace_handler,
+        crate::web::workspaces::upload_workspace_document_handler,
+        crate::web::workspaces::list_workspace_documents_handler,
+        crate::web::workspaces::create_workspace_session_handler,
+        crate::web::workspaces::document_activity_handler,
+        crate::web::glossary::list_glossary_handler,
+        crate::web::gallery::list_gallery_handler,
+        crate::web::gallery::create_gallery_session_handler,
+        crate::web::gallery::add_gallery_document_handler,
+        crate::web::sharing::share_document_handler,
+        crate::web::sharing::list_document_shares_handler,
+        crate::web::sharing::unshare_document_handler,
+        crate::web::sharing::list_shared_documents_handler,
+        crate::web::sharing::create_shared_session_handler,
+        crate::web::sharing::list_document_notes_handler,
+        crate::web::admin::tts_usage_report_handler,
+        crate::web::admin::llm_usage_report_handler,
+        crate::web::admin::latency_report_handler,
+        crate::web::admin::integrity_report_handler,
+        crate::web::admin::repair_integrity_handler,
+        crate::web::analytics::usage_report_handler,
+        crate::web::backups::create_backup_handler,
+        crate::web::backups::list_backups_handler,
+        crate::web::backups::download_backup_handler,
+        crate::web::api_keys::get_openai_key_handler,
+        crate::web::api_keys::save_openai_key_handler,
+        crate::web::api_keys::delete_openai_key_handler,
+        crate::web::ws_handler::ws_handler,
     ),
     components(
         schemas(
+            ErrorMessage,
             CreateSessionResponse,
             NoteItem,           // ✅ Add this
+            NoteSourceKind,
             ListNotesResponse,
             SessionListItem,        // ✅ Add this
             ListSessionsResponse,
+            DocumentListItem,
+            ListDocumentsResponse,
+            DocumentResponse,
+            UpdateTitleRequest,
+            QaPairItem,
+            ListQaPairsResponse,
+            AskRequest,
+            AskResponse,
             SignupRequest,      // Add
             LoginRequest,       // Add
             AuthResponse,       // Add
+            GuestSessionResponse,
+            AuthorizeUrlResponse,
+            NotionExportRequest,
+            ExportResponse,
+            ExternalDocumentItem,
+            ListExternalDocumentsResponse,
+            GoogleImportRequest,
+            ImportResponse,
+            ImportedBook,
+            ImportHighlightsResponse,
+            AddFeedRequest,
+            SaveLinkRequest,
+            FeedItem,
+            ListFeedsResponse,
+            QueueEntry,
+            ListQueueResponse,
+            AddTagsRequest,
+            TagItem,
+            DocumentTagsResponse,
+            ListTagsResponse,
+            RateSessionRequest,
+            PlanKindBody,
+            CreatePlanRequest,
+            PlanItem,
+            ListPlansResponse,
+            CalendarFeedResponse,
+            GoalBody,
+            GoalProgressBody,
+            GoalResponse,
+            Verbosity,
+            ReadingLevelBody,
+            PreferencesBody,
+            PreferencesPatch,
+            ProviderVoices,
+            ListVoicesResponse,
+            VoicePreviewRequest,
+            CreateWorkspaceRequest,
+            JoinWorkspaceRequest,
+            WorkspaceItem,
+            ListWorkspacesResponse,
+            WorkspaceDocumentItem,
+            ListWorkspaceDocumentsResponse,
+            WorkspaceSessionResponse,
+            ActivityQuestion,
+            DocumentActivityResponse,
+            GlossaryItem,
+            ListGlossaryResponse,
+            GalleryItem,
+            ListGalleryResponse,
+            GallerySessionResponse,
+            ShareDocumentRequest,
+            DocumentShareItem,
+            ListDocumentSharesResponse,
+            SharedDocumentItem,
+            ListSharedDocumentsResponse,
+            SharedSessionResponse,
+            ListDocumentNotesResponse,
+            TtsUsageDay,
+            TtsUsageTotal,
+            TtsUsageReport,
+            LlmUsageDay,
+            LlmUsageTotal,
+            LlmUsageReport,
+            LatencyStageBody,
+            LatencyDay,
+            LatencyReport,
+            IntegrityReportBody,
+            UsageFeature,
+            UsageDay,
+            UsageReport,
+            BackupItem,
+            ListBackupsResponse,
+            SaveApiKeyRequest,
+            ApiKeyStatusResponse,
         )
     ),
     tags(
         (name = "Reading Assistant API", description = "API endpoints for the interactive audio reader."),
         (name = "Authentication", description = "User authentication endpoints"),  // Add
-    )
+        (name = "Reading Queue", description = "Read-later queue fed by RSS feeds and saved links"),
+        (name = "Tags", description = "Document tags, autocomplete and suggestions"),
+        (name = "Preferences", description = "Per-user settings such as answer length"),
+        (name = "Voices", description = "TTS voice listing and previews"),
+        (name = "Workspaces", description = "Teacher/student class workspaces"),
+        (name = "API Keys", description = "Bring-your-own OpenAI API keys, encrypted at rest"),
+        (name = "Admin", description = "Operator-only reports such as TTS usage"),
+        (name = "Integrations", description = "Third-party integrations such as Notion export and Google Drive import"),
+    ),
+    modifiers(&SecurityAddon, &ErrorBodies)
 )]
 pub struct ApiDoc;
+
+/// Defines the `session_cookie` scheme the endpoints' `security` refers to.
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let components = openapi.components.get_or_insert_with(Default::default);
+        components.add_security_scheme(
+            "session_cookie",
+            SecurityScheme::ApiKey(ApiKey::Cookie(ApiKeyValue::with_description(
+                "session",
+                "Set by `/auth/login`, `/auth/signup` and `/auth/guest`.",
+            ))),
+        );
+    }
+}
+
+/// Documents the plain-text body of every error response that doesn't describe its own.
+/// Handlers fail with `(StatusCode, String)`, which axum sends as `text/plain`.
+struct ErrorBodies;
+
+impl Modify for ErrorBodies {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        for item in openapi.paths.paths.values_mut() {
+            let operations = [
+                &mut item.get,
+                &mut item.put,
+                &mut item.post,
+                &mut item.delete,
+                &mut item.patch,
+            ];
+            for operation in operations.into_iter().flatten() {
+                for (status, response) in operation.responses.responses.iter_mut() {
+                    let is_error = status.starts_with('4') || status.starts_with('5');
+                    if let RefOr::T(response) = response {
+                        if is_error && response.content.is_empty() {
+                            response.content.insert(
+                                "text/plain".to_string(),
+                                Content::new(Some(Ref::from_schema_name("ErrorMessage"))),
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
 //=========================================================================================
 // API Response and Payload Structs
 //=========================================================================================
 
+/// The plain-text message sent with an error status, e.g. "Session not found".
+#[derive(ToSchema)]
+#[schema(example = "Session not found")]
+pub struct ErrorMessage(String);
+
 /// The response payload sent after successfully creating a session.
 #[derive(Serialize, ToSchema)]
 pub struct CreateSessionResponse {
@@ -66,12 +327,72 @@ pub struct SessionListItem {
     session_id: Uuid,
     document_id: Uuid,
     created_at: String,  // ISO 8601 timestamp
+    last_accessed_at: String,
+    education_mode: bool,
+    /// The user's title for the session, if they gave it one.
+    title: Option<String>,
     // Add more fields as needed (document name, preview, etc.)
 }
 
+#[derive(Deserialize, IntoParams)]
+pub struct ListSessionsQuery {
+    /// Only return sessions whose document carries this tag.
+    pub tag: Option<String>,
+}
+
 #[derive(Serialize, ToSchema)]
 pub struct ListSessionsResponse {
-    sessions: Vec<SessionListItem>,
+    /// Only the fields asked for with `fields`, if any.
+    #[schema(value_type = Vec<SessionListItem>)]
+    sessions: Vec<serde_json::Value>,
+    /// Pass as `cursor` to get the next page; absent on the last page.
+    next_cursor: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DocumentListItem {
+    document_id: Uuid,
+    title: Option<String>,
+    created_at: String,  // ISO 8601 timestamp
+    /// The start of the document's text.
+    preview: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ListDocumentsResponse {
+    #[schema(value_type = Vec<DocumentListItem>)]
+    documents: Vec<serde_json::Value>,
+    next_cursor: Option<String>,
+}
+
+/// A document of the user's library, with its full text.
+#[derive(Serialize, ToSchema)]
+pub struct DocumentResponse {
+    document_id: Uuid,
+    title: Option<String>,
+    created_at: String,  // ISO 8601 timestamp
+    /// How many sentences the document is read as.
+    sentence_count: usize,
+    text: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct QaPairItem {
+    qa_pair_id: Uuid,
+    session_id: Uuid,
+    question: String,
+    /// Only the part the user heard when `interrupted`.
+    answer: String,
+    /// The user stopped the answer partway.
+    interrupted: bool,
+    created_at: String,  // ISO 8601 timestamp
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ListQaPairsResponse {
+    #[schema(value_type = Vec<QaPairItem>)]
+    qa_pairs: Vec<serde_json::Value>,
+    next_cursor: Option<String>,
 }
 
 #[derive(Serialize, ToSchema)]
@@ -80,28 +401,120 @@ pub struct NoteItem {
     session_id: Uuid,
     text: String,
     created_at: String,  // ISO 8601 timestamp
+    /// The passage (sentence indices, end exclusive) the note's question was asked
+    /// about. Send `seek_to` with the start index to jump back to it.
+    source_start_index: Option<usize>,
+    source_end_index: Option<usize>,
+    source: NoteSourceKind,
+}
+
+impl From<Note> for NoteItem {
+    fn from(note: Note) -> Self {
+        NoteItem {
+            note_id: note.id,
+            session_id: note.session_id,
+            text: note.generated_note_text,
+            created_at: note.created_at.to_rfc3339(),
+            source_start_index: note.source_start_index,
+            source_end_index: note.source_end_index,
+            source: note.source.into(),
+        }
+    }
+}
+
+/// What a note was generated from.
+#[derive(Serialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum NoteSourceKind {
+    /// A question the user asked.
+    Question,
+    /// A paragraph's key points, taken automatically while reading.
+    Auto,
+    /// A summary of the session's other notes.
+    Summary,
+    /// A recap of a whole week's notes, across sessions.
+    WeeklyRecap,
+    /// A Kindle or Readwise highlight (or note on one), imported by the user.
+    Imported,
+}
+
+impl From<NoteSource> for NoteSourceKind {
+    fn from(source: NoteSource) -> Self {
+        match source {
+            NoteSource::Question => NoteSourceKind::Question,
+            NoteSource::Auto => NoteSourceKind::Auto,
+            NoteSource::Summary => NoteSourceKind::Summary,
+            NoteSource::WeeklyRecap => NoteSourceKind::WeeklyRecap,
+            NoteSource::Imported => NoteSourceKind::Imported,
+        }
+    }
 }
 
 #[derive(Serialize, ToSchema)]
 pub struct ListNotesResponse {
-    notes: Vec<NoteItem>,
+    #[schema(value_type = Vec<NoteItem>)]
+    notes: Vec<serde_json::Value>,
+    next_cursor: Option<String>,
+}
+
+/// How much of a document's text is shown in document lists.
+const DOCUMENT_PREVIEW_CHARS: usize = 160;
+
+/// The longest title a session or document can be given.
+const MAX_TITLE_CHARS: usize = 200;
+
+/// A new title for a session or document. A missing, `null` or blank title clears it.
+#[derive(Deserialize, ToSchema)]
+pub struct UpdateTitleRequest {
+    #[serde(default)]
+    title: Option<String>,
+}
+
+impl UpdateTitleRequest {
+    /// The trimmed title, `None` to clear it, or 400 if it is too long.
+    fn title(&self) -> Result<Option<&str>, (StatusCode, String)> {
+        let title = self.title.as_deref().map(str::trim).filter(|title| !title.is_empty());
+        if title.is_some_and(|title| title.chars().count() > MAX_TITLE_CHARS) {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                format!("Titles are limited to {} characters", MAX_TITLE_CHARS),
+            ));
+        }
+        Ok(title)
+    }
 }
 
 //=========================================================================================
 // REST API Handlers
 //=========================================================================================
 
+/// Rejects documents longer than the configured maximum, whoever uploads them.
+pub fn check_document_length(
+    state: &AppState,
+    document_text: &str,
+) -> Result<(), (StatusCode, String)> {
+    let max_chars = state.config.max_document_chars;
+    if document_text.chars().count() > max_chars {
+        return Err((
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!("Documents are limited to {} characters", max_chars),
+        ));
+    }
+    Ok(())
+}
+
 /// Create a new session by uploading a document.
 ///
 /// Requires authentication. The user_id is extracted from the auth session.
 #[utoipa::path(
     post,
     path = "/sessions",
-    request_body(content_type = "multipart/form-data", description = "The document to upload."),
+    request_body(content_type = "multipart/form-data", description = "The document to upload, plus an optional `cleanup=true` field to run the LLM cleanup pass before chunking and an optional `education_mode=true` field for age-appropriate answers and stricter moderation."),
     responses(
         (status = 201, description = "Session created successfully", body = CreateSessionResponse),
         (status = 400, description = "Bad request (e.g., missing file)"),
         (status = 401, description = "Unauthorized - no valid session"),
+        (status = 413, description = "Document longer than the configured maximum"),
         (status = 500, description = "Internal server error")
     ),
     security(
@@ -114,14 +527,36 @@ pub async fn create_session_handler(
     mut multipart: Multipart,
 ) -> Result<impl IntoResponse, (StatusCode, String)> {
     // No need to parse headers or validate user anymore!
-    
-    let (file_name, file_text) =
-        if let Some(field) = multipart.next_field().await.map_err(|e| {
-            (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                format!("Failed to read multipart data: {}", e),
-            )
-        })? {
+
+    // The form carries the file plus optional `cleanup` and `education_mode` flags.
+    let mut upload: Option<(String, String)> = None;
+    let mut cleanup = false;
+    let mut education_mode = false;
+
+    while let Some(field) = multipart.next_field().await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to read multipart data: {}", e),
+        )
+    })? {
+        let field_name = field.name().map(str::to_string);
+        if field_name.as_deref() == Some("cleanup") {
+            let value = field.text().await.map_err(|e| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    format!("Failed to read cleanup flag: {}", e),
+                )
+            })?;
+            cleanup = matches!(value.trim(), "true" | "1" | "on");
+        } else if field_name.as_deref() == Some("education_mode") {
+            let value = field.text().await.map_err(|e| {
+                (
+                    StatusCode::BAD_REQUEST,
+                    format!("Failed to read education_mode flag: {}", e),
+                )
+            })?;
+            education_mode = matches!(value.trim(), "true" | "1" | "on");
+        } else if upload.is_none() {
             let name = field.file_name().unwrap_or("untitled.txt").to_string();
             let data = field.bytes().await.map_err(|e| {
                 (
@@ -135,24 +570,49 @@ pub async fn create_session_handler(
                     format!("Uploaded file is not valid UTF-8 text: {}", e),
                 )
             })?;
-            (name, text)
-        } else {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                "Multipart form must include a file".to_string(),
-            ));
-        };
+            upload = Some((name, text));
+        }
+    }
+
+    let (file_name, mut file_text) = upload.ok_or((
+        StatusCode::BAD_REQUEST,
+        "Multipart form must include a file".to_string(),
+    ))?;
+    check_document_length(&app_state, &file_text)?;
+    check_guest_upload(&app_state, user_id, &file_text).await?;
+
+    // Optionally repair PDF/OCR artifacts before the text is chunked for reading.
+    if cleanup {
+        file_text = app_state
+            .cleanup_adapter
+            .clean_text(&file_text)
+            .await
+            .map_err(|e| {
+                error!("Failed to clean up uploaded text: {:?}", e);
+                (
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Failed to clean up uploaded text".to_string(),
+                )
+            })?;
+    }
 
     let db = &app_state.db;
     let result = async {
         // User already exists from signup/login, no need to get_or_create_user
         let doc = db.create_document(user_id, &file_name, &file_text).await?;
-        db.create_session(user_id, doc.id).await
+        db.create_session(user_id, doc.id, education_mode).await
     }
     .await;
 
     match result {
         Ok(session) => {
+            tokio::spawn(index_document(app_state.clone(), session.document_id));
+            tokio::spawn(suggest_and_save_tags(
+                app_state.clone(),
+                user_id,
+                session.document_id,
+                file_text,
+            ));
             let response = CreateSessionResponse {
                 session_id: session.id,
                 document_id: session.document_id,
@@ -173,8 +633,11 @@ pub async fn create_session_handler(
  #[utoipa::path(
     get,
     path = "/sessions",
+    params(ListSessionsQuery, ListParams),
     responses(
         (status = 200, description = "Sessions retrieved successfully", body = ListSessionsResponse),
+        (status = 304, description = "The sessions haven't changed since the ETag in If-None-Match"),
+        (status = 400, description = "Bad request (e.g., unknown field or invalid cursor)"),
         (status = 401, description = "Unauthorized - no valid session"),
         (status = 500, description = "Internal server error")
     ),
@@ -185,10 +648,30 @@ pub async fn create_session_handler(
 pub async fn list_sessions_handler(
     State(app_state): State<Arc<AppState>>,
     Extension(user_id): Extension<Uuid>,
-) -> Result<impl IntoResponse, (StatusCode, String)> {
+    Query(query): Query<ListSessionsQuery>,
+    Query(list): Query<ListParams>,
+    headers: HeaderMap,
+    uri: Uri,
+) -> Result<Response, (StatusCode, String)> {
+    let tag = query.tag.map(|t| t.trim().to_lowercase());
+
+    // Polling clients get a 304 without the list being loaded
+    let version = app_state
+        .db
+        .get_sessions_version(user_id, tag.as_deref())
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch sessions version: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch sessions".to_string())
+        })?;
+    let etag = list_etag(version, uri.query());
+    if is_not_modified(&headers, &etag) {
+        return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response());
+    }
+
     let sessions = app_state
         .db
-        .get_sessions_by_user(user_id)
+        .get_sessions_by_user(user_id, tag.as_deref())
         .await
         .map_err(|e| {
             error!("Failed to fetch sessions: {:?}", e);
@@ -201,24 +684,32 @@ pub async fn list_sessions_handler(
             session_id: s.id,
             document_id: s.document_id,
             created_at: s.created_at.to_rfc3339(),
+            last_accessed_at: s.last_accessed_at.to_rfc3339(),
+            education_mode: s.education_mode,
+            title: s.title,
         })
         .collect();
 
+    let page = list.page(session_items)?;
     let response = ListSessionsResponse {
-        sessions: session_items,
+        sessions: page.items,
+        next_cursor: page.next_cursor,
     };
     
-    Ok((StatusCode::OK, Json(response)))
+    Ok((StatusCode::OK, [(header::ETAG, etag)], Json(response)).into_response())
 }
 
 #[utoipa::path(
     get,
     path = "/sessions/{session_id}/notes",
     params(
-        ("session_id" = Uuid, Path, description = "Session ID")
+        ("session_id" = Uuid, Path, description = "Session ID"),
+        ListParams
     ),
     responses(
         (status = 200, description = "Notes retrieved successfully", body = ListNotesResponse),
+        (status = 304, description = "The notes haven't changed since the ETag in If-None-Match"),
+        (status = 400, description = "Bad request (e.g., unknown field or invalid cursor)"),
         (status = 401, description = "Unauthorized"),
         (status = 404, description = "Session not found"),
         (status = 500, description = "Internal server error")
@@ -231,7 +722,10 @@ pub async fn list_notes_handler(
     State(app_state): State<Arc<AppState>>,
     Extension(user_id): Extension<Uuid>,
     axum::extract::Path(session_id): axum::extract::Path<Uuid>,
-) -> Result<impl IntoResponse, (StatusCode, String)> {
+    Query(list): Query<ListParams>,
+    headers: HeaderMap,
+    uri: Uri,
+) -> Result<Response, (StatusCode, String)> {
     // First, verify the session belongs to this user
     let session = app_state
         .db
@@ -245,6 +739,20 @@ pub async fn list_notes_handler(
     if session.user_id != user_id {
         return Err((StatusCode::FORBIDDEN, "Access denied".to_string()));
     }
+
+    // Polling clients get a 304 without the notes being loaded
+    let version = app_state
+        .db
+        .get_notes_version(session_id)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch notes version: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch notes".to_string())
+        })?;
+    let etag = list_etag(version, uri.query());
+    if is_not_modified(&headers, &etag) {
+        return Ok((StatusCode::NOT_MODIFIED, [(header::ETAG, etag)]).into_response());
+    }
     
     // Fetch notes for this session
     let notes = app_state
@@ -256,19 +764,412 @@ pub async fn list_notes_handler(
             (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch notes".to_string())
         })?;
     
-    let note_items: Vec<NoteItem> = notes
-        .into_iter()
-        .map(|n| NoteItem {
-            note_id: n.id,
-            session_id: n.session_id,
-            text: n.generated_note_text,
-            created_at: n.created_at.to_rfc3339(),
-        })
-        .collect();
+    let note_items: Vec<NoteItem> = notes.into_iter().map(NoteItem::from).collect();
     
+    let page = list.page(note_items)?;
     let response = ListNotesResponse {
-        notes: note_items,
+        notes: page.items,
+        next_cursor: page.next_cursor,
     };
     
+    Ok((StatusCode::OK, [(header::ETAG, etag)], Json(response)).into_response())
+}
+
+#[utoipa::path(
+    get,
+    path = "/sessions/{session_id}/qa-pairs",
+    params(
+        ("session_id" = Uuid, Path, description = "Session ID"),
+        ListParams
+    ),
+    responses(
+        (status = 200, description = "Questions and answers retrieved successfully", body = ListQaPairsResponse),
+        (status = 400, description = "Bad request (e.g., unknown field or invalid cursor)"),
+        (status = 401, description = "Unauthorized"),
+        (status = 404, description = "Session not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn list_qa_pairs_handler(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    axum::extract::Path(session_id): axum::extract::Path<Uuid>,
+    Query(list): Query<ListParams>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let session = app_state
+        .db
+        .get_session_by_id(session_id)
+        .await
+        .map_err(|e| {
+            error!("Failed to get session: {:?}", e);
+            (StatusCode::NOT_FOUND, "Session not found".to_string())
+        })?;
+
+    if session.user_id != user_id {
+        return Err((StatusCode::FORBIDDEN, "Access denied".to_string()));
+    }
+
+    let qa_pairs = app_state
+        .db
+        .get_qa_pairs_for_session(session_id)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch Q&A pairs: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch questions".to_string())
+        })?;
+
+    let items: Vec<QaPairItem> = qa_pairs
+        .into_iter()
+        .map(|qa| QaPairItem {
+            qa_pair_id: qa.id,
+            session_id: qa.session_id,
+            question: qa.question_text,
+            answer: qa.answer_text,
+            interrupted: qa.answer_interrupted,
+            created_at: qa.created_at.to_rfc3339(),
+        })
+        .collect();
+
+    let page = list.page(items)?;
+    let response = ListQaPairsResponse {
+        qa_pairs: page.items,
+        next_cursor: page.next_cursor,
+    };
+
+    Ok((StatusCode::OK, Json(response)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/sessions/{session_id}",
+    params(
+        ("session_id" = Uuid, Path, description = "Session ID")
+    ),
+    responses(
+        (status = 204, description = "Session deleted, with its questions and notes"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Session belongs to another user"),
+        (status = 404, description = "Session not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn delete_session_handler(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    axum::extract::Path(session_id): axum::extract::Path<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let session = app_state
+        .db
+        .get_session_by_id(session_id)
+        .await
+        .map_err(|e| {
+            error!("Failed to get session: {:?}", e);
+            (StatusCode::NOT_FOUND, "Session not found".to_string())
+        })?;
+
+    if session.user_id != user_id {
+        return Err((StatusCode::FORBIDDEN, "Access denied".to_string()));
+    }
+
+    match app_state.db.delete_session(session_id).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(PortError::NotFound(_)) => {
+            Err((StatusCode::NOT_FOUND, "Session not found".to_string()))
+        }
+        Err(e) => {
+            error!("Failed to delete session: {:?}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, "Failed to delete session".to_string()))
+        }
+    }
+}
+
+#[utoipa::path(
+    patch,
+    path = "/sessions/{session_id}",
+    params(
+        ("session_id" = Uuid, Path, description = "Session ID")
+    ),
+    request_body = UpdateTitleRequest,
+    responses(
+        (status = 204, description = "Session title updated"),
+        (status = 400, description = "Title too long"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Session belongs to another user"),
+        (status = 404, description = "Session not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn update_session_handler(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    axum::extract::Path(session_id): axum::extract::Path<Uuid>,
+    Json(req): Json<UpdateTitleRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let title = req.title()?;
+    let session = app_state
+        .db
+        .get_session_by_id(session_id)
+        .await
+        .map_err(|e| {
+            error!("Failed to get session: {:?}", e);
+            (StatusCode::NOT_FOUND, "Session not found".to_string())
+        })?;
+
+    if session.user_id != user_id {
+        return Err((StatusCode::FORBIDDEN, "Access denied".to_string()));
+    }
+
+    match app_state.db.set_session_title(session_id, title).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(PortError::NotFound(_)) => {
+            Err((StatusCode::NOT_FOUND, "Session not found".to_string()))
+        }
+        Err(e) => {
+            error!("Failed to update session title: {:?}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, "Failed to update session".to_string()))
+        }
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/documents",
+    params(ListParams),
+    responses(
+        (status = 200, description = "Documents retrieved successfully", body = ListDocumentsResponse),
+        (status = 400, description = "Bad request (e.g., unknown field or invalid cursor)"),
+        (status = 401, description = "Unauthorized"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn list_documents_handler(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Query(list): Query<ListParams>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    // Documents can be whole books, so only the requested page is loaded unless the
+    // list has to be sorted.
+    let query_page = list.query_page()?;
+    let (offset, limit) = match query_page {
+        Some((offset, limit)) => (offset, Some(limit + 1)),
+        None => (0, None),
+    };
+    let documents = app_state
+        .db
+        .get_document_summaries_by_user(user_id, offset, limit, DOCUMENT_PREVIEW_CHARS)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch documents: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch documents".to_string())
+        })?;
+
+    let items: Vec<DocumentListItem> = documents
+        .into_iter()
+        .map(|d| DocumentListItem {
+            document_id: d.id,
+            title: d.title,
+            created_at: d.created_at.to_rfc3339(),
+            preview: d.preview,
+        })
+        .collect();
+
+    let page = match query_page {
+        Some(_) => list.loaded_page(items)?,
+        None => list.page(items)?,
+    };
+    let response = ListDocumentsResponse {
+        documents: page.items,
+        next_cursor: page.next_cursor,
+    };
+
+    Ok((StatusCode::OK, Json(response)))
+}
+
+/// Fetches a document and checks that it belongs to the user.
+async fn get_owned_document(
+    app_state: &AppState,
+    document_id: Uuid,
+    user_id: Uuid,
+) -> Result<Document, (StatusCode, String)> {
+    let document = match app_state.db.get_document_by_id(document_id).await {
+        Ok(document) => document,
+        Err(PortError::NotFound(_)) => {
+            return Err((StatusCode::NOT_FOUND, "Document not found".to_string()));
+        }
+        Err(e) => {
+            error!("Failed to get document: {:?}", e);
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, "Failed to get document".to_string()));
+        }
+    };
+
+    if document.user_id != user_id {
+        return Err((StatusCode::FORBIDDEN, "Access denied".to_string()));
+    }
+    Ok(document)
+}
+
+#[utoipa::path(
+    get,
+    path = "/documents/{document_id}",
+    params(
+        ("document_id" = Uuid, Path, description = "Document ID")
+    ),
+    responses(
+        (status = 200, description = "Document retrieved successfully", body = DocumentResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Document belongs to another user"),
+        (status = 404, description = "Document not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn get_document_handler(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    axum::extract::Path(document_id): axum::extract::Path<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let document = get_owned_document(&app_state, document_id, user_id).await?;
+    let sentence_count = app_state
+        .db
+        .count_document_chunks(document_id)
+        .await
+        .map_err(|e| {
+            error!("Failed to count document chunks: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch document".to_string())
+        })?;
+
+    let response = DocumentResponse {
+        document_id: document.id,
+        title: document.title,
+        created_at: document.created_at.to_rfc3339(),
+        sentence_count,
+        text: document.original_text,
+    };
+
     Ok((StatusCode::OK, Json(response)))
+}
+
+#[utoipa::path(
+    delete,
+    path = "/documents/{document_id}",
+    params(
+        ("document_id" = Uuid, Path, description = "Document ID")
+    ),
+    responses(
+        (status = 204, description = "Document deleted, with every session on it and their notes and questions"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Document belongs to another user"),
+        (status = 404, description = "Document not found"),
+        (status = 409, description = "Document is shared, has sessions of other users, or is in a workspace or the gallery; deleting it would delete other users' sessions and notes"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn delete_document_handler(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    axum::extract::Path(document_id): axum::extract::Path<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    get_owned_document(&app_state, document_id, user_id).await?;
+
+    // Deleting the document would take other users' sessions and notes along with it,
+    // and pull it out of workspaces and the gallery, so it is refused while anyone else
+    // depends on it.
+    let usage = app_state
+        .db
+        .get_document_usage(document_id)
+        .await
+        .map_err(|e| {
+            error!("Failed to get document usage: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to delete document".to_string())
+        })?;
+    let mut uses = Vec::new();
+    if usage.shares > 0 {
+        uses.push(format!("shared with {} user(s)", usage.shares));
+    }
+    if usage.other_users_sessions > 0 {
+        uses.push(format!("being read by other users in {} session(s)", usage.other_users_sessions));
+    }
+    if usage.workspaces > 0 {
+        uses.push(format!("in {} workspace(s)", usage.workspaces));
+    }
+    if usage.in_gallery {
+        uses.push("in the gallery".to_string());
+    }
+    if !uses.is_empty() {
+        return Err((
+            StatusCode::CONFLICT,
+            format!(
+                "This document is {}, so it can't be deleted.",
+                uses.join(", ")
+            ),
+        ));
+    }
+
+    match app_state.db.delete_document(document_id).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(PortError::NotFound(_)) => {
+            Err((StatusCode::NOT_FOUND, "Document not found".to_string()))
+        }
+        Err(e) => {
+            error!("Failed to delete document: {:?}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, "Failed to delete document".to_string()))
+        }
+    }
+}
+
+#[utoipa::path(
+    patch,
+    path = "/documents/{document_id}",
+    params(
+        ("document_id" = Uuid, Path, description = "Document ID")
+    ),
+    request_body = UpdateTitleRequest,
+    responses(
+        (status = 204, description = "Document title updated"),
+        (status = 400, description = "Title too long"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Document belongs to another user"),
+        (status = 404, description = "Document not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn update_document_handler(
+    State(app_state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    axum::extract::Path(document_id): axum::extract::Path<Uuid>,
+    Json(req): Json<UpdateTitleRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let title = req.title()?;
+    get_owned_document(&app_state, document_id, user_id).await?;
+
+    match app_state.db.set_document_title(document_id, title).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(PortError::NotFound(_)) => {
+            Err((StatusCode::NOT_FOUND, "Document not found".to_string()))
+        }
+        Err(e) => {
+            error!("Failed to update document title: {:?}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, "Failed to update document".to_string()))
+        }
+    }
 }
\ No newline at end of file
@@ -0,0 +1,163 @@
+//! services/api/src/web/retrieval.rs
+//!
+//! Retrieval for question answering. Every sentence chunk of a document is embedded
+//! in the background, and each question is answered from the passage being read plus
+//! the chunks most similar to the question, so questions about earlier (or later)
+//! parts of a long document can be answered too. The same search across the user's
+//! other documents points out when another one covers the question's topic.
+
+use crate::web::state::{AppState, SessionState};
+use reading_assistant_core::{
+    domain::RelatedPassage,
+    ports::{PortError, PortResult},
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    ops::Range,
+    sync::Arc,
+};
+use tracing::{error, info};
+use uuid::Uuid;
+
+/// How many chunks are embedded per request.
+const EMBEDDING_BATCH_SIZE: usize = 256;
+/// How many of the chunks most similar to a question are added to its context.
+const RETRIEVED_CHUNKS: usize = 5;
+/// Sentences kept on each side of a retrieved chunk, since one sentence alone
+/// rarely makes sense out of context.
+const RETRIEVED_NEIGHBOURS: usize = 1;
+/// How similar a passage of another document must be to a question for that
+/// document to count as covering the same topic.
+const RELATED_DOCUMENT_MIN_SIMILARITY: f32 = 0.45;
+
+/// What the document index turned up for a question.
+#[derive(Default)]
+pub struct Retrieved {
+    /// The chunks of the session's document most relevant to the question, best first.
+    pub relevant: Vec<usize>,
+    /// The text of the relevant chunks and their neighbours, by index. They are read
+    /// from the database, since they can lie outside the sentences a session holds.
+    pub sentences: BTreeMap<usize, String>,
+    /// A passage of another of the user's documents on the same topic, if there is one.
+    pub related: Option<RelatedPassage>,
+}
+
+/// A "fire-and-forget" background task that embeds the chunks of a document that
+/// don't have an embedding yet. Run after upload, and when a session opens so that
+/// older documents (and any failed runs) are caught up.
+pub async fn index_document(app_state: Arc<AppState>, document_id: Uuid) {
+    let chunks = match app_state.db.get_unembedded_chunks(document_id).await {
+        Ok(chunks) if chunks.is_empty() => return,
+        Ok(chunks) => chunks,
+        Err(e) => {
+            error!("Failed to load chunks to embed for document {}: {:?}", document_id, e);
+            return;
+        }
+    };
+
+    info!("Embedding {} chunks of document {}.", chunks.len(), document_id);
+    for batch in chunks.chunks(EMBEDDING_BATCH_SIZE) {
+        let texts: Vec<String> = batch.iter().map(|(_, text)| text.clone()).collect();
+        let embeddings = match app_state.embedding_adapter.embed(&texts).await {
+            Ok(embeddings) => embeddings,
+            Err(e) => {
+                error!("Failed to embed chunks of document {}: {:?}", document_id, e);
+                return;
+            }
+        };
+        let indexed: Vec<(usize, Vec<f32>)> = batch
+            .iter()
+            .map(|(index, _)| *index)
+            .zip(embeddings)
+            .collect();
+        if let Err(e) = app_state.db.save_chunk_embeddings(document_id, &indexed).await {
+            error!("Failed to save chunk embeddings for document {}: {:?}", document_id, e);
+            return;
+        }
+    }
+    info!("Finished embedding document {}.", document_id);
+}
+
+/// Searches the session's document, and the user's other documents, for `question`.
+/// Nothing is found in documents that haven't been embedded yet.
+pub async fn retrieve(
+    app_state: &Arc<AppState>,
+    user_id: Uuid,
+    document_id: Uuid,
+    question: &str,
+) -> PortResult<Retrieved> {
+    let query = app_state
+        .embedding_adapter
+        .embed(&[question.to_string()])
+        .await?
+        .into_iter()
+        .next()
+        .ok_or_else(|| PortError::Unexpected("No embedding returned for question.".to_string()))?;
+
+    let (relevant, related) = tokio::try_join!(
+        app_state.db.search_document_chunks(document_id, &query, RETRIEVED_CHUNKS),
+        app_state.db.find_related_passage(user_id, document_id, &query),
+    )?;
+    let relevant: Vec<usize> = relevant.into_iter().map(|(index, _)| index).collect();
+    let neighbourhoods: BTreeSet<usize> = relevant
+        .iter()
+        .flat_map(|&index| index.saturating_sub(RETRIEVED_NEIGHBOURS)..=index + RETRIEVED_NEIGHBOURS)
+        .collect();
+    let indices: Vec<usize> = neighbourhoods.into_iter().collect();
+    let sentences = app_state
+        .db
+        .get_document_chunks_at(document_id, &indices)
+        .await?
+        .into_iter()
+        .collect();
+    Ok(Retrieved {
+        relevant,
+        sentences,
+        related: related.filter(|passage| passage.similarity >= RELATED_DOCUMENT_MIN_SIMILARITY),
+    })
+}
+
+/// Builds the document context for a question: the passage being read (`window`),
+/// followed by the retrieved chunks outside it, each with its neighbouring sentences.
+/// Every sentence is prefixed with its index, e.g. "[12]", so the model can cite the
+/// sentences its answer is based on. The sentences of `window` must be loaded.
+pub fn build_context(session: &SessionState, window: Range<usize>, retrieved: &Retrieved) -> String {
+    let current = window
+        .clone()
+        .filter_map(|index| {
+            let sentence = session.chunked_document.get(index)?;
+            Some(format!("[{}] {}", index, sentence))
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let mut outside_window = retrieved
+        .sentences
+        .iter()
+        .filter(|(index, _)| !window.contains(*index))
+        .peekable();
+    if outside_window.peek().is_none() {
+        return current;
+    }
+
+    // Consecutive sentences are joined into one passage, in document order.
+    let mut passages: Vec<String> = Vec::new();
+    let mut previous: Option<usize> = None;
+    for (&index, sentence) in outside_window {
+        let numbered = format!("[{}] {}", index, sentence);
+        match passages.last_mut() {
+            Some(passage) if previous.map(|p| p + 1) == Some(index) => {
+                passage.push(' ');
+                passage.push_str(&numbered);
+            }
+            _ => passages.push(numbered),
+        }
+        previous = Some(index);
+    }
+
+    format!(
+        "CURRENT PASSAGE:\n{}\n\nRELATED PASSAGES FROM ELSEWHERE IN THE DOCUMENT:\n{}",
+        current,
+        passages.join("\n...\n")
+    )
+}
@@ -0,0 +1,42 @@
+//! services/api/src/web/sentence_cache.rs
+//!
+//! The windows of document sentences loaded by open sessions, shared between them:
+//! sessions on the same document (a class reading a workspace document, one user on
+//! two devices) load the same windows, so each is held in memory once. Windows are
+//! only referenced weakly and are dropped with the last session holding them.
+
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::{Arc, Mutex, Weak};
+use uuid::Uuid;
+
+/// A window of a document, by document and sentence indices.
+type WindowKey = (Uuid, usize, usize);
+
+#[derive(Default)]
+pub struct SentenceCache {
+    windows: Mutex<HashMap<WindowKey, Weak<[String]>>>,
+}
+
+impl SentenceCache {
+    /// The sentences of `range` of a document, if a session still holds them.
+    pub fn get(&self, document_id: Uuid, range: &Range<usize>) -> Option<Arc<[String]>> {
+        let windows = self.windows.lock().unwrap();
+        windows.get(&(document_id, range.start, range.end))?.upgrade()
+    }
+
+    /// Shares the sentences of `range` of a document, just loaded, with later sessions.
+    /// Windows no session holds any more are forgotten on the way.
+    pub fn insert(
+        &self,
+        document_id: Uuid,
+        range: &Range<usize>,
+        sentences: Vec<String>,
+    ) -> Arc<[String]> {
+        let sentences: Arc<[String]> = sentences.into();
+        let mut windows = self.windows.lock().unwrap();
+        windows.retain(|_, window| window.strong_count() > 0);
+        windows.insert((document_id, range.start, range.end), Arc::downgrade(&sentences));
+        sentences
+    }
+}
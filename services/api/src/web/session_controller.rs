@@ -0,0 +1,244 @@
+//! services/api/src/web/session_controller.rs
+//!
+//! Owns the reading task of one WebSocket connection and drives the session's
+//! state machine, so the socket loop only decides *which* transition to make.
+//! Transitions the current mode doesn't allow are reported to the client and ignored.
+
+use crate::web::{
+    protocol::{AudioKind, ErrorCode, ServerMessage},
+    reading_task::reading_process,
+    state::{AppState, SessionEvent, SessionState},
+    ws_handler::send_error,
+    ws_writer::WsSender,
+};
+use std::sync::Arc;
+use tokio::{sync::Mutex, task::JoinHandle};
+use tracing::{error, info, warn};
+
+pub struct SessionController {
+    app_state: Arc<AppState>,
+    session_state_lock: Arc<Mutex<SessionState>>,
+    ws_sender: WsSender,
+    reading_task: Option<JoinHandle<()>>,
+}
+
+impl SessionController {
+    pub fn new(
+        app_state: Arc<AppState>,
+        session_state_lock: Arc<Mutex<SessionState>>,
+        ws_sender: WsSender,
+    ) -> Self {
+        Self {
+            app_state,
+            session_state_lock,
+            ws_sender,
+            reading_task: None,
+        }
+    }
+
+    /// Starts a reading task from the current position.
+    pub async fn start_reading(&mut self) {
+        let session_state_lock = self.session_state_lock.clone();
+        let mut session = session_state_lock.lock().await;
+        if self.apply(&mut session, SessionEvent::StartReading).await {
+            self.spawn_reading_task(&session);
+        }
+    }
+
+    /// Stops reading; `resume` picks up where it left off.
+    pub async fn pause(&mut self) {
+        let session_state_lock = self.session_state_lock.clone();
+        let mut session = session_state_lock.lock().await;
+        self.apply(&mut session, SessionEvent::Pause).await;
+    }
+
+    /// Resumes reading after a pause.
+    pub async fn resume(&mut self) {
+        let session_state_lock = self.session_state_lock.clone();
+        let mut session = session_state_lock.lock().await;
+        if self.apply(&mut session, SessionEvent::Resume).await {
+            self.continue_reading(&session).await;
+        }
+    }
+
+    /// Stops reading so the user can ask a question.
+    pub async fn interrupt(&mut self) {
+        let session_state_lock = self.session_state_lock.clone();
+        let mut session = session_state_lock.lock().await;
+        self.apply(&mut session, SessionEvent::Interrupt).await;
+    }
+
+    /// Starts handling the question or quiz answer the user just finished speaking.
+    /// Returns `false` if there was no question to handle.
+    pub async fn submit_question(&mut self) -> bool {
+        let session_state_lock = self.session_state_lock.clone();
+        let mut session = session_state_lock.lock().await;
+        self.apply(&mut session, SessionEvent::SubmitQuestion).await
+    }
+
+    /// Stops reading and starts handling a typed question, which needs no listening
+    /// phase. Returns `false` if a question can't be asked now.
+    pub async fn submit_text_question(&mut self) -> bool {
+        let session_state_lock = self.session_state_lock.clone();
+        let mut session = session_state_lock.lock().await;
+        self.apply(&mut session, SessionEvent::Interrupt).await
+            && self.apply(&mut session, SessionEvent::SubmitQuestion).await
+    }
+
+    /// Stops reading so a quiz can be prepared. Returns `false` if a quiz can't start now.
+    pub async fn start_quiz(&mut self) -> bool {
+        let session_state_lock = self.session_state_lock.clone();
+        let mut session = session_state_lock.lock().await;
+        self.apply(&mut session, SessionEvent::StartQuiz).await
+    }
+
+    /// Goes back to waiting for the user's next question.
+    pub async fn finish_processing(&mut self) {
+        let session_state_lock = self.session_state_lock.clone();
+        let mut session = session_state_lock.lock().await;
+        self.apply(&mut session, SessionEvent::FinishProcessing).await;
+    }
+
+    /// Waits for the user's reply to a clarifying question.
+    pub async fn ask_clarification(&mut self) {
+        let session_state_lock = self.session_state_lock.clone();
+        let mut session = session_state_lock.lock().await;
+        self.apply(&mut session, SessionEvent::AskClarification).await;
+    }
+
+    /// Resumes reading once an answer has been spoken.
+    pub async fn resume_after_answer(&mut self) {
+        let session_state_lock = self.session_state_lock.clone();
+        let mut session = session_state_lock.lock().await;
+        if self.apply(&mut session, SessionEvent::StartReading).await {
+            self.continue_reading(&session).await;
+        }
+    }
+
+    /// Moves reading to `sentence_index` after a spoken "skip to" request and starts
+    /// reading from there.
+    pub async fn seek(&mut self, sentence_index: usize) {
+        let session_state_lock = self.session_state_lock.clone();
+        let mut session = session_state_lock.lock().await;
+        if self.apply(&mut session, SessionEvent::StartReading).await {
+            self.read_from(&mut session, sentence_index).await;
+        }
+    }
+
+    /// Jumps reading to `sentence_index` at the client's request, acknowledges it with
+    /// `ReadingSeeked` and starts reading from there. Returns whether the current mode
+    /// allowed the jump.
+    pub async fn jump_to(&mut self, sentence_index: usize) -> bool {
+        self.jump(|_| sentence_index).await
+    }
+
+    /// Goes back to read the last `sentences` sentences again slower, like `jump_to`.
+    /// The session is only set up to read slower if the current mode allows the jump.
+    pub async fn reread_slower(&mut self, sentences: usize) -> bool {
+        self.jump(|session| session.reread_slower(sentences)).await
+    }
+
+    /// Applies a seek, and if it is allowed moves reading to the sentence `target`
+    /// picks, after making whatever changes to the session go with it.
+    async fn jump(&mut self, target: impl FnOnce(&mut SessionState) -> usize) -> bool {
+        let session_state_lock = self.session_state_lock.clone();
+        let mut session = session_state_lock.lock().await;
+        if !self.apply(&mut session, SessionEvent::Seek).await {
+            return false;
+        }
+        let sentence_index = target(&mut session);
+        // Tell the client first so it drops stale audio before the new passage plays.
+        let seek_msg = ServerMessage::ReadingSeeked { sentence_index };
+        if self.ws_sender.send(&seek_msg).await.is_err() {
+            warn!("Failed to send ReadingSeeked message. Client may have disconnected.");
+        }
+        self.read_from(&mut session, sentence_index).await;
+        true
+    }
+
+    /// Moves and persists the reading position, then starts a reading task there.
+    async fn read_from(&mut self, session: &mut SessionState, sentence_index: usize) {
+        session.reading_progress_index = sentence_index;
+        session.auto_notes_from = sentence_index;
+        session.checkpoint_from = sentence_index;
+        if let Err(e) = self
+            .app_state
+            .db
+            .update_session_progress(session.session_id, session.device_id.as_deref(), sentence_index)
+            .await
+        {
+            error!("Failed to persist seek position: {:?}", e);
+        }
+        self.spawn_reading_task(session);
+    }
+
+    /// Stops reading because the session is ending.
+    pub async fn end(&mut self) {
+        let session_state_lock = self.session_state_lock.clone();
+        let mut session = session_state_lock.lock().await;
+        self.apply(&mut session, SessionEvent::End).await;
+    }
+
+    /// Stops the reading task for good. Called when the connection closes.
+    pub fn shutdown(self) {
+        if let Some(handle) = self.reading_task {
+            handle.abort();
+        }
+    }
+
+    /// Applies `event`, telling the client if the current mode doesn't allow it.
+    /// Returns whether the transition happened.
+    async fn apply(&self, session: &mut SessionState, event: SessionEvent) -> bool {
+        match session.apply(event) {
+            Ok(()) => true,
+            Err(e) => {
+                warn!("Rejected session transition: {}", e);
+                send_error(&self.ws_sender, ErrorCode::InvalidTransition, &e.to_string()).await;
+                false
+            }
+        }
+    }
+
+    /// Starts a reading task, or, if every sentence has already been sent, just
+    /// tells the client to play out the audio it has queued.
+    async fn continue_reading(&mut self, session: &SessionState) {
+        if !session.all_sentences_sent() {
+            self.spawn_reading_task(session);
+            return;
+        }
+
+        // Without audio there is nothing queued, so the reader is already done.
+        if session.audio_free {
+            if self.ws_sender.send(&ServerMessage::ReadingEnded).await.is_err() {
+                error!("Failed to send ReadingEnded message.");
+            }
+            return;
+        }
+
+        info!("All audio already generated, just resuming frontend playback");
+        if self.ws_sender.send(&ServerMessage::ReadingStarted).await.is_err() {
+            error!("Failed to send ReadingStarted message.");
+        }
+        if self.ws_sender.send_audio(AudioKind::Resume, None, Vec::new()).await.is_err() {
+            error!("Failed to send empty audio trigger.");
+        }
+    }
+
+    /// Spawns a reading task bound to the token handed out by the last transition.
+    /// The previous task is aborted too, so it can't touch the session once it has
+    /// been replaced, even between two checks of its token.
+    fn spawn_reading_task(&mut self, session: &SessionState) {
+        if let Some(handle) = self.reading_task.take() {
+            handle.abort();
+        }
+        let token = session.cancellation_token.clone();
+        let app_state = self.app_state.clone();
+        let session_state_lock = self.session_state_lock.clone();
+        let ws_sender = self.ws_sender.clone();
+        self.reading_task = Some(tokio::spawn(async move {
+            if let Err(e) = reading_process(app_state, session_state_lock, ws_sender, token).await {
+                error!("Reading process failed: {:?}", e);
+            }
+        }));
+    }
+}
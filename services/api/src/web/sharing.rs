@@ -0,0 +1,367 @@
+//! services/api/src/web/sharing.rs
+//!
+//! Endpoints for sharing a document with specific users, e.g. a study partner: the
+//! owner grants and revokes access by email, and the people it is shared with can
+//! start their own sessions on it and read the owner's notes on it.
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use reading_assistant_core::{domain::DocumentShare, ports::PortError};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::web::{middleware::TenantId, rest::NoteItem, state::AppState};
+
+//=========================================================================================
+// Request/Response Types
+//=========================================================================================
+
+#[derive(Deserialize, ToSchema)]
+pub struct ShareDocumentRequest {
+    /// The email the other user signed up with.
+    pub email: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DocumentShareItem {
+    pub user_id: Uuid,
+    pub email: String,
+    pub shared_at: String, // ISO 8601 timestamp
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ListDocumentSharesResponse {
+    pub shares: Vec<DocumentShareItem>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct SharedDocumentItem {
+    pub document_id: Uuid,
+    /// Who shared it. Absent if the owner has no email.
+    pub owner_email: Option<String>,
+    pub shared_at: String, // ISO 8601 timestamp
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ListSharedDocumentsResponse {
+    pub documents: Vec<SharedDocumentItem>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct SharedSessionResponse {
+    pub session_id: Uuid,
+    pub document_id: Uuid,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ListDocumentNotesResponse {
+    pub notes: Vec<NoteItem>,
+}
+
+fn share_item(share: DocumentShare) -> DocumentShareItem {
+    DocumentShareItem {
+        user_id: share.user_id,
+        email: share.user_email,
+        shared_at: share.shared_at.to_rfc3339(),
+    }
+}
+
+/// Returns the document's owner if the user owns it or it is shared with them,
+/// otherwise 404 or 403.
+async fn require_document_access(
+    state: &AppState,
+    document_id: Uuid,
+    user_id: Uuid,
+) -> Result<Uuid, (StatusCode, String)> {
+    let document = state.db.get_document_by_id(document_id).await.map_err(|e| {
+        error!("Failed to get document: {:?}", e);
+        (StatusCode::NOT_FOUND, "Document not found".to_string())
+    })?;
+    if document.user_id == user_id {
+        return Ok(document.user_id);
+    }
+
+    let shares = state.db.get_document_shares(document_id).await.map_err(|e| {
+        error!("Failed to fetch document shares: {:?}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to check access".to_string())
+    })?;
+    if shares.iter().any(|share| share.user_id == user_id) {
+        Ok(document.user_id)
+    } else {
+        Err((StatusCode::FORBIDDEN, "Access denied".to_string()))
+    }
+}
+
+/// Verifies that the document exists and belongs to the user.
+async fn require_document_owner(
+    state: &AppState,
+    document_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), (StatusCode, String)> {
+    let document = state.db.get_document_by_id(document_id).await.map_err(|e| {
+        error!("Failed to get document: {:?}", e);
+        (StatusCode::NOT_FOUND, "Document not found".to_string())
+    })?;
+    if document.user_id != user_id {
+        return Err((StatusCode::FORBIDDEN, "Only the document's owner can do this".to_string()));
+    }
+    Ok(())
+}
+
+//=========================================================================================
+// Handlers
+//=========================================================================================
+
+/// POST /documents/{document_id}/share - Share a document with another user (owner only)
+#[utoipa::path(
+    post,
+    path = "/documents/{document_id}/share",
+    params(
+        ("document_id" = Uuid, Path, description = "Document ID")
+    ),
+    request_body = ShareDocumentRequest,
+    responses(
+        (status = 201, description = "Document shared", body = DocumentShareItem),
+        (status = 400, description = "Sharing with yourself"),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 403, description = "Not the document's owner"),
+        (status = 404, description = "Document or user not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn share_document_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Extension(TenantId(tenant_id)): Extension<TenantId>,
+    Path(document_id): Path<Uuid>,
+    Json(req): Json<ShareDocumentRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    require_document_owner(&state, document_id, user_id).await?;
+
+    let partner = match state.db.get_user_by_email(tenant_id, req.email.trim()).await {
+        Ok(partner) => partner,
+        Err(PortError::NotFound(_)) => {
+            return Err((StatusCode::NOT_FOUND, "No user with that email".to_string()));
+        }
+        Err(e) => {
+            error!("Failed to look up user to share with: {:?}", e);
+            return Err((StatusCode::INTERNAL_SERVER_ERROR, "Failed to share document".to_string()));
+        }
+    };
+    if partner.user_id == user_id {
+        return Err((StatusCode::BAD_REQUEST, "You already own this document".to_string()));
+    }
+
+    let share = state
+        .db
+        .share_document(document_id, partner.user_id)
+        .await
+        .map_err(|e| {
+            error!("Failed to share document: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to share document".to_string())
+        })?;
+
+    Ok((StatusCode::CREATED, Json(share_item(share))))
+}
+
+/// GET /documents/{document_id}/shares - List who a document is shared with (owner only)
+#[utoipa::path(
+    get,
+    path = "/documents/{document_id}/shares",
+    params(
+        ("document_id" = Uuid, Path, description = "Document ID")
+    ),
+    responses(
+        (status = 200, description = "Shares retrieved successfully", body = ListDocumentSharesResponse),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 403, description = "Not the document's owner"),
+        (status = 404, description = "Document not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn list_document_shares_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Path(document_id): Path<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    require_document_owner(&state, document_id, user_id).await?;
+
+    let shares = state.db.get_document_shares(document_id).await.map_err(|e| {
+        error!("Failed to fetch document shares: {:?}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch shares".to_string())
+    })?;
+
+    let shares = shares.into_iter().map(share_item).collect();
+    Ok((StatusCode::OK, Json(ListDocumentSharesResponse { shares })))
+}
+
+/// DELETE /documents/{document_id}/shares/{user_id} - Stop sharing a document with a user (owner only)
+#[utoipa::path(
+    delete,
+    path = "/documents/{document_id}/shares/{user_id}",
+    params(
+        ("document_id" = Uuid, Path, description = "Document ID"),
+        ("user_id" = Uuid, Path, description = "The user the document is shared with")
+    ),
+    responses(
+        (status = 204, description = "Access revoked"),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 403, description = "Not the document's owner"),
+        (status = 404, description = "Document not found, or not shared with that user"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn unshare_document_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Path((document_id, partner_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    require_document_owner(&state, document_id, user_id).await?;
+
+    match state.db.unshare_document(document_id, partner_id).await {
+        Ok(()) => Ok(StatusCode::NO_CONTENT),
+        Err(PortError::NotFound(_)) => Err((
+            StatusCode::NOT_FOUND,
+            "Document is not shared with that user".to_string(),
+        )),
+        Err(e) => {
+            error!("Failed to unshare document: {:?}", e);
+            Err((StatusCode::INTERNAL_SERVER_ERROR, "Failed to unshare document".to_string()))
+        }
+    }
+}
+
+/// GET /documents/shared - List the documents other users shared with you
+#[utoipa::path(
+    get,
+    path = "/documents/shared",
+    responses(
+        (status = 200, description = "Shared documents retrieved successfully", body = ListSharedDocumentsResponse),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn list_shared_documents_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let shares = state.db.get_documents_shared_with(user_id).await.map_err(|e| {
+        error!("Failed to fetch shared documents: {:?}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch shared documents".to_string())
+    })?;
+
+    let documents = shares
+        .into_iter()
+        .map(|share| SharedDocumentItem {
+            document_id: share.document_id,
+            owner_email: share.owner_email,
+            shared_at: share.shared_at.to_rfc3339(),
+        })
+        .collect();
+    Ok((StatusCode::OK, Json(ListSharedDocumentsResponse { documents })))
+}
+
+/// POST /documents/{document_id}/sessions - Start your own session on a document you own or that was shared with you
+#[utoipa::path(
+    post,
+    path = "/documents/{document_id}/sessions",
+    params(
+        ("document_id" = Uuid, Path, description = "Document ID")
+    ),
+    responses(
+        (status = 201, description = "Session created", body = SharedSessionResponse),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 403, description = "Document not shared with you"),
+        (status = 404, description = "Document not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn create_shared_session_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Path(document_id): Path<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    require_document_access(&state, document_id, user_id).await?;
+
+    let session = state
+        .db
+        .create_session(user_id, document_id, false)
+        .await
+        .map_err(|e| {
+            error!("Failed to create session on shared document: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to create session".to_string())
+        })?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(SharedSessionResponse {
+            session_id: session.id,
+            document_id,
+        }),
+    ))
+}
+
+/// GET /documents/{document_id}/notes - The owner's notes on a document, plus your own
+#[utoipa::path(
+    get,
+    path = "/documents/{document_id}/notes",
+    params(
+        ("document_id" = Uuid, Path, description = "Document ID")
+    ),
+    responses(
+        (status = 200, description = "Notes retrieved successfully", body = ListDocumentNotesResponse),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 403, description = "Document not shared with you"),
+        (status = 404, description = "Document not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn list_document_notes_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Path(document_id): Path<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let owner_id = require_document_access(&state, document_id, user_id).await?;
+
+    // Other people the document is shared with keep their notes to themselves.
+    let mut user_ids = vec![owner_id];
+    if user_id != owner_id {
+        user_ids.push(user_id);
+    }
+    let notes = state
+        .db
+        .get_notes_for_document_by_users(document_id, &user_ids)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch document notes: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch notes".to_string())
+        })?;
+
+    let notes = notes.into_iter().map(NoteItem::from).collect();
+    Ok((StatusCode::OK, Json(ListDocumentNotesResponse { notes })))
+}
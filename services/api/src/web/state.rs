@@ -2,12 +2,28 @@
 //!
 //! Defines the application's shared and session-specific states.
 
-use crate::config::Config;
+use crate::adapters::{CachedTtsAdapter, OpenAiAdapters, OpenAiTtsAdapter};
+use crate::config::{Config, NoteProvider, QaProvider, TtsProvider};
+use crate::crypto::SecretCipher;
+use crate::web::announcements::AnnouncementAudio;
+use crate::web::handoff::SessionHandoffs;
+use crate::web::sentence_cache::SentenceCache;
+use crate::web::voices::MIN_SPEED;
+use async_openai::{config::OpenAIConfig, types::Voice, Client};
+use reading_assistant_core::chunker::{self, chunk_into_sentences, Chapter, DocumentStructure, CHUNKER_VERSION};
+use reading_assistant_core::domain::{
+    AnswerVerbosity, AudioFormat, InterruptedAnswer, QuizQuestion, ReadingLevel, SessionSnapshot, SpeechSettings,
+};
+use reading_assistant_core::sentence_window::SentenceWindow;
 use reading_assistant_core::ports::{
-    DatabaseService, NoteGenerationService, PortResult, QuestionAnsweringService,
-    SpeechToTextService, TextToSpeechService,
+    AnalyticsService, AudioCacheService, BackupStorageService, ContentFetchService, DatabaseService, DictionaryService, DocumentImportService, EmbeddingService, ModerationService, NoteExportService,
+    NoteGenerationService, PortResult, QuestionAnsweringService, SpeechToTextService,
+    TextCleanupService, TextToSpeechService,
 };
+use std::ops::Range;
 use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::{Mutex, MutexGuard};
 use tokio_util::sync::CancellationToken; // Import the CancellationToken
 use uuid::Uuid;
 
@@ -24,20 +40,95 @@ pub struct AppState {
     pub tts_adapter: Arc<dyn TextToSpeechService>,
     pub qa_adapter: Arc<dyn QuestionAnsweringService>,
     pub notes_adapter: Arc<dyn NoteGenerationService>,
+    pub cleanup_adapter: Arc<dyn TextCleanupService>,
+    pub content_fetcher: Arc<dyn ContentFetchService>,
+    pub embedding_adapter: Arc<dyn EmbeddingService>,
+    pub moderation_adapter: Arc<dyn ModerationService>,
+    pub dictionary_adapter: Arc<dyn DictionaryService>,
+    /// The Notion exporter, present only when Notion OAuth credentials are configured.
+    pub notion_adapter: Option<Arc<dyn NoteExportService>>,
+    /// The Google Drive importer, present only when Google OAuth credentials are configured.
+    pub google_drive_adapter: Option<Arc<dyn DocumentImportService>>,
+    /// Encrypts user secrets at rest, present only when a secrets key is configured.
+    pub secret_cipher: Option<Arc<SecretCipher>>,
+    /// Synthesized audio shared by all sessions, present only when a cache dir is configured.
+    pub audio_cache: Option<Arc<dyn AudioCacheService>>,
+    /// Where backups are written, present only when a backup dir is configured.
+    pub backup_storage: Option<Arc<dyn BackupStorageService>>,
+    /// Records anonymized feature usage.
+    pub analytics: Arc<dyn AnalyticsService>,
+    /// The connections serving live sessions, for handing them to another device.
+    pub handoffs: Arc<SessionHandoffs>,
+    /// Synthesized announcements like the welcome message, kept in memory.
+    pub announcements: Arc<AnnouncementAudio>,
+    /// The document sentences open sessions hold, shared between sessions.
+    pub sentence_cache: Arc<SentenceCache>,
+}
+
+impl AppState {
+    /// Returns a copy whose OpenAI adapters bill to `api_key` instead of the operator's
+    /// key. Used for sessions of users who brought their own key; their usage is not
+    /// metered since it never appears on the operator's invoice. Questions, notes and
+    /// speech stay with the operator's adapters when they aren't OpenAI's.
+    pub fn with_openai_key(&self, api_key: &str) -> Self {
+        let client = Client::with_config(OpenAIConfig::new().with_api_key(api_key));
+        // The configured voice was validated at startup.
+        let tts_voice = OpenAiTtsAdapter::parse_voice(&self.config.tts_voice).unwrap_or(Voice::Alloy);
+        let openai = OpenAiAdapters::new(client, &self.config, tts_voice);
+        let tts_adapter: Arc<dyn TextToSpeechService> = match (self.config.tts_provider, &self.audio_cache) {
+            (TtsProvider::Piper, _) => self.tts_adapter.clone(),
+            (TtsProvider::OpenAi, Some(cache)) => Arc::new(CachedTtsAdapter::new(openai.tts, cache.clone())),
+            (TtsProvider::OpenAi, None) => openai.tts,
+        };
+
+        Self {
+            sst_adapter: openai.sst,
+            tts_adapter,
+            qa_adapter: match self.config.qa_provider {
+                QaProvider::OpenAi => openai.qa,
+                QaProvider::Anthropic | QaProvider::Ollama => self.qa_adapter.clone(),
+            },
+            notes_adapter: match self.config.note_provider {
+                NoteProvider::OpenAi => openai.notes,
+                NoteProvider::Ollama => self.notes_adapter.clone(),
+            },
+            cleanup_adapter: openai.cleanup,
+            embedding_adapter: openai.embedding,
+            moderation_adapter: openai.moderation,
+            ..self.clone()
+        }
+    }
 }
 
 //=========================================================================================
 // SessionState (Specific to One WebSocket Connection)
 //=========================================================================================
 
-/// An enum representing the current mode of the user's session.
-#[derive(Debug, Clone, PartialEq, Eq)]
-pub enum SessionMode {
-    Reading,
-    InterruptedListening,
-    ProcessingQuestion,
-    Answering,
-    Paused,
+pub use reading_assistant_core::session_machine::{InvalidTransition, SessionEvent, SessionMode};
+
+/// A "read that again slower" reads at this fraction of the session's speed.
+const SLOWER_REREAD_FACTOR: f32 = 0.75;
+/// Auto notes are taken on at least this many sentences, so a heading isn't noted alone.
+const AUTO_NOTE_MIN_SENTENCES: usize = 3;
+/// Auto notes are taken at least this often, for documents without paragraph breaks,
+/// which count as paragraphs this long for checkpoints too.
+const AUTO_NOTE_MAX_SENTENCES: usize = 20;
+
+/// "Explain this more simply" covers at least this many of the last sentences read, so
+/// a paragraph that has only just started isn't explained without its context.
+const SIMPLIFY_MIN_SENTENCES: usize = 3;
+/// It covers at most this many, for long paragraphs and documents without paragraph breaks.
+const SIMPLIFY_MAX_SENTENCES: usize = 8;
+
+/// Progress through an active quiz.
+pub struct QuizState {
+    pub questions: Vec<QuizQuestion>,
+    /// Index of the question currently awaiting an answer.
+    pub current: usize,
+    pub correct_answers: usize,
+    /// A one-question comprehension checkpoint of active reading, which reads on once
+    /// it is answered instead of ending with a score.
+    pub checkpoint: bool,
 }
 
 /// The state for a single, active WebSocket connection.
@@ -45,14 +136,94 @@ pub struct SessionState {
     pub user_id: Uuid,
     pub document_id: Uuid,
     pub session_id: Uuid,
-    pub chunked_document: Vec<String>,
+    /// The client-provided device identifier, if any.
+    pub device_id: Option<String>,
+    /// The furthest sentence reached on any device when the session was opened.
+    pub furthest_read_index: usize,
+    /// Where this device last listened when the session was opened, or where the
+    /// device it was handed off from stopped.
+    pub device_index: Option<usize>,
+    /// The document's sentences: all of them, or for long documents a window around
+    /// the reading position. Load sentences with `load_sentences` before using them.
+    pub chunked_document: SentenceWindow,
     pub reading_progress_index: usize,
     pub current_mode: SessionMode,
+    /// Sentences being read again slower, until reading moves past them or the user
+    /// interrupts.
+    pub slow_reread: Option<Range<usize>>,
     pub audio_buffer: Vec<u8>,
+    /// The question being recorded went over the buffer limit; the rest of its audio
+    /// is dropped and it isn't answered.
+    pub audio_buffer_overflowed: bool,
+    /// How much of `audio_buffer` the last partial transcript covered.
+    pub partial_transcript_bytes: usize,
+    /// Set while a partial transcript is being made, so they don't pile up.
+    pub partial_transcript_in_flight: bool,
     pub last_question: Option<String>,
     pub last_answer: Option<String>,
+    /// The document sentences the last answer cited, for "where does it say that?".
+    pub last_answer_sources: Vec<usize>,
+    /// The last answer, if the user stopped it before the end, until they go on with it,
+    /// ask something else, or reading moves on.
+    pub interrupted_answer: Option<InterruptedAnswer>,
+    /// The ambiguous question a clarifying question was asked about, while `Clarifying`.
+    pub pending_clarification: Option<String>,
+    /// Audio-free mode: text is sent instead of speech. Set from the client's capabilities.
+    pub audio_free: bool,
+    /// How speech is synthesized: the voice and speed from the user's preferences (until
+    /// changed with `SetVoice` or `SetSpeed`), and the audio format settled at `Init`.
+    pub speech: SpeechSettings,
+    /// Child/education mode: age-appropriate prompts and stricter moderation.
+    pub education_mode: bool,
+    /// How long spoken answers should be, from the user's preferences.
+    pub answer_verbosity: AnswerVerbosity,
+    /// The level "explain this more simply" re-explains at, from the user's preferences.
+    pub reading_level: ReadingLevel,
+    /// Narration pacing from the user's preferences: silence after each sentence
+    /// and a fade on both of its ends, in milliseconds.
+    pub sentence_gap_ms: u32,
+    pub crossfade_ms: u32,
+    /// Whether key-point notes are taken on each paragraph as it is read.
+    pub auto_notes: bool,
+    /// Active reading: paragraphs between comprehension checkpoints, from the user's
+    /// preferences. `None` reads straight through.
+    pub checkpoint_paragraphs: Option<u32>,
+    /// The sentences that open a paragraph, for auto notes, checkpoints and skipping a
+    /// paragraph. Empty if the stored sentences no longer match the document's text.
+    pub paragraph_starts: Vec<usize>,
+    /// The document's chapters, in order, for `GoToChapter`. Empty if it has no headings.
+    pub chapters: Vec<Chapter>,
+    /// The first sentence not yet covered by an auto note.
+    pub auto_notes_from: usize,
+    /// The first sentence read since the last checkpoint.
+    pub checkpoint_from: usize,
+    /// When this connection opened the session, for end-of-session stats.
+    pub opened_at: Instant,
+    /// The reading position when this connection opened the session.
+    pub opened_at_index: usize,
+    /// This connection's entry in `AppState::handoffs`. `None` outside a WebSocket.
+    pub connection_id: Option<Uuid>,
+    /// Whether to open with the user's weekly recap, if there is an unspoken one.
+    pub weekly_recap: bool,
+    /// Whether to open with a "last time we covered" recap, because the user asked
+    /// for one and the session sat untouched longer than the configured gap.
+    pub resume_recap_due: bool,
+    /// Questions answered on this connection.
+    pub questions_asked: usize,
+    /// Whether the client streams a hot-word sidechannel while reading.
+    pub hotword_enabled: bool,
+    /// Set while a sidechannel clip is being checked, so clips don't pile up.
+    pub hotword_check_in_flight: bool,
+    /// The active quiz, if the user is being quizzed.
+    pub quiz: Option<QuizState>,
+    /// Groups this connection's usage events without identifying the user. `None`
+    /// when the user opted out of analytics.
+    pub analytics_visit_id: Option<Uuid>,
     /// A token to gracefully cancel the current reading task.
     pub cancellation_token: CancellationToken,
+    /// Cancelled when the user cancels the question being processed, e.g. after
+    /// seeing it was transcribed wrong. Replaced for every question.
+    pub question_token: CancellationToken,
 }
 
 //=========================================================================================
@@ -61,35 +232,378 @@ pub struct SessionState {
 
 impl SessionState {
     /// Creates a new `SessionState` by fetching the required data from the database.
-    pub async fn new(app_state: Arc<AppState>, session_id: Uuid) -> PortResult<Self> {
+    /// `resume_at` is where another device stopped, when the session was handed from
+    /// it, or where the last connection dropped, when the client resumes the session;
+    /// reading resumes there instead of at the saved position.
+    pub async fn new(
+        app_state: Arc<AppState>,
+        session_id: Uuid,
+        device_id: Option<String>,
+        resume_at: Option<usize>,
+    ) -> PortResult<Self> {
         let session_domain = app_state.db.get_session_by_id(session_id).await?;
-        let document_domain = app_state
+        let document_id = session_domain.document_id;
+
+        // Chunks and their structure are persisted at upload time. Documents from before
+        // that are chunked once here, and older chunks get their structure recorded once,
+        // so later connections never need the original text.
+        let layout = app_state.db.get_document_layout(document_id).await?;
+        let (sentence_count, structure) = match layout.structure {
+            Some(structure) if layout.sentence_count > 0 => (layout.sentence_count, structure),
+            _ => {
+                let text = app_state.db.get_document_by_id(document_id).await?.original_text;
+                if layout.sentence_count == 0 {
+                    let sentences = chunk_into_sentences(&text);
+                    let structure = chunker::structure(&text);
+                    app_state
+                        .db
+                        .save_document_chunks(document_id, CHUNKER_VERSION, &sentences, &structure)
+                        .await?;
+                    (sentences.len(), structure)
+                } else {
+                    // Paragraph breaks and headings can only be recovered with the chunker
+                    // that split the stored chunks. Chunks from an older one keep sections of
+                    // `AUTO_NOTE_MAX_SENTENCES` each and have no chapters.
+                    let structure = if layout.chunker_version == CHUNKER_VERSION {
+                        chunker::structure(&text)
+                    } else {
+                        DocumentStructure::default()
+                    };
+                    app_state.db.save_document_structure(document_id, &structure).await?;
+                    (layout.sentence_count, structure)
+                }
+            }
+        };
+
+        let device_index = match (&device_id, resume_at) {
+            (_, Some(index)) => Some(index.min(sentence_count)),
+            (Some(device_id), None) => app_state.db.get_device_position(session_id, device_id).await?,
+            (None, None) => None,
+        };
+        let furthest_read_index = session_domain.reading_progress_index;
+        let preferences = app_state
             .db
-            .get_document_by_id(session_domain.document_id)
+            .get_user_preferences(session_domain.user_id)
             .await?;
 
-        let sentences = chunk_into_sentences(&document_domain.original_text);
+        let reading_progress_index = device_index.unwrap_or(furthest_read_index);
+
+        let idle_secs = (chrono::Utc::now() - session_domain.last_accessed_at).num_seconds();
+        let resume_recap_due = preferences.resume_recap
+            && resume_at.is_none()
+            && reading_progress_index > 0
+            && idle_secs >= app_state.config.resume_recap_min_gap_secs as i64;
 
-        Ok(Self {
+        let mut session = Self {
             user_id: session_domain.user_id,
             document_id: session_domain.document_id,
             session_id,
-            chunked_document: sentences,
-            reading_progress_index: session_domain.reading_progress_index,
+            device_id,
+            furthest_read_index,
+            device_index,
+            chunked_document: SentenceWindow::unloaded(sentence_count),
+            reading_progress_index,
             current_mode: SessionMode::Reading,
+            slow_reread: None,
             audio_buffer: Vec::new(),
+            audio_buffer_overflowed: false,
+            partial_transcript_bytes: 0,
+            partial_transcript_in_flight: false,
             last_question: None,
             last_answer: None,
+            last_answer_sources: Vec::new(),
+            interrupted_answer: None,
+            pending_clarification: None,
+            audio_free: false,
+            speech: SpeechSettings {
+                voice: preferences.voice.clone(),
+                speed: preferences.speaking_speed,
+                format: AudioFormat::default(),
+            },
+            education_mode: session_domain.education_mode,
+            answer_verbosity: preferences.answer_verbosity,
+            reading_level: preferences.reading_level,
+            sentence_gap_ms: preferences.sentence_gap_ms,
+            crossfade_ms: preferences.crossfade_ms,
+            auto_notes: preferences.auto_notes,
+            checkpoint_paragraphs: preferences.checkpoint_paragraphs,
+            paragraph_starts: structure.paragraph_starts,
+            chapters: structure.chapters,
+            auto_notes_from: reading_progress_index,
+            checkpoint_from: reading_progress_index,
+            opened_at: Instant::now(),
+            opened_at_index: reading_progress_index,
+            connection_id: None,
+            weekly_recap: preferences.weekly_recap,
+            resume_recap_due,
+            questions_asked: 0,
+            hotword_enabled: false,
+            hotword_check_in_flight: false,
+            quiz: None,
+            analytics_visit_id: (!preferences.analytics_opt_out).then(Uuid::new_v4),
             // The token is initialized here for the first reading task.
             cancellation_token: CancellationToken::new(),
-        })
+            question_token: CancellationToken::new(),
+        };
+        session
+            .load_sentences(&app_state, reading_progress_index..reading_progress_index + 1)
+            .await?;
+        Ok(session)
+    }
+
+    /// Makes sure the sentences of `range` are loaded, moving the window of a long
+    /// document to them if they aren't. Windows other sessions hold are shared.
+    /// For sessions other tasks share, use `lock_with_sentences` instead.
+    pub async fn load_sentences(&mut self, app_state: &AppState, range: Range<usize>) -> PortResult<()> {
+        let capacity = app_state.config.session_sentence_window;
+        let Some(load) = self.chunked_document.range_to_load(range, capacity) else {
+            return Ok(());
+        };
+        let sentences = match app_state.sentence_cache.get(self.document_id, &load) {
+            Some(sentences) => sentences,
+            None => {
+                let sentences = app_state
+                    .db
+                    .get_document_chunk_range(self.document_id, load.clone())
+                    .await?;
+                app_state.sentence_cache.insert(self.document_id, &load, sentences)
+            }
+        };
+        self.chunked_document.replace(load.start, sentences);
+        Ok(())
+    }
+}
+
+/// Locks the session with the sentences of `range` loaded, like `load_sentences`, but
+/// releases the lock while they are read from the database, so pause, interrupt and
+/// seek aren't held up by the read. Returns `None` if `token` was cancelled meanwhile.
+pub async fn lock_with_sentences<'a>(
+    session_state_lock: &'a Mutex<SessionState>,
+    app_state: &AppState,
+    range: Range<usize>,
+    token: &CancellationToken,
+) -> PortResult<Option<MutexGuard<'a, SessionState>>> {
+    let capacity = app_state.config.session_sentence_window;
+    // Held until installed, since the cache only keeps windows sessions hold.
+    let mut read: Option<(Range<usize>, Arc<[String]>)> = None;
+    loop {
+        let mut session = session_state_lock.lock().await;
+        if token.is_cancelled() {
+            return Ok(None);
+        }
+        let Some(load) = session.chunked_document.range_to_load(range.clone(), capacity) else {
+            return Ok(Some(session));
+        };
+        let document_id = session.document_id;
+        let sentences = read
+            .take()
+            .filter(|(read_range, _)| *read_range == load)
+            .map(|(_, sentences)| sentences)
+            .or_else(|| app_state.sentence_cache.get(document_id, &load));
+        if let Some(sentences) = sentences {
+            session.chunked_document.replace(load.start, sentences);
+            return Ok(Some(session));
+        }
+
+        // Another task may move the window while the lock is released; the check
+        // above runs again on the new one.
+        drop(session);
+        let sentences = app_state.db.get_document_chunk_range(document_id, load.clone()).await?;
+        read = Some((load.clone(), app_state.sentence_cache.insert(document_id, &load, sentences)));
+    }
+}
+
+//=========================================================================================
+// SessionState Implementation (Reconnection)
+//=========================================================================================
+
+impl SessionState {
+    /// What this connection holds in memory, to save when it closes.
+    pub fn snapshot(&self) -> SessionSnapshot {
+        SessionSnapshot {
+            session_id: self.session_id,
+            mode: self.current_mode,
+            reading_progress_index: self.reading_progress_index,
+            last_question: self.last_question.clone(),
+            last_answer: self.last_answer.clone(),
+            pending_clarification: self.pending_clarification.clone(),
+            interrupted_answer: self.interrupted_answer.clone(),
+            saved_at: chrono::Utc::now(),
+        }
+    }
+
+    /// Picks up the conversation and mode of a dropped connection. The position is
+    /// restored by passing the snapshot's to `new` as `resume_at`.
+    pub fn restore(&mut self, snapshot: SessionSnapshot) {
+        self.current_mode = snapshot.mode.resumed();
+        self.last_question = snapshot.last_question;
+        self.last_answer = snapshot.last_answer;
+        if self.current_mode == SessionMode::Clarifying {
+            self.pending_clarification = snapshot.pending_clarification;
+        }
+        self.interrupted_answer = snapshot.interrupted_answer;
     }
 }
 
-/// A helper function to split a block of text into sentences.
-fn chunk_into_sentences(text: &str) -> Vec<String> {
-    text.split(|c: char| c == '.' || c == '?' || c == '!')
-        .filter(|s| !s.trim().is_empty())
-        .map(|s| format!("{}.", s.trim()))
-        .collect()
+//=========================================================================================
+// SessionState Implementation (Mode Transitions)
+//=========================================================================================
+// The socket loop drives these through `SessionController`; the reading and hot-word
+// tasks apply their events directly, which is why the cancellation token lives here.
+
+impl SessionState {
+    /// Moves the session to the mode `event` leads to. Every transition ends the
+    /// current reading task; entering `Reading` hands out a fresh token for the next.
+    pub fn apply(&mut self, event: SessionEvent) -> Result<(), InvalidTransition> {
+        let next = self.current_mode.transition(event)?;
+        self.cancellation_token.cancel();
+        if next == SessionMode::Reading {
+            self.cancellation_token = CancellationToken::new();
+            // Once reading moves on, "go on" means the reading again.
+            self.interrupted_answer = None;
+        }
+        if next == SessionMode::ProcessingQuestion {
+            self.question_token = CancellationToken::new();
+        }
+        if event == SessionEvent::Interrupt {
+            self.audio_buffer.clear();
+            self.audio_buffer_overflowed = false;
+            self.partial_transcript_bytes = 0;
+            self.slow_reread = None;
+        }
+        // A checkpoint question is only answered from the checkpoint; leaving it any
+        // other way skips it.
+        if self.current_mode == SessionMode::Checkpoint
+            && !matches!(event, SessionEvent::Interrupt | SessionEvent::SubmitQuestion)
+        {
+            self.quiz = None;
+        }
+        // Only the user's reply keeps the ambiguous question around.
+        if self.current_mode == SessionMode::Clarifying
+            && !matches!(event, SessionEvent::Interrupt | SessionEvent::SubmitQuestion)
+        {
+            self.pending_clarification = None;
+        }
+        self.current_mode = next;
+        Ok(())
+    }
+
+    /// Where reading restarts to hear the last `sentences` sentences again. Reading has
+    /// sent up to, not including, the current position, so one back is the last one sent.
+    pub fn skip_back_index(&self, sentences: usize) -> usize {
+        self.reading_progress_index
+            .min(self.chunked_document.len())
+            .saturating_sub(sentences)
+    }
+
+    /// Where reading restarts to skip the rest of the paragraph the last sentence sent
+    /// belongs to, or `None` if no paragraph starts after it.
+    pub fn next_paragraph_index(&self) -> Option<usize> {
+        let current = self.skip_back_index(1);
+        self.paragraph_starts.iter().copied().find(|&start| start > current)
+    }
+
+    /// The most recently read sentences, for "explain this more simply": the paragraph
+    /// the last sentence sent belongs to, up to that sentence, with at least the last
+    /// `SIMPLIFY_MIN_SENTENCES` and at most the last `SIMPLIFY_MAX_SENTENCES`.
+    pub fn recent_passage(&self) -> Range<usize> {
+        let end = self.reading_progress_index.min(self.chunked_document.len());
+        let current = self.skip_back_index(1);
+        let paragraph_start = self
+            .paragraph_starts
+            .iter()
+            .copied()
+            .take_while(|&start| start <= current)
+            .last()
+            .unwrap_or(0);
+        let start = paragraph_start
+            .min(end.saturating_sub(SIMPLIFY_MIN_SENTENCES))
+            .max(end.saturating_sub(SIMPLIFY_MAX_SENTENCES));
+        start..end
+    }
+
+    /// Sets up the last `sentences` sentences to be read again slower, and returns
+    /// where reading restarts to read them.
+    pub fn reread_slower(&mut self, sentences: usize) -> usize {
+        let start = self.skip_back_index(sentences);
+        let end = self.reading_progress_index.min(self.chunked_document.len());
+        self.slow_reread = (start < end).then_some(start..end);
+        start
+    }
+
+    /// How to synthesize the sentence at the reading position: slower while it is
+    /// being read again slower, otherwise with the session's speech. A re-read that
+    /// reading has moved past is forgotten.
+    pub fn reading_speech(&mut self) -> SpeechSettings {
+        match &self.slow_reread {
+            Some(range) if range.contains(&self.reading_progress_index) => {
+                let speed = self.speech.speed.unwrap_or(1.0) * SLOWER_REREAD_FACTOR;
+                SpeechSettings {
+                    speed: Some(speed.max(MIN_SPEED)),
+                    ..self.speech.clone()
+                }
+            }
+            Some(_) => {
+                self.slow_reread = None;
+                self.speech.clone()
+            }
+            None => self.speech.clone(),
+        }
+    }
+
+    /// Whether audio for every sentence has already been sent to the client.
+    pub fn all_sentences_sent(&self) -> bool {
+        self.reading_progress_index >= self.chunked_document.len()
+    }
+
+    /// Called after reading moves past a sentence. If auto notes are on and that sentence
+    /// closed a paragraph, returns the sentences read since the last auto note so they
+    /// can be noted. Paragraphs shorter than `AUTO_NOTE_MIN_SENTENCES` (often headings)
+    /// are noted together with the next one.
+    pub fn take_auto_note_section(&mut self) -> Option<Range<usize>> {
+        let (start, end) = (self.auto_notes_from, self.reading_progress_index);
+        if !self.auto_notes || end <= start {
+            return None;
+        }
+        let document_ended = end >= self.chunked_document.len();
+        let paragraph_ended = self.paragraph_starts.binary_search(&end).is_ok();
+        let len = end - start;
+        if document_ended
+            || (paragraph_ended && len >= AUTO_NOTE_MIN_SENTENCES)
+            || len >= AUTO_NOTE_MAX_SENTENCES
+        {
+            self.auto_notes_from = end;
+            Some(start..end)
+        } else {
+            None
+        }
+    }
+
+    /// Called after reading moves past a sentence. With active reading on, returns the
+    /// sentences read since the last checkpoint once they span `checkpoint_paragraphs`
+    /// paragraphs, so reading can stop at the paragraph break for a question on them.
+    /// There is no checkpoint at the end of the document.
+    pub fn take_checkpoint_section(&mut self) -> Option<Range<usize>> {
+        let every = self.checkpoint_paragraphs? as usize;
+        let (start, end) = (self.checkpoint_from, self.reading_progress_index);
+        if end <= start || end >= self.chunked_document.len() {
+            return None;
+        }
+        let due = if self.paragraph_starts.is_empty() {
+            end - start >= every * AUTO_NOTE_MAX_SENTENCES
+        } else {
+            let paragraphs_ended = self
+                .paragraph_starts
+                .iter()
+                .filter(|&&paragraph_start| paragraph_start > start && paragraph_start <= end)
+                .count();
+            self.paragraph_starts.binary_search(&end).is_ok() && paragraphs_ended >= every
+        };
+        if due {
+            self.checkpoint_from = end;
+            Some(start..end)
+        } else {
+            None
+        }
+    }
 }
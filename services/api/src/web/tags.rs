@@ -0,0 +1,272 @@
+//! services/api/src/web/tags.rs
+//!
+//! Endpoints for user-defined document tags, tag autocomplete, and the background
+//! task that suggests tags for newly uploaded documents.
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tracing::{error, info};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::web::state::AppState;
+
+//=========================================================================================
+// Request/Response Types
+//=========================================================================================
+
+#[derive(Deserialize, ToSchema)]
+pub struct AddTagsRequest {
+    pub tags: Vec<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct TagItem {
+    pub tag: String,
+    /// True for LLM suggestions the user has not confirmed yet.
+    pub suggested: bool,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DocumentTagsResponse {
+    pub tags: Vec<TagItem>,
+}
+
+#[derive(Deserialize, IntoParams)]
+pub struct TagAutocompleteQuery {
+    /// Only return tags starting with this prefix.
+    pub prefix: Option<String>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ListTagsResponse {
+    pub tags: Vec<String>,
+}
+
+/// Normalizes user-entered tags: trimmed, lowercase, non-empty, deduplicated.
+fn normalize_tags(tags: Vec<String>) -> Vec<String> {
+    let mut normalized: Vec<String> = tags
+        .into_iter()
+        .map(|t| t.trim().to_lowercase())
+        .filter(|t| !t.is_empty() && t.len() <= 40)
+        .collect();
+    normalized.sort();
+    normalized.dedup();
+    normalized
+}
+
+/// Verifies that the document exists and belongs to the user.
+async fn check_document_owner(
+    state: &AppState,
+    document_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), (StatusCode, String)> {
+    let document = state.db.get_document_by_id(document_id).await.map_err(|e| {
+        error!("Failed to get document: {:?}", e);
+        (StatusCode::NOT_FOUND, "Document not found".to_string())
+    })?;
+    if document.user_id != user_id {
+        return Err((StatusCode::FORBIDDEN, "Access denied".to_string()));
+    }
+    Ok(())
+}
+
+//=========================================================================================
+// Background Suggestions
+//=========================================================================================
+
+/// A "fire-and-forget" background task that asks the LLM for tag suggestions for a new document.
+pub async fn suggest_and_save_tags(
+    app_state: Arc<AppState>,
+    user_id: Uuid,
+    document_id: Uuid,
+    text: String,
+) {
+    let existing = app_state
+        .db
+        .get_tags_by_user(user_id, None)
+        .await
+        .unwrap_or_default();
+
+    match app_state.notes_adapter.suggest_tags(&text, &existing).await {
+        Ok(tags) if !tags.is_empty() => {
+            if let Err(e) = app_state
+                .db
+                .add_document_tags(document_id, user_id, &tags, true)
+                .await
+            {
+                error!("Failed to save suggested tags for document {}: {:?}", document_id, e);
+            } else {
+                info!("Suggested tags {:?} for document {}.", tags, document_id);
+            }
+        }
+        Ok(_) => {}
+        Err(e) => error!("Failed to suggest tags for document {}: {:?}", document_id, e),
+    }
+}
+
+//=========================================================================================
+// Handlers
+//=========================================================================================
+
+/// GET /tags - Autocomplete the user's existing tags
+#[utoipa::path(
+    get,
+    path = "/tags",
+    params(TagAutocompleteQuery),
+    responses(
+        (status = 200, description = "Matching tags", body = ListTagsResponse),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn list_tags_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Query(query): Query<TagAutocompleteQuery>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let prefix = query.prefix.map(|p| p.trim().to_lowercase());
+    let tags = state
+        .db
+        .get_tags_by_user(user_id, prefix.as_deref())
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch tags: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch tags".to_string())
+        })?;
+
+    Ok((StatusCode::OK, Json(ListTagsResponse { tags })))
+}
+
+/// GET /documents/{document_id}/tags - List a document's tags and pending suggestions
+#[utoipa::path(
+    get,
+    path = "/documents/{document_id}/tags",
+    params(
+        ("document_id" = Uuid, Path, description = "Document ID")
+    ),
+    responses(
+        (status = 200, description = "Document tags", body = DocumentTagsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Access denied"),
+        (status = 404, description = "Document not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn get_document_tags_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Path(document_id): Path<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    check_document_owner(&state, document_id, user_id).await?;
+
+    let tags = state.db.get_document_tags(document_id).await.map_err(|e| {
+        error!("Failed to fetch document tags: {:?}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch tags".to_string())
+    })?;
+
+    let tags = tags
+        .into_iter()
+        .map(|t| TagItem {
+            tag: t.tag,
+            suggested: t.suggested,
+        })
+        .collect();
+
+    Ok((StatusCode::OK, Json(DocumentTagsResponse { tags })))
+}
+
+/// POST /documents/{document_id}/tags - Add tags (or confirm suggested ones)
+#[utoipa::path(
+    post,
+    path = "/documents/{document_id}/tags",
+    params(
+        ("document_id" = Uuid, Path, description = "Document ID")
+    ),
+    request_body = AddTagsRequest,
+    responses(
+        (status = 204, description = "Tags added"),
+        (status = 400, description = "No valid tags supplied"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Access denied"),
+        (status = 404, description = "Document not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn add_document_tags_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Path(document_id): Path<Uuid>,
+    Json(req): Json<AddTagsRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    check_document_owner(&state, document_id, user_id).await?;
+
+    let tags = normalize_tags(req.tags);
+    if tags.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "No valid tags supplied".to_string()));
+    }
+
+    state
+        .db
+        .add_document_tags(document_id, user_id, &tags, false)
+        .await
+        .map_err(|e| {
+            error!("Failed to add document tags: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to add tags".to_string())
+        })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// DELETE /documents/{document_id}/tags/{tag} - Remove a tag or dismiss a suggestion
+#[utoipa::path(
+    delete,
+    path = "/documents/{document_id}/tags/{tag}",
+    params(
+        ("document_id" = Uuid, Path, description = "Document ID"),
+        ("tag" = String, Path, description = "Tag to remove")
+    ),
+    responses(
+        (status = 204, description = "Tag removed"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Access denied"),
+        (status = 404, description = "Document not found"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn remove_document_tag_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Path((document_id, tag)): Path<(Uuid, String)>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    check_document_owner(&state, document_id, user_id).await?;
+
+    state
+        .db
+        .remove_document_tag(document_id, &tag.to_lowercase())
+        .await
+        .map_err(|e| {
+            error!("Failed to remove document tag: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to remove tag".to_string())
+        })?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,183 @@
+//! services/api/src/web/voices.rs
+//!
+//! Endpoints that let the settings UI list the available TTS voices and
+//! audition them before committing to one.
+
+use axum::{
+    extract::{Query, State},
+    http::{header, HeaderMap, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use reading_assistant_core::ports::PortError;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::error;
+use utoipa::{IntoParams, ToSchema};
+
+use crate::web::media::audio_response;
+use crate::web::state::{AppState, SessionState};
+
+/// The sentence read aloud by the voice preview endpoints.
+const PREVIEW_TEXT: &str =
+    "Hi there! This is how I will sound while reading your documents and answering your questions.";
+
+/// The speed range the TTS providers accept.
+pub const MIN_SPEED: f32 = 0.25;
+pub const MAX_SPEED: f32 = 4.0;
+
+/// Sets the session's speaking speed, clamped to the range the providers accept, and
+/// saves it as the user's preference. `rate` must be finite.
+pub async fn set_speaking_speed(
+    app_state: &Arc<AppState>,
+    session_state_lock: &Arc<Mutex<SessionState>>,
+    rate: f32,
+) {
+    let speed = Some(rate.clamp(MIN_SPEED, MAX_SPEED)).filter(|&speed| speed != 1.0);
+    let mut session = session_state_lock.lock().await;
+    session.speech.speed = speed;
+    let user_id = session.user_id;
+    drop(session);
+
+    let mut preferences = app_state
+        .db
+        .get_user_preferences(user_id)
+        .await
+        .unwrap_or_default();
+    preferences.speaking_speed = speed;
+    if let Err(e) = app_state.db.save_user_preferences(user_id, &preferences).await {
+        error!("Failed to save speaking speed preference: {:?}", e);
+    }
+}
+
+/// A preview only changes if the server's TTS provider does, so players may keep it a day.
+const PREVIEW_CACHE_CONTROL: &str = "private, max-age=86400";
+
+//=========================================================================================
+// Request/Response Types
+//=========================================================================================
+
+#[derive(Serialize, ToSchema)]
+pub struct ProviderVoices {
+    pub provider: String,
+    pub voices: Vec<String>,
+    /// The voice the server reads with by default.
+    pub default_voice: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ListVoicesResponse {
+    pub providers: Vec<ProviderVoices>,
+}
+
+#[derive(Deserialize, ToSchema, IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct VoicePreviewRequest {
+    pub voice: String,
+    /// Playback speed from 0.25 to 4.0. Defaults to 1.0.
+    pub speed: Option<f32>,
+}
+
+//=========================================================================================
+// Handlers
+//=========================================================================================
+
+/// GET /voices - List available voices per TTS provider
+#[utoipa::path(
+    get,
+    path = "/voices",
+    responses(
+        (status = 200, description = "Available voices", body = ListVoicesResponse),
+        (status = 401, description = "Unauthorized - no valid session")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn list_voices_handler(State(state): State<Arc<AppState>>) -> impl IntoResponse {
+    let tts = &state.tts_adapter;
+    Json(ListVoicesResponse {
+        providers: vec![ProviderVoices {
+            provider: tts.provider().to_string(),
+            voices: tts.available_voices(),
+            default_voice: tts.default_voice(),
+        }],
+    })
+}
+
+/// POST /voices/preview - Synthesize a short sample clip for a voice and speed
+#[utoipa::path(
+    post,
+    path = "/voices/preview",
+    request_body = VoicePreviewRequest,
+    responses(
+        (status = 200, description = "WAV sample clip", content_type = "audio/wav"),
+        (status = 400, description = "Unknown voice or speed out of range"),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn preview_voice_handler(
+    State(state): State<Arc<AppState>>,
+    Json(req): Json<VoicePreviewRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let audio = synthesize_preview(&state, &req).await?;
+    Ok(([(header::CONTENT_TYPE, "audio/wav")], audio))
+}
+
+/// GET /voices/preview - The same sample clip, cacheable and seekable by media players
+#[utoipa::path(
+    get,
+    path = "/voices/preview",
+    params(VoicePreviewRequest),
+    responses(
+        (status = 200, description = "WAV sample clip", content_type = "audio/wav"),
+        (status = 206, description = "The requested byte range of the clip", content_type = "audio/wav"),
+        (status = 304, description = "The clip hasn't changed since the ETag in If-None-Match"),
+        (status = 400, description = "Unknown voice or speed out of range"),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 416, description = "The requested range lies outside the clip"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn get_voice_preview_handler(
+    State(state): State<Arc<AppState>>,
+    Query(req): Query<VoicePreviewRequest>,
+    headers: HeaderMap,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let audio = synthesize_preview(&state, &req).await?;
+    Ok(audio_response(&headers, audio, "audio/wav", PREVIEW_CACHE_CONTROL))
+}
+
+/// Reads the preview sentence in the requested voice and speed.
+async fn synthesize_preview(
+    state: &AppState,
+    req: &VoicePreviewRequest,
+) -> Result<Vec<u8>, (StatusCode, String)> {
+    let speed = req.speed.unwrap_or(1.0);
+    if !(MIN_SPEED..=MAX_SPEED).contains(&speed) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            format!("Speed must be between {} and {}", MIN_SPEED, MAX_SPEED),
+        ));
+    }
+
+    state
+        .tts_adapter
+        .generate_audio_with_voice(PREVIEW_TEXT, &req.voice, speed)
+        .await
+        .map_err(|e| match e {
+            PortError::NotFound(msg) => (StatusCode::BAD_REQUEST, msg),
+            e => {
+                error!("Failed to generate voice preview: {:?}", e);
+                (StatusCode::INTERNAL_SERVER_ERROR, "Failed to generate preview".to_string())
+            }
+        })
+}
@@ -0,0 +1,504 @@
+//! services/api/src/web/workspaces.rs
+//!
+//! Endpoints for class workspaces: a teacher creates a workspace and uploads
+//! documents to it, students join by code and start their own sessions on those
+//! documents, and the teacher reviews the questions and notes per document.
+
+use axum::{
+    extract::{Multipart, Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Extension, Json,
+};
+use reading_assistant_core::domain::{Workspace, WorkspaceDocument, WorkspaceRole};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::sync::Arc;
+use tracing::error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::web::{
+    middleware::TenantId, rest::check_document_length, retrieval::index_document, state::AppState,
+};
+
+//=========================================================================================
+// Request/Response Types
+//=========================================================================================
+
+#[derive(Deserialize, ToSchema)]
+pub struct CreateWorkspaceRequest {
+    pub name: String,
+}
+
+#[derive(Deserialize, ToSchema)]
+pub struct JoinWorkspaceRequest {
+    pub join_code: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct WorkspaceItem {
+    pub workspace_id: Uuid,
+    pub name: String,
+    /// Either `teacher` or `student`.
+    pub role: String,
+    /// Only shown to teachers, who share it with their students.
+    pub join_code: Option<String>,
+    pub created_at: String, // ISO 8601 timestamp
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ListWorkspacesResponse {
+    pub workspaces: Vec<WorkspaceItem>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct WorkspaceDocumentItem {
+    pub document_id: Uuid,
+    pub title: String,
+    pub added_at: String, // ISO 8601 timestamp
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ListWorkspaceDocumentsResponse {
+    pub documents: Vec<WorkspaceDocumentItem>,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct WorkspaceSessionResponse {
+    pub session_id: Uuid,
+    pub document_id: Uuid,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct ActivityQuestion {
+    pub session_id: Uuid,
+    pub question: String,
+    pub answer: String,
+}
+
+#[derive(Serialize, ToSchema)]
+pub struct DocumentActivityResponse {
+    /// The number of sessions with at least one question asked.
+    pub active_sessions: usize,
+    pub questions: Vec<ActivityQuestion>,
+    pub notes: Vec<String>,
+}
+
+fn workspace_item(workspace: Workspace, role: WorkspaceRole) -> WorkspaceItem {
+    WorkspaceItem {
+        workspace_id: workspace.id,
+        name: workspace.name,
+        role: match role {
+            WorkspaceRole::Teacher => "teacher",
+            WorkspaceRole::Student => "student",
+        }
+        .to_string(),
+        join_code: (role == WorkspaceRole::Teacher).then_some(workspace.join_code),
+        created_at: workspace.created_at.to_rfc3339(),
+    }
+}
+
+fn document_item(document: WorkspaceDocument) -> WorkspaceDocumentItem {
+    WorkspaceDocumentItem {
+        document_id: document.document_id,
+        title: document.title,
+        added_at: document.added_at.to_rfc3339(),
+    }
+}
+
+/// Returns the user's role in the workspace, or 403 if they are not a member.
+async fn require_member(
+    state: &AppState,
+    workspace_id: Uuid,
+    user_id: Uuid,
+) -> Result<WorkspaceRole, (StatusCode, String)> {
+    state
+        .db
+        .get_workspace_role(workspace_id, user_id)
+        .await
+        .map_err(|e| {
+            error!("Failed to check workspace membership: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to check membership".to_string())
+        })?
+        .ok_or((StatusCode::FORBIDDEN, "Not a member of this workspace".to_string()))
+}
+
+async fn require_teacher(
+    state: &AppState,
+    workspace_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), (StatusCode, String)> {
+    match require_member(state, workspace_id, user_id).await? {
+        WorkspaceRole::Teacher => Ok(()),
+        WorkspaceRole::Student => Err((
+            StatusCode::FORBIDDEN,
+            "Only teachers can do this".to_string(),
+        )),
+    }
+}
+
+/// Verifies that the document has been shared into the workspace.
+async fn require_workspace_document(
+    state: &AppState,
+    workspace_id: Uuid,
+    document_id: Uuid,
+) -> Result<(), (StatusCode, String)> {
+    state
+        .db
+        .get_workspace_document(workspace_id, document_id)
+        .await
+        .map(|_| ())
+        .map_err(|e| {
+            error!("Failed to get workspace document: {:?}", e);
+            (StatusCode::NOT_FOUND, "Document not found in workspace".to_string())
+        })
+}
+
+//=========================================================================================
+// Handlers
+//=========================================================================================
+
+/// POST /workspaces - Create a class workspace as its teacher
+#[utoipa::path(
+    post,
+    path = "/workspaces",
+    request_body = CreateWorkspaceRequest,
+    responses(
+        (status = 201, description = "Workspace created", body = WorkspaceItem),
+        (status = 400, description = "Missing name"),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn create_workspace_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Json(req): Json<CreateWorkspaceRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let name = req.name.trim();
+    if name.is_empty() {
+        return Err((StatusCode::BAD_REQUEST, "Workspace name is required".to_string()));
+    }
+
+    let join_code = Uuid::new_v4().simple().to_string()[..8].to_uppercase();
+    let workspace = state
+        .db
+        .create_workspace(user_id, name, &join_code)
+        .await
+        .map_err(|e| {
+            error!("Failed to create workspace: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to create workspace".to_string())
+        })?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(workspace_item(workspace, WorkspaceRole::Teacher)),
+    ))
+}
+
+/// GET /workspaces - List the workspaces the user belongs to
+#[utoipa::path(
+    get,
+    path = "/workspaces",
+    responses(
+        (status = 200, description = "Workspaces retrieved successfully", body = ListWorkspacesResponse),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn list_workspaces_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let workspaces = state.db.get_workspaces_for_user(user_id).await.map_err(|e| {
+        error!("Failed to fetch workspaces: {:?}", e);
+        (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch workspaces".to_string())
+    })?;
+
+    let workspaces = workspaces
+        .into_iter()
+        .map(|(workspace, role)| workspace_item(workspace, role))
+        .collect();
+
+    Ok((StatusCode::OK, Json(ListWorkspacesResponse { workspaces })))
+}
+
+/// POST /workspaces/join - Join a workspace as a student using its join code
+#[utoipa::path(
+    post,
+    path = "/workspaces/join",
+    request_body = JoinWorkspaceRequest,
+    responses(
+        (status = 200, description = "Joined workspace", body = WorkspaceItem),
+        (status = 401, description = "Unauthorized - no valid session"),
+        (status = 404, description = "No workspace with that code"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn join_workspace_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Extension(TenantId(tenant_id)): Extension<TenantId>,
+    Json(req): Json<JoinWorkspaceRequest>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    let join_code = req.join_code.trim().to_uppercase();
+    let workspace = state
+        .db
+        .get_workspace_by_join_code(tenant_id, &join_code)
+        .await
+        .map_err(|e| {
+            error!("Failed to find workspace by join code: {:?}", e);
+            (StatusCode::NOT_FOUND, "No workspace with that code".to_string())
+        })?;
+
+    state
+        .db
+        .add_workspace_member(workspace.id, user_id, WorkspaceRole::Student)
+        .await
+        .map_err(|e| {
+            error!("Failed to join workspace: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to join workspace".to_string())
+        })?;
+
+    // Re-read the role: a teacher entering their own code stays a teacher.
+    let role = require_member(&state, workspace.id, user_id).await?;
+    Ok((StatusCode::OK, Json(workspace_item(workspace, role))))
+}
+
+/// POST /workspaces/{workspace_id}/documents - Upload a document to the workspace (teachers only)
+#[utoipa::path(
+    post,
+    path = "/workspaces/{workspace_id}/documents",
+    params(
+        ("workspace_id" = Uuid, Path, description = "Workspace ID")
+    ),
+    request_body(content_type = "multipart/form-data", description = "The document to share with the class."),
+    responses(
+        (status = 201, description = "Document shared", body = WorkspaceDocumentItem),
+        (status = 400, description = "Bad request (e.g., missing file)"),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Only teachers can upload"),
+        (status = 413, description = "Document longer than the configured maximum"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn upload_workspace_document_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Path(workspace_id): Path<Uuid>,
+    mut multipart: Multipart,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    require_teacher(&state, workspace_id, user_id).await?;
+
+    let field = multipart
+        .next_field()
+        .await
+        .map_err(|e| {
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                format!("Failed to read multipart data: {}", e),
+            )
+        })?
+        .ok_or((
+            StatusCode::BAD_REQUEST,
+            "Multipart form must include a file".to_string(),
+        ))?;
+
+    let title = field.file_name().unwrap_or("untitled.txt").to_string();
+    let data = field.bytes().await.map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            format!("Failed to read file bytes: {}", e),
+        )
+    })?;
+    let text = String::from_utf8(data.to_vec()).map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            format!("Uploaded file is not valid UTF-8 text: {}", e),
+        )
+    })?;
+    check_document_length(&state, &text)?;
+
+    let db = &state.db;
+    let result = async {
+        let doc = db.create_document(user_id, &title, &text).await?;
+        db.add_workspace_document(workspace_id, doc.id, &title).await
+    }
+    .await;
+
+    match result {
+        Ok(document) => {
+            tokio::spawn(index_document(state.clone(), document.document_id));
+            Ok((StatusCode::CREATED, Json(document_item(document))))
+        }
+        Err(e) => {
+            error!("Failed to share workspace document: {:?}", e);
+            Err((
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to share document".to_string(),
+            ))
+        }
+    }
+}
+
+/// GET /workspaces/{workspace_id}/documents - List the workspace's documents
+#[utoipa::path(
+    get,
+    path = "/workspaces/{workspace_id}/documents",
+    params(
+        ("workspace_id" = Uuid, Path, description = "Workspace ID")
+    ),
+    responses(
+        (status = 200, description = "Documents retrieved successfully", body = ListWorkspaceDocumentsResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not a member of this workspace"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn list_workspace_documents_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Path(workspace_id): Path<Uuid>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    require_member(&state, workspace_id, user_id).await?;
+
+    let documents = state
+        .db
+        .get_workspace_documents(workspace_id)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch workspace documents: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch documents".to_string())
+        })?;
+
+    let documents = documents.into_iter().map(document_item).collect();
+    Ok((StatusCode::OK, Json(ListWorkspaceDocumentsResponse { documents })))
+}
+
+/// POST /workspaces/{workspace_id}/documents/{document_id}/sessions - Start your own session on a workspace document
+#[utoipa::path(
+    post,
+    path = "/workspaces/{workspace_id}/documents/{document_id}/sessions",
+    params(
+        ("workspace_id" = Uuid, Path, description = "Workspace ID"),
+        ("document_id" = Uuid, Path, description = "Document ID")
+    ),
+    responses(
+        (status = 201, description = "Session created", body = WorkspaceSessionResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Not a member of this workspace"),
+        (status = 404, description = "Document not found in workspace"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn create_workspace_session_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Path((workspace_id, document_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    require_member(&state, workspace_id, user_id).await?;
+    require_workspace_document(&state, workspace_id, document_id).await?;
+
+    let session = state
+        .db
+        .create_session(user_id, document_id, false)
+        .await
+        .map_err(|e| {
+            error!("Failed to create workspace session: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to create session".to_string())
+        })?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(WorkspaceSessionResponse {
+            session_id: session.id,
+            document_id,
+        }),
+    ))
+}
+
+/// GET /workspaces/{workspace_id}/documents/{document_id}/activity - Questions and notes across the class (teachers only)
+#[utoipa::path(
+    get,
+    path = "/workspaces/{workspace_id}/documents/{document_id}/activity",
+    params(
+        ("workspace_id" = Uuid, Path, description = "Workspace ID"),
+        ("document_id" = Uuid, Path, description = "Document ID")
+    ),
+    responses(
+        (status = 200, description = "Activity retrieved successfully", body = DocumentActivityResponse),
+        (status = 401, description = "Unauthorized"),
+        (status = 403, description = "Only teachers can view activity"),
+        (status = 404, description = "Document not found in workspace"),
+        (status = 500, description = "Internal server error")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
+pub async fn document_activity_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(user_id): Extension<Uuid>,
+    Path((workspace_id, document_id)): Path<(Uuid, Uuid)>,
+) -> Result<impl IntoResponse, (StatusCode, String)> {
+    require_teacher(&state, workspace_id, user_id).await?;
+    require_workspace_document(&state, workspace_id, document_id).await?;
+
+    let qa_pairs = state
+        .db
+        .get_qa_pairs_for_document(document_id)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch document questions: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch activity".to_string())
+        })?;
+    let notes = state
+        .db
+        .get_notes_for_document(document_id)
+        .await
+        .map_err(|e| {
+            error!("Failed to fetch document notes: {:?}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to fetch activity".to_string())
+        })?;
+
+    let active_sessions = qa_pairs
+        .iter()
+        .map(|q| q.session_id)
+        .collect::<HashSet<_>>()
+        .len();
+
+    Ok((
+        StatusCode::OK,
+        Json(DocumentActivityResponse {
+            active_sessions,
+            questions: qa_pairs
+                .into_iter()
+                .map(|q| ActivityQuestion {
+                    session_id: q.session_id,
+                    question: q.question_text,
+                    answer: q.answer_text,
+                })
+                .collect(),
+            notes: notes.into_iter().map(|n| n.generated_note_text).collect(),
+        }),
+    ))
+}
@@ -4,29 +4,63 @@
 //! It manages the session's state machine and delegates tasks.
 
 use crate::{
+    audio::{MAX_CROSSFADE_MS, MAX_SENTENCE_GAP_MS},
     web::{
-        protocol::{ClientMessage, ServerMessage},
-        qa_task::{qa_process, QaOutcome},
-        reading_task::reading_process,
+        analytics::track,
+        api_keys::app_state_for_user,
+        goals::record_listening_time,
+        handoff::{Registration, HANDOFF_TOKEN_TTL},
+        hotword::{check_hotword_clip, MAX_SIDECHANNEL_CLIP_BYTES},
+        partial_transcript::send_partial_transcript,
+        protocol::{AudioFormatSpec, AudioKind, ChapterEntry, ClientCapabilities, ClientMessage, ErrorCode, ServerMessage, SessionStats},
+        qa_task::{generate_and_save_summary_note, qa_process, speak, speak_resume_recap, QaOutcome},
+        quiz_task::{quiz_answer_process, start_quiz, QuizOutcome},
+        ratings,
+        recap_task::speak_weekly_recap,
+        retrieval::index_document,
+        session_controller::SessionController,
         state::{AppState, SessionMode, SessionState},
+        voices::set_speaking_speed,
+        ws_writer::{spawn_writer, WsSender},
     },
 };
 use axum::{
     extract::{
-        ws::{Message, WebSocket},
+        ws::{close_code, CloseFrame, Message, WebSocket},
         State, WebSocketUpgrade,
     },
     response::Response,
     Extension,
 };
-use futures::{stream::{SplitSink, StreamExt}, SinkExt};
-use std::sync::Arc;
-use tokio::{sync::Mutex, task::JoinHandle};
-use tokio_util::sync::CancellationToken;
+use futures::stream::{SplitStream, StreamExt};
+use reading_assistant_core::{
+    audio_format,
+    domain::{AudioFormat, SessionSnapshot, SpeechSettings, UsageEventKind},
+    ports::{PortError, PortResult},
+};
+use std::{sync::Arc, time::Duration};
+use tokio::{
+    sync::{mpsc, watch, Mutex},
+    time::{interval_at, Instant, MissedTickBehavior},
+};
 use tracing::{error, info, warn};
 use uuid::Uuid;
 
 /// The handler for upgrading HTTP requests to WebSocket connections.
+#[utoipa::path(
+    get,
+    path = "/ws",
+    description = "Upgrades to the WebSocket used for reading sessions. Text frames carry JSON \
+        messages (see `web::protocol`): the client starts with an `init` message naming the \
+        session, and audio is exchanged as Binary frames.",
+    responses(
+        (status = 101, description = "Switching to the WebSocket protocol"),
+        (status = 401, description = "Unauthorized - no valid session")
+    ),
+    security(
+        ("session_cookie" = [])
+    )
+)]
 pub async fn ws_handler(
     ws: WebSocketUpgrade,
     State(app_state): State<Arc<AppState>>,
@@ -38,266 +72,958 @@ pub async fn ws_handler(
 async fn handle_socket(socket: WebSocket, app_state: Arc<AppState>, user_id: Uuid) {  // ✅ Add user_id param
     info!("New WebSocket connection established for user: {}", user_id);
 
-    // The sender is wrapped in an Arc<Mutex<>> to allow for shared mutable access across tasks.
+    // All writes go through a dedicated writer task; tasks share cloneable handles to it.
     let (sender, mut receiver) = socket.split();
-    let ws_sender = Arc::new(Mutex::new(sender));
+    let ws_sender = spawn_writer(sender);
 
-    let session_state_lock: Arc<Mutex<SessionState>>;
+    // Users who brought their own OpenAI key have this session billed to it.
+    let app_state = match app_state_for_user(&app_state, user_id).await {
+        Ok(app_state) => app_state,
+        Err(e) => {
+            error!("Failed to load user API key: {:?}", e);
+            close_with_error(
+                &ws_sender,
+                ErrorCode::ApiKeyUnusable,
+                "Your saved OpenAI API key could not be used. Please save it again.",
+            )
+            .await;
+            return;
+        }
+    };
 
     // --- 1. Initialization Phase ---
-    if let Some(Ok(Message::Text(init_json))) = receiver.next().await {
-        match serde_json::from_str::<ClientMessage>(&init_json) {
-            Ok(ClientMessage::Init { session_id }) => {
-                info!("Initializing session with ID: {}", session_id);
-                
-                // ✅ Validate that the session belongs to this user
-                match app_state.db.get_session_by_id(session_id).await {
-                    Ok(session) => {
-                        if session.user_id != user_id {
-                            error!("Session {} does not belong to user {}", session_id, user_id);
-                            let err_msg = ServerMessage::Error {
-                                message: "Unauthorized: Session does not belong to this user.".to_string(),
-                            };
-                            let err_json = serde_json::to_string(&err_msg).unwrap();
-                            let _ = ws_sender.lock().await.send(Message::Text(err_json.into())).await;
-                            return;
-                        }
-                    }
-                    Err(e) => {
-                        error!("Failed to get session: {:?}", e);
-                        let err_msg = ServerMessage::Error {
-                            message: "Failed to load session data.".to_string(),
-                        };
-                        let err_json = serde_json::to_string(&err_msg).unwrap();
-                        let _ = ws_sender.lock().await.send(Message::Text(err_json.into())).await;
-                        return;
-                    }
-                }
-                
-                match SessionState::new(app_state.clone(), session_id).await {
-                    Ok(state) => {
-                        session_state_lock = Arc::new(Mutex::new(state));
-                        let init_msg = ServerMessage::SessionInitialized { session_id };
-                        let init_json = serde_json::to_string(&init_msg).unwrap();
-                        if ws_sender.lock().await.send(Message::Text(init_json.into())).await.is_err() {
-                            error!("Failed to send session initialized message.");
-                            return;
-                        }
-                        let welcome_text = "Hi there! I am looking forward to discussing the information you have provided today! If at any point you have a question, please feel free to interrupt me, or if you need to pause our session, just click pause! I will now begin reading the information!";
-                
-                        match app_state.tts_adapter.generate_audio(welcome_text).await {
-                            Ok(welcome_audio) => {
-                                if ws_sender.lock().await.send(Message::Binary(welcome_audio.into())).await.is_err() {
-                                    error!("Failed to send welcome audio.");
-                                    return;
-                                }
-                            }
-                            Err(e) => {
-                                error!("Failed to generate welcome audio: {:?}", e);
-                                return;
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        error!("Failed to initialize session state: {:?}", e);
-                        let err_msg = ServerMessage::Error {
-                            message: "Failed to load session data.".to_string(),
-                        };
-                        let err_json = serde_json::to_string(&err_msg).unwrap();
-                        let _ = ws_sender.lock().await.send(Message::Text(err_json.into())).await;
-                        return;
-                    }
-                }
-            }
-            _ => {
-                error!("First message was not a valid Init message.");
+    let mut request = match read_init(&mut receiver).await {
+        Ok(request) => request,
+        Err((code, message)) => {
+            close_with_error(&ws_sender, code, message).await;
+            return;
+        }
+    };
+    let (mut session_state_lock, mut registration) =
+        match initialize_session(&request, &app_state, &ws_sender, user_id).await {
+            Ok(initialized) => initialized,
+            Err((code, message)) => {
+                close_with_error(&ws_sender, code, message).await;
                 return;
             }
-        }
-    } else {
-        error!("Client disconnected before sending Init message.");
-        return;
+        };
+
+    // Frames are read by their own task, so a cancel can arrive while a question is processed.
+    // The reader acts on whichever session is current, which changes when the client switches.
+    let (current_session_tx, current_session_rx) = watch::channel(session_state_lock.clone());
+    let mut incoming = spawn_reader(receiver, current_session_rx);
+
+    // --- 2. Main Message Loop, once for each session opened on the connection ---
+    loop {
+        let exit = run_session(&app_state, &session_state_lock, &registration, &ws_sender, &mut incoming, user_id).await;
+        close_session(&app_state, &session_state_lock, &registration).await;
+        let SessionExit::Switch(session_id) = exit else {
+            break;
+        };
+
+        // The session just left was saved, so switching back to it picks up where it stood.
+        info!("Switching to session {}.", session_id);
+        request = InitRequest { session_id, handoff_token: None, resume: true, ..request };
+        (session_state_lock, registration) = match initialize_session(&request, &app_state, &ws_sender, user_id).await {
+            Ok(initialized) => initialized,
+            Err((code, message)) => {
+                close_with_error(&ws_sender, code, message).await;
+                break;
+            }
+        };
+        current_session_tx.send_replace(session_state_lock.clone());
+    }
+    info!("WebSocket connection closed.");
+}
+
+/// What the client asked for in its `Init` message.
+struct InitRequest {
+    session_id: Uuid,
+    device_id: Option<String>,
+    capabilities: ClientCapabilities,
+    audio_format: Option<AudioFormatSpec>,
+    handoff_token: Option<String>,
+    resume: bool,
+}
+
+/// Why a session's message loop stopped.
+enum SessionExit {
+    /// The connection is done: the client left, ended the session or handed it off.
+    Closed,
+    /// The client asked to switch to another of its sessions on the same connection.
+    Switch(Uuid),
+}
+
+/// Runs an initialized session until the client leaves it: any recaps due, then reading
+/// and the client's messages.
+async fn run_session(
+    app_state: &Arc<AppState>,
+    session_state_lock: &Arc<Mutex<SessionState>>,
+    registration: &Registration,
+    ws_sender: &WsSender,
+    incoming: &mut mpsc::Receiver<Message>,
+    user_id: Uuid,
+) -> SessionExit {
+    // A new week's recap comes first, since it spans more than this document.
+    if let Err(e) = speak_weekly_recap(app_state, session_state_lock, ws_sender).await {
+        warn!("Failed to speak weekly recap: {:?}", e);
     }
 
-    // --- 2. Main Message Loop ---
-    // Rest of the function stays exactly the same...
-    let mut reading_task_handle: Option<JoinHandle<()>> = {
+    // Returning after a long break: remind the user where they were before reading resumes.
+    let (session_id, resume_recap_due) = {
         let session = session_state_lock.lock().await;
-        let task = {
-            let app_state = app_state.clone();
-            let session_state_lock = session_state_lock.clone();
-            let ws_sender = ws_sender.clone();
-            let token = session.cancellation_token.clone();
-            tokio::spawn(async move {
-                if let Err(e) = reading_process(app_state, session_state_lock, ws_sender, token).await {
-                    error!("Reading process failed: {:?}", e);
-                }
-            })
-        };
-        Some(task)
+        (session.session_id, session.resume_recap_due)
     };
+    if resume_recap_due {
+        if let Err(e) = speak_resume_recap(app_state, session_state_lock, ws_sender).await {
+            warn!("Failed to speak resume recap: {:?}", e);
+        }
+    }
 
-    loop {
-        if let Some(Ok(msg)) = receiver.next().await {
+    let mut controller =
+        SessionController::new(app_state.clone(), session_state_lock.clone(), ws_sender.clone());
+    // A resumed session may be paused or waiting on the user, and stays that way.
+    if session_state_lock.lock().await.current_mode == SessionMode::Reading {
+        controller.start_reading().await;
+    }
+
+    // The session's in-memory state is also saved every few seconds, not only when the
+    // connection closes, so a crash loses little of it.
+    let autosave_secs = app_state.config.session_autosave_interval_secs;
+    let autosave_period = Duration::from_secs(autosave_secs.max(1));
+    let mut autosave = interval_at(Instant::now() + autosave_period, autosave_period);
+    autosave.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    let exit = loop {
+        let next = tokio::select! {
+            next = incoming.recv() => next,
+            _ = autosave.tick(), if autosave_secs > 0 => {
+                autosave_snapshot(app_state, session_state_lock).await;
+                continue;
+            }
+            _ = registration.handed_off.cancelled() => {
+                info!("Session handed off to another device.");
+                if ws_sender.send(&ServerMessage::SessionHandedOff).await.is_err() {
+                    warn!("Failed to send SessionHandedOff message. Client may have disconnected.");
+                }
+                if ws_sender.close(None).await.is_err() {
+                    warn!("Failed to send close frame.");
+                }
+                break SessionExit::Closed;
+            }
+        };
+        if let Some(msg) = next {
             match msg {
                 Message::Text(text) => {
-                    handle_text_message(
+                    // Switching replaces the session this loop runs, so it is handled here.
+                    if let Ok(ClientMessage::SwitchSession { session_id: target_id }) =
+                        serde_json::from_str::<ClientMessage>(&text)
+                    {
+                        if target_id == session_id {
+                            continue;
+                        }
+                        match check_session_owner(app_state, target_id, user_id).await {
+                            Ok(()) => break SessionExit::Switch(target_id),
+                            Err((code, message)) => {
+                                send_error(ws_sender, code, message).await;
+                                continue;
+                            }
+                        }
+                    }
+                    let keep_open = handle_text_message(
                         text.to_string(),
-                        &app_state,
-                        &session_state_lock,
-                        &ws_sender,
-                        &mut reading_task_handle,
+                        app_state,
+                        session_state_lock,
+                        ws_sender,
+                        &mut controller,
                     )
                     .await;
+                    if !keep_open {
+                        break SessionExit::Closed;
+                    }
                 }
                 Message::Binary(data) => {
                     let mut session = session_state_lock.lock().await;
-                    if session.current_mode == SessionMode::InterruptedListening {
+                    if session.current_mode.accepts_question_audio() {
+                        if session.audio_buffer_overflowed {
+                            continue;
+                        }
+                        if session.audio_buffer.len() + data.len() > app_state.config.max_question_audio_bytes {
+                            warn!("Question audio went over {} bytes, dropping it.", app_state.config.max_question_audio_bytes);
+                            session.audio_buffer = Vec::new();
+                            session.audio_buffer_overflowed = true;
+                            drop(session);
+                            send_error(ws_sender, ErrorCode::QuestionTooLong, "That question was too long to hear. Please ask it again, more briefly.").await;
+                            continue;
+                        }
                         session.audio_buffer.extend_from_slice(&data);
+                        let step = app_state.config.partial_transcript_step_bytes;
+                        if step > 0
+                            && !session.partial_transcript_in_flight
+                            && session.audio_buffer.len() >= session.partial_transcript_bytes + step
+                        {
+                            session.partial_transcript_in_flight = true;
+                            session.partial_transcript_bytes = session.audio_buffer.len();
+                            tokio::spawn(send_partial_transcript(
+                                app_state.clone(),
+                                session_state_lock.clone(),
+                                ws_sender.clone(),
+                                session.audio_buffer.clone(),
+                            ));
+                        }
+                    } else if session.current_mode.accepts_hotword_clips()
+                        && session.hotword_enabled
+                        && !session.hotword_check_in_flight
+                        && data.len() <= MAX_SIDECHANNEL_CLIP_BYTES
+                    {
+                        // While reading, Binary frames are hot-word sidechannel clips.
+                        // Clips arriving while one is still being checked are dropped.
+                        session.hotword_check_in_flight = true;
+                        tokio::spawn(check_hotword_clip(
+                            app_state.clone(),
+                            session_state_lock.clone(),
+                            ws_sender.clone(),
+                            data.to_vec(),
+                        ));
                     }
                 }
                 Message::Close(_) => {
                     info!("Client sent close message.");
-                    break;
+                    break SessionExit::Closed;
                 }
                 _ => {}
             }
         } else {
             info!("Client disconnected.");
-            break;
+            break SessionExit::Closed;
+        }
+    };
+    controller.shutdown();
+    exit
+}
+
+/// Lets go of a session the connection is done with: keeps a snapshot to resume it from,
+/// releases it for other devices and records the time listened.
+async fn close_session(
+    app_state: &Arc<AppState>,
+    session_state_lock: &Arc<Mutex<SessionState>>,
+    registration: &Registration,
+) {
+    let (session_id, user_id, listened_secs, snapshot) = {
+        let session = session_state_lock.lock().await;
+        // Nothing is kept for a session the user ended or took to another device.
+        let snapshot = (session.current_mode != SessionMode::Ended && !registration.handed_off.is_cancelled())
+            .then(|| session.snapshot());
+        (session.session_id, session.user_id, session.opened_at.elapsed().as_secs(), snapshot)
+    };
+    match snapshot {
+        Some(snapshot) => {
+            if let Err(e) = app_state.db.save_session_snapshot(&snapshot).await {
+                warn!("Failed to save snapshot of session {}: {:?}", session_id, e);
+            }
+        }
+        // Throw away the last autosave, so it isn't resumed from later.
+        None => {
+            if let Err(e) = app_state.db.take_session_snapshot(session_id).await {
+                warn!("Failed to discard snapshot of session {}: {:?}", session_id, e);
+            }
         }
     }
+    app_state.handoffs.unregister(session_id, registration.connection_id);
+    record_listening_time(app_state, user_id, listened_secs).await;
+}
 
-    // --- 3. Cleanup ---
-    if let Some(handle) = reading_task_handle {
-        handle.abort();
+/// Saves what the session holds in memory while its connection is still open, so a
+/// client reconnecting after a crash resumes from at most one autosave interval ago.
+async fn autosave_snapshot(app_state: &Arc<AppState>, session_state_lock: &Arc<Mutex<SessionState>>) {
+    let snapshot = {
+        let session = session_state_lock.lock().await;
+        if session.current_mode == SessionMode::Ended {
+            return;
+        }
+        session.snapshot()
+    };
+    if let Err(e) = app_state.db.save_session_snapshot(&snapshot).await {
+        warn!("Failed to autosave snapshot of session {}: {:?}", snapshot.session_id, e);
+    }
+}
+
+/// How long after a connection drops its session can still be resumed.
+const SESSION_RESUME_WINDOW_MINUTES: i64 = 30;
+
+/// How many incoming frames can wait while the socket loop is busy.
+const INCOMING_QUEUE_LEN: usize = 32;
+
+/// Spawns the task that reads frames from the client and queues them for the socket
+/// loop. `CancelQuestion` and `CancelAnswer` are acted on immediately instead, since
+/// the socket loop is busy while the question they cancel is processed. Likewise an
+/// `InterruptStarted` during an answer stops it at once, and is then queued so the
+/// user's next question is listened for. These act on the session `current_session` holds
+/// when they arrive. The queue closes when the client disconnects.
+fn spawn_reader(
+    mut receiver: SplitStream<WebSocket>,
+    current_session: watch::Receiver<Arc<Mutex<SessionState>>>,
+) -> mpsc::Receiver<Message> {
+    let (incoming_tx, incoming_rx) = mpsc::channel::<Message>(INCOMING_QUEUE_LEN);
+
+    tokio::spawn(async move {
+        while let Some(Ok(msg)) = receiver.next().await {
+            if let Message::Text(text) = &msg {
+                let session_state_lock = current_session.borrow().clone();
+                if let Ok(ClientMessage::CancelQuestion | ClientMessage::CancelAnswer) =
+                    serde_json::from_str::<ClientMessage>(text)
+                {
+                    info!("Cancel message received, cancelling the current question.");
+                    session_state_lock.lock().await.question_token.cancel();
+                    continue;
+                }
+                if let Ok(ClientMessage::InterruptStarted) = serde_json::from_str::<ClientMessage>(text) {
+                    let session = session_state_lock.lock().await;
+                    if session.current_mode == SessionMode::ProcessingQuestion {
+                        info!("User interrupted the answer, stopping it.");
+                        session.question_token.cancel();
+                    }
+                }
+            }
+            if incoming_tx.send(msg).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    incoming_rx
+}
+
+/// Waits for the client's `Init` message. On failure, returns the error to close with.
+async fn read_init(receiver: &mut SplitStream<WebSocket>) -> Result<InitRequest, (ErrorCode, &'static str)> {
+    let Some(Ok(Message::Text(init_json))) = receiver.next().await else {
+        error!("Client disconnected before sending Init message.");
+        return Err((ErrorCode::InitRequired, "Expected an init message."));
+    };
+    let Ok(ClientMessage::Init { session_id, device_id, capabilities, audio_format, handoff_token, resume }) =
+        serde_json::from_str::<ClientMessage>(&init_json)
+    else {
+        error!("First message was not a valid Init message.");
+        return Err((ErrorCode::InitRequired, "Expected an init message."));
+    };
+    Ok(InitRequest { session_id, device_id, capabilities, audio_format, handoff_token, resume })
+}
+
+/// Checks the session exists and belongs to the user. On failure, returns the error to report.
+async fn check_session_owner(
+    app_state: &Arc<AppState>,
+    session_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), (ErrorCode, &'static str)> {
+    // ✅ Validate that the session belongs to this user
+    match app_state.db.get_session_by_id(session_id).await {
+        Ok(session) if session.user_id != user_id => {
+            error!("Session {} does not belong to user {}", session_id, user_id);
+            Err((ErrorCode::Unauthorized, "Unauthorized: Session does not belong to this user."))
+        }
+        Ok(_) => Ok(()),
+        Err(PortError::NotFound(_)) => {
+            error!("Session {} not found", session_id);
+            Err((ErrorCode::SessionNotFound, "Session not found."))
+        }
+        Err(e) => {
+            error!("Failed to get session: {:?}", e);
+            Err((ErrorCode::SessionLoadFailed, "Failed to load session data."))
+        }
+    }
+}
+
+/// Opens the session the client asked for: checks it belongs to the user, takes it over
+/// from another device if the client brought a handoff token, loads it (with its last
+/// connection's state, when resuming) and greets the user. On failure, returns the error to close with.
+async fn initialize_session(
+    request: &InitRequest,
+    app_state: &Arc<AppState>,
+    ws_sender: &WsSender,
+    user_id: Uuid,
+) -> Result<(Arc<Mutex<SessionState>>, Registration), (ErrorCode, &'static str)> {
+    let InitRequest { session_id, device_id, capabilities, audio_format: requested_format, handoff_token, resume } = request;
+    let (session_id, resume) = (*session_id, *resume);
+    info!("Initializing session with ID: {}", session_id);
+    check_session_owner(app_state, session_id, user_id).await?;
+
+    // Claiming the handoff disconnects the other device before the session is loaded,
+    // so its position is final.
+    let handed_off_at = handoff_token
+        .as_deref()
+        .and_then(|token| app_state.handoffs.claim(session_id, token));
+    if handoff_token.is_some() && handed_off_at.is_none() {
+        warn!("Handoff token for session {} was not valid.", session_id);
+    }
+
+    // A handoff already says where to pick up; otherwise a resuming client gets back
+    // what its last connection held when it dropped.
+    let snapshot = if resume && handed_off_at.is_none() {
+        take_recent_snapshot(app_state, session_id).await
+    } else {
+        None
+    };
+    let resume_at = handed_off_at.or(snapshot.as_ref().map(|snapshot| snapshot.reading_progress_index));
+
+    let mut state = SessionState::new(app_state.clone(), session_id, device_id.clone(), resume_at)
+        .await
+        .map_err(|e| {
+            error!("Failed to initialize session state: {:?}", e);
+            (ErrorCode::SessionLoadFailed, "Failed to load session data.")
+        })?;
+    track(app_state, state.analytics_visit_id, UsageEventKind::SessionOpened);
+    let resumed_msg = snapshot.map(|snapshot| {
+        state.restore(snapshot);
+        ServerMessage::SessionResumed {
+            mode: state.current_mode.into(),
+            last_question: state.last_question.clone(),
+            last_answer: state.last_answer.clone(),
+        }
+    });
+    state.hotword_enabled = capabilities.hotword;
+    state.audio_free = capabilities.audio_free;
+    state.speech.format = negotiate_audio_format(app_state, *requested_format);
+    let audio_free = state.audio_free;
+    let speech = state.speech.clone();
+    let positions_msg = ServerMessage::ReadingPositions {
+        furthest_read_index: state.furthest_read_index,
+        device_index: state.device_index,
+    };
+    let contents_msg = ServerMessage::TableOfContents {
+        chapters: state
+            .chapters
+            .iter()
+            .map(|chapter| ChapterEntry {
+                title: chapter.title.clone(),
+                sentence_index: chapter.sentence_index,
+            })
+            .collect(),
+    };
+    let registration = app_state.handoffs.register(session_id);
+    state.connection_id = Some(registration.connection_id);
+    // Documents uploaded before retrieval existed are embedded on first open.
+    tokio::spawn(index_document(app_state.clone(), state.document_id));
+    let session_state_lock = Arc::new(Mutex::new(state));
+
+    // A failed send means the client is gone; the close frame below goes nowhere either.
+    let init_msg = ServerMessage::SessionInitialized {
+        session_id,
+        audio_format: (!audio_free).then(|| speech.format.into()),
+    };
+    if ws_sender.send(&init_msg).await.is_err() {
+        error!("Failed to send session initialized message.");
+    }
+    if ws_sender.send(&positions_msg).await.is_err() {
+        error!("Failed to send reading positions message.");
+    }
+    if ws_sender.send(&contents_msg).await.is_err() {
+        error!("Failed to send table of contents message.");
+    }
+    if let Some(resumed_msg) = &resumed_msg {
+        if ws_sender.send(resumed_msg).await.is_err() {
+            error!("Failed to send session resumed message.");
+        }
+    }
+    if handoff_token.is_some() && handed_off_at.is_none() {
+        send_error(
+            ws_sender,
+            ErrorCode::HandoffFailed,
+            "That handoff has expired, so reading continues from your saved position.",
+        )
+        .await;
+    }
+
+    // A session handed over mid-sentence or resumed carries straight on, without the greeting.
+    if handed_off_at.is_none() && resumed_msg.is_none() {
+        // The greeting is a nicety: if it can't be synthesized, reading starts without it.
+        if let Err(e) = send_welcome(app_state, ws_sender, audio_free, &speech).await {
+            warn!("Failed to send welcome message, skipping it: {:?}", e);
+        }
+    }
+
+    Ok((session_state_lock, registration))
+}
+
+/// Greets the client with the configured welcome message, spoken from the shared
+/// announcement cache.
+async fn send_welcome(
+    app_state: &Arc<AppState>,
+    ws_sender: &WsSender,
+    audio_free: bool,
+    speech: &SpeechSettings,
+) -> PortResult<()> {
+    let welcome_text = &app_state.config.welcome_message;
+    if audio_free {
+        return speak(app_state, ws_sender, welcome_text, audio_free, speech).await;
+    }
+
+    let audio = app_state
+        .announcements
+        .get_or_synthesize(app_state.tts_adapter.as_ref(), welcome_text, speech)
+        .await?;
+    if ws_sender.send_captioned_audio(welcome_text, AudioKind::Welcome, None, audio).await.is_err() {
+        return Err(PortError::Unexpected(
+            "Failed to send welcome audio to client.".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Takes the snapshot the session's last connection left, if it is recent enough to
+/// resume from. A snapshot that can't be loaded just means starting afresh.
+async fn take_recent_snapshot(app_state: &Arc<AppState>, session_id: Uuid) -> Option<SessionSnapshot> {
+    let snapshot = match app_state.db.take_session_snapshot(session_id).await {
+        Ok(snapshot) => snapshot?,
+        Err(e) => {
+            warn!("Failed to load snapshot of session {}: {:?}", session_id, e);
+            return None;
+        }
+    };
+    let age = chrono::Utc::now() - snapshot.saved_at;
+    if age > chrono::Duration::minutes(SESSION_RESUME_WINDOW_MINUTES) {
+        info!("Snapshot of session {} is too old to resume.", session_id);
+        return None;
+    }
+    Some(snapshot)
+}
+
+/// The format to send this session's speech in: what the client asked for, if the TTS
+/// provider can produce it, with uncompressed audio pinned to an explicit sample rate
+/// so the client never has to guess it.
+pub fn negotiate_audio_format(app_state: &Arc<AppState>, requested: Option<AudioFormatSpec>) -> AudioFormat {
+    let tts = &app_state.tts_adapter;
+    let requested = requested.map(AudioFormat::from).unwrap_or_default();
+    let mut format = audio_format::negotiate(requested, &tts.supported_encodings());
+    if requested.encoding != format.encoding {
+        info!("{:?} audio isn't available from this TTS provider; sending WAV.", requested.encoding);
+    }
+    if !format.encoding.is_compressed() && format.sample_rate.is_none() {
+        format.sample_rate = Some(tts.native_sample_rate());
+    }
+    format
+}
+
+/// Reports a recoverable error to the client. The connection stays open.
+pub async fn send_error(
+    ws_sender: &WsSender,
+    code: ErrorCode,
+    message: &str,
+) {
+    let err_msg = ServerMessage::Error {
+        code,
+        message: message.to_string(),
+        fatal: false,
+    };
+    if ws_sender.send(&err_msg).await.is_err() {
+        warn!("Failed to send error message. Client may have disconnected.");
+    }
+}
+
+/// Reports a fatal error to the client, then closes the connection with a close
+/// frame carrying the same message.
+async fn close_with_error(
+    ws_sender: &WsSender,
+    code: ErrorCode,
+    message: &str,
+) {
+    let err_msg = ServerMessage::Error {
+        code,
+        message: message.to_string(),
+        fatal: true,
+    };
+    let close_frame = CloseFrame {
+        code: match code {
+            ErrorCode::Unauthorized => close_code::POLICY,
+            ErrorCode::InitRequired | ErrorCode::InvalidMessage => close_code::PROTOCOL,
+            _ => close_code::ERROR,
+        },
+        reason: message.into(),
+    };
+
+    if ws_sender.send(&err_msg).await.is_err() {
+        warn!("Failed to send error message. Client may have disconnected.");
+        return;
+    }
+    if ws_sender.close(Some(close_frame)).await.is_err() {
+        warn!("Failed to send close frame.");
+    }
+}
+
+/// Handles a submitted question, or quiz answer while a quiz is running, then moves
+/// the session on according to the outcome. `typed` carries a typed question; otherwise
+/// the question is the buffered audio.
+async fn handle_question(
+    app_state: &Arc<AppState>,
+    session_state_lock: &Arc<Mutex<SessionState>>,
+    ws_sender: &WsSender,
+    controller: &mut SessionController,
+    typed: Option<String>,
+) {
+    let in_quiz = session_state_lock.lock().await.quiz.is_some();
+
+    // While a quiz is running, speech is an answer rather than a question.
+    if in_quiz {
+        match quiz_answer_process(
+            app_state.clone(),
+            session_state_lock.clone(),
+            ws_sender.clone(),
+            typed,
+        )
+        .await
+        {
+            Ok(QuizOutcome::ReadOn) => {
+                info!("Checkpoint passed. Restarting reading task.");
+                controller.resume_after_answer().await;
+            }
+            Ok(QuizOutcome::AwaitingUser) => controller.finish_processing().await,
+            Err(e) => {
+                error!("Error in quiz answer process: {:?}", e);
+                send_error(ws_sender, ErrorCode::QuizFailed, "Sorry, I couldn't grade that answer. Please try again.").await;
+                controller.finish_processing().await;
+            }
+        }
+        return;
+    }
+
+    match qa_process(
+        app_state.clone(),
+        session_state_lock.clone(),
+        ws_sender.clone(), // Cloning the Arc is cheap and correct.
+        typed,
+    )
+    .await
+    {
+        Ok(QaOutcome::ResumeReading) => {
+            info!("QA process resulted in ResumeReading. Restarting reading task.");
+            controller.resume_after_answer().await;
+        }
+        Ok(QaOutcome::Seek { sentence_index }) => {
+            info!("QA process resulted in Seek to sentence {}. Restarting reading task.", sentence_index);
+            controller.seek(sentence_index).await;
+        }
+        Ok(QaOutcome::Pause) => {
+            info!("QA process resulted in Pause. Pausing reading.");
+            controller.finish_processing().await;
+            controller.pause().await;
+            if ws_sender.send(&ServerMessage::ReadingPaused).await.is_err() {
+                warn!("Failed to send ReadingPaused message. Client may have disconnected.");
+            }
+        }
+        Ok(QaOutcome::QuestionAnswered) => {
+            info!("QA process resulted in QuestionAnswered. Awaiting next interrupt.");
+            controller.finish_processing().await;
+        }
+        Ok(QaOutcome::Cancelled) => {
+            info!("QA process was cancelled. Awaiting next interrupt.");
+            controller.finish_processing().await;
+        }
+        Ok(QaOutcome::ClarificationAsked) => {
+            info!("QA process asked a clarifying question. Awaiting the reply.");
+            controller.ask_clarification().await;
+        }
+        Err(e) => {
+            error!("Error in QA process: {:?}", e);
+            controller.finish_processing().await;
+            // Let the client leave its "answering" state so the user can ask again.
+            send_error(ws_sender, ErrorCode::AnswerFailed, "Sorry, I couldn't answer that. Please try again.").await;
+            if ws_sender.send(&ServerMessage::AnsweringEnded).await.is_err() {
+                warn!("Failed to send AnsweringEnded message. Client may have disconnected.");
+            }
+        }
     }
-    info!("WebSocket connection closed.");
 }
 
 /// Helper function to handle the logic for different `ClientMessage` variants.
+/// Returns `false` once the session has ended and the connection should close.
 async fn handle_text_message(
     text: String,
     app_state: &Arc<AppState>,
     session_state_lock: &Arc<Mutex<SessionState>>,
-    ws_sender: &Arc<Mutex<SplitSink<WebSocket, Message>>>,
-    reading_task_handle: &mut Option<JoinHandle<()>>,
-) {
+    ws_sender: &WsSender,
+    controller: &mut SessionController,
+) -> bool {
     match serde_json::from_str::<ClientMessage>(&text) {
         Ok(client_msg) => match client_msg {
+            // Handled by the reader task as soon as it arrives.
+            ClientMessage::CancelQuestion | ClientMessage::CancelAnswer => {}
             ClientMessage::InterruptStarted => {
                 info!("InterruptStarted message received. Cancelling reading task.");
-                let mut session = session_state_lock.lock().await;
-                session.cancellation_token.cancel();
-                session.current_mode = SessionMode::InterruptedListening;
-                session.audio_buffer.clear();
+                controller.interrupt().await;
             }
             ClientMessage::InterruptEnded => {
                 info!("InterruptEnded message received.");
+                // A question cut off at the buffer limit isn't answered; the client has
+                // already been told to ask again.
                 {
                     let mut session = session_state_lock.lock().await;
-                    session.current_mode = SessionMode::ProcessingQuestion;
+                    if session.audio_buffer_overflowed {
+                        session.audio_buffer_overflowed = false;
+                        return true;
+                    }
+                }
+                if controller.submit_question().await {
+                    handle_question(app_state, session_state_lock, ws_sender, controller, None).await;
+                }
+            }
+            ClientMessage::TextQuestion { text } => {
+                info!("TextQuestion message received.");
+                let text = text.trim();
+                if text.is_empty() {
+                    send_error(ws_sender, ErrorCode::InvalidMessage, "Questions must not be empty.").await;
+                    return true;
                 }
+                if controller.submit_text_question().await {
+                    handle_question(app_state, session_state_lock, ws_sender, controller, Some(text.to_string())).await;
+                }
+            }
+            ClientMessage::StartQuiz { question_count } => {
+                info!("StartQuiz message received.");
+                if !controller.start_quiz().await {
+                    return true;
+                }
+                let visit_id = session_state_lock.lock().await.analytics_visit_id;
+                track(app_state, visit_id, UsageEventKind::QuizStarted);
 
-                match qa_process(
+                if let Err(e) = start_quiz(
                     app_state.clone(),
                     session_state_lock.clone(),
-                    ws_sender.clone(), // Cloning the Arc is cheap and correct.
+                    ws_sender.clone(),
+                    question_count,
                 )
                 .await
                 {
-                    Ok(QaOutcome::ResumeReading) => {
-                        info!("QA process resulted in ResumeReading. Restarting reading task.");
-                        let mut session = session_state_lock.lock().await;
-                            // Check if all audio already generated
-                            if session.reading_progress_index >= session.chunked_document.len() {
-                                info!("All audio already generated, just resuming frontend playback");
-                                let start_msg = ServerMessage::ReadingStarted;
-                                let start_json = serde_json::to_string(&start_msg).unwrap();
-                                if ws_sender.lock().await.send(Message::Text(start_json.into())).await.is_err() {
-                                    error!("Failed to send ReadingStarted message.");
-                                }
-                                if ws_sender.lock().await.send(Message::Binary(vec![].into())).await.is_err() {
-                                    error!("Failed to send empty audio trigger.");
-                                }
-                        } 
-                        else{
-                        info!("We entered into here");
-                        session.current_mode = SessionMode::Reading;
-                        session.cancellation_token = CancellationToken::new();
-                        let task = {
-                            let app_state = app_state.clone();
-                            let session_state_lock = session_state_lock.clone();
-                            let ws_sender = ws_sender.clone();
-                            let token = session.cancellation_token.clone();
-                            tokio::spawn(async move {
-                                info!("reading task being started");
-                                if let Err(e) = reading_process(app_state, session_state_lock, ws_sender, token).await {
-                                    error!("Reading process failed: {:?}", e);
-                                }
-                            })
-                        };
-                        *reading_task_handle = Some(task);
-                    }
-                    }
-                    Ok(QaOutcome::QuestionAnswered) => {
-                        info!("QA process resulted in QuestionAnswered. Awaiting next interrupt.");
-                        let mut session = session_state_lock.lock().await;
-                        session.current_mode = SessionMode::InterruptedListening;
-                    }
-                    Err(e) => {
-                        error!("Error in QA process: {:?}", e);
-                        let mut session = session_state_lock.lock().await;
-                        session.current_mode = SessionMode::InterruptedListening;
-                    }
+                    error!("Failed to start quiz: {:?}", e);
+                    send_error(ws_sender, ErrorCode::QuizFailed, "Failed to start the quiz.").await;
                 }
+
+                controller.finish_processing().await;
             }
             ClientMessage::PauseReading => {
                 info!("PauseReading message received.");
-                let mut session = session_state_lock.lock().await;
-                session.cancellation_token.cancel();
-                session.current_mode = SessionMode::Paused;
+                let visit_id = session_state_lock.lock().await.analytics_visit_id;
+                track(app_state, visit_id, UsageEventKind::Paused);
+                controller.pause().await;
             }
             ClientMessage::ResumeReading => {
-            info!("ResumeReading message received.");
-            let mut session = session_state_lock.lock().await;
-            if session.current_mode == SessionMode::Paused {
-                // Check if all audio already generated
-                if session.reading_progress_index >= session.chunked_document.len() {
-                    info!("All audio already generated, just resuming frontend playback");
-                    let start_msg = ServerMessage::ReadingStarted;
-                    let start_json = serde_json::to_string(&start_msg).unwrap();
-                    if ws_sender.lock().await.send(Message::Text(start_json.into())).await.is_err() {
-                        error!("Failed to send ReadingStarted message.");
-                    }
-                    if ws_sender.lock().await.send(Message::Binary(vec![].into())).await.is_err() {
-                        error!("Failed to send empty audio trigger.");
-                    }
+                info!("ResumeReading message received.");
+                let visit_id = session_state_lock.lock().await.analytics_visit_id;
+                track(app_state, visit_id, UsageEventKind::Resumed);
+                controller.resume().await;
+            }
+            ClientMessage::SeekTo { sentence_index } => {
+                info!("SeekTo message received: sentence {}", sentence_index);
+                let total_sentences = session_state_lock.lock().await.chunked_document.len();
+                if sentence_index >= total_sentences {
+                    send_error(ws_sender, ErrorCode::InvalidMessage, "That sentence is not in this document.").await;
+                    return true;
+                }
+                if controller.jump_to(sentence_index).await {
+                    let visit_id = session_state_lock.lock().await.analytics_visit_id;
+                    track(app_state, visit_id, UsageEventKind::Seeked);
+                }
+            }
+            ClientMessage::StartHandoff { sentence_index } => {
+                info!("StartHandoff message received.");
+                let offered = {
+                    let session = session_state_lock.lock().await;
+                    let sentence_index = sentence_index
+                        .unwrap_or(session.reading_progress_index)
+                        .min(session.chunked_document.len());
+                    session.connection_id.and_then(|connection_id| {
+                        app_state.handoffs.offer(session.session_id, connection_id, sentence_index)
+                    })
+                };
+                let Some(token) = offered else {
+                    send_error(ws_sender, ErrorCode::HandoffFailed, "This session can't be handed off right now.").await;
+                    return true;
+                };
+                let ready_msg = ServerMessage::HandoffReady {
+                    token,
+                    expires_in_secs: HANDOFF_TOKEN_TTL.as_secs(),
+                };
+                if ws_sender.send(&ready_msg).await.is_err() {
+                    warn!("Failed to send HandoffReady message. Client may have disconnected.");
+                }
+            }
+            ClientMessage::GoToChapter { index } => {
+                info!("GoToChapter message received: chapter {}", index);
+                let (chapter, visit_id) = {
+                    let session = session_state_lock.lock().await;
+                    (session.chapters.get(index).map(|chapter| chapter.sentence_index), session.analytics_visit_id)
+                };
+                let Some(sentence_index) = chapter else {
+                    send_error(ws_sender, ErrorCode::InvalidMessage, "That chapter is not in this document.").await;
+                    return true;
+                };
+                if controller.jump_to(sentence_index).await {
+                    track(app_state, visit_id, UsageEventKind::Seeked);
+                }
+            }
+            ClientMessage::SkipBack { sentences } => {
+                info!("SkipBack message received: {} sentences", sentences);
+                let (sentence_index, visit_id) = {
+                    let session = session_state_lock.lock().await;
+                    (session.skip_back_index(sentences), session.analytics_visit_id)
+                };
+                if controller.jump_to(sentence_index).await {
+                    track(app_state, visit_id, UsageEventKind::Seeked);
+                }
+            }
+            ClientMessage::RereadSlower { sentences } => {
+                info!("RereadSlower message received: {} sentences", sentences);
+                if controller.reread_slower(sentences).await {
+                    let visit_id = session_state_lock.lock().await.analytics_visit_id;
+                    track(app_state, visit_id, UsageEventKind::Seeked);
+                }
+            }
+            ClientMessage::SetAnswerVerbosity { verbosity } => {
+                info!("SetAnswerVerbosity message received: {:?}", verbosity);
+                let mut session = session_state_lock.lock().await;
+                session.answer_verbosity = verbosity.into();
+                let user_id = session.user_id;
+                drop(session);
+
+                // Persist so the choice sticks for future sessions too.
+                let mut preferences = app_state
+                    .db
+                    .get_user_preferences(user_id)
+                    .await
+                    .unwrap_or_default();
+                preferences.answer_verbosity = verbosity.into();
+                if let Err(e) = app_state.db.save_user_preferences(user_id, &preferences).await {
+                    error!("Failed to save answer verbosity preference: {:?}", e);
+                }
+            }
+            ClientMessage::SetNarrationPacing { sentence_gap_ms, crossfade_ms } => {
+                info!(
+                    "SetNarrationPacing message received: gap {}ms, crossfade {}ms",
+                    sentence_gap_ms, crossfade_ms
+                );
+                let sentence_gap_ms = sentence_gap_ms.min(MAX_SENTENCE_GAP_MS);
+                let crossfade_ms = crossfade_ms.min(MAX_CROSSFADE_MS);
+                let mut session = session_state_lock.lock().await;
+                session.sentence_gap_ms = sentence_gap_ms;
+                session.crossfade_ms = crossfade_ms;
+                let user_id = session.user_id;
+                drop(session);
+
+                let mut preferences = app_state
+                    .db
+                    .get_user_preferences(user_id)
+                    .await
+                    .unwrap_or_default();
+                preferences.sentence_gap_ms = sentence_gap_ms;
+                preferences.crossfade_ms = crossfade_ms;
+                if let Err(e) = app_state.db.save_user_preferences(user_id, &preferences).await {
+                    error!("Failed to save narration pacing preference: {:?}", e);
+                }
+            }
+            ClientMessage::SetSpeed { rate } => {
+                info!("SetSpeed message received: {}", rate);
+                if !rate.is_finite() {
+                    send_error(ws_sender, ErrorCode::InvalidMessage, "Speed must be a number.").await;
+                    return true;
+                }
+                set_speaking_speed(app_state, session_state_lock, rate).await;
+            }
+            ClientMessage::SetVoice { voice } => {
+                info!("SetVoice message received: '{}'", voice);
+                let voice = voice.trim();
+                if !voice.is_empty() && !app_state.tts_adapter.available_voices().iter().any(|v| v == voice) {
+                    send_error(ws_sender, ErrorCode::InvalidMessage, &format!("Unknown voice '{}'.", voice)).await;
+                    return true;
+                }
+                let voice = if voice.is_empty() {
+                    let user_id = session_state_lock.lock().await.user_id;
+                    app_state
+                        .db
+                        .get_user_preferences(user_id)
+                        .await
+                        .unwrap_or_default()
+                        .voice
                 } else {
-                    // Still have sentences to generate
-                    session.current_mode = SessionMode::Reading;
-                    session.cancellation_token = CancellationToken::new();
-                    let task = {
-                        let app_state = app_state.clone();
-                        let session_state_lock = session_state_lock.clone();
-                        let ws_sender = ws_sender.clone();
-                        let token = session.cancellation_token.clone();
-                        tokio::spawn(async move {
-                            if let Err(e) = reading_process(app_state, session_state_lock, ws_sender, token).await {
-                                error!("Reading process failed: {:?}", e);
+                    Some(voice.to_string())
+                };
+                session_state_lock.lock().await.speech.voice = voice;
+            }
+            ClientMessage::RateSession { rating, feedback } => {
+                info!("RateSession message received: {}", rating);
+                let session_id = session_state_lock.lock().await.session_id;
+                match ratings::build_rating(session_id, rating, feedback) {
+                    Ok(rating) => match app_state.db.save_session_rating(&rating).await {
+                        Ok(()) => {
+                            if ws_sender.send(&ServerMessage::SessionRated).await.is_err() {
+                                error!("Failed to send SessionRated message.");
                             }
-                        })
-                    };
-                    *reading_task_handle = Some(task);
+                        }
+                        Err(e) => {
+                            error!("Failed to save rating for session {}: {:?}", session_id, e);
+                            send_error(ws_sender, ErrorCode::RatingFailed, "Failed to save the rating.").await;
+                        }
+                    },
+                    Err(message) => send_error(ws_sender, ErrorCode::InvalidMessage, &message).await,
                 }
             }
-        }
+            ClientMessage::EndSession => {
+                info!("EndSession message received.");
+                controller.end().await;
+                end_session(app_state, session_state_lock, ws_sender).await;
+                return false;
+            }
             ClientMessage::Init { .. } => {
                 warn!("Received subsequent Init message, which is ignored.");
             }
+            // Handled by the socket loop, since it replaces the session.
+            ClientMessage::SwitchSession { .. } => {}
         },
         Err(e) => {
             warn!("Failed to deserialize client message: {}", e);
+            send_error(ws_sender, ErrorCode::InvalidMessage, "Unrecognized message, ignored.").await;
         }
     }
+    true
+}
+
+/// Finalizes a session once reading has stopped: flushes progress, kicks off the summary note,
+/// reports the session's stats, and closes the socket.
+async fn end_session(
+    app_state: &Arc<AppState>,
+    session_state_lock: &Arc<Mutex<SessionState>>,
+    ws_sender: &WsSender,
+) {
+    let (session_id, stats) = {
+        let session = session_state_lock.lock().await;
+        if let Err(e) = app_state
+            .db
+            .update_session_progress(
+                session.session_id,
+                session.device_id.as_deref(),
+                session.reading_progress_index,
+            )
+            .await
+        {
+            error!("Failed to flush progress for session {}: {:?}", session.session_id, e);
+        }
+
+        let stats = SessionStats {
+            time_listened_secs: session.opened_at.elapsed().as_secs(),
+            questions_asked: session.questions_asked,
+            sentences_read: session
+                .reading_progress_index
+                .saturating_sub(session.opened_at_index),
+            reading_progress_index: session.reading_progress_index,
+            total_sentences: session.chunked_document.len(),
+        };
+        (session.session_id, stats)
+    };
+
+    tokio::spawn(generate_and_save_summary_note(app_state.clone(), session_id));
+
+    let summary_msg = ServerMessage::SessionSummary { stats };
+    if ws_sender.send(&summary_msg).await.is_err() {
+        warn!("Failed to send SessionSummary message. Client may have disconnected.");
+    }
+    if ws_sender.close(None).await.is_err() {
+        warn!("Failed to send close frame.");
+    }
 }
@@ -0,0 +1,221 @@
+//! services/api/src/web/ws_writer.rs
+//!
+//! The single writer for a WebSocket connection. Tasks queue frames through a
+//! cloneable `WsSender` instead of contending on a shared sink, so a slow audio
+//! send in one task never blocks another task's control message.
+//!
+//! Frames travel in two lanes. The ordered lane carries audio and the messages
+//! that describe it (`ReadingStarted`, `AnsweringEnded`, ...), which must stay in
+//! sequence with the audio. The control lane carries out-of-band messages such as
+//! errors and acknowledgements, and is always drained first, so those overtake
+//! any audio still waiting to go out.
+//!
+//! Each Binary frame is queued together with the `AudioHeader` that describes it,
+//! as one item of the ordered lane, so a send cancelled partway (by a seek, pause
+//! or interrupt) queues both or neither, and the two are never separated.
+
+use crate::web::protocol::{AudioKind, ServerMessage};
+use axum::extract::ws::{CloseFrame, Message, WebSocket};
+use futures::{stream::SplitSink, SinkExt};
+use thiserror::Error;
+use tokio::sync::mpsc;
+use tracing::{info, warn};
+
+/// How many control frames can be queued before senders wait.
+const CONTROL_QUEUE_LEN: usize = 32;
+/// How many ordered items (a message, or audio with its header) can be queued before
+/// senders wait. Kept small so that little audio is already queued when reading is
+/// interrupted.
+const ORDERED_QUEUE_LEN: usize = 4;
+
+/// The connection's writer has stopped, because the client disconnected or the
+/// connection was closed.
+#[derive(Debug, Error)]
+#[error("WebSocket connection closed")]
+pub struct ConnectionClosed;
+
+/// A handle for queueing frames to the client. Cheap to clone.
+#[derive(Clone)]
+pub struct WsSender {
+    control_tx: mpsc::Sender<Message>,
+    /// Frames that must go out back to back are queued as one item.
+    ordered_tx: mpsc::Sender<Vec<Message>>,
+}
+
+impl WsSender {
+    /// Queues a message, in the control or ordered lane depending on its kind.
+    pub async fn send(&self, msg: &ServerMessage) -> Result<(), ConnectionClosed> {
+        let frame = text_frame(msg);
+        if msg.is_control() {
+            self.control_tx.send(frame).await.map_err(|_| ConnectionClosed)
+        } else {
+            self.send_ordered(vec![frame]).await
+        }
+    }
+
+    /// Queues frames in the ordered lane as one item, so they are written back to back.
+    async fn send_ordered(&self, frames: Vec<Message>) -> Result<(), ConnectionClosed> {
+        self.ordered_tx.send(frames).await.map_err(|_| ConnectionClosed)
+    }
+
+    /// Queues an audio clip and its `AudioHeader` behind the ordered frames already
+    /// waiting. `index` is the sentence index for `Reading` audio and the sentence's
+    /// position for `Answer` audio.
+    pub async fn send_audio(
+        &self,
+        kind: AudioKind,
+        index: Option<usize>,
+        audio: Vec<u8>,
+    ) -> Result<(), ConnectionClosed> {
+        self.send_ordered(audio_frames(kind, index, audio)).await
+    }
+
+    /// Queues a `Caption` with the text of an audio clip, immediately followed by the clip,
+    /// so the client shows each caption as its audio starts.
+    pub async fn send_captioned_audio(
+        &self,
+        text: &str,
+        kind: AudioKind,
+        index: Option<usize>,
+        audio: Vec<u8>,
+    ) -> Result<(), ConnectionClosed> {
+        self.send_ordered(captioned_audio_frames(text, kind, index, audio)).await
+    }
+
+    /// Like `send_captioned_audio`, with `lead` (an ordered message such as
+    /// `SentenceStarted`) queued in the same item, so the client never gets it without
+    /// the audio it announces.
+    pub async fn send_captioned_audio_after(
+        &self,
+        lead: &ServerMessage,
+        text: &str,
+        kind: AudioKind,
+        index: Option<usize>,
+        audio: Vec<u8>,
+    ) -> Result<(), ConnectionClosed> {
+        debug_assert!(!lead.is_control());
+        let mut frames = vec![text_frame(lead)];
+        frames.extend(captioned_audio_frames(text, kind, index, audio));
+        self.send_ordered(frames).await
+    }
+
+    /// Queues a close frame in the control lane. The writer stops once it is sent,
+    /// dropping anything still queued.
+    pub async fn close(&self, frame: Option<CloseFrame>) -> Result<(), ConnectionClosed> {
+        self.control_tx
+            .send(Message::Close(frame))
+            .await
+            .map_err(|_| ConnectionClosed)
+    }
+}
+
+fn text_frame(msg: &ServerMessage) -> Message {
+    let json = serde_json::to_string(msg).unwrap();
+    Message::Text(json.into())
+}
+
+/// An `AudioHeader` and the Binary frame it describes.
+fn audio_frames(kind: AudioKind, index: Option<usize>, audio: Vec<u8>) -> Vec<Message> {
+    let header = ServerMessage::AudioHeader {
+        kind,
+        index,
+        byte_length: audio.len(),
+    };
+    vec![text_frame(&header), Message::Binary(audio.into())]
+}
+
+/// A `Caption` with an audio clip's text, then the clip and its header.
+fn captioned_audio_frames(text: &str, kind: AudioKind, index: Option<usize>, audio: Vec<u8>) -> Vec<Message> {
+    let mut frames = vec![text_frame(&ServerMessage::Caption { text: text.to_string() })];
+    frames.extend(audio_frames(kind, index, audio));
+    frames
+}
+
+/// Spawns the writer task for `sink`. The task owns the sink, so queued frames are
+/// still delivered after the socket loop returns; it runs until a close frame is
+/// sent, a send fails, or every `WsSender` has been dropped.
+pub fn spawn_writer(mut sink: SplitSink<WebSocket, Message>) -> WsSender {
+    let (control_tx, mut control_rx) = mpsc::channel::<Message>(CONTROL_QUEUE_LEN);
+    let (ordered_tx, mut ordered_rx) = mpsc::channel::<Vec<Message>>(ORDERED_QUEUE_LEN);
+
+    tokio::spawn(async move {
+        'writer: loop {
+            let frames = tokio::select! {
+                biased;
+                Some(frame) = control_rx.recv() => vec![frame],
+                Some(frames) = ordered_rx.recv() => frames,
+                else => break,
+            };
+            for frame in frames {
+                let is_close = matches!(frame, Message::Close(_));
+                if let Err(e) = sink.send(frame).await {
+                    warn!("Failed to write to WebSocket, stopping writer: {}", e);
+                    break 'writer;
+                }
+                if is_close {
+                    break 'writer;
+                }
+            }
+        }
+        info!("WebSocket writer stopped.");
+    });
+
+    WsSender { control_tx, ordered_tx }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn cancelled_audio_sends_never_leave_a_header_behind() {
+        let (control_tx, _control_rx) = mpsc::channel(CONTROL_QUEUE_LEN);
+        let (ordered_tx, mut ordered_rx) = mpsc::channel(ORDERED_QUEUE_LEN);
+        let sender = WsSender { control_tx, ordered_tx };
+
+        // Fill the ordered lane so the next send has to wait, then cancel it the
+        // way a seek cancels a sentence being read.
+        for index in 0..ORDERED_QUEUE_LEN {
+            sender.send_audio(AudioKind::Reading, Some(index), vec![0; 4]).await.unwrap();
+        }
+        tokio::select! {
+            _ = sender.send_audio(AudioKind::Reading, Some(99), vec![0; 8]) => {
+                panic!("the ordered lane should be full");
+            }
+            _ = tokio::time::sleep(Duration::from_millis(10)) => {}
+        }
+        drop(sender);
+
+        let mut items = 0;
+        while let Some(frames) = ordered_rx.recv().await {
+            assert!(matches!(
+                frames.as_slice(),
+                [Message::Text(_), Message::Binary(audio)] if audio.len() == 4
+            ));
+            items += 1;
+        }
+        assert_eq!(items, ORDERED_QUEUE_LEN);
+    }
+
+    #[tokio::test]
+    async fn lead_messages_share_an_item_with_their_audio() {
+        let (control_tx, _control_rx) = mpsc::channel(CONTROL_QUEUE_LEN);
+        let (ordered_tx, mut ordered_rx) = mpsc::channel(ORDERED_QUEUE_LEN);
+        let sender = WsSender { control_tx, ordered_tx };
+
+        let started = ServerMessage::SentenceStarted { index: 3, text: "Hi.".to_string() };
+        sender
+            .send_captioned_audio_after(&started, "Hi.", AudioKind::Reading, Some(3), vec![0; 4])
+            .await
+            .unwrap();
+        drop(sender);
+
+        let frames = ordered_rx.recv().await.unwrap();
+        assert!(matches!(
+            frames.as_slice(),
+            [Message::Text(_), Message::Text(_), Message::Text(_), Message::Binary(_)]
+        ));
+        assert!(ordered_rx.recv().await.is_none());
+    }
+}